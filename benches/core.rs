@@ -0,0 +1,52 @@
+//! `todo-core` request building and list parsing.
+//!
+//! # Design
+//! - `build_*` are measured per call: each allocates the URL, headers and
+//!   body of a fresh `HttpRequest`, which is the cost a zero-copy or arena
+//!   change would cut.
+//! - `parse_list_todos` takes its response by value, so every iteration
+//!   gets a fresh clone from `iter_batched`, outside the timed section.
+//!   Throughput is reported in body bytes.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use todo_benches::{list_response, BASE_URL, LIST_SIZES};
+use todo_core::{CreateTodo, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+fn build(c: &mut Criterion) {
+    let client = TodoClient::new(BASE_URL);
+    let id = Uuid::from_u128(1);
+    let create = CreateTodo {
+        title: "Buy milk".to_string(),
+        completed: false,
+    };
+    let update = UpdateTodo {
+        title: Some("Buy oat milk".to_string()),
+        completed: Some(true),
+    };
+    let mut group = c.benchmark_group("build");
+    group.bench_function("list_todos", |b| b.iter(|| client.build_list_todos()));
+    group.bench_function("get_todo", |b| b.iter(|| client.build_get_todo(black_box(id))));
+    group.bench_function("create_todo", |b| b.iter(|| client.build_create_todo(black_box(&create))));
+    group.bench_function("update_todo", |b| b.iter(|| client.build_update_todo(black_box(id), black_box(&update))));
+    group.bench_function("delete_todo", |b| b.iter(|| client.build_delete_todo(black_box(id))));
+    group.finish();
+}
+
+fn parse_list(c: &mut Criterion) {
+    let client = TodoClient::new(BASE_URL);
+    let mut group = c.benchmark_group("parse_list_todos");
+    for len in LIST_SIZES {
+        let response = list_response(len);
+        group.throughput(Throughput::Bytes(response.body.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &response, |b, response| {
+            b.iter_batched(|| response.clone(), |r| client.parse_list_todos(r).unwrap(), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, build, parse_list);
+criterion_main!(benches);
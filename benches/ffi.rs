@@ -0,0 +1,72 @@
+//! Converting parsed todos into C ABI results.
+//!
+//! # Design
+//! - `ok_todo_list` times `FfiTodoResult::ok_todo_list` alone: the todos
+//!   are parsed in the batch setup and each result is freed by a guard
+//!   that criterion drops after the timed section.
+//! - `todo_parse_list_todos` is the whole entry point a C host calls,
+//!   free included, so it can be compared with the core-only
+//!   `parse_list_todos` group of the `core` bench.
+//!
+//! Run in release (the default for `cargo bench`): debug builds also track
+//! every pointer handed out, which would dominate the numbers.
+
+use std::ffi::CString;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use todo_benches::{list_response, BASE_URL, LIST_SIZES};
+use todo_core::TodoClient;
+use todo_ffi::types::{FfiErrorCode, FfiHttpResponse, FfiTodoResult};
+
+/// Frees the result when criterion drops the routine's output.
+struct Owned(*mut FfiTodoResult);
+
+impl Drop for Owned {
+    fn drop(&mut self) {
+        todo_ffi::todo_free_result(self.0);
+    }
+}
+
+fn ok_todo_list(c: &mut Criterion) {
+    let client = TodoClient::new(BASE_URL);
+    let mut group = c.benchmark_group("ok_todo_list");
+    for len in LIST_SIZES {
+        let response = list_response(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &response, |b, response| {
+            b.iter_batched(
+                || client.parse_list_todos(response.clone()).unwrap(),
+                |todos| Owned(FfiTodoResult::ok_todo_list(todos)),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn parse_list_entry_point(c: &mut Criterion) {
+    let base_url = CString::new(BASE_URL).unwrap();
+    let client = todo_ffi::todo_client_new(base_url.as_ptr());
+    let mut group = c.benchmark_group("todo_parse_list_todos");
+    for len in LIST_SIZES {
+        let response = list_response(len);
+        let body = CString::new(response.body).unwrap();
+        let ffi_response = FfiHttpResponse {
+            status: response.status,
+            body: body.as_ptr(),
+        };
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &ffi_response, |b, ffi_response| {
+            b.iter(|| {
+                let result = todo_ffi::todo_parse_list_todos(client, ffi_response);
+                assert!(matches!(unsafe { &(*result).error_code }, FfiErrorCode::Ok));
+                todo_ffi::todo_free_result(result);
+            })
+        });
+    }
+    group.finish();
+    todo_ffi::todo_client_free(client);
+}
+
+criterion_group!(benches, ok_todo_list, parse_list_entry_point);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Inputs shared by the benchmarks in this directory.
+
+use todo_core::HttpResponse;
+use uuid::Uuid;
+
+/// Base URL every benchmarked client is built with.
+pub const BASE_URL: &str = "http://localhost:3000";
+
+/// List sizes: a typical page, a large account, and a full export.
+pub const LIST_SIZES: [usize; 3] = [10, 1_000, 100_000];
+
+/// A `200` list response with `len` todos, shaped like the mock server's.
+/// Titles vary in length so the parser does not see one repeated string.
+pub fn list_response(len: usize) -> HttpResponse {
+    let mut body = String::with_capacity(len * 96 + 2);
+    body.push('[');
+    for i in 0..len {
+        if i > 0 {
+            body.push(',');
+        }
+        let id = Uuid::from_u128(i as u128 + 1);
+        let title = "x".repeat(8 + i % 32);
+        let completed = i % 3 == 0;
+        body.push_str(&format!(r#"{{"id":"{id}","title":"todo {i} {title}","completed":{completed}}}"#));
+    }
+    body.push(']');
+    HttpResponse {
+        status: 200,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body,
+    }
+}
@@ -0,0 +1,1376 @@
+/* AUTO GENERATED FILE, DO NOT EDIT.
+ *
+ * Generated by todo-bindgen from todo_client.h and the JSON test vectors.
+ * Regenerate with `cargo run -p todo-bindgen -- c bindings/c`.
+ *
+ * Runs every test vector through both the UTF-8 (todo_*) and UTF-16
+ * (todo_net_*) entry points; `lifecycle` calls the functions no vector
+ * reaches. Prints one line per failed check and exits non-zero if any
+ * failed. Needs C11 for the u"" literals. */
+
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "todo_client.h"
+
+#define BASE_URL "http://localhost:3000"
+#define BASE_URL_U16 (const uint16_t *)u"http://localhost:3000"
+
+static int checks;
+static int failures;
+
+#define CHECK(name, cond) check((name), (cond), #cond, __LINE__)
+
+static void check(const char *name, int ok, const char *expr, int line) {
+    checks++;
+    if (!ok) {
+        failures++;
+        fprintf(stderr, "FAIL %s (line %d): %s\n", name, line, expr);
+    }
+}
+
+static int str_eq(const char *a, const char *b) {
+    return a && b && strcmp(a, b) == 0;
+}
+
+/* A request as a test vector describes it. */
+typedef struct Expected {
+    int method;
+    const char *path;
+    /* Key, value, key, value, ... */
+    const char *const *headers;
+    size_t headers_len;
+    /* Compact `"key":value` members of the JSON body; NULL for no body. */
+    const char *const *members;
+    size_t members_len;
+} Expected;
+
+typedef struct ExpectedTodo {
+    const char *id;
+    const char *title;
+    int completed;
+} ExpectedTodo;
+
+/* True if `body` is a compact JSON object with exactly `members`, in any
+ * order. */
+static int body_matches(const char *body, const char *const *members, size_t members_len) {
+    size_t len = 2;
+    if (!body || body[0] != '{') {
+        return 0;
+    }
+    for (size_t i = 0; i < members_len; i++) {
+        if (!strstr(body, members[i])) {
+            return 0;
+        }
+        len += strlen(members[i]) + (i > 0);
+    }
+    return strlen(body) == len && body[len - 1] == '}';
+}
+
+static void check_request(const char *name, int method, const char *path, const FfiFfiHeader *headers,
+                          uint32_t headers_len, const char *body, const Expected *expected) {
+    CHECK(name, method == expected->method);
+    CHECK(name, str_eq(path, expected->path));
+    CHECK(name, headers_len == expected->headers_len);
+    for (size_t i = 0; i < headers_len && i < expected->headers_len; i++) {
+        CHECK(name, str_eq(headers[i].key, expected->headers[2 * i]));
+        CHECK(name, str_eq(headers[i].value, expected->headers[2 * i + 1]));
+    }
+    if (expected->members) {
+        CHECK(name, body_matches(body, expected->members, expected->members_len));
+    } else {
+        CHECK(name, body == NULL);
+    }
+}
+
+static void check_todo(const char *name, const char *id, const char *title, int completed,
+                       const ExpectedTodo *expected) {
+    CHECK(name, str_eq(id, expected->id));
+    CHECK(name, str_eq(title, expected->title));
+    CHECK(name, !completed == !expected->completed);
+}
+
+static void list_empty_list(const FfiFfiTodoClient *client) {
+    const char *name = "list/empty_list";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "[]"};
+    FfiFfiTodoResult *result = todo_parse_list_todos(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST) {
+            const FfiFfiTodoList *list = result->data;
+            CHECK(name, list->len == 0);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_list_empty_list(const FfiFfiTodoClient *client) {
+    const char *name = "list/empty_list (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_list_todos(client, 200, (const uint16_t *)u"[]");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST) {
+            const FfiFfiNetTodoList *list = result->data;
+            CHECK(name, list->len == 0);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void list_two_items(const FfiFfiTodoClient *client) {
+    const char *name = "list/two_items";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "[{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"First\",\"completed\":false},{\"id\":\"00000000-0000-0000-0000-000000000002\",\"title\":\"Second\",\"completed\":true}]"};
+    FfiFfiTodoResult *result = todo_parse_list_todos(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST) {
+            const FfiFfiTodoList *list = result->data;
+            CHECK(name, list->len == 2);
+            static const ExpectedTodo want[] = {
+                {"00000000-0000-0000-0000-000000000001", "First", 0},
+                {"00000000-0000-0000-0000-000000000002", "Second", 1},
+            };
+            for (uint32_t i = 0; i < list->len && i < 2; i++) {
+                check_todo(name, list->items[i].id, list->items[i].title, list->items[i].completed, &want[i]);
+            }
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_list_two_items(const FfiFfiTodoClient *client) {
+    const char *name = "list/two_items (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_list_todos(client, 200, (const uint16_t *)u"[{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"First\",\"completed\":false},{\"id\":\"00000000-0000-0000-0000-000000000002\",\"title\":\"Second\",\"completed\":true}]");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO_LIST) {
+            const FfiFfiNetTodoList *list = result->data;
+            CHECK(name, list->len == 2);
+            static const ExpectedTodo want[] = {
+                {"00000000-0000-0000-0000-000000000001", "First", 0},
+                {"00000000-0000-0000-0000-000000000002", "Second", 1},
+            };
+            for (uint32_t i = 0; i < list->len && i < 2; i++) {
+                check_todo(name, list->items[i].id, list->items[i].title, list->items[i].completed, &want[i]);
+            }
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void list_service_unavailable(const FfiFfiTodoClient *client) {
+    const char *name = "list/service_unavailable";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {503, ""};
+    FfiFfiTodoResult *result = todo_parse_list_todos(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 503);
+    }
+    todo_free_result(result);
+}
+
+static void net_list_service_unavailable(const FfiFfiTodoClient *client) {
+    const char *name = "list/service_unavailable (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_list_todos(client, 503, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 503);
+    }
+    todo_net_free_result(result);
+}
+
+static void list_malformed_json(const FfiFfiTodoClient *client) {
+    const char *name = "list/malformed_json";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "[{\"id\":\"not-a-uuid\",\"title\":\"x\",\"completed\":false}]"};
+    FfiFfiTodoResult *result = todo_parse_list_todos(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_free_result(result);
+}
+
+static void net_list_malformed_json(const FfiFfiTodoClient *client) {
+    const char *name = "list/malformed_json (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_list_todos(client);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_list_todos(client, 200, (const uint16_t *)u"[{\"id\":\"not-a-uuid\",\"title\":\"x\",\"completed\":false}]");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_found(const FfiFfiTodoClient *client) {
+    const char *name = "get/found";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Test\",\"completed\":false}"};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Test", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_get_found(const FfiFfiTodoClient *client) {
+    const char *name = "get/found (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 200, (const uint16_t *)u"{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Test\",\"completed\":false}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiNetTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Test", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void get_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "get/not_found";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000099");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {404, ""};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "get/not_found (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000099");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 404, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_unauthorized(const FfiFfiTodoClient *client) {
+    const char *name = "get/unauthorized";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {401, ""};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_UNAUTHORIZED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 401);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_unauthorized(const FfiFfiTodoClient *client) {
+    const char *name = "get/unauthorized (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 401, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_UNAUTHORIZED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 401);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_forbidden(const FfiFfiTodoClient *client) {
+    const char *name = "get/forbidden";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {403, ""};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 403);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_forbidden(const FfiFfiTodoClient *client) {
+    const char *name = "get/forbidden (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 403, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 403);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_server_error_with_body(const FfiFfiTodoClient *client) {
+    const char *name = "get/server_error_with_body";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {500, "{\"error\":\"database unavailable\"}"};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 500);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_server_error_with_body(const FfiFfiTodoClient *client) {
+    const char *name = "get/server_error_with_body (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 500, (const uint16_t *)u"{\"error\":\"database unavailable\"}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_HTTP);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 500);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_rate_limited(const FfiFfiTodoClient *client) {
+    const char *name = "get/rate_limited";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {429, ""};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_RATE_LIMITED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 429);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_rate_limited(const FfiFfiTodoClient *client) {
+    const char *name = "get/rate_limited (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 429, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_RATE_LIMITED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 429);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_malformed_json(const FfiFfiTodoClient *client) {
+    const char *name = "get/malformed_json";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "{\"id\":"};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_malformed_json(const FfiFfiTodoClient *client) {
+    const char *name = "get/malformed_json (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 200, (const uint16_t *)u"{\"id\":");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_net_free_result(result);
+}
+
+static void get_empty_body(const FfiFfiTodoClient *client) {
+    const char *name = "get/empty_body";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_get_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, ""};
+    FfiFfiTodoResult *result = todo_parse_get_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_free_result(result);
+}
+
+static void net_get_empty_body(const FfiFfiTodoClient *client) {
+    const char *name = "get/empty_body (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_GET,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_get_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_get_todo(client, 200, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_net_free_result(result);
+}
+
+static void create_basic_create(const FfiFfiTodoClient *client) {
+    const char *name = "create/basic_create";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiHttpRequest *req = todo_build_create_todo(client, "Buy milk", false);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {201, "{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Buy milk\",\"completed\":false}"};
+    FfiFfiTodoResult *result = todo_parse_create_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Buy milk", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_create_basic_create(const FfiFfiTodoClient *client) {
+    const char *name = "create/basic_create (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_create_todo(client, (const uint16_t *)u"Buy milk", 0);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_create_todo(client, 201, (const uint16_t *)u"{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Buy milk\",\"completed\":false}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiNetTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Buy milk", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void create_create_with_completed_true(const FfiFfiTodoClient *client) {
+    const char *name = "create/create_with_completed_true";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":true", "\"title\":\"Already done\""}, 2,
+    };
+    FfiFfiHttpRequest *req = todo_build_create_todo(client, "Already done", true);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {201, "{\"id\":\"00000000-0000-0000-0000-000000000002\",\"title\":\"Already done\",\"completed\":true}"};
+    FfiFfiTodoResult *result = todo_parse_create_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000002", "Already done", 1};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_create_create_with_completed_true(const FfiFfiTodoClient *client) {
+    const char *name = "create/create_with_completed_true (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":true", "\"title\":\"Already done\""}, 2,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_create_todo(client, (const uint16_t *)u"Already done", 1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_create_todo(client, 201, (const uint16_t *)u"{\"id\":\"00000000-0000-0000-0000-000000000002\",\"title\":\"Already done\",\"completed\":true}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiNetTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000002", "Already done", 1};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void create_conflict(const FfiFfiTodoClient *client) {
+    const char *name = "create/conflict";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiHttpRequest *req = todo_build_create_todo(client, "Buy milk", false);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {409, "{\"error\":\"duplicate title\"}"};
+    FfiFfiTodoResult *result = todo_parse_create_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_CONFLICT);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 409);
+    }
+    todo_free_result(result);
+}
+
+static void net_create_conflict(const FfiFfiTodoClient *client) {
+    const char *name = "create/conflict (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_create_todo(client, (const uint16_t *)u"Buy milk", 0);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_create_todo(client, 409, (const uint16_t *)u"{\"error\":\"duplicate title\"}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_CONFLICT);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 409);
+    }
+    todo_net_free_result(result);
+}
+
+static void create_empty_body(const FfiFfiTodoClient *client) {
+    const char *name = "create/empty_body";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiHttpRequest *req = todo_build_create_todo(client, "Buy milk", false);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {201, ""};
+    FfiFfiTodoResult *result = todo_parse_create_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_free_result(result);
+}
+
+static void net_create_empty_body(const FfiFfiTodoClient *client) {
+    const char *name = "create/empty_body (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_POST,
+        "http://localhost:3000/todos",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":false", "\"title\":\"Buy milk\""}, 2,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_create_todo(client, (const uint16_t *)u"Buy milk", 0);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_create_todo(client, 201, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_DESERIALIZATION);
+        CHECK(name, result->error_message != NULL);
+    }
+    todo_net_free_result(result);
+}
+
+static void update_update_title_only(const FfiFfiTodoClient *client) {
+    const char *name = "update/update_title_only";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"title\":\"Updated title\""}, 1,
+    };
+    FfiFfiHttpRequest *req = todo_build_update_todo(client, "00000000-0000-0000-0000-000000000001", "Updated title", -1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Updated title\",\"completed\":false}"};
+    FfiFfiTodoResult *result = todo_parse_update_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Updated title", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_update_update_title_only(const FfiFfiTodoClient *client) {
+    const char *name = "update/update_title_only (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"title\":\"Updated title\""}, 1,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_update_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001", (const uint16_t *)u"Updated title", -1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_update_todo(client, 200, (const uint16_t *)u"{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Updated title\",\"completed\":false}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiNetTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Updated title", 0};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void update_update_completed_only(const FfiFfiTodoClient *client) {
+    const char *name = "update/update_completed_only";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":true"}, 1,
+    };
+    FfiFfiHttpRequest *req = todo_build_update_todo(client, "00000000-0000-0000-0000-000000000001", NULL, 1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {200, "{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Test\",\"completed\":true}"};
+    FfiFfiTodoResult *result = todo_parse_update_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Test", 1};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_free_result(result);
+}
+
+static void net_update_update_completed_only(const FfiFfiTodoClient *client) {
+    const char *name = "update/update_completed_only (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"completed\":true"}, 1,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_update_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001", NULL, 1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_update_todo(client, 200, (const uint16_t *)u"{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Test\",\"completed\":true}");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_TODO);
+        if (result->data_tag == FFI_FFI_DATA_TAG_TODO) {
+            const FfiFfiNetTodo *todo = result->data;
+            const ExpectedTodo want = {"00000000-0000-0000-0000-000000000001", "Test", 1};
+            check_todo(name, todo->id, todo->title, todo->completed, &want);
+        }
+    }
+    todo_net_free_result(result);
+}
+
+static void update_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "update/not_found";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"title\":\"Updated title\""}, 1,
+    };
+    FfiFfiHttpRequest *req = todo_build_update_todo(client, "00000000-0000-0000-0000-000000000099", "Updated title", -1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {404, ""};
+    FfiFfiTodoResult *result = todo_parse_update_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_free_result(result);
+}
+
+static void net_update_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "update/not_found (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_PUT,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        (const char *const[]){"content-type", "application/json"}, 1,
+        (const char *const[]){"\"title\":\"Updated title\""}, 1,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_update_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000099", (const uint16_t *)u"Updated title", -1);
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_update_todo(client, 404, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_net_free_result(result);
+}
+
+static void delete_success(const FfiFfiTodoClient *client) {
+    const char *name = "delete/success";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_delete_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {204, ""};
+    FfiFfiTodoResult *result = todo_parse_delete_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_NONE);
+    }
+    todo_free_result(result);
+}
+
+static void net_delete_success(const FfiFfiTodoClient *client) {
+    const char *name = "delete/success (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_delete_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_delete_todo(client, 204, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_OK);
+        CHECK(name, result->data_tag == FFI_FFI_DATA_TAG_NONE);
+    }
+    todo_net_free_result(result);
+}
+
+static void delete_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "delete/not_found";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_delete_todo(client, "00000000-0000-0000-0000-000000000099");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {404, ""};
+    FfiFfiTodoResult *result = todo_parse_delete_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_free_result(result);
+}
+
+static void net_delete_not_found(const FfiFfiTodoClient *client) {
+    const char *name = "delete/not_found (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000099",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_delete_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000099");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_delete_todo(client, 404, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 404);
+    }
+    todo_net_free_result(result);
+}
+
+static void delete_rate_limited(const FfiFfiTodoClient *client) {
+    const char *name = "delete/rate_limited";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiHttpRequest *req = todo_build_delete_todo(client, "00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse response = {429, ""};
+    FfiFfiTodoResult *result = todo_parse_delete_todo(client, &response);
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_RATE_LIMITED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 429);
+    }
+    todo_free_result(result);
+}
+
+static void net_delete_rate_limited(const FfiFfiTodoClient *client) {
+    const char *name = "delete/rate_limited (net)";
+    const Expected expected = {
+        FFI_FFI_HTTP_METHOD_DELETE,
+        "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001",
+        NULL, 0,
+        NULL, 0,
+    };
+    FfiFfiNetHttpRequest *req = todo_net_build_delete_todo(client, (const uint16_t *)u"00000000-0000-0000-0000-000000000001");
+    CHECK(name, req != NULL);
+    if (req) {
+        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);
+    }
+    todo_net_free_request(req);
+
+    FfiFfiNetTodoResult *result = todo_net_parse_delete_todo(client, 429, (const uint16_t *)u"");
+    CHECK(name, result != NULL);
+    if (result) {
+        CHECK(name, result->error_code == FFI_FFI_ERROR_CODE_RATE_LIMITED);
+        CHECK(name, result->error_message != NULL);
+        CHECK(name, result->http_status == 429);
+    }
+    todo_net_free_result(result);
+}
+
+/* Functions no test vector reaches, and argument errors. */
+static void lifecycle(void) {
+    const char *name = "lifecycle";
+
+    FfiFfiTodoClient *client = todo_client_new(BASE_URL);
+    FfiFfiTodoClient *copy = todo_client_clone(client);
+    CHECK(name, copy != NULL && todo_client_eq(client, copy));
+    FfiFfiTodoClient *auth = todo_client_new_basic_auth(BASE_URL, "user", "pass");
+    CHECK(name, auth != NULL && !todo_client_eq(client, auth));
+    CHECK(name, todo_client_eq(NULL, NULL) && !todo_client_eq(client, NULL));
+    CHECK(name, todo_client_new("not a url") == NULL);
+    CHECK(name, todo_client_new_basic_auth(BASE_URL, "us:er", "pass") == NULL);
+    CHECK(name, todo_build_get_todo(client, "not-a-uuid") == NULL);
+    CHECK(name, todo_build_list_todos(NULL) == NULL);
+
+    FfiFfiHttpRequest *req = todo_build_delete_all(client);
+    CHECK(name, req && req->method == FFI_FFI_HTTP_METHOD_DELETE && str_eq(req->path, BASE_URL "/todos"));
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse deleted = {200, "{\"deleted\":3}"};
+    FfiFfiTodoResult *result = todo_parse_delete_all(client, &deleted);
+    CHECK(name, result && result->data_tag == FFI_FFI_DATA_TAG_COUNT && *(const uint64_t *)result->data == 3);
+    todo_free_result(result);
+    result = todo_parse_get_todo(client, NULL);
+    CHECK(name, result && result->error_code == FFI_FFI_ERROR_CODE_NULL_ARG && result->error_message != NULL);
+    todo_free_result(result);
+
+    FfiFfiNetHttpRequest *net_req = todo_net_build_delete_all(client);
+    CHECK(name, net_req && net_req->method == FFI_FFI_HTTP_METHOD_DELETE && str_eq(net_req->path, BASE_URL "/todos"));
+    todo_net_free_request(net_req);
+    FfiFfiNetTodoResult *net_result = todo_net_parse_delete_all(client, 200, (const uint16_t *)u"{\"deleted\":3}");
+    CHECK(name, net_result && net_result->data_tag == FFI_FFI_DATA_TAG_COUNT && *(const uint64_t *)net_result->data == 3);
+    todo_net_free_result(net_result);
+
+    /* Every release function accepts null. */
+    todo_free_request(NULL);
+    todo_free_result(NULL);
+    todo_free_string(NULL);
+    todo_net_free_request(NULL);
+    todo_net_free_result(NULL);
+    todo_client_free(NULL);
+
+    todo_client_free(auth);
+    todo_client_free(copy);
+    todo_client_free(client);
+}
+
+int main(void) {
+    FfiFfiTodoClient *client = todo_client_new(BASE_URL);
+    FfiFfiTodoClient *net_client = todo_net_client_new(BASE_URL_U16);
+    if (!client || !net_client) {
+        fprintf(stderr, "FAIL could not create clients\n");
+        return 1;
+    }
+    list_empty_list(client);
+    net_list_empty_list(net_client);
+    list_two_items(client);
+    net_list_two_items(net_client);
+    list_service_unavailable(client);
+    net_list_service_unavailable(net_client);
+    list_malformed_json(client);
+    net_list_malformed_json(net_client);
+    get_found(client);
+    net_get_found(net_client);
+    get_not_found(client);
+    net_get_not_found(net_client);
+    get_unauthorized(client);
+    net_get_unauthorized(net_client);
+    get_forbidden(client);
+    net_get_forbidden(net_client);
+    get_server_error_with_body(client);
+    net_get_server_error_with_body(net_client);
+    get_rate_limited(client);
+    net_get_rate_limited(net_client);
+    get_malformed_json(client);
+    net_get_malformed_json(net_client);
+    get_empty_body(client);
+    net_get_empty_body(net_client);
+    create_basic_create(client);
+    net_create_basic_create(net_client);
+    create_create_with_completed_true(client);
+    net_create_create_with_completed_true(net_client);
+    create_conflict(client);
+    net_create_conflict(net_client);
+    create_empty_body(client);
+    net_create_empty_body(net_client);
+    update_update_title_only(client);
+    net_update_update_title_only(net_client);
+    update_update_completed_only(client);
+    net_update_update_completed_only(net_client);
+    update_not_found(client);
+    net_update_not_found(net_client);
+    delete_success(client);
+    net_delete_success(net_client);
+    delete_not_found(client);
+    net_delete_not_found(net_client);
+    delete_rate_limited(client);
+    net_delete_rate_limited(net_client);
+    lifecycle();
+    todo_client_free(client);
+    todo_client_free(net_client);
+
+    printf("%d checks, %d failed\n", checks, failures);
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,18 @@
+use std::env;
+
+fn main() {
+    let ffi_include = env::var("DEP_TODO_FFI_INCLUDE").expect("todo-ffi exports its include dir");
+
+    println!("cargo:rerun-if-changed=include/todo/client.hpp");
+    println!("cargo:rerun-if-changed=tests/client_test.cpp");
+    cc::Build::new()
+        .cpp(true)
+        .std("c++17")
+        .include(&ffi_include)
+        .include("include")
+        .file("tests/client_test.cpp")
+        .warnings(true)
+        .extra_warnings(true)
+        .warnings_into_errors(true)
+        .compile("todo_cpp_tests");
+}
@@ -0,0 +1,277 @@
+// Header-only C++17 wrapper around the todo C ABI (todo_client.h).
+//
+// Design:
+// - Every pointer the C API hands out is owned by a std::unique_ptr whose
+//   deleter calls the matching todo_*_free function, so nothing leaks on
+//   early return or exception.
+// - C API failures become todo::Error exceptions carrying the FFI error
+//   code and HTTP status; successful parses return plain values.
+// - Like the C API, nothing here performs I/O: build_* returns a Request to
+//   execute, and parse_* takes the status and body that came back.
+#ifndef TODO_CLIENT_HPP
+#define TODO_CLIENT_HPP
+
+#include <cstddef>
+#include <cstdint>
+#include <iterator>
+#include <memory>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <string_view>
+#include <utility>
+#include <vector>
+
+#include "todo_client.h"
+
+namespace todo {
+
+using ErrorCode = FfiFfiErrorCode;
+
+/// A failed build or parse. `status()` is the HTTP status when the server
+/// answered, 0 otherwise.
+class Error : public std::runtime_error {
+public:
+    Error(ErrorCode code, std::uint16_t status, const std::string& message)
+        : std::runtime_error(message), code_(code), status_(status) {}
+
+    ErrorCode code() const noexcept { return code_; }
+    std::uint16_t status() const noexcept { return status_; }
+
+private:
+    ErrorCode code_;
+    std::uint16_t status_;
+};
+
+namespace detail {
+
+struct ClientDeleter {
+    void operator()(FfiFfiTodoClient* client) const noexcept { todo_client_free(client); }
+};
+
+struct RequestDeleter {
+    void operator()(FfiFfiHttpRequest* request) const noexcept { todo_free_request(request); }
+};
+
+struct ResultDeleter {
+    void operator()(FfiFfiTodoResult* result) const noexcept { todo_free_result(result); }
+};
+
+using ResultPtr = std::unique_ptr<FfiFfiTodoResult, ResultDeleter>;
+
+inline std::string_view view(const char* s) { return s ? std::string_view(s) : std::string_view(); }
+
+/// Take ownership of `raw` and throw if it reports an error.
+inline ResultPtr checked(FfiFfiTodoResult* raw) {
+    ResultPtr result(raw);
+    if (!result) {
+        throw Error(FFI_FFI_ERROR_CODE_PANIC, 0, "todo: parse returned null");
+    }
+    if (result->error_code != FFI_FFI_ERROR_CODE_OK) {
+        throw Error(result->error_code, result->http_status, std::string(view(result->error_message)));
+    }
+    return result;
+}
+
+}  // namespace detail
+
+/// An owned copy of one todo.
+struct Todo {
+    std::string id;
+    std::string title;
+    bool completed = false;
+};
+
+/// Borrowed view of one todo inside a `TodoList`; valid while the list is.
+class TodoView {
+public:
+    explicit TodoView(const FfiFfiTodo* todo) noexcept : todo_(todo) {}
+
+    std::string_view id() const noexcept { return detail::view(todo_->id); }
+    std::string_view title() const noexcept { return detail::view(todo_->title); }
+    bool completed() const noexcept { return todo_->completed; }
+    Todo to_owned() const { return Todo{std::string(id()), std::string(title()), completed()}; }
+
+private:
+    const FfiFfiTodo* todo_;
+};
+
+/// The todos from a list response, iterable without copying.
+class TodoList {
+public:
+    class const_iterator {
+    public:
+        using iterator_category = std::forward_iterator_tag;
+        using value_type = TodoView;
+        using difference_type = std::ptrdiff_t;
+        using pointer = void;
+        using reference = TodoView;
+
+        explicit const_iterator(const FfiFfiTodo* at) noexcept : at_(at) {}
+        TodoView operator*() const noexcept { return TodoView(at_); }
+        const_iterator& operator++() noexcept {
+            ++at_;
+            return *this;
+        }
+        const_iterator operator++(int) noexcept {
+            const_iterator before = *this;
+            ++at_;
+            return before;
+        }
+        bool operator==(const const_iterator& other) const noexcept { return at_ == other.at_; }
+        bool operator!=(const const_iterator& other) const noexcept { return at_ != other.at_; }
+
+    private:
+        const FfiFfiTodo* at_;
+    };
+
+    explicit TodoList(detail::ResultPtr result) : result_(std::move(result)) {}
+
+    std::size_t size() const noexcept { return list().len; }
+    bool empty() const noexcept { return size() == 0; }
+    TodoView operator[](std::size_t i) const noexcept { return TodoView(list().items + i); }
+    const_iterator begin() const noexcept { return const_iterator(list().items); }
+    const_iterator end() const noexcept { return const_iterator(list().items + list().len); }
+
+private:
+    const FfiFfiTodoList& list() const noexcept { return *static_cast<const FfiFfiTodoList*>(result_->data); }
+
+    detail::ResultPtr result_;
+};
+
+/// An HTTP request to execute, as built by `Client`.
+class Request {
+public:
+    explicit Request(FfiFfiHttpRequest* raw) : raw_(raw) {
+        if (!raw_) {
+            throw Error(FFI_FFI_ERROR_CODE_VALIDATION, 0, "todo: invalid request arguments");
+        }
+    }
+
+    FfiFfiHttpMethod method() const noexcept { return raw_->method; }
+    const char* method_name() const noexcept {
+        switch (raw_->method) {
+            case FFI_FFI_HTTP_METHOD_GET: return "GET";
+            case FFI_FFI_HTTP_METHOD_POST: return "POST";
+            case FFI_FFI_HTTP_METHOD_PUT: return "PUT";
+            case FFI_FFI_HTTP_METHOD_DELETE: return "DELETE";
+            case FFI_FFI_HTTP_METHOD_OPTIONS: return "OPTIONS";
+            case FFI_FFI_HTTP_METHOD_TRACE: return "TRACE";
+            case FFI_FFI_HTTP_METHOD_PATCH: return "PATCH";
+            case FFI_FFI_HTTP_METHOD_HEAD: return "HEAD";
+        }
+        return "";
+    }
+    /// Full URL, query string included.
+    std::string_view url() const noexcept { return detail::view(raw_->path); }
+    std::optional<std::string_view> body() const noexcept {
+        if (!raw_->body) {
+            return std::nullopt;
+        }
+        return std::string_view(raw_->body);
+    }
+    std::vector<std::pair<std::string_view, std::string_view>> headers() const {
+        std::vector<std::pair<std::string_view, std::string_view>> out;
+        out.reserve(raw_->headers_len);
+        for (std::uint32_t i = 0; i < raw_->headers_len; ++i) {
+            out.emplace_back(detail::view(raw_->headers[i].key), detail::view(raw_->headers[i].value));
+        }
+        return out;
+    }
+    const FfiFfiHttpRequest& raw() const noexcept { return *raw_; }
+
+private:
+    std::unique_ptr<FfiFfiHttpRequest, detail::RequestDeleter> raw_;
+};
+
+/// Owning handle to a `TodoClient`. Copies clone the client's configuration.
+class Client {
+public:
+    /// Throws `Error` with `FFI_FFI_ERROR_CODE_VALIDATION` for a malformed URL.
+    explicit Client(const std::string& base_url) : raw_(todo_client_new(base_url.c_str())) {
+        if (!raw_) {
+            throw Error(FFI_FFI_ERROR_CODE_VALIDATION, 0, "todo: invalid base URL: " + base_url);
+        }
+    }
+
+    Client(const Client& other) : raw_(todo_client_clone(other.raw_.get())) {
+        if (!raw_) {
+            throw std::bad_alloc();
+        }
+    }
+    Client& operator=(const Client& other) {
+        if (this != &other) {
+            *this = Client(other);
+        }
+        return *this;
+    }
+    Client(Client&&) noexcept = default;
+    Client& operator=(Client&&) noexcept = default;
+
+    bool operator==(const Client& other) const noexcept { return todo_client_eq(raw_.get(), other.raw_.get()); }
+    bool operator!=(const Client& other) const noexcept { return !(*this == other); }
+
+    Request build_list_todos() const { return Request(todo_build_list_todos(raw_.get())); }
+    Request build_get_todo(const std::string& id) const { return Request(todo_build_get_todo(raw_.get(), id.c_str())); }
+    Request build_create_todo(const std::string& title, bool completed = false) const {
+        return Request(todo_build_create_todo(raw_.get(), title.c_str(), completed));
+    }
+    /// Fields left empty are not sent.
+    Request build_update_todo(const std::string& id, const std::optional<std::string>& title,
+                              std::optional<bool> completed) const {
+        const char* title_ptr = title ? title->c_str() : nullptr;
+        std::int32_t completed_flag = completed ? static_cast<std::int32_t>(*completed) : -1;
+        return Request(todo_build_update_todo(raw_.get(), id.c_str(), title_ptr, completed_flag));
+    }
+    Request build_delete_todo(const std::string& id) const {
+        return Request(todo_build_delete_todo(raw_.get(), id.c_str()));
+    }
+    Request build_delete_all() const { return Request(todo_build_delete_all(raw_.get())); }
+
+    TodoList parse_list_todos(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        return TodoList(detail::checked(todo_parse_list_todos(raw_.get(), &response)));
+    }
+    Todo parse_get_todo(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        return single(todo_parse_get_todo(raw_.get(), &response));
+    }
+    Todo parse_create_todo(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        return single(todo_parse_create_todo(raw_.get(), &response));
+    }
+    Todo parse_update_todo(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        return single(todo_parse_update_todo(raw_.get(), &response));
+    }
+    void parse_delete_todo(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        detail::checked(todo_parse_delete_todo(raw_.get(), &response));
+    }
+    /// Returns how many todos the server removed.
+    std::uint64_t parse_delete_all(std::uint16_t status, const std::string& body) const {
+        FfiFfiHttpResponse response = respond(status, body);
+        auto result = detail::checked(todo_parse_delete_all(raw_.get(), &response));
+        return *static_cast<const std::uint64_t*>(result->data);
+    }
+
+    const FfiFfiTodoClient* raw() const noexcept { return raw_.get(); }
+
+private:
+    static FfiFfiHttpResponse respond(std::uint16_t status, const std::string& body) noexcept {
+        FfiFfiHttpResponse response;
+        response.status = status;
+        response.body = body.c_str();
+        return response;
+    }
+    static Todo single(FfiFfiTodoResult* raw) {
+        auto result = detail::checked(raw);
+        return TodoView(static_cast<const FfiFfiTodo*>(result->data)).to_owned();
+    }
+
+    std::unique_ptr<FfiFfiTodoClient, detail::ClientDeleter> raw_;
+};
+
+}  // namespace todo
+
+#endif  // TODO_CLIENT_HPP
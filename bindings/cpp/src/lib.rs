@@ -0,0 +1,16 @@
+//! Build harness for the header-only C++ wrapper in `include/todo/client.hpp`.
+//!
+//! # Design
+//! The wrapper ships as a header, so there is nothing to compile for users.
+//! This crate exists so the workspace compiles and runs it: `build.rs`
+//! builds `tests/client_test.cpp` with `cc`, and `tests/cpp.rs` calls into
+//! it. Depending on `todo-ffi` links the C ABI the C++ code calls.
+
+use std::os::raw::c_int;
+
+use todo_ffi as _;
+
+extern "C" {
+    /// Runs the C++ test suite; returns the number of failed checks.
+    pub fn todo_cpp_run_tests() -> c_int;
+}
@@ -0,0 +1,109 @@
+// Tests for include/todo/client.hpp, compiled by build.rs and run from
+// tests/cpp.rs through todo_cpp_run_tests().
+#include <cstdio>
+#include <string>
+#include <vector>
+
+#include "todo/client.hpp"
+
+namespace {
+
+int failures = 0;
+
+#define CHECK(cond)                                                          \
+    do {                                                                     \
+        if (!(cond)) {                                                       \
+            std::fprintf(stderr, "%s:%d: CHECK failed: %s\n", __FILE__, __LINE__, #cond); \
+            ++failures;                                                      \
+        }                                                                    \
+    } while (0)
+
+const char* kId = "00000000-0000-0000-0000-000000000001";
+
+void builds_requests() {
+    todo::Client client("http://localhost:3000");
+
+    todo::Request list = client.build_list_todos();
+    CHECK(list.method() == FFI_FFI_HTTP_METHOD_GET);
+    CHECK(list.url() == "http://localhost:3000/todos");
+    CHECK(!list.body());
+
+    todo::Request create = client.build_create_todo("Buy milk");
+    CHECK(std::string(create.method_name()) == "POST");
+    CHECK(create.body() && create.body()->find("\"Buy milk\"") != std::string_view::npos);
+    auto headers = create.headers();
+    CHECK(headers.size() == 1 && headers[0].first == "content-type");
+
+    todo::Request update = client.build_update_todo(kId, std::nullopt, true);
+    CHECK(update.body() && *update.body() == "{\"completed\":true}");
+}
+
+void rejects_bad_input() {
+    bool threw = false;
+    try {
+        todo::Client client("localhost:3000");
+    } catch (const todo::Error& e) {
+        threw = e.code() == FFI_FFI_ERROR_CODE_VALIDATION;
+    }
+    CHECK(threw);
+
+    todo::Client client("http://localhost:3000");
+    threw = false;
+    try {
+        client.build_get_todo("not-a-uuid");
+    } catch (const todo::Error&) {
+        threw = true;
+    }
+    CHECK(threw);
+}
+
+void parses_lists_with_iterators() {
+    todo::Client client("http://localhost:3000");
+    std::string body = std::string("[{\"id\":\"") + kId + "\",\"title\":\"A\",\"completed\":false}," +
+                       "{\"id\":\"" + kId + "\",\"title\":\"B\",\"completed\":true}]";
+    todo::TodoList todos = client.parse_list_todos(200, body);
+    CHECK(todos.size() == 2);
+
+    std::vector<std::string> titles;
+    for (todo::TodoView todo : todos) {
+        titles.emplace_back(todo.title());
+    }
+    CHECK((titles == std::vector<std::string>{"A", "B"}));
+    CHECK(todos[1].completed());
+    CHECK(client.parse_list_todos(200, "[]").empty());
+}
+
+void maps_errors_to_exceptions() {
+    todo::Client client("http://localhost:3000");
+    try {
+        client.parse_get_todo(404, "");
+        CHECK(false);
+    } catch (const todo::Error& e) {
+        CHECK(e.code() == FFI_FFI_ERROR_CODE_NOT_FOUND);
+        CHECK(e.status() == 404);
+    }
+
+    std::string body = std::string("{\"id\":\"") + kId + "\",\"title\":\"A\",\"completed\":true}";
+    todo::Todo todo = client.parse_create_todo(201, body);
+    CHECK(todo.id == kId && todo.title == "A" && todo.completed);
+    CHECK(client.parse_delete_all(200, "{\"deleted\":3}") == 3);
+}
+
+void copies_are_equal_clients() {
+    todo::Client client("http://localhost:3000");
+    todo::Client copy = client;
+    CHECK(copy == client);
+    todo::Client moved = std::move(copy);
+    CHECK(moved.build_list_todos().url() == "http://localhost:3000/todos");
+}
+
+}  // namespace
+
+extern "C" int todo_cpp_run_tests() {
+    builds_requests();
+    rejects_bad_input();
+    parses_lists_with_iterators();
+    maps_errors_to_exceptions();
+    copies_are_equal_clients();
+    return failures;
+}
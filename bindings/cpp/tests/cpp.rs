@@ -0,0 +1,7 @@
+//! Runs the compiled C++ wrapper tests.
+
+#[test]
+fn cpp_wrapper_suite_passes() {
+    let failures = unsafe { todo_cpp::todo_cpp_run_tests() };
+    assert_eq!(failures, 0, "C++ checks failed; see stderr");
+}
@@ -0,0 +1,554 @@
+// AUTO GENERATED FILE, DO NOT EDIT.
+//
+// Generated by todo-bindgen from todo_client.h, in the layout of
+// package:ffigen output. Regenerate with
+// `cargo run -p todo-bindgen -- dart bindings/dart/lib/src`.
+// ignore_for_file: camel_case_types, non_constant_identifier_names
+// ignore_for_file: constant_identifier_names, unused_field
+
+import 'dart:ffi' as ffi;
+
+/// Raw bindings to the todo_ffi C library.
+class TodoBindings {
+  /// Holds the symbol lookup function.
+  final ffi.Pointer<T> Function<T extends ffi.NativeType>(String symbolName) _lookup;
+
+  /// The symbols are looked up in [dynamicLibrary].
+  TodoBindings(ffi.DynamicLibrary dynamicLibrary) : _lookup = dynamicLibrary.lookup;
+
+  ffi.Pointer<FfiFfiTodoClient> todo_client_new(
+    ffi.Pointer<ffi.Char> base_url,
+  ) {
+    return _todo_client_new(base_url);
+  }
+
+  late final todo_client_newPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Char>)>>('todo_client_new');
+  late final _todo_client_new = todo_client_newPtr.asFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Char>)>();
+
+  ffi.Pointer<FfiFfiTodoClient> todo_client_new_basic_auth(
+    ffi.Pointer<ffi.Char> base_url,
+    ffi.Pointer<ffi.Char> user,
+    ffi.Pointer<ffi.Char> pass,
+  ) {
+    return _todo_client_new_basic_auth(base_url, user, pass);
+  }
+
+  late final todo_client_new_basic_authPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>)>>('todo_client_new_basic_auth');
+  late final _todo_client_new_basic_auth = todo_client_new_basic_authPtr.asFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>)>();
+
+  void todo_client_free(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_client_free(client);
+  }
+
+  late final todo_client_freePtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_client_free');
+  late final _todo_client_free = todo_client_freePtr.asFunction<void Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiTodoClient> todo_client_clone(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_client_clone(client);
+  }
+
+  late final todo_client_clonePtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_client_clone');
+  late final _todo_client_clone = todo_client_clonePtr.asFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  bool todo_client_eq(
+    ffi.Pointer<FfiFfiTodoClient> a,
+    ffi.Pointer<FfiFfiTodoClient> b,
+  ) {
+    return _todo_client_eq(a, b);
+  }
+
+  late final todo_client_eqPtr = _lookup<ffi.NativeFunction<ffi.Bool Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiTodoClient>)>>('todo_client_eq');
+  late final _todo_client_eq = todo_client_eqPtr.asFunction<bool Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_list_todos(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_build_list_todos(client);
+  }
+
+  late final todo_build_list_todosPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_build_list_todos');
+  late final _todo_build_list_todos = todo_build_list_todosPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_get_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Char> id,
+  ) {
+    return _todo_build_get_todo(client, id);
+  }
+
+  late final todo_build_get_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>)>>('todo_build_get_todo');
+  late final _todo_build_get_todo = todo_build_get_todoPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_create_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Char> title,
+    bool completed,
+  ) {
+    return _todo_build_create_todo(client, title, completed);
+  }
+
+  late final todo_build_create_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>, ffi.Bool)>>('todo_build_create_todo');
+  late final _todo_build_create_todo = todo_build_create_todoPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>, bool)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_update_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Char> id,
+    ffi.Pointer<ffi.Char> title,
+    int completed,
+  ) {
+    return _todo_build_update_todo(client, id, title, completed);
+  }
+
+  late final todo_build_update_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>, ffi.Int32)>>('todo_build_update_todo');
+  late final _todo_build_update_todo = todo_build_update_todoPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>, ffi.Pointer<ffi.Char>, int)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_delete_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Char> id,
+  ) {
+    return _todo_build_delete_todo(client, id);
+  }
+
+  late final todo_build_delete_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>)>>('todo_build_delete_todo');
+  late final _todo_build_delete_todo = todo_build_delete_todoPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Char>)>();
+
+  ffi.Pointer<FfiFfiHttpRequest> todo_build_delete_all(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_build_delete_all(client);
+  }
+
+  late final todo_build_delete_allPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_build_delete_all');
+  late final _todo_build_delete_all = todo_build_delete_allPtr.asFunction<ffi.Pointer<FfiFfiHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_list_todos(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_list_todos(client, response);
+  }
+
+  late final todo_parse_list_todosPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_list_todos');
+  late final _todo_parse_list_todos = todo_parse_list_todosPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_get_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_get_todo(client, response);
+  }
+
+  late final todo_parse_get_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_get_todo');
+  late final _todo_parse_get_todo = todo_parse_get_todoPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_create_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_create_todo(client, response);
+  }
+
+  late final todo_parse_create_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_create_todo');
+  late final _todo_parse_create_todo = todo_parse_create_todoPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_update_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_update_todo(client, response);
+  }
+
+  late final todo_parse_update_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_update_todo');
+  late final _todo_parse_update_todo = todo_parse_update_todoPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_delete_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_delete_todo(client, response);
+  }
+
+  late final todo_parse_delete_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_delete_todo');
+  late final _todo_parse_delete_todo = todo_parse_delete_todoPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  ffi.Pointer<FfiFfiTodoResult> todo_parse_delete_all(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<FfiFfiHttpResponse> response,
+  ) {
+    return _todo_parse_delete_all(client, response);
+  }
+
+  late final todo_parse_delete_allPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>>('todo_parse_delete_all');
+  late final _todo_parse_delete_all = todo_parse_delete_allPtr.asFunction<ffi.Pointer<FfiFfiTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<FfiFfiHttpResponse>)>();
+
+  void todo_free_request(
+    ffi.Pointer<FfiFfiHttpRequest> req,
+  ) {
+    return _todo_free_request(req);
+  }
+
+  late final todo_free_requestPtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<FfiFfiHttpRequest>)>>('todo_free_request');
+  late final _todo_free_request = todo_free_requestPtr.asFunction<void Function(ffi.Pointer<FfiFfiHttpRequest>)>();
+
+  void todo_free_result(
+    ffi.Pointer<FfiFfiTodoResult> result,
+  ) {
+    return _todo_free_result(result);
+  }
+
+  late final todo_free_resultPtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<FfiFfiTodoResult>)>>('todo_free_result');
+  late final _todo_free_result = todo_free_resultPtr.asFunction<void Function(ffi.Pointer<FfiFfiTodoResult>)>();
+
+  void todo_free_string(
+    ffi.Pointer<ffi.Char> s,
+  ) {
+    return _todo_free_string(s);
+  }
+
+  late final todo_free_stringPtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Char>)>>('todo_free_string');
+  late final _todo_free_string = todo_free_stringPtr.asFunction<void Function(ffi.Pointer<ffi.Char>)>();
+
+  ffi.Pointer<FfiFfiTodoClient> todo_net_client_new(
+    ffi.Pointer<ffi.Uint16> base_url,
+  ) {
+    return _todo_net_client_new(base_url);
+  }
+
+  late final todo_net_client_newPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Uint16>)>>('todo_net_client_new');
+  late final _todo_net_client_new = todo_net_client_newPtr.asFunction<ffi.Pointer<FfiFfiTodoClient> Function(ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_list_todos(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_net_build_list_todos(client);
+  }
+
+  late final todo_net_build_list_todosPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_net_build_list_todos');
+  late final _todo_net_build_list_todos = todo_net_build_list_todosPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_get_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Uint16> id,
+  ) {
+    return _todo_net_build_get_todo(client, id);
+  }
+
+  late final todo_net_build_get_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>)>>('todo_net_build_get_todo');
+  late final _todo_net_build_get_todo = todo_net_build_get_todoPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_create_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Uint16> title,
+    int completed,
+  ) {
+    return _todo_net_build_create_todo(client, title, completed);
+  }
+
+  late final todo_net_build_create_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>, ffi.Uint8)>>('todo_net_build_create_todo');
+  late final _todo_net_build_create_todo = todo_net_build_create_todoPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>, int)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_update_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Uint16> id,
+    ffi.Pointer<ffi.Uint16> title,
+    int completed,
+  ) {
+    return _todo_net_build_update_todo(client, id, title, completed);
+  }
+
+  late final todo_net_build_update_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>, ffi.Pointer<ffi.Uint16>, ffi.Int32)>>('todo_net_build_update_todo');
+  late final _todo_net_build_update_todo = todo_net_build_update_todoPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>, ffi.Pointer<ffi.Uint16>, int)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_delete_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    ffi.Pointer<ffi.Uint16> id,
+  ) {
+    return _todo_net_build_delete_todo(client, id);
+  }
+
+  late final todo_net_build_delete_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>)>>('todo_net_build_delete_todo');
+  late final _todo_net_build_delete_todo = todo_net_build_delete_todoPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetHttpRequest> todo_net_build_delete_all(
+    ffi.Pointer<FfiFfiTodoClient> client,
+  ) {
+    return _todo_net_build_delete_all(client);
+  }
+
+  late final todo_net_build_delete_allPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>>('todo_net_build_delete_all');
+  late final _todo_net_build_delete_all = todo_net_build_delete_allPtr.asFunction<ffi.Pointer<FfiFfiNetHttpRequest> Function(ffi.Pointer<FfiFfiTodoClient>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_list_todos(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_list_todos(client, status, body);
+  }
+
+  late final todo_net_parse_list_todosPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_list_todos');
+  late final _todo_net_parse_list_todos = todo_net_parse_list_todosPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_get_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_get_todo(client, status, body);
+  }
+
+  late final todo_net_parse_get_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_get_todo');
+  late final _todo_net_parse_get_todo = todo_net_parse_get_todoPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_create_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_create_todo(client, status, body);
+  }
+
+  late final todo_net_parse_create_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_create_todo');
+  late final _todo_net_parse_create_todo = todo_net_parse_create_todoPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_update_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_update_todo(client, status, body);
+  }
+
+  late final todo_net_parse_update_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_update_todo');
+  late final _todo_net_parse_update_todo = todo_net_parse_update_todoPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_delete_todo(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_delete_todo(client, status, body);
+  }
+
+  late final todo_net_parse_delete_todoPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_delete_todo');
+  late final _todo_net_parse_delete_todo = todo_net_parse_delete_todoPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  ffi.Pointer<FfiFfiNetTodoResult> todo_net_parse_delete_all(
+    ffi.Pointer<FfiFfiTodoClient> client,
+    int status,
+    ffi.Pointer<ffi.Uint16> body,
+  ) {
+    return _todo_net_parse_delete_all(client, status, body);
+  }
+
+  late final todo_net_parse_delete_allPtr = _lookup<ffi.NativeFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>('todo_net_parse_delete_all');
+  late final _todo_net_parse_delete_all = todo_net_parse_delete_allPtr.asFunction<ffi.Pointer<FfiFfiNetTodoResult> Function(ffi.Pointer<FfiFfiTodoClient>, int, ffi.Pointer<ffi.Uint16>)>();
+
+  void todo_net_free_request(
+    ffi.Pointer<FfiFfiNetHttpRequest> req,
+  ) {
+    return _todo_net_free_request(req);
+  }
+
+  late final todo_net_free_requestPtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<FfiFfiNetHttpRequest>)>>('todo_net_free_request');
+  late final _todo_net_free_request = todo_net_free_requestPtr.asFunction<void Function(ffi.Pointer<FfiFfiNetHttpRequest>)>();
+
+  void todo_net_free_result(
+    ffi.Pointer<FfiFfiNetTodoResult> result,
+  ) {
+    return _todo_net_free_result(result);
+  }
+
+  late final todo_net_free_resultPtr = _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<FfiFfiNetTodoResult>)>>('todo_net_free_result');
+  late final _todo_net_free_result = todo_net_free_resultPtr.asFunction<void Function(ffi.Pointer<FfiFfiNetTodoResult>)>();
+}
+
+final class FfiFfiTodoClient extends ffi.Opaque {}
+
+final class FfiFfiHeader extends ffi.Struct {
+  external ffi.Pointer<ffi.Char> key;
+
+  external ffi.Pointer<ffi.Char> value;
+}
+
+final class FfiFfiHttpRequest extends ffi.Struct {
+  @ffi.UnsignedInt()
+  external int method;
+
+  external ffi.Pointer<ffi.Char> path;
+
+  external ffi.Pointer<FfiFfiHeader> headers;
+
+  @ffi.Uint32()
+  external int headers_len;
+
+  external ffi.Pointer<ffi.Char> body;
+}
+
+final class FfiFfiTodoResult extends ffi.Struct {
+  @ffi.UnsignedInt()
+  external int error_code;
+
+  external ffi.Pointer<ffi.Char> error_message;
+
+  @ffi.Uint16()
+  external int http_status;
+
+  @ffi.UnsignedInt()
+  external int data_tag;
+
+  external ffi.Pointer<ffi.Void> data;
+}
+
+final class FfiFfiHttpResponse extends ffi.Struct {
+  @ffi.Uint16()
+  external int status;
+
+  external ffi.Pointer<ffi.Char> body;
+}
+
+final class FfiFfiNetHttpRequest extends ffi.Struct {
+  @ffi.Int32()
+  external int method;
+
+  external ffi.Pointer<ffi.Char> path;
+
+  external ffi.Pointer<FfiFfiHeader> headers;
+
+  @ffi.Uint32()
+  external int headers_len;
+
+  external ffi.Pointer<ffi.Char> body;
+}
+
+final class FfiFfiNetTodoResult extends ffi.Struct {
+  @ffi.Int32()
+  external int error_code;
+
+  external ffi.Pointer<ffi.Char> error_message;
+
+  @ffi.Uint16()
+  external int http_status;
+
+  @ffi.Int32()
+  external int data_tag;
+
+  external ffi.Pointer<ffi.Void> data;
+}
+
+final class FfiFfiTodo extends ffi.Struct {
+  external ffi.Pointer<ffi.Char> id;
+
+  external ffi.Pointer<ffi.Char> title;
+
+  @ffi.Bool()
+  external bool completed;
+}
+
+final class FfiFfiTodoList extends ffi.Struct {
+  external ffi.Pointer<FfiFfiTodo> items;
+
+  @ffi.Uint32()
+  external int len;
+}
+
+final class FfiFfiNetTodo extends ffi.Struct {
+  external ffi.Pointer<ffi.Char> id;
+
+  external ffi.Pointer<ffi.Char> title;
+
+  @ffi.Uint8()
+  external int completed;
+}
+
+final class FfiFfiNetTodoList extends ffi.Struct {
+  external ffi.Pointer<FfiFfiNetTodo> items;
+
+  @ffi.Uint32()
+  external int len;
+}
+
+/// Values of the C enum `FfiFfiDataTag`.
+abstract class FfiFfiDataTag {
+  static const int FFI_FFI_DATA_TAG_NONE = 0;
+  static const int FFI_FFI_DATA_TAG_TODO = 1;
+  static const int FFI_FFI_DATA_TAG_TODO_LIST = 2;
+  static const int FFI_FFI_DATA_TAG_COUNT = 3;
+
+  /// The variant's name without the enum prefix, e.g. `GET`.
+  static String name(int value) => switch (value) {
+        0 => 'NONE',
+        1 => 'TODO',
+        2 => 'TODO_LIST',
+        3 => 'COUNT',
+        _ => 'FfiFfiDataTag($value)',
+      };
+}
+
+/// Values of the C enum `FfiFfiErrorCode`.
+abstract class FfiFfiErrorCode {
+  static const int FFI_FFI_ERROR_CODE_OK = 0;
+  static const int FFI_FFI_ERROR_CODE_NOT_FOUND = 1;
+  static const int FFI_FFI_ERROR_CODE_HTTP = 2;
+  static const int FFI_FFI_ERROR_CODE_DESERIALIZATION = 3;
+  static const int FFI_FFI_ERROR_CODE_SERIALIZATION = 4;
+  static const int FFI_FFI_ERROR_CODE_PANIC = 5;
+  static const int FFI_FFI_ERROR_CODE_NULL_ARG = 6;
+  static const int FFI_FFI_ERROR_CODE_CONFLICT = 7;
+  static const int FFI_FFI_ERROR_CODE_VALIDATION = 8;
+  static const int FFI_FFI_ERROR_CODE_RATE_LIMITED = 9;
+  static const int FFI_FFI_ERROR_CODE_TRANSPORT = 10;
+  static const int FFI_FFI_ERROR_CODE_SCHEMA_MISMATCH = 11;
+  static const int FFI_FFI_ERROR_CODE_TLS = 12;
+  static const int FFI_FFI_ERROR_CODE_PRECONDITION_FAILED = 13;
+  static const int FFI_FFI_ERROR_CODE_UNAUTHORIZED = 14;
+
+  /// The variant's name without the enum prefix, e.g. `GET`.
+  static String name(int value) => switch (value) {
+        0 => 'OK',
+        1 => 'NOT_FOUND',
+        2 => 'HTTP',
+        3 => 'DESERIALIZATION',
+        4 => 'SERIALIZATION',
+        5 => 'PANIC',
+        6 => 'NULL_ARG',
+        7 => 'CONFLICT',
+        8 => 'VALIDATION',
+        9 => 'RATE_LIMITED',
+        10 => 'TRANSPORT',
+        11 => 'SCHEMA_MISMATCH',
+        12 => 'TLS',
+        13 => 'PRECONDITION_FAILED',
+        14 => 'UNAUTHORIZED',
+        _ => 'FfiFfiErrorCode($value)',
+      };
+}
+
+/// Values of the C enum `FfiFfiHttpMethod`.
+abstract class FfiFfiHttpMethod {
+  static const int FFI_FFI_HTTP_METHOD_GET = 0;
+  static const int FFI_FFI_HTTP_METHOD_POST = 1;
+  static const int FFI_FFI_HTTP_METHOD_PUT = 2;
+  static const int FFI_FFI_HTTP_METHOD_DELETE = 3;
+  static const int FFI_FFI_HTTP_METHOD_OPTIONS = 4;
+  static const int FFI_FFI_HTTP_METHOD_TRACE = 5;
+  static const int FFI_FFI_HTTP_METHOD_PATCH = 6;
+  static const int FFI_FFI_HTTP_METHOD_HEAD = 7;
+
+  /// The variant's name without the enum prefix, e.g. `GET`.
+  static String name(int value) => switch (value) {
+        0 => 'GET',
+        1 => 'POST',
+        2 => 'PUT',
+        3 => 'DELETE',
+        4 => 'OPTIONS',
+        5 => 'TRACE',
+        6 => 'PATCH',
+        7 => 'HEAD',
+        _ => 'FfiFfiHttpMethod($value)',
+      };
+}
@@ -0,0 +1,222 @@
+/// Dart and Flutter bindings for the todo client core.
+///
+/// As with every binding, the host does the I/O: each call builds a request
+/// through the C library, hands it to the [Transport] the caller supplied,
+/// and parses the response that comes back. Build the native library with
+/// `cargo build -p todo-ffi --release` and ship it with the app.
+library todo;
+
+import 'dart:convert';
+import 'dart:ffi';
+import 'dart:io';
+
+import 'package:ffi/ffi.dart';
+
+import 'src/bindings.g.dart';
+
+export 'src/bindings.g.dart' show TodoBindings, FfiFfiErrorCode;
+
+/// A request for the [Transport] to send.
+class TodoRequest {
+  const TodoRequest(this.method, this.url, this.headers, this.body);
+
+  final String method;
+
+  /// Full URL, query string included.
+  final String url;
+
+  final List<(String, String)> headers;
+  final String? body;
+}
+
+/// What the [Transport] received for a [TodoRequest].
+class TodoResponse {
+  const TodoResponse(this.status, this.body);
+
+  final int status;
+  final String body;
+}
+
+/// Sends a request and completes with its response. Supplied by the host,
+/// e.g. [ioTransport] or an adapter over `package:http`.
+typedef Transport = Future<TodoResponse> Function(TodoRequest request);
+
+/// A single todo item.
+class Todo {
+  const Todo({required this.id, required this.title, required this.completed});
+
+  final String id;
+  final String title;
+  final bool completed;
+
+  @override
+  String toString() => 'Todo($id, $title, completed: $completed)';
+}
+
+/// A failed call, as reported by the C library.
+class TodoException implements Exception {
+  const TodoException(this.code, this.status, this.message);
+
+  /// One of the [FfiFfiErrorCode] constants.
+  final int code;
+
+  /// The HTTP status, or 0 when no response was involved.
+  final int status;
+
+  final String message;
+
+  @override
+  String toString() => 'TodoException(${FfiFfiErrorCode.name(code)}, $status): $message';
+}
+
+/// A [Transport] over `dart:io`'s [HttpClient].
+Transport ioTransport([HttpClient? client]) {
+  final http = client ?? HttpClient();
+  return (request) async {
+    final req = await http.openUrl(request.method, Uri.parse(request.url));
+    for (final (name, value) in request.headers) {
+      req.headers.set(name, value);
+    }
+    final body = request.body;
+    if (body != null) {
+      req.add(utf8.encode(body));
+    }
+    final resp = await req.close();
+    return TodoResponse(resp.statusCode, await resp.transform(utf8.decoder).join());
+  };
+}
+
+TodoBindings _open() {
+  if (Platform.isIOS) {
+    return TodoBindings(DynamicLibrary.process());
+  }
+  final name = Platform.isMacOS
+      ? 'libtodo_ffi.dylib'
+      : Platform.isWindows
+          ? 'todo_ffi.dll'
+          : 'libtodo_ffi.so';
+  return TodoBindings(DynamicLibrary.open(name));
+}
+
+final TodoBindings _ffi = _open();
+
+typedef _Parse = Pointer<FfiFfiTodoResult> Function(
+    Pointer<FfiFfiTodoClient> client, Pointer<FfiFfiHttpResponse> response);
+
+/// Client for the todo API.
+///
+/// The native client is freed by [close], or by a [NativeFinalizer] once
+/// this object is garbage collected. Requests and results are copied into
+/// Dart objects and freed as soon as they are read.
+class TodoClient implements Finalizable {
+  TodoClient(String baseUrl, {required this.transport}) : _ptr = _newClient(baseUrl) {
+    _finalizer.attach(this, _ptr.cast(), detach: this);
+  }
+
+  static final _finalizer = NativeFinalizer(_ffi.todo_client_freePtr.cast());
+
+  static Pointer<FfiFfiTodoClient> _newClient(String baseUrl) {
+    final ptr = using((arena) => _ffi.todo_client_new(_cString(baseUrl, arena)));
+    if (ptr == nullptr) {
+      throw ArgumentError.value(baseUrl, 'baseUrl', 'not a valid base URL');
+    }
+    return ptr;
+  }
+
+  final Transport transport;
+  Pointer<FfiFfiTodoClient> _ptr;
+
+  /// Frees the native client now instead of at garbage collection. Calls
+  /// made afterwards fail with a [TodoException].
+  void close() {
+    if (_ptr == nullptr) {
+      return;
+    }
+    _finalizer.detach(this);
+    _ffi.todo_client_free(_ptr);
+    _ptr = nullptr;
+  }
+
+  Future<List<Todo>> listTodos() =>
+      _call((_) => _ffi.todo_build_list_todos(_ptr), _ffi.todo_parse_list_todos, _todoList);
+
+  Future<Todo> getTodo(String id) =>
+      _call((a) => _ffi.todo_build_get_todo(_ptr, _cString(id, a)), _ffi.todo_parse_get_todo, _todo);
+
+  Future<Todo> createTodo(String title, {bool completed = false}) => _call(
+      (a) => _ffi.todo_build_create_todo(_ptr, _cString(title, a), completed),
+      _ffi.todo_parse_create_todo,
+      _todo);
+
+  /// Updates the fields that are given; at least one must be.
+  Future<Todo> updateTodo(String id, {String? title, bool? completed}) => _call(
+      (a) => _ffi.todo_build_update_todo(_ptr, _cString(id, a),
+          title == null ? nullptr : _cString(title, a), completed == null ? -1 : (completed ? 1 : 0)),
+      _ffi.todo_parse_update_todo,
+      _todo);
+
+  Future<void> deleteTodo(String id) =>
+      _call((a) => _ffi.todo_build_delete_todo(_ptr, _cString(id, a)), _ffi.todo_parse_delete_todo, (_) {});
+
+  /// Deletes every todo and completes with how many there were.
+  Future<int> deleteAll() =>
+      _call((_) => _ffi.todo_build_delete_all(_ptr), _ffi.todo_parse_delete_all, _count);
+
+  Future<T> _call<T>(
+    Pointer<FfiFfiHttpRequest> Function(Arena arena) build,
+    _Parse parse,
+    T Function(FfiFfiTodoResult result) decode,
+  ) async {
+    final request = using((arena) => _takeRequest(build(arena)));
+    final response = await transport(request);
+    return using((arena) {
+      final native = arena<FfiFfiHttpResponse>();
+      native.ref.status = response.status;
+      native.ref.body = _cString(response.body, arena);
+      return _takeResult(parse(_ptr, native), decode);
+    });
+  }
+}
+
+Pointer<Char> _cString(String s, Arena arena) => s.toNativeUtf8(allocator: arena).cast();
+
+String _string(Pointer<Char> p) => p.cast<Utf8>().toDartString();
+
+TodoRequest _takeRequest(Pointer<FfiFfiHttpRequest> ptr) {
+  if (ptr == nullptr) {
+    throw const TodoException(FfiFfiErrorCode.FFI_FFI_ERROR_CODE_VALIDATION, 0, 'request rejected: invalid arguments');
+  }
+  try {
+    final req = ptr.ref;
+    final headers = [
+      for (var i = 0; i < req.headers_len; i++) (_string(req.headers[i].key), _string(req.headers[i].value)),
+    ];
+    final body = req.body == nullptr ? null : _string(req.body);
+    return TodoRequest(FfiFfiHttpMethod.name(req.method), _string(req.path), headers, body);
+  } finally {
+    _ffi.todo_free_request(ptr);
+  }
+}
+
+T _takeResult<T>(Pointer<FfiFfiTodoResult> ptr, T Function(FfiFfiTodoResult result) decode) {
+  try {
+    final result = ptr.ref;
+    if (result.error_code != FfiFfiErrorCode.FFI_FFI_ERROR_CODE_OK) {
+      throw TodoException(result.error_code, result.http_status, _string(result.error_message));
+    }
+    return decode(result);
+  } finally {
+    _ffi.todo_free_result(ptr);
+  }
+}
+
+Todo _readTodo(FfiFfiTodo t) => Todo(id: _string(t.id), title: _string(t.title), completed: t.completed);
+
+Todo _todo(FfiFfiTodoResult result) => _readTodo(result.data.cast<FfiFfiTodo>().ref);
+
+List<Todo> _todoList(FfiFfiTodoResult result) {
+  final list = result.data.cast<FfiFfiTodoList>().ref;
+  return [for (var i = 0; i < list.len; i++) _readTodo(list.items[i])];
+}
+
+int _count(FfiFfiTodoResult result) => result.data.cast<Uint64>().value;
@@ -0,0 +1,211 @@
+// <auto-generated>
+// Generated by todo-bindgen from todo_client.h. Do not edit.
+// </auto-generated>
+//
+// Interop layer for the todo_ffi library. As with every binding, the host
+// owns HTTP: a todo_net_build_* call returns a RequestHandle describing the
+// request to send, and todo_net_parse_* decodes the response into a
+// ResultHandle. Argument semantics are documented on the C functions.
+
+using System;
+using System.Runtime.InteropServices;
+
+namespace Todo.Interop;
+
+/// <summary>Values of the C enum <c>FfiFfiDataTag</c>.</summary>
+public enum DataTag : int
+{
+    None = 0,
+    Todo = 1,
+    TodoList = 2,
+    Count = 3,
+}
+
+/// <summary>Values of the C enum <c>FfiFfiErrorCode</c>.</summary>
+public enum ErrorCode : int
+{
+    Ok = 0,
+    NotFound = 1,
+    Http = 2,
+    Deserialization = 3,
+    Serialization = 4,
+    Panic = 5,
+    NullArg = 6,
+    Conflict = 7,
+    Validation = 8,
+    RateLimited = 9,
+    Transport = 10,
+    SchemaMismatch = 11,
+    Tls = 12,
+    PreconditionFailed = 13,
+    Unauthorized = 14,
+}
+
+/// <summary>Values of the C enum <c>FfiFfiHttpMethod</c>.</summary>
+public enum HttpMethod : int
+{
+    Get = 0,
+    Post = 1,
+    Put = 2,
+    Delete = 3,
+    Options = 4,
+    Trace = 5,
+    Patch = 6,
+    Head = 7,
+}
+
+/// <summary>Blittable mirror of <c>FfiFfiHeader</c>.</summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct Header
+{
+    public IntPtr Key;
+    public IntPtr Value;
+}
+
+/// <summary>Blittable mirror of <c>FfiFfiNetHttpRequest</c>.</summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct HttpRequest
+{
+    public int Method;
+    public IntPtr Path;
+    public IntPtr Headers;
+    public uint HeadersLen;
+    public IntPtr Body;
+}
+
+/// <summary>Blittable mirror of <c>FfiFfiNetTodoResult</c>.</summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct TodoResult
+{
+    public int ErrorCode;
+    public IntPtr ErrorMessage;
+    public ushort HttpStatus;
+    public int DataTag;
+    public IntPtr Data;
+}
+
+/// <summary>Blittable mirror of <c>FfiFfiNetTodo</c>.</summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct Todo
+{
+    public IntPtr Id;
+    public IntPtr Title;
+    public byte Completed;
+}
+
+/// <summary>Blittable mirror of <c>FfiFfiNetTodoList</c>.</summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct TodoList
+{
+    public IntPtr Items;
+    public uint Len;
+}
+
+/// <summary>Owns a <c>FfiFfiTodoClient*</c>; released with <c>todo_client_free</c>.</summary>
+public sealed class ClientHandle : SafeHandle
+{
+    public ClientHandle() : base(IntPtr.Zero, ownsHandle: true) { }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    protected override bool ReleaseHandle()
+    {
+        NativeMethods.todo_client_free(handle);
+        return true;
+    }
+}
+
+/// <summary>Owns a <c>FfiFfiNetHttpRequest*</c>; released with <c>todo_net_free_request</c>.</summary>
+public sealed class RequestHandle : SafeHandle
+{
+    public RequestHandle() : base(IntPtr.Zero, ownsHandle: true) { }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    /// <summary>Copies the native struct. Strings stay native; read them with <c>Marshal.PtrToStringUTF8</c>.</summary>
+    public HttpRequest Read() => Marshal.PtrToStructure<HttpRequest>(handle);
+
+    protected override bool ReleaseHandle()
+    {
+        NativeMethods.todo_net_free_request(handle);
+        return true;
+    }
+}
+
+/// <summary>Owns a <c>FfiFfiNetTodoResult*</c>; released with <c>todo_net_free_result</c>.</summary>
+public sealed class ResultHandle : SafeHandle
+{
+    public ResultHandle() : base(IntPtr.Zero, ownsHandle: true) { }
+
+    public override bool IsInvalid => handle == IntPtr.Zero;
+
+    /// <summary>Copies the native struct. Strings stay native; read them with <c>Marshal.PtrToStringUTF8</c>.</summary>
+    public TodoResult Read() => Marshal.PtrToStructure<TodoResult>(handle);
+
+    protected override bool ReleaseHandle()
+    {
+        NativeMethods.todo_net_free_result(handle);
+        return true;
+    }
+}
+
+/// <summary>P/Invoke declarations for the .NET-facing C ABI.</summary>
+public static class NativeMethods
+{
+    public const string Library = "todo_ffi";
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void todo_client_free(IntPtr client);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ClientHandle todo_client_clone(ClientHandle client);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    [return: MarshalAs(UnmanagedType.U1)]
+    public static extern bool todo_client_eq(ClientHandle a, ClientHandle b);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ClientHandle todo_net_client_new([MarshalAs(UnmanagedType.LPWStr)] string baseUrl);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_list_todos(ClientHandle client);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_get_todo(ClientHandle client, [MarshalAs(UnmanagedType.LPWStr)] string id);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_create_todo(ClientHandle client, [MarshalAs(UnmanagedType.LPWStr)] string title, byte completed);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_update_todo(ClientHandle client, [MarshalAs(UnmanagedType.LPWStr)] string id, [MarshalAs(UnmanagedType.LPWStr)] string title, int completed);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_delete_todo(ClientHandle client, [MarshalAs(UnmanagedType.LPWStr)] string id);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern RequestHandle todo_net_build_delete_all(ClientHandle client);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_list_todos(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_get_todo(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_create_todo(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_update_todo(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_delete_todo(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern ResultHandle todo_net_parse_delete_all(ClientHandle client, ushort status, [MarshalAs(UnmanagedType.LPWStr)] string body);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void todo_net_free_request(IntPtr req);
+
+    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]
+    public static extern void todo_net_free_result(IntPtr result);
+}
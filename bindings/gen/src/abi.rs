@@ -0,0 +1,433 @@
+//! A model of the C ABI, read back from the header cbindgen generates.
+//!
+//! # Design
+//! The header is the contract every foreign binding links against, so it is
+//! the source of truth rather than the Rust signatures. Parsing only has to
+//! cover what cbindgen emits for `todo-ffi` — enum and struct typedefs,
+//! opaque handles, and function prototypes — so this is a small tokenizer
+//! plus a recursive-descent pass, not a C parser. Anything it does not
+//! recognise is an error, which keeps a change in the header from being
+//! silently dropped by a generator.
+
+use std::fmt;
+
+/// Macros from `cbindgen.toml` that annotate declarations but carry no type
+/// information.
+const ANNOTATIONS: [&str; 3] = ["FFI", "TODO_NULLABLE", "TODO_RETURNS_OWNED"];
+
+/// A C type as it appears in a declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CType {
+    Void,
+    Bool,
+    Char,
+    Int { signed: bool, bits: u8 },
+    /// A typedef'd enum or struct, by its C name.
+    Named(String),
+    /// `is_const` is true when the pointee is `const`.
+    Pointer { to: Box<CType>, is_const: bool },
+}
+
+impl CType {
+    /// The pointee's name when this is a pointer to a named type.
+    pub fn pointee_name(&self) -> Option<&str> {
+        match self {
+            CType::Pointer { to, .. } => match to.as_ref() {
+                CType::Named(name) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// True for `const char *`, the ABI's borrowed-string argument.
+    pub fn is_const_str(&self) -> bool {
+        matches!(self, CType::Pointer { to, is_const: true } if **to == CType::Char)
+    }
+}
+
+impl fmt::Display for CType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CType::Void => f.write_str("void"),
+            CType::Bool => f.write_str("bool"),
+            CType::Char => f.write_str("char"),
+            CType::Int { signed, bits } => write!(f, "{}int{bits}_t", if *signed { "" } else { "u" }),
+            CType::Named(name) => f.write_str(name),
+            CType::Pointer { to, is_const } => write!(f, "{}{to} *", if *is_const { "const " } else { "" }),
+        }
+    }
+}
+
+/// A named, typed slot: a struct field or a function parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub ty: CType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enum {
+    pub name: String,
+    /// Variant names as written in the header, with their values.
+    pub variants: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Struct {
+    pub name: String,
+    /// `None` for an opaque handle (`typedef struct X X;`).
+    pub fields: Option<Vec<Param>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub ret: CType,
+    pub params: Vec<Param>,
+}
+
+/// Everything a header declares, in declaration order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Abi {
+    pub enums: Vec<Enum>,
+    pub structs: Vec<Struct>,
+    pub functions: Vec<Function>,
+}
+
+impl Abi {
+    /// Parse the contents of a cbindgen-generated header.
+    pub fn parse(header: &str) -> Result<Abi, String> {
+        let source: String = header
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tokens = tokenize(&source)?;
+        Parser { tokens, pos: 0 }.parse()
+    }
+
+    pub fn enum_named(&self, name: &str) -> Result<&Enum, String> {
+        self.enums.iter().find(|e| e.name == name).ok_or_else(|| format!("header declares no enum {name}"))
+    }
+
+    pub fn struct_named(&self, name: &str) -> Result<&Struct, String> {
+        self.structs.iter().find(|s| s.name == name).ok_or_else(|| format!("header declares no struct {name}"))
+    }
+
+    pub fn function(&self, name: &str) -> Result<&Function, String> {
+        self.functions.iter().find(|f| f.name == name).ok_or_else(|| format!("header declares no function {name}"))
+    }
+
+    /// Fail unless struct `name` has every field in `fields`. Generators call
+    /// this for the fields their hand-written glue reads.
+    pub fn require_fields(&self, name: &str, fields: &[&str]) -> Result<(), String> {
+        let declared = self.struct_named(name)?.fields.as_ref().ok_or_else(|| format!("{name} is opaque"))?;
+        for field in fields {
+            if !declared.iter().any(|f| f.name == *field) {
+                return Err(format!("{name} has no field {field}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if source[start..].starts_with("/*") => {
+                let end = source[start + 2..].find("*/").ok_or("unterminated comment")?;
+                let resume = start + 2 + end + 2;
+                while chars.peek().is_some_and(|(i, _)| *i < resume) {
+                    chars.next();
+                }
+            }
+            '/' if source[start..].starts_with("//") => {
+                while chars.peek().is_some_and(|(_, c)| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '"' => {
+                let end = source[start + 1..].find('"').ok_or("unterminated string")?;
+                let resume = start + 1 + end + 1;
+                tokens.push(source[start..resume].to_string());
+                while chars.peek().is_some_and(|(i, _)| *i < resume) {
+                    chars.next();
+                }
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '-' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.peek().copied() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &source[start..end];
+                if !ANNOTATIONS.contains(&word) {
+                    tokens.push(word.to_string());
+                }
+            }
+            '{' | '}' | '(' | ')' | ';' | ',' | '*' | '=' => tokens.push(c.to_string()),
+            other => return Err(format!("unexpected character {other:?} in header")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of header")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        let token = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(format!("expected `{expected}`, found `{token}`"))
+        }
+    }
+
+    /// Tokens up to (not including) the next `stop` at nesting depth zero;
+    /// consumes `stop`.
+    fn until(&mut self, stop: &str) -> Result<Vec<String>, String> {
+        let mut out = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let token = self.next()?;
+            match token.as_str() {
+                t if t == stop && depth == 0 => return Ok(out),
+                "(" | "{" => depth += 1,
+                ")" | "}" => depth = depth.checked_sub(1).ok_or(format!("unbalanced `{token}`"))?,
+                _ => {}
+            }
+            out.push(token);
+        }
+    }
+
+    fn parse(mut self) -> Result<Abi, String> {
+        let mut abi = Abi::default();
+        while let Some(token) = self.peek() {
+            match token {
+                // The C++ guard: `extern "C" {` ... `}`.
+                "extern" => {
+                    self.next()?;
+                    self.next()?;
+                    self.expect("{")?;
+                }
+                "}" => {
+                    self.next()?;
+                }
+                "typedef" => {
+                    self.next()?;
+                    match self.next()?.as_str() {
+                        "enum" => abi.enums.push(self.parse_enum()?),
+                        "struct" => abi.structs.push(self.parse_struct()?),
+                        other => return Err(format!("unsupported typedef `{other}`")),
+                    }
+                }
+                _ => abi.functions.push(self.parse_function()?),
+            }
+        }
+        Ok(abi)
+    }
+
+    fn parse_enum(&mut self) -> Result<Enum, String> {
+        let name = self.next()?;
+        self.expect("{")?;
+        let mut variants = Vec::new();
+        let mut value = 0;
+        for item in split_commas(self.until("}")?) {
+            match item.as_slice() {
+                [variant] => variants.push((variant.clone(), value)),
+                [variant, eq, number] if eq == "=" => {
+                    value = number.parse().map_err(|_| format!("bad value for {variant}: {number}"))?;
+                    variants.push((variant.clone(), value));
+                }
+                other => return Err(format!("bad enum variant in {name}: {other:?}")),
+            }
+            value += 1;
+        }
+        self.typedef_alias(&name)?;
+        Ok(Enum { name, variants })
+    }
+
+    fn parse_struct(&mut self) -> Result<Struct, String> {
+        let name = self.next()?;
+        let fields = if self.peek() == Some("{") {
+            self.next()?;
+            let body = self.until("}")?;
+            let fields = body
+                .split(|t| t == ";")
+                .filter(|decl| !decl.is_empty())
+                .map(parse_decl)
+                .collect::<Result<_, _>>()?;
+            Some(fields)
+        } else {
+            None
+        };
+        self.typedef_alias(&name)?;
+        Ok(Struct { name, fields })
+    }
+
+    /// cbindgen aliases every tag to the same name: `} Name;`.
+    fn typedef_alias(&mut self, name: &str) -> Result<(), String> {
+        let alias = self.next()?;
+        if alias != name {
+            return Err(format!("typedef {name} is aliased as {alias}"));
+        }
+        self.expect(";")
+    }
+
+    fn parse_function(&mut self) -> Result<Function, String> {
+        let head = self.until("(")?;
+        let Param { name, ty: ret } = parse_decl(&head)?;
+        let params = self.until(")")?;
+        self.expect(";")?;
+        let params = if params == ["void"] {
+            Vec::new()
+        } else {
+            split_commas(params).iter().map(|p| parse_decl(p)).collect::<Result<_, _>>()?
+        };
+        Ok(Function { name, ret, params })
+    }
+}
+
+fn split_commas(tokens: Vec<String>) -> Vec<Vec<String>> {
+    tokens.split(|t| t == ",").filter(|item| !item.is_empty()).map(<[String]>::to_vec).collect()
+}
+
+/// `<type tokens> <name>`, as in a field, parameter or function head.
+fn parse_decl(tokens: &[String]) -> Result<Param, String> {
+    let (name, ty) = tokens.split_last().ok_or("empty declaration")?;
+    Ok(Param { name: name.clone(), ty: parse_type(ty)? })
+}
+
+fn parse_type(tokens: &[String]) -> Result<CType, String> {
+    let mut is_const = false;
+    let mut base = None;
+    let mut depth = 0;
+    for token in tokens {
+        match token.as_str() {
+            "const" => is_const = true,
+            "struct" | "enum" => {}
+            "*" => depth += 1,
+            word if base.is_none() && depth == 0 => base = Some(word),
+            other => return Err(format!("unsupported type syntax at `{other}` in {tokens:?}")),
+        }
+    }
+    let base = base.ok_or_else(|| format!("no base type in {tokens:?}"))?;
+    let mut ty = match base {
+        "void" => CType::Void,
+        "bool" => CType::Bool,
+        "char" => CType::Char,
+        int if int.ends_with("_t") && int.contains("int") => {
+            let signed = !int.starts_with('u');
+            let bits = int.trim_start_matches('u').trim_start_matches("int").trim_end_matches("_t");
+            CType::Int { signed, bits: bits.parse().map_err(|_| format!("unsupported integer type {int}"))? }
+        }
+        named => CType::Named(named.to_string()),
+    };
+    for level in 0..depth {
+        // `const` binds to the innermost pointee, which is all cbindgen emits.
+        ty = CType::Pointer { to: Box::new(ty), is_const: is_const && level == 0 };
+    }
+    Ok(ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = r#"
+#ifndef T_H
+#define T_H
+/** Tag. */
+typedef enum FfiFfiTag {
+  FFI_FFI_TAG_NONE = 0,
+  /** Docs inside bodies are skipped. */
+  FFI_FFI_TAG_SOME,
+  FFI_FFI_TAG_LATE = 7,
+} FfiFfiTag;
+
+typedef struct FfiFfiHandle FfiFfiHandle;
+
+typedef struct FfiFfiPair {
+  char *TODO_NULLABLE key;
+  enum FfiFfiTag tag;
+  uint16_t len;
+} FfiFfiPair;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+FFI
+TODO_RETURNS_OWNED
+struct FfiFfiHandle *TODO_NULLABLE t_new(const char *TODO_NULLABLE url,
+                                         int32_t flag);
+FFI void t_free(struct FfiFfiHandle *TODO_NULLABLE handle);
+FFI bool t_ready(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+#endif
+"#;
+
+    #[test]
+    fn parses_cbindgen_output() {
+        let abi = Abi::parse(HEADER).unwrap();
+        assert_eq!(
+            abi.enum_named("FfiFfiTag").unwrap().variants,
+            vec![
+                ("FFI_FFI_TAG_NONE".to_string(), 0),
+                ("FFI_FFI_TAG_SOME".to_string(), 1),
+                ("FFI_FFI_TAG_LATE".to_string(), 7),
+            ]
+        );
+        assert_eq!(abi.struct_named("FfiFfiHandle").unwrap().fields, None);
+        let pair = abi.struct_named("FfiFfiPair").unwrap().fields.clone().unwrap();
+        assert_eq!(pair[0].ty.to_string(), "char *");
+        assert_eq!(pair[1].ty, CType::Named("FfiFfiTag".to_string()));
+        assert_eq!(pair[2].ty, CType::Int { signed: false, bits: 16 });
+
+        let new = abi.function("t_new").unwrap();
+        assert_eq!(new.ret.pointee_name(), Some("FfiFfiHandle"));
+        assert!(new.params[0].ty.is_const_str());
+        assert_eq!(new.params[1].ty.to_string(), "int32_t");
+        assert_eq!(abi.function("t_free").unwrap().ret, CType::Void);
+        assert!(abi.function("t_ready").unwrap().params.is_empty());
+    }
+
+    #[test]
+    fn require_fields_names_the_missing_field() {
+        let abi = Abi::parse(HEADER).unwrap();
+        assert!(abi.require_fields("FfiFfiPair", &["key", "len"]).is_ok());
+        assert_eq!(abi.require_fields("FfiFfiPair", &["value"]).unwrap_err(), "FfiFfiPair has no field value");
+        assert_eq!(abi.require_fields("FfiFfiHandle", &["x"]).unwrap_err(), "FfiFfiHandle is opaque");
+    }
+
+    #[test]
+    fn rejects_what_it_does_not_understand() {
+        assert!(Abi::parse("typedef union U { int a; } U;").is_err());
+        assert!(Abi::parse("void f(int (*cb)(void));").is_err());
+        assert!(Abi::parse("/* open").is_err());
+    }
+}
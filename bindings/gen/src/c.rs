@@ -0,0 +1,529 @@
+//! C backend: renders `bindings/c/conformance.c`, a test program that runs
+//! the JSON test vectors through the C ABI.
+//!
+//! # Design
+//! The other backends emit bindings; this one emits the checks. Each vector
+//! case becomes two C functions, one per string flavour: the UTF-8
+//! `todo_*` entry points and the UTF-16 `todo_net_*` ones. Each builds the
+//! request, compares it field by field, then parses the simulated response
+//! and compares the outcome. Functions no vector reaches are called from a
+//! fixed `lifecycle` section.
+//!
+//! Generation fails if the program would not call every function in the
+//! header, so a new entry point cannot ship without conformance coverage.
+//!
+//! Request bodies are compared as the set of their `"key":value` members
+//! plus the total length, which is exact for the flat objects the vectors
+//! use and needs no JSON parser in C. Nested bodies are rejected.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+use todo_test_support::{ExpectedResult, ExpectedTodo, TestCase};
+
+use crate::abi::Abi;
+use crate::naming::{screaming, C_TYPE_PREFIX};
+
+/// Base URL every case's client is created with.
+const BASE_URL: &str = "http://localhost:3000";
+
+/// Render the conformance program from the header and the test vectors,
+/// given as `(file stem, JSON source)` pairs.
+pub fn generate(abi: &Abi, vectors: &[(&str, &str)]) -> Result<String, String> {
+    let mut out = String::from(PREAMBLE);
+    let _ = writeln!(out, "#define BASE_URL {}", c_str(BASE_URL));
+    let _ = writeln!(out, "#define BASE_URL_U16 {}", u16_str(BASE_URL));
+    out.push_str(HELPERS);
+    let mut cases = Vec::new();
+    for (stem, source) in vectors {
+        let file = todo_test_support::parse(source).map_err(|e| format!("{stem}.json: {e}"))?;
+        let op = Operation::from_stem(stem)?;
+        for case in &file.cases {
+            let case_name = &case.name;
+            for flavour in [&UTF8, &UTF16] {
+                let function = format!("{}{stem}_{}", flavour.case_prefix, identifier(case_name));
+                render_case(&mut out, abi, flavour, &op, &function, &format!("{stem}/{case_name}{}", flavour.label), case)
+                    .map_err(|e| format!("{stem}.json case {case_name}: {e}"))?;
+                cases.push((flavour, function));
+            }
+        }
+    }
+    out.push_str(LIFECYCLE);
+    out.push_str("\nint main(void) {\n");
+    out.push_str("    FfiFfiTodoClient *client = todo_client_new(BASE_URL);\n");
+    out.push_str("    FfiFfiTodoClient *net_client = todo_net_client_new(BASE_URL_U16);\n");
+    out.push_str("    if (!client || !net_client) {\n");
+    out.push_str("        fprintf(stderr, \"FAIL could not create clients\\n\");\n        return 1;\n    }\n");
+    for (flavour, function) in &cases {
+        let _ = writeln!(out, "    {function}({});", flavour.client);
+    }
+    out.push_str("    lifecycle();\n");
+    out.push_str("    todo_client_free(client);\n    todo_client_free(net_client);\n\n");
+    out.push_str("    printf(\"%d checks, %d failed\\n\", checks, failures);\n");
+    out.push_str("    return failures == 0 ? 0 : 1;\n}\n");
+
+    for f in &abi.functions {
+        if !out.contains(&format!("{}(", f.name)) {
+            return Err(format!("the conformance program does not call {}", f.name));
+        }
+    }
+    Ok(out)
+}
+
+/// One of the two string flavours of the C ABI.
+struct Flavour {
+    /// Prefix of the entry points: `todo_` or `todo_net_`.
+    functions: &'static str,
+    request: &'static str,
+    result: &'static str,
+    todo: &'static str,
+    list: &'static str,
+    /// Name of the client variable in `main`.
+    client: &'static str,
+    case_prefix: &'static str,
+    /// Appended to case names in failure messages.
+    label: &'static str,
+    utf16: bool,
+}
+
+const UTF8: Flavour = Flavour {
+    functions: "todo_",
+    request: "HttpRequest",
+    result: "TodoResult",
+    todo: "Todo",
+    list: "TodoList",
+    client: "client",
+    case_prefix: "",
+    label: "",
+    utf16: false,
+};
+
+const UTF16: Flavour = Flavour {
+    functions: "todo_net_",
+    request: "NetHttpRequest",
+    result: "NetTodoResult",
+    todo: "NetTodo",
+    list: "NetTodoList",
+    client: "net_client",
+    case_prefix: "net_",
+    label: " (net)",
+    utf16: true,
+};
+
+impl Flavour {
+    fn string(&self, s: &str) -> String {
+        if self.utf16 {
+            u16_str(s)
+        } else {
+            c_str(s)
+        }
+    }
+
+    fn bool(&self, b: bool) -> &'static str {
+        match (self.utf16, b) {
+            (false, true) => "true",
+            (false, false) => "false",
+            (true, true) => "1",
+            (true, false) => "0",
+        }
+    }
+}
+
+/// The operation a vector file covers, named after its file stem.
+struct Operation {
+    /// Suffix of the `build_*` / `parse_*` entry points.
+    suffix: &'static str,
+    /// The id argument comes from `input_id`.
+    takes_id: bool,
+}
+
+impl Operation {
+    fn from_stem(stem: &str) -> Result<Operation, String> {
+        let (suffix, takes_id) = match stem {
+            "list" => ("list_todos", false),
+            "get" => ("get_todo", true),
+            "create" => ("create_todo", false),
+            "update" => ("update_todo", true),
+            "delete" => ("delete_todo", true),
+            other => return Err(format!("no operation for test vector file {other}.json")),
+        };
+        Ok(Operation { suffix, takes_id })
+    }
+
+    /// The build call's arguments after the client.
+    fn build_args(&self, flavour: &Flavour, case: &TestCase) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        if self.takes_id {
+            args.push(flavour.string(case.input_id.as_deref().ok_or("no input_id")?));
+        }
+        let input = case.input.as_ref();
+        let title = input.and_then(|input| input.title.as_deref());
+        let completed = input.and_then(|input| input.completed);
+        match self.suffix {
+            "create_todo" => {
+                args.push(flavour.string(title.ok_or("create input without a title")?));
+                args.push(flavour.bool(completed.unwrap_or(false)).to_string());
+            }
+            "update_todo" => {
+                args.push(title.map_or("NULL".to_string(), |title| flavour.string(title)));
+                args.push(completed.map_or("-1".to_string(), |completed| i32::from(completed).to_string()));
+            }
+            _ => {}
+        }
+        Ok(args)
+    }
+}
+
+fn render_case(
+    out: &mut String,
+    abi: &Abi,
+    flavour: &Flavour,
+    op: &Operation,
+    function: &str,
+    label: &str,
+    case: &TestCase,
+) -> Result<(), String> {
+    let build = format!("{}build_{}", flavour.functions, op.suffix);
+    let parse = format!("{}parse_{}", flavour.functions, op.suffix);
+    abi.function(&build)?;
+    abi.function(&parse)?;
+    let request = format!("{C_TYPE_PREFIX}{}", flavour.request);
+    let result = format!("{C_TYPE_PREFIX}{}", flavour.result);
+
+    let _ = writeln!(out, "\nstatic void {function}(const FfiFfiTodoClient *client) {{");
+    let _ = writeln!(out, "    const char *name = {};", c_str(label));
+
+    let expected = &case.expected_request;
+    let method = variant(abi, "HttpMethod", &expected.method)?;
+    let path = format!("{BASE_URL}{}", expected.path);
+    let headers: Vec<String> = expected.headers.iter().flat_map(|(key, value)| [c_str(key), c_str(value)]).collect();
+    let members = match &expected.body {
+        None => None,
+        Some(Value::Object(map)) => Some(
+            map.iter()
+                .map(|(key, value)| match value {
+                    Value::Object(_) | Value::Array(_) => Err(format!("body member {key} is not a scalar")),
+                    _ => Ok(c_str(&format!("{}:{value}", Value::from(key.as_str())))),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        Some(other) => return Err(format!("body {other} is not an object")),
+    };
+    let _ = writeln!(out, "    const Expected expected = {{");
+    let _ = writeln!(out, "        {method},");
+    let _ = writeln!(out, "        {},", c_str(&path));
+    if headers.is_empty() {
+        out.push_str("        NULL, 0,\n");
+    } else {
+        let _ = writeln!(out, "        (const char *const[]){{{}}}, {},", headers.join(", "), headers.len() / 2);
+    }
+    match &members {
+        None => out.push_str("        NULL, 0,\n"),
+        Some(members) if members.is_empty() => out.push_str("        (const char *const[]){NULL}, 0,\n"),
+        Some(members) => {
+            let _ = writeln!(out, "        (const char *const[]){{{}}}, {},", members.join(", "), members.len());
+        }
+    }
+    out.push_str("    };\n");
+
+    let mut args = vec!["client".to_string()];
+    args.extend(op.build_args(flavour, case)?);
+    let _ = writeln!(out, "    {request} *req = {build}({});", args.join(", "));
+    out.push_str("    CHECK(name, req != NULL);\n");
+    out.push_str("    if (req) {\n");
+    out.push_str("        check_request(name, (int)req->method, req->path, req->headers, req->headers_len, req->body, &expected);\n");
+    out.push_str("    }\n");
+    let _ = writeln!(out, "    {}free_request(req);\n", flavour.functions);
+
+    let status = case.simulated_response.status;
+    let body = &case.simulated_response.body;
+    if flavour.utf16 {
+        let _ = writeln!(out, "    {result} *result = {parse}(client, {status}, {});", flavour.string(body));
+    } else {
+        let _ = writeln!(out, "    const FfiFfiHttpResponse response = {{{status}, {}}};", c_str(body));
+        let _ = writeln!(out, "    {result} *result = {parse}(client, &response);");
+    }
+    out.push_str("    CHECK(name, result != NULL);\n");
+    out.push_str("    if (result) {\n");
+    render_outcome(out, abi, flavour, case)?;
+    out.push_str("    }\n");
+    let _ = writeln!(out, "    {}free_result(result);\n}}", flavour.functions);
+    Ok(())
+}
+
+/// Checks on `result` for the case's `expected_error` (and its
+/// `expected_http_status`) or `expected_result`.
+fn render_outcome(out: &mut String, abi: &Abi, flavour: &Flavour, case: &TestCase) -> Result<(), String> {
+    if let Some(error) = case.expected_error {
+        let code = variant(abi, "ErrorCode", error.as_str())?;
+        let _ = writeln!(out, "        CHECK(name, result->error_code == {code});");
+        out.push_str("        CHECK(name, result->error_message != NULL);\n");
+        if let Some(status) = case.expected_http_status {
+            let _ = writeln!(out, "        CHECK(name, result->http_status == {status});");
+        }
+        return Ok(());
+    }
+    let ok = variant(abi, "ErrorCode", "Ok")?;
+    let _ = writeln!(out, "        CHECK(name, result->error_code == {ok});");
+    let todo = format!("{C_TYPE_PREFIX}{}", flavour.todo);
+    match &case.expected_result {
+        None => {
+            let tag = variant(abi, "DataTag", "None")?;
+            let _ = writeln!(out, "        CHECK(name, result->data_tag == {tag});");
+        }
+        Some(ExpectedResult::List(items)) => {
+            let tag = variant(abi, "DataTag", "TodoList")?;
+            let list = format!("{C_TYPE_PREFIX}{}", flavour.list);
+            let _ = writeln!(out, "        CHECK(name, result->data_tag == {tag});");
+            let _ = writeln!(out, "        if (result->data_tag == {tag}) {{");
+            let _ = writeln!(out, "            const {list} *list = result->data;");
+            let _ = writeln!(out, "            CHECK(name, list->len == {});", items.len());
+            if !items.is_empty() {
+                out.push_str("            static const ExpectedTodo want[] = {\n");
+                for item in items {
+                    let _ = writeln!(out, "                {},", expected_todo(item));
+                }
+                out.push_str("            };\n");
+                let _ = writeln!(out, "            for (uint32_t i = 0; i < list->len && i < {}; i++) {{", items.len());
+                out.push_str("                check_todo(name, list->items[i].id, list->items[i].title, list->items[i].completed, &want[i]);\n");
+                out.push_str("            }\n");
+            }
+            out.push_str("        }\n");
+        }
+        Some(ExpectedResult::Todo(item)) => {
+            let tag = variant(abi, "DataTag", "Todo")?;
+            let _ = writeln!(out, "        CHECK(name, result->data_tag == {tag});");
+            let _ = writeln!(out, "        if (result->data_tag == {tag}) {{");
+            let _ = writeln!(out, "            const {todo} *todo = result->data;");
+            let _ = writeln!(out, "            const ExpectedTodo want = {};", expected_todo(item));
+            out.push_str("            check_todo(name, todo->id, todo->title, todo->completed, &want);\n");
+            out.push_str("        }\n");
+        }
+    }
+    Ok(())
+}
+
+fn expected_todo(todo: &ExpectedTodo) -> String {
+    format!("{{{}, {}, {}}}", c_str(&todo.id), c_str(&todo.title), i32::from(todo.completed))
+}
+
+/// The header's name for a variant of `FfiFfi<enum_name>`, given the
+/// vectors' spelling: (`HttpMethod`, `POST`) → `FFI_FFI_HTTP_METHOD_POST`,
+/// (`ErrorCode`, `NotFound`) → `FFI_FFI_ERROR_CODE_NOT_FOUND`.
+fn variant(abi: &Abi, enum_name: &str, name: &str) -> Result<String, String> {
+    let c_enum = abi.enum_named(&format!("{C_TYPE_PREFIX}{enum_name}"))?;
+    let suffix = if name.chars().all(|c| !c.is_ascii_lowercase()) { name.to_string() } else { screaming(name) };
+    let wanted = format!("{}_{suffix}", screaming(&c_enum.name));
+    c_enum
+        .variants
+        .iter()
+        .find(|(variant, _)| *variant == wanted)
+        .map(|(variant, _)| variant.clone())
+        .ok_or_else(|| format!("{} has no variant {wanted}", c_enum.name))
+}
+
+/// A vector case name as a C identifier.
+fn identifier(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect()
+}
+
+/// `s` as a C string literal. Bytes outside printable ASCII are octal
+/// escapes; `?` is escaped so no trigraph can form.
+fn c_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for b in s.bytes() {
+        match b {
+            b'"' | b'\\' | b'?' => {
+                out.push('\\');
+                out.push(b as char);
+            }
+            0x20..=0x7e => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\{b:03o}");
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// `s` as a NUL-terminated UTF-16 string for the `todo_net_*` functions.
+fn u16_str(s: &str) -> String {
+    let mut out = String::from("(const uint16_t *)u\"");
+    for c in s.chars() {
+        match c {
+            '"' | '\\' | '?' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            // Universal character names cannot spell these.
+            c if (c as u32) < 0xa0 => {
+                let _ = write!(out, "\\{:03o}", c as u32);
+            }
+            c if (c as u32) <= 0xffff => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => {
+                let _ = write!(out, "\\U{:08x}", c as u32);
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+const PREAMBLE: &str = r#"/* AUTO GENERATED FILE, DO NOT EDIT.
+ *
+ * Generated by todo-bindgen from todo_client.h and the JSON test vectors.
+ * Regenerate with `cargo run -p todo-bindgen -- c bindings/c`.
+ *
+ * Runs every test vector through both the UTF-8 (todo_*) and UTF-16
+ * (todo_net_*) entry points; `lifecycle` calls the functions no vector
+ * reaches. Prints one line per failed check and exits non-zero if any
+ * failed. Needs C11 for the u"" literals. */
+
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "todo_client.h"
+
+"#;
+
+const HELPERS: &str = r#"
+static int checks;
+static int failures;
+
+#define CHECK(name, cond) check((name), (cond), #cond, __LINE__)
+
+static void check(const char *name, int ok, const char *expr, int line) {
+    checks++;
+    if (!ok) {
+        failures++;
+        fprintf(stderr, "FAIL %s (line %d): %s\n", name, line, expr);
+    }
+}
+
+static int str_eq(const char *a, const char *b) {
+    return a && b && strcmp(a, b) == 0;
+}
+
+/* A request as a test vector describes it. */
+typedef struct Expected {
+    int method;
+    const char *path;
+    /* Key, value, key, value, ... */
+    const char *const *headers;
+    size_t headers_len;
+    /* Compact `"key":value` members of the JSON body; NULL for no body. */
+    const char *const *members;
+    size_t members_len;
+} Expected;
+
+typedef struct ExpectedTodo {
+    const char *id;
+    const char *title;
+    int completed;
+} ExpectedTodo;
+
+/* True if `body` is a compact JSON object with exactly `members`, in any
+ * order. */
+static int body_matches(const char *body, const char *const *members, size_t members_len) {
+    size_t len = 2;
+    if (!body || body[0] != '{') {
+        return 0;
+    }
+    for (size_t i = 0; i < members_len; i++) {
+        if (!strstr(body, members[i])) {
+            return 0;
+        }
+        len += strlen(members[i]) + (i > 0);
+    }
+    return strlen(body) == len && body[len - 1] == '}';
+}
+
+static void check_request(const char *name, int method, const char *path, const FfiFfiHeader *headers,
+                          uint32_t headers_len, const char *body, const Expected *expected) {
+    CHECK(name, method == expected->method);
+    CHECK(name, str_eq(path, expected->path));
+    CHECK(name, headers_len == expected->headers_len);
+    for (size_t i = 0; i < headers_len && i < expected->headers_len; i++) {
+        CHECK(name, str_eq(headers[i].key, expected->headers[2 * i]));
+        CHECK(name, str_eq(headers[i].value, expected->headers[2 * i + 1]));
+    }
+    if (expected->members) {
+        CHECK(name, body_matches(body, expected->members, expected->members_len));
+    } else {
+        CHECK(name, body == NULL);
+    }
+}
+
+static void check_todo(const char *name, const char *id, const char *title, int completed,
+                       const ExpectedTodo *expected) {
+    CHECK(name, str_eq(id, expected->id));
+    CHECK(name, str_eq(title, expected->title));
+    CHECK(name, !completed == !expected->completed);
+}
+"#;
+
+const LIFECYCLE: &str = r#"
+/* Functions no test vector reaches, and argument errors. */
+static void lifecycle(void) {
+    const char *name = "lifecycle";
+
+    FfiFfiTodoClient *client = todo_client_new(BASE_URL);
+    FfiFfiTodoClient *copy = todo_client_clone(client);
+    CHECK(name, copy != NULL && todo_client_eq(client, copy));
+    FfiFfiTodoClient *auth = todo_client_new_basic_auth(BASE_URL, "user", "pass");
+    CHECK(name, auth != NULL && !todo_client_eq(client, auth));
+    CHECK(name, todo_client_eq(NULL, NULL) && !todo_client_eq(client, NULL));
+    CHECK(name, todo_client_new("not a url") == NULL);
+    CHECK(name, todo_client_new_basic_auth(BASE_URL, "us:er", "pass") == NULL);
+    CHECK(name, todo_build_get_todo(client, "not-a-uuid") == NULL);
+    CHECK(name, todo_build_list_todos(NULL) == NULL);
+
+    FfiFfiHttpRequest *req = todo_build_delete_all(client);
+    CHECK(name, req && req->method == FFI_FFI_HTTP_METHOD_DELETE && str_eq(req->path, BASE_URL "/todos"));
+    todo_free_request(req);
+
+    const FfiFfiHttpResponse deleted = {200, "{\"deleted\":3}"};
+    FfiFfiTodoResult *result = todo_parse_delete_all(client, &deleted);
+    CHECK(name, result && result->data_tag == FFI_FFI_DATA_TAG_COUNT && *(const uint64_t *)result->data == 3);
+    todo_free_result(result);
+    result = todo_parse_get_todo(client, NULL);
+    CHECK(name, result && result->error_code == FFI_FFI_ERROR_CODE_NULL_ARG && result->error_message != NULL);
+    todo_free_result(result);
+
+    FfiFfiNetHttpRequest *net_req = todo_net_build_delete_all(client);
+    CHECK(name, net_req && net_req->method == FFI_FFI_HTTP_METHOD_DELETE && str_eq(net_req->path, BASE_URL "/todos"));
+    todo_net_free_request(net_req);
+    FfiFfiNetTodoResult *net_result = todo_net_parse_delete_all(client, 200, (const uint16_t *)u"{\"deleted\":3}");
+    CHECK(name, net_result && net_result->data_tag == FFI_FFI_DATA_TAG_COUNT && *(const uint64_t *)net_result->data == 3);
+    todo_net_free_result(net_result);
+
+    /* Every release function accepts null. */
+    todo_free_request(NULL);
+    todo_free_result(NULL);
+    todo_free_string(NULL);
+    todo_net_free_request(NULL);
+    todo_net_free_result(NULL);
+    todo_client_free(NULL);
+
+    todo_client_free(auth);
+    todo_client_free(copy);
+    todo_client_free(client);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_c_literals() {
+        assert_eq!(c_str(r#"a"b\c??/"#), r#""a\"b\\c\?\?/""#);
+        assert_eq!(c_str("é\n"), r#""\303\251\012""#);
+        assert_eq!(u16_str("é\u{7f}😀"), r#"(const uint16_t *)u"\u00e9\177\U0001f600""#);
+    }
+}
@@ -0,0 +1,181 @@
+//! C# backend: renders `bindings/dotnet/TodoInterop.cs` from the header.
+//!
+//! # Design
+//! .NET binds to the `todo_net_*` surface (see `todo_ffi::dotnet`) plus the
+//! client lifecycle functions; the UTF-8 entry points are left to other
+//! hosts. The output stays at the interop layer: enums, blittable structs,
+//! a `SafeHandle` per owned pointer type and the `DllImport` declarations.
+//!
+//! Mapping a struct field fails on `bool` and on C enums, whose size is
+//! implementation-defined. That turns the blittable promise of the `net`
+//! layouts into something generation checks.
+
+use std::fmt::Write;
+
+use crate::abi::{Abi, CType, Function, Struct};
+use crate::naming::{camel, pascal, type_name, variant_suffix};
+
+const NET_PREFIX: &str = "todo_net_";
+
+/// Client functions that take no strings, so .NET calls them directly.
+const LIFECYCLE: &[&str] = &["todo_client_free", "todo_client_clone", "todo_client_eq"];
+
+/// Owned pointer types: the C# handle class, the pointee, its release
+/// function, and the struct `Read()` marshals it to (none when opaque).
+const HANDLES: &[(&str, &str, &str, Option<&str>)] = &[
+    ("ClientHandle", "FfiFfiTodoClient", "todo_client_free", None),
+    ("RequestHandle", "FfiFfiNetHttpRequest", "todo_net_free_request", Some("HttpRequest")),
+    ("ResultHandle", "FfiFfiNetTodoResult", "todo_net_free_result", Some("TodoResult")),
+];
+
+/// True for the functions `TodoInterop.cs` declares.
+pub fn wraps(function: &str) -> bool {
+    function.starts_with(NET_PREFIX) || LIFECYCLE.contains(&function)
+}
+
+/// C# name for a C struct: `FfiFfiNetTodoList` → `TodoList`.
+fn struct_name(c_name: &str) -> &str {
+    let name = type_name(c_name);
+    name.strip_prefix("Net").unwrap_or(name)
+}
+
+/// Render the C# source.
+pub fn generate(abi: &Abi) -> Result<String, String> {
+    let mut out = String::from(PREAMBLE);
+    for e in &abi.enums {
+        let _ = writeln!(out, "\n/// <summary>Values of the C enum <c>{}</c>.</summary>", e.name);
+        let _ = writeln!(out, "public enum {} : int\n{{", type_name(&e.name));
+        for (variant, value) in &e.variants {
+            let _ = writeln!(out, "    {} = {value},", pascal(variant_suffix(&e.name, variant), &[]));
+        }
+        out.push_str("}\n");
+    }
+    for s in blittable_structs(abi)? {
+        render_struct(&mut out, s)?;
+    }
+    for (class, pointee, release, read) in HANDLES {
+        let f = abi.function(release)?;
+        match (&f.ret, f.params.as_slice()) {
+            (CType::Void, [p]) if p.ty.pointee_name() == Some(*pointee) => {}
+            _ => return Err(format!("{release} does not release a {pointee}")),
+        }
+        let _ = writeln!(out, "\n/// <summary>Owns a <c>{pointee}*</c>; released with <c>{release}</c>.</summary>");
+        let _ = writeln!(out, "public sealed class {class} : SafeHandle\n{{");
+        let _ = writeln!(out, "    public {class}() : base(IntPtr.Zero, ownsHandle: true) {{ }}\n");
+        out.push_str("    public override bool IsInvalid => handle == IntPtr.Zero;\n");
+        if let Some(read) = read {
+            let _ = writeln!(out, "\n    /// <summary>Copies the native struct. Strings stay native; read them with <c>Marshal.PtrToStringUTF8</c>.</summary>");
+            let _ = writeln!(out, "    public {read} Read() => Marshal.PtrToStructure<{read}>(handle);");
+        }
+        let _ = writeln!(out, "\n    protected override bool ReleaseHandle()\n    {{");
+        let _ = writeln!(out, "        NativeMethods.{release}(handle);\n        return true;\n    }}\n}}");
+    }
+    out.push_str("\n/// <summary>P/Invoke declarations for the .NET-facing C ABI.</summary>\n");
+    out.push_str("public static class NativeMethods\n{\n    public const string Library = \"todo_ffi\";\n");
+    for f in abi.functions.iter().filter(|f| wraps(&f.name)) {
+        render_function(&mut out, f)?;
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// The `net` structs and every struct they point to, in header order.
+fn blittable_structs(abi: &Abi) -> Result<Vec<&Struct>, String> {
+    let mut wanted: Vec<&str> = Vec::new();
+    let mut pending: Vec<&str> =
+        abi.structs.iter().filter(|s| s.name.starts_with("FfiFfiNet")).map(|s| s.name.as_str()).collect();
+    while let Some(name) = pending.pop() {
+        if wanted.contains(&name) {
+            continue;
+        }
+        wanted.push(name);
+        let fields = abi.struct_named(name)?.fields.as_deref().unwrap_or_default();
+        pending.extend(fields.iter().filter_map(|f| f.ty.pointee_name()));
+    }
+    Ok(abi.structs.iter().filter(|s| wanted.contains(&s.name.as_str())).collect())
+}
+
+fn render_struct(out: &mut String, s: &Struct) -> Result<(), String> {
+    let fields = s.fields.as_ref().ok_or_else(|| format!("{} is opaque", s.name))?;
+    let _ = writeln!(out, "\n/// <summary>Blittable mirror of <c>{}</c>.</summary>", s.name);
+    let _ = writeln!(out, "[StructLayout(LayoutKind.Sequential)]\npublic struct {}\n{{", struct_name(&s.name));
+    for field in fields {
+        let ty = match &field.ty {
+            CType::Pointer { .. } => "IntPtr",
+            CType::Int { signed, bits } => int_type(*signed, *bits)?,
+            other => return Err(format!("{}.{} is not blittable: {other}", s.name, field.name)),
+        };
+        let _ = writeln!(out, "    public {ty} {};", pascal(&field.name, &[]));
+    }
+    out.push_str("}\n");
+    Ok(())
+}
+
+fn int_type(signed: bool, bits: u8) -> Result<&'static str, String> {
+    Ok(match (signed, bits) {
+        (true, 8) => "sbyte",
+        (false, 8) => "byte",
+        (true, 16) => "short",
+        (false, 16) => "ushort",
+        (true, 32) => "int",
+        (false, 32) => "uint",
+        (true, 64) => "long",
+        (false, 64) => "ulong",
+        _ => return Err(format!("no C# type for a {bits}-bit integer")),
+    })
+}
+
+/// Handle class for an owned pointer to `pointee`, if it is one.
+fn handle_class(pointee: Option<&str>) -> Option<&'static str> {
+    HANDLES.iter().find(|(_, p, _, _)| Some(*p) == pointee).map(|(class, _, _, _)| *class)
+}
+
+fn render_function(out: &mut String, f: &Function) -> Result<(), String> {
+    let unsupported = |what: String| format!("no C# mapping for {}: {what}", f.name);
+    let releases = HANDLES.iter().any(|(_, _, release, _)| *release == f.name);
+    let ret = match &f.ret {
+        CType::Void => "void",
+        CType::Bool => "bool",
+        CType::Int { signed, bits } => int_type(*signed, *bits)?,
+        ty => handle_class(ty.pointee_name()).ok_or_else(|| unsupported(format!("return type {ty}")))?,
+    };
+    let mut params = Vec::new();
+    for p in &f.params {
+        let name = camel(&p.name, &[]);
+        let ty = match &p.ty {
+            CType::Pointer { to, .. } if **to == (CType::Int { signed: false, bits: 16 }) => {
+                "[MarshalAs(UnmanagedType.LPWStr)] string".to_string()
+            }
+            // The release function runs inside `ReleaseHandle`, which has
+            // only the raw pointer.
+            CType::Pointer { .. } if releases => "IntPtr".to_string(),
+            CType::Bool => "[MarshalAs(UnmanagedType.U1)] bool".to_string(),
+            CType::Int { signed, bits } => int_type(*signed, *bits)?.to_string(),
+            ty => handle_class(ty.pointee_name())
+                .ok_or_else(|| unsupported(format!("parameter {}: {ty}", p.name)))?
+                .to_string(),
+        };
+        params.push(format!("{ty} {name}"));
+    }
+    let _ = writeln!(out, "\n    [DllImport(Library, CallingConvention = CallingConvention.Cdecl)]");
+    if f.ret == CType::Bool {
+        out.push_str("    [return: MarshalAs(UnmanagedType.U1)]\n");
+    }
+    let _ = writeln!(out, "    public static extern {ret} {}({});", f.name, params.join(", "));
+    Ok(())
+}
+
+const PREAMBLE: &str = r#"// <auto-generated>
+// Generated by todo-bindgen from todo_client.h. Do not edit.
+// </auto-generated>
+//
+// Interop layer for the todo_ffi library. As with every binding, the host
+// owns HTTP: a todo_net_build_* call returns a RequestHandle describing the
+// request to send, and todo_net_parse_* decodes the response into a
+// ResultHandle. Argument semantics are documented on the C functions.
+
+using System;
+using System.Runtime.InteropServices;
+
+namespace Todo.Interop;
+"#;
@@ -0,0 +1,396 @@
+//! Go (cgo) backend: renders `bindings/go/todo.go` from the header.
+//!
+//! # Design
+//! The file has two parts. The fixed glue (error type, plain-data Go
+//! structs, copying out of and freeing C results) is written here once.
+//! It is checked against the ABI with `Abi::require_fields`, so renaming a
+//! field it reads fails generation instead of breaking cgo later. Enums and
+//! exported functions are generated: each function is classified by its
+//! signature and becomes a `Client` method or a `NewClient*` constructor.
+//! A function the rules do not cover is an error rather than an omission.
+//!
+//! C results never outlive the call that produced them. They are copied
+//! into Go values and freed at once, so the only finalizer is the client
+//! handle's.
+
+use std::fmt::Write;
+
+use crate::abi::{Abi, CType, Function, Param};
+use crate::naming::{camel, pascal, type_name, variant_suffix, C_FN_PREFIX};
+
+/// Words upper-cased whole in Go identifiers, per Go naming conventions.
+const INITIALISMS: &[&str] = &["id", "url", "http", "tls", "utf8"];
+
+/// `const char *` parameters that may be null, exposed as `*string`.
+const NULLABLE_ARGS: &[(&str, &str)] = &[("todo_build_update_todo", "title")];
+
+/// Go names that differ from the mechanical translation.
+const RENAMES: &[(&str, &str)] = &[("todo_client_eq", "Equal")];
+
+/// Exported functions the package deliberately does not call.
+/// `todo_free_string` frees strings handed out on their own; every string
+/// Go sees is part of a request or result and is freed with it.
+pub const NOT_WRAPPED: &[&str] = &["todo_free_string"];
+
+/// The .NET-shaped surface duplicates the UTF-8 one, which cgo handles
+/// natively.
+const NET_PREFIX: &str = "todo_net_";
+
+/// True for exported functions the Go package does not wrap.
+pub fn skipped(function: &str) -> bool {
+    NOT_WRAPPED.contains(&function) || function.starts_with(NET_PREFIX)
+}
+
+const CLIENT: &str = "FfiFfiTodoClient";
+const REQUEST: &str = "FfiFfiHttpRequest";
+const RESPONSE: &str = "FfiFfiHttpResponse";
+const RESULT: &str = "FfiFfiTodoResult";
+const ERROR_CODE: &str = "FfiFfiErrorCode";
+
+/// Render the Go package source.
+pub fn generate(abi: &Abi) -> Result<String, String> {
+    check_glue(abi)?;
+    let mut out = String::from(PREAMBLE);
+    for e in &abi.enums {
+        let go_type = type_name(&e.name);
+        let _ = writeln!(out, "\n// {go_type} mirrors the C enum {}.", e.name);
+        let _ = writeln!(out, "type {go_type} int\n");
+        for (variant, value) in &e.variants {
+            let _ = writeln!(out, "const {} {go_type} = {value}", enum_const(&e.name, variant));
+        }
+        let _ = writeln!(out, "\nfunc (v {go_type}) String() string {{\n\tswitch v {{");
+        for (variant, _) in &e.variants {
+            let suffix = variant_suffix(&e.name, variant);
+            let _ = writeln!(out, "\tcase {}:\n\t\treturn \"{suffix}\"", enum_const(&e.name, variant));
+        }
+        let _ = writeln!(out, "\t}}\n\treturn fmt.Sprintf(\"{go_type}(%d)\", int(v))\n}}");
+    }
+    out.push_str(GLUE);
+    for (variant, _) in abi.enum_named(ERROR_CODE)?.variants.iter().skip(1) {
+        let suffix = pascal(variant_suffix(ERROR_CODE, variant), INITIALISMS);
+        let code = enum_const(ERROR_CODE, variant);
+        let _ = writeln!(out, "\n// Err{suffix} matches any Error whose Code is {code}.");
+        let _ = writeln!(out, "var Err{suffix} = &Error{{Code: {code}}}");
+    }
+    for function in &abi.functions {
+        if let Some(method) = render_function(function)? {
+            out.push('\n');
+            out.push_str(&method);
+        }
+    }
+    Ok(out)
+}
+
+/// Fail unless the header has every type, field and function the fixed
+/// glue refers to.
+fn check_glue(abi: &Abi) -> Result<(), String> {
+    abi.require_fields(REQUEST, &["method", "path", "headers", "headers_len", "body"])?;
+    abi.require_fields("FfiFfiHeader", &["key", "value"])?;
+    abi.require_fields(RESPONSE, &["status", "body"])?;
+    abi.require_fields(RESULT, &["error_code", "error_message", "http_status", "data_tag", "data"])?;
+    abi.require_fields("FfiFfiTodo", &["id", "title", "completed"])?;
+    abi.require_fields("FfiFfiTodoList", &["items", "len"])?;
+    for name in ["todo_client_free", "todo_free_request", "todo_free_result"] {
+        abi.function(name)?;
+    }
+    let variants = [
+        ("FfiFfiHttpMethod", None),
+        (ERROR_CODE, Some(&["OK", "VALIDATION"][..])),
+        ("FfiFfiDataTag", Some(&["TODO", "TODO_LIST", "COUNT"][..])),
+    ];
+    for (name, required) in variants {
+        let e = abi.enum_named(name)?;
+        for suffix in required.unwrap_or_default() {
+            if !e.variants.iter().any(|(v, _)| variant_suffix(name, v) == *suffix) {
+                return Err(format!("{name} has no variant {suffix}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `HttpMethodGet` for (`FfiFfiHttpMethod`, `FFI_FFI_HTTP_METHOD_GET`).
+fn enum_const(enum_name: &str, variant: &str) -> String {
+    format!("{}{}", type_name(enum_name), pascal(variant_suffix(enum_name, variant), INITIALISMS))
+}
+
+fn is_client(ty: &CType) -> bool {
+    ty.pointee_name() == Some(CLIENT)
+}
+
+/// The Go source for one exported function, or `None` when the fixed glue
+/// already covers it.
+fn render_function(f: &Function) -> Result<Option<String>, String> {
+    let name = f.name.as_str();
+    if name == "todo_client_free" || name == "todo_free_request" || name == "todo_free_result" {
+        return Ok(None);
+    }
+    if skipped(name) {
+        return Ok(None);
+    }
+    let unsupported = |what: String| format!("no Go mapping for {name}: {what}");
+    let stem = name.strip_prefix(C_FN_PREFIX).ok_or_else(|| unsupported("missing prefix".into()))?;
+
+    let (returns, wrap) = match (&f.ret, f.ret.pointee_name()) {
+        (_, Some(CLIENT)) => ("(*Client, error)", format!("return wrapClient(ret, \"{name}\")")),
+        (_, Some(REQUEST)) => ("(*Request, error)", format!("return takeRequest(ret, \"{name}\")")),
+        (_, Some(RESULT)) => ("(*Result, error)", format!("return takeResult(ret, \"{name}\")")),
+        (CType::Bool, _) => ("bool", "return bool(ret)".to_string()),
+        (other, _) => return Err(unsupported(format!("return type {other}"))),
+    };
+
+    let mut params = f.params.as_slice();
+    let receiver = params.first().filter(|p| is_client(&p.ty)).map(|p| p.name.clone());
+    if receiver.is_some() {
+        params = &params[1..];
+    }
+    let go_name = match (RENAMES.iter().find(|(c, _)| *c == name), &receiver) {
+        (Some((_, go)), _) => go.to_string(),
+        (None, Some(_)) => pascal(stem.strip_prefix("client_").unwrap_or(stem), INITIALISMS),
+        (None, None) => match stem.strip_prefix("client_new") {
+            Some(rest) if f.ret.pointee_name() == Some(CLIENT) => format!("NewClient{}", pascal(rest, INITIALISMS)),
+            _ => return Err(unsupported("free function that is not a client constructor".into())),
+        },
+    };
+
+    let mut signature = Vec::new();
+    let mut prologue = String::new();
+    let mut args = Vec::new();
+    let mut keep_alive = Vec::new();
+    if receiver.is_some() {
+        args.push("handle(c)".to_string());
+        keep_alive.push("c".to_string());
+    }
+    for Param { name: c_name, ty } in params {
+        let go = camel(c_name, INITIALISMS);
+        let local = format!("c{}", pascal(c_name, INITIALISMS));
+        if ty.is_const_str() {
+            if NULLABLE_ARGS.contains(&(name, c_name.as_str())) {
+                signature.push(format!("{go} *string"));
+                let _ = write!(
+                    prologue,
+                    "\tvar {local} *C.char\n\tif {go} != nil {{\n\t\t{local} = C.CString(*{go})\n\t\tdefer C.free(unsafe.Pointer({local}))\n\t}}\n"
+                );
+            } else {
+                signature.push(format!("{go} string"));
+                let _ = write!(prologue, "\t{local} := C.CString({go})\n\tdefer C.free(unsafe.Pointer({local}))\n");
+            }
+            args.push(local);
+        } else if is_client(ty) {
+            signature.push(format!("{go} *Client"));
+            args.push(format!("handle({go})"));
+            keep_alive.push(go);
+        } else if ty.pointee_name() == Some(RESPONSE) {
+            signature.push(format!("{go} Response"));
+            let _ = write!(
+                prologue,
+                "\t{local}Body := C.CString({go}.Body)\n\tdefer C.free(unsafe.Pointer({local}Body))\n\t{local} := C.{RESPONSE}{{status: C.uint16_t({go}.Status), body: {local}Body}}\n"
+            );
+            args.push(format!("&{local}"));
+        } else {
+            let (go_type, c_type) = match ty {
+                CType::Bool => ("bool".to_string(), "bool".to_string()),
+                CType::Int { signed, bits } => {
+                    let go_type = format!("{}int{bits}", if *signed { "" } else { "u" });
+                    (go_type, ty.to_string())
+                }
+                other => return Err(unsupported(format!("parameter {c_name}: {other}"))),
+            };
+            signature.push(format!("{go} {go_type}"));
+            args.push(format!("C.{c_type}({go})"));
+        }
+    }
+
+    let mut out = format!("// {go_name} wraps {name}.\n");
+    match &receiver {
+        Some(_) => {
+            let _ = writeln!(out, "func (c *Client) {go_name}({}) {returns} {{", signature.join(", "));
+        }
+        None => {
+            let _ = writeln!(out, "func {go_name}({}) {returns} {{", signature.join(", "));
+        }
+    }
+    out.push_str(&prologue);
+    let _ = writeln!(out, "\tret := C.{name}({})", args.join(", "));
+    for value in keep_alive {
+        let _ = writeln!(out, "\truntime.KeepAlive({value})");
+    }
+    let _ = writeln!(out, "\t{wrap}\n}}");
+    Ok(Some(out))
+}
+
+const PREAMBLE: &str = r#"// Code generated by todo-bindgen from todo_client.h. DO NOT EDIT.
+
+// Package todo wraps the todo_ffi C library for Go hosts.
+//
+// As with every binding, the host owns HTTP: Build* methods return a
+// Request to send, and Parse* methods decode the Response that came back.
+// Argument semantics are documented on the C functions in todo_client.h.
+// Build the library first with `cargo build -p todo-ffi --release`.
+package todo
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../../ffi/include
+#cgo LDFLAGS: -L${SRCDIR}/../../target/release -ltodo_ffi
+#include <stdlib.h>
+#include "todo_client.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+"#;
+
+const GLUE: &str = r#"
+// Error is a failed call. errors.Is matches it against the Err* values by
+// Code alone.
+type Error struct {
+	Code ErrorCode
+
+	// Status is the HTTP status, or 0 when no response was involved.
+	Status int
+
+	Message string
+}
+
+func (e *Error) Error() string {
+	if e.Status != 0 {
+		return fmt.Sprintf("todo: %s (HTTP %d): %s", e.Code, e.Status, e.Message)
+	}
+	return fmt.Sprintf("todo: %s: %s", e.Code, e.Message)
+}
+
+// Is reports whether target is an *Error with the same Code.
+func (e *Error) Is(target error) bool {
+	t, ok := target.(*Error)
+	return ok && t.Code == e.Code
+}
+
+// rejected reports a C function that returned null, which it does for
+// null, non-UTF-8 or otherwise invalid arguments.
+func rejected(function string) error {
+	return &Error{Code: ErrorCodeValidation, Message: function + " rejected its arguments"}
+}
+
+// Header is one request header.
+type Header struct {
+	Name  string
+	Value string
+}
+
+// Request describes an HTTP request for the host to send. Path is the full
+// URL, query string included.
+type Request struct {
+	Method  HttpMethod
+	Path    string
+	Headers []Header
+
+	// Body is nil when the request has none.
+	Body *string
+}
+
+// Response is what the host received after sending a Request.
+type Response struct {
+	Status int
+	Body   string
+}
+
+// Todo is a single todo item.
+type Todo struct {
+	ID        string
+	Title     string
+	Completed bool
+}
+
+// Result is a successful Parse* call. Which field is set depends on the
+// operation: Todo for single-item calls, Todos for lists and Count for
+// delete-all.
+type Result struct {
+	Todo  *Todo
+	Todos []Todo
+	Count uint64
+}
+
+// Client owns a todo_ffi client handle. Close releases it; a Client that is
+// never closed is released by a finalizer once unreachable. Calls made
+// after Close return an error.
+type Client struct {
+	ptr *C.FfiFfiTodoClient
+}
+
+func wrapClient(ptr *C.FfiFfiTodoClient, function string) (*Client, error) {
+	if ptr == nil {
+		return nil, rejected(function)
+	}
+	c := &Client{ptr: ptr}
+	runtime.SetFinalizer(c, (*Client).Close)
+	return c, nil
+}
+
+// handle is c's C pointer, or null for a nil Client.
+func handle(c *Client) *C.FfiFfiTodoClient {
+	if c == nil {
+		return nil
+	}
+	return c.ptr
+}
+
+// Close releases the handle. Calling it again does nothing.
+func (c *Client) Close() {
+	if c.ptr == nil {
+		return
+	}
+	C.todo_client_free(c.ptr)
+	c.ptr = nil
+	runtime.SetFinalizer(c, nil)
+}
+
+func takeRequest(req *C.FfiFfiHttpRequest, function string) (*Request, error) {
+	if req == nil {
+		return nil, rejected(function)
+	}
+	defer C.todo_free_request(req)
+	out := &Request{Method: HttpMethod(req.method), Path: C.GoString(req.path)}
+	for _, h := range unsafe.Slice(req.headers, req.headers_len) {
+		out.Headers = append(out.Headers, Header{Name: C.GoString(h.key), Value: C.GoString(h.value)})
+	}
+	if req.body != nil {
+		body := C.GoString(req.body)
+		out.Body = &body
+	}
+	return out, nil
+}
+
+func takeResult(res *C.FfiFfiTodoResult, function string) (*Result, error) {
+	if res == nil {
+		return nil, rejected(function)
+	}
+	defer C.todo_free_result(res)
+	if code := ErrorCode(res.error_code); code != ErrorCodeOk {
+		return nil, &Error{Code: code, Status: int(res.http_status), Message: C.GoString(res.error_message)}
+	}
+	out := &Result{}
+	switch DataTag(res.data_tag) {
+	case DataTagTodo:
+		todo := goTodo((*C.FfiFfiTodo)(res.data))
+		out.Todo = &todo
+	case DataTagTodoList:
+		list := (*C.FfiFfiTodoList)(res.data)
+		items := unsafe.Slice(list.items, list.len)
+		out.Todos = make([]Todo, 0, len(items))
+		for i := range items {
+			out.Todos = append(out.Todos, goTodo(&items[i]))
+		}
+	case DataTagCount:
+		out.Count = uint64(*(*C.uint64_t)(res.data))
+	}
+	return out, nil
+}
+
+func goTodo(t *C.FfiFfiTodo) Todo {
+	return Todo{ID: C.GoString(t.id), Title: C.GoString(t.title), Completed: bool(t.completed)}
+}
+"#;
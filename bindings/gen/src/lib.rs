@@ -0,0 +1,30 @@
+//! Generates foreign-language bindings from the `todo-ffi` C header.
+//!
+//! # Design
+//! `abi` parses the header cbindgen writes into a small model; each backend
+//! renders that model for one language. Generated sources are checked in
+//! so users of a binding need no Rust toolchain. Each backend has a test in
+//! `tests/` that fails when the checked-in copy no longer matches the
+//! header, so the bindings cannot drift from the FFI surface unnoticed.
+//! The `c` backend renders a conformance program from the header and
+//! [`TEST_VECTORS`] instead of bindings.
+
+pub mod abi;
+pub mod c;
+pub mod csharp;
+pub mod dart;
+pub mod go;
+pub mod naming;
+pub mod ruby;
+
+/// The header of the `todo-ffi` this crate was built against.
+pub const HEADER: &str = include_str!(env!("TODO_FFI_HEADER"));
+
+/// The shared test vectors, as `(file stem, JSON source)` pairs.
+pub const TEST_VECTORS: [(&str, &str); 5] = [
+    ("list", include_str!("../../../test-vectors/list.json")),
+    ("get", include_str!("../../../test-vectors/get.json")),
+    ("create", include_str!("../../../test-vectors/create.json")),
+    ("update", include_str!("../../../test-vectors/update.json")),
+    ("delete", include_str!("../../../test-vectors/delete.json")),
+];
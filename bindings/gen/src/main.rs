@@ -0,0 +1,38 @@
+//! `todo-bindgen <language> <out-dir>`: regenerate a binding's sources.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use todo_bindgen::abi::Abi;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [language, out_dir] = args.as_slice() else {
+        eprintln!("usage: todo-bindgen <go|csharp|dart|ruby|c> <out-dir>");
+        return ExitCode::FAILURE;
+    };
+    let generated = Abi::parse(todo_bindgen::HEADER).and_then(|abi| match language.as_str() {
+        "go" => todo_bindgen::go::generate(&abi).map(|source| vec![("todo.go", source)]),
+        "dart" => todo_bindgen::dart::generate(&abi).map(|source| vec![("bindings.g.dart", source)]),
+        "ruby" => todo_bindgen::ruby::generate(&abi).map(|source| vec![("native.rb", source)]),
+        "c" => todo_bindgen::c::generate(&abi, &todo_bindgen::TEST_VECTORS).map(|source| vec![("conformance.c", source)]),
+        "csharp" => todo_bindgen::csharp::generate(&abi).map(|source| vec![("TodoInterop.cs", source)]),
+        other => Err(format!("unknown language {other}")),
+    });
+    let files = match generated {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("todo-bindgen: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for (name, source) in files {
+        let path = Path::new(out_dir).join(name);
+        if let Err(e) = std::fs::write(&path, source) {
+            eprintln!("todo-bindgen: writing {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("wrote {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
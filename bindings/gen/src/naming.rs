@@ -0,0 +1,73 @@
+//! Case conversions between C names and target-language names.
+
+/// cbindgen's `prefix` ("Ffi") applied to types already named `Ffi*`.
+pub const C_TYPE_PREFIX: &str = "FfiFfi";
+
+/// Prefix of every exported function.
+pub const C_FN_PREFIX: &str = "todo_";
+
+/// C type name without the cbindgen prefix: `FfiFfiHttpMethod` → `HttpMethod`.
+pub fn type_name(c_name: &str) -> &str {
+    c_name.strip_prefix(C_TYPE_PREFIX).unwrap_or(c_name)
+}
+
+/// `FfiFfiHttpMethod` → `FFI_FFI_HTTP_METHOD`, the prefix cbindgen puts on
+/// each of that enum's variants.
+pub fn screaming(camel: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in camel.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
+
+/// A variant's name with its enum's prefix removed:
+/// (`FfiFfiHttpMethod`, `FFI_FFI_HTTP_METHOD_GET`) → `GET`.
+pub fn variant_suffix<'a>(enum_name: &str, variant: &'a str) -> &'a str {
+    let prefix = screaming(enum_name);
+    variant.strip_prefix(&prefix).and_then(|rest| rest.strip_prefix('_')).unwrap_or(variant)
+}
+
+/// `not_found` or `NOT_FOUND` → `NotFound`. Words in `initialisms` are
+/// upper-cased whole (`base_url` → `BaseURL` given `["url"]`).
+pub fn pascal(snake: &str, initialisms: &[&str]) -> String {
+    snake
+        .split('_')
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            let lower = word.to_ascii_lowercase();
+            if initialisms.contains(&lower.as_str()) {
+                return lower.to_ascii_uppercase();
+            }
+            let mut chars = lower.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `base_url` → `baseURL` given `["url"]`; the first word stays lower-case.
+pub fn camel(snake: &str, initialisms: &[&str]) -> String {
+    let mut words = snake.splitn(2, '_');
+    let first = words.next().unwrap_or_default().to_ascii_lowercase();
+    first + &pascal(words.next().unwrap_or_default(), initialisms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(type_name("FfiFfiTodoResult"), "TodoResult");
+        assert_eq!(screaming("FfiFfiHttpMethod"), "FFI_FFI_HTTP_METHOD");
+        assert_eq!(variant_suffix("FfiFfiDataTag", "FFI_FFI_DATA_TAG_TODO_LIST"), "TODO_LIST");
+        assert_eq!(pascal("NULL_ARG", &[]), "NullArg");
+        assert_eq!(pascal("build_get_todo", &[]), "BuildGetTodo");
+        assert_eq!(pascal("http", &["http"]), "HTTP");
+        assert_eq!(camel("base_url", &["url"]), "baseURL");
+        assert_eq!(camel("id", &["id"]), "id");
+    }
+}
@@ -0,0 +1,171 @@
+//! Ruby backend: renders `bindings/ruby/lib/todo/native.rb` for the `ffi`
+//! gem.
+//!
+//! # Design
+//! Like the Dart backend, it binds every exported function under its C
+//! name and leaves the idiomatic API to the hand-written `lib/todo.rb`.
+//! Structs become `FFI::Struct` layouts and enums `FFI::Enum`s whose
+//! symbols are the lower-cased variant suffixes (`:not_found`).
+//!
+//! Memory management is derived from the header rather than listed here:
+//! a `void` function named `*free*` taking one pointer to a struct is that
+//! struct's release function. Such a struct becomes an `FFI::ManagedStruct`
+//! (or an `FFI::AutoPointer` when opaque) whose `release` calls it, and
+//! gets a `take` that wraps a returned pointer, mapping null to `nil`.
+//! Functions themselves return plain pointers, because a managed type as
+//! the return type would wrap null pointers too.
+
+use std::fmt::Write;
+
+use crate::abi::{Abi, CType, Function, Struct};
+use crate::naming::{type_name, variant_suffix};
+
+/// The struct released by `f`, if `f` is a release function.
+pub fn released_struct(f: &Function) -> Option<&str> {
+    match (&f.ret, f.params.as_slice()) {
+        (CType::Void, [p]) if f.name.contains("free") => p.ty.pointee_name(),
+        _ => None,
+    }
+}
+
+fn release_function<'a>(abi: &'a Abi, s: &Struct) -> Option<&'a str> {
+    abi.functions.iter().find(|f| released_struct(f) == Some(s.name.as_str())).map(|f| f.name.as_str())
+}
+
+/// Render the Ruby source.
+pub fn generate(abi: &Abi) -> Result<String, String> {
+    let mut out = String::from(PREAMBLE);
+    for e in &abi.enums {
+        let symbols: Vec<String> = e
+            .variants
+            .iter()
+            .map(|(variant, value)| format!(":{}, {value}", variant_suffix(&e.name, variant).to_ascii_lowercase()))
+            .collect();
+        let name = type_name(&e.name);
+        let _ = writeln!(out, "\n    # Values of the C enum `{}`.", e.name);
+        let _ = writeln!(out, "    {name} = enum(:{}, [{}])", snake(name), symbols.join(", "));
+    }
+    for s in &abi.structs {
+        render_struct(&mut out, abi, s)?;
+    }
+    out.push('\n');
+    for f in &abi.functions {
+        let params = f.params.iter().map(|p| param_type(abi, &p.ty)).collect::<Result<Vec<_>, _>>()?;
+        let ret = match &f.ret {
+            CType::Void => ":void".to_string(),
+            ty => field_type(abi, ty)?,
+        };
+        let _ = writeln!(out, "    attach_function :{}, [{}], {ret}", f.name, params.join(", "));
+    }
+    out.push_str("  end\nend\n");
+    Ok(out)
+}
+
+fn render_struct(out: &mut String, abi: &Abi, s: &Struct) -> Result<(), String> {
+    let name = type_name(&s.name);
+    let release = release_function(abi, s);
+    let base = match (&s.fields, release) {
+        (None, None) => return Ok(()),
+        (None, Some(_)) => "FFI::AutoPointer",
+        (Some(_), None) => "FFI::Struct",
+        (Some(_), Some(_)) => "FFI::ManagedStruct",
+    };
+    let _ = writeln!(out, "\n    # `{}`{}.", s.name, release.map(|r| format!(", released with `{r}`")).unwrap_or_default());
+    let _ = writeln!(out, "    class {name} < {base}");
+    if let Some(fields) = &s.fields {
+        let mut layout = Vec::new();
+        for field in fields {
+            layout.push(format!(":{}, {}", field.name, field_type(abi, &field.ty)?));
+        }
+        let _ = writeln!(out, "      layout {}", layout.join(",\n             "));
+    }
+    if let Some(release) = release {
+        if s.fields.is_some() {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "      def self.release(ptr)\n        Native.{release}(ptr)\n      end\n");
+        out.push_str("      # Takes ownership of `ptr`; `nil` when it is null.\n");
+        out.push_str("      def self.take(ptr)\n        ptr.null? ? nil : new(ptr)\n      end\n");
+    }
+    out.push_str("    end\n");
+    Ok(())
+}
+
+fn is_enum(abi: &Abi, name: &str) -> bool {
+    abi.enums.iter().any(|e| e.name == name)
+}
+
+/// Type for a struct field or return value. Pointers stay raw, since
+/// strings read from them still have to be freed by their owner.
+fn field_type(abi: &Abi, ty: &CType) -> Result<String, String> {
+    Ok(match ty {
+        CType::Void => return Err("void field".to_string()),
+        CType::Bool => ":bool".to_string(),
+        CType::Char => ":char".to_string(),
+        CType::Int { signed, bits } => format!(":{}int{bits}", if *signed { "" } else { "u" }),
+        CType::Named(name) if is_enum(abi, name) => type_name(name).to_string(),
+        CType::Named(name) => return Err(format!("struct {name} passed by value")),
+        CType::Pointer { .. } => ":pointer".to_string(),
+    })
+}
+
+/// Type for a parameter: `const char *` arguments take Ruby strings (or
+/// `nil` for null).
+fn param_type(abi: &Abi, ty: &CType) -> Result<String, String> {
+    if ty.is_const_str() {
+        return Ok(":string".to_string());
+    }
+    field_type(abi, ty)
+}
+
+/// `HttpMethod` → `http_method`.
+fn snake(pascal: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in pascal.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+const PREAMBLE: &str = r#"# frozen_string_literal: true
+
+# AUTO GENERATED FILE, DO NOT EDIT.
+#
+# Generated by todo-bindgen from todo_client.h. Regenerate with
+# `cargo run -p todo-bindgen -- ruby bindings/ruby/lib/todo`.
+
+require "ffi"
+
+module Todo
+  # Raw bindings to the todo_ffi C library, under the C names.
+  module Native
+    extend FFI::Library
+
+    ffi_lib ENV.fetch("TODO_FFI_LIB", "todo_ffi")
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_functions_manage_their_struct() {
+        let abi = Abi::parse(
+            "typedef struct FfiFfiBox FfiFfiBox;\n\
+             typedef struct FfiFfiPair { uint16_t a; char *b; } FfiFfiPair;\n\
+             struct FfiFfiBox *t_new(const char *name);\n\
+             void t_free(struct FfiFfiBox *b);\n\
+             void t_free_string(char *s);\n",
+        )
+        .unwrap();
+        let out = generate(&abi).unwrap();
+        assert!(out.contains("class Box < FFI::AutoPointer"));
+        assert!(out.contains("Native.t_free(ptr)"));
+        assert!(out.contains("class Pair < FFI::Struct\n      layout :a, :uint16,\n             :b, :pointer\n"));
+        assert!(out.contains("attach_function :t_new, [:string], :pointer"));
+        assert_eq!(abi.functions.iter().filter_map(released_struct).collect::<Vec<_>>(), ["FfiFfiBox"]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Keeps `bindings/c/conformance.c` in sync and runs it.
+//!
+//! # Design
+//! Besides the usual regenerate-and-compare check, the program is compiled
+//! with the compiler the `cc` crate picks for this target, linked against
+//! the `libtodo_ffi` cargo built next to this test, and run. Any failed
+//! check fails the test. Skipped when no C compiler is found or it is
+//! MSVC, whose command line this test does not speak.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use todo_bindgen::abi::Abi;
+use todo_bindgen::{c, HEADER, TEST_VECTORS};
+
+fn program() -> PathBuf {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../c/conformance.c")).to_path_buf()
+}
+
+fn generated() -> String {
+    c::generate(&Abi::parse(HEADER).unwrap(), &TEST_VECTORS).unwrap()
+}
+
+#[test]
+fn checked_in_program_is_up_to_date() {
+    let checked_in = std::fs::read_to_string(program()).unwrap();
+    assert!(
+        checked_in == generated(),
+        "bindings/c/conformance.c is stale; run `cargo run -p todo-bindgen -- c bindings/c`"
+    );
+}
+
+#[test]
+fn uncovered_function_fails_generation() {
+    let mut abi = Abi::parse(HEADER).unwrap();
+    let mut extra = abi.functions[0].clone();
+    extra.name = "todo_not_exercised".to_string();
+    abi.functions.push(extra);
+    let err = c::generate(&abi, &TEST_VECTORS).unwrap_err();
+    assert!(err.contains("todo_not_exercised"), "{err}");
+}
+
+/// `target/<profile>`, where cargo put `libtodo_ffi` next to this test's `deps/`.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn conformance_program_passes() {
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("HOST"))
+        .opt_level(0)
+        .std("c11")
+        .cargo_metadata(false)
+        .cargo_warnings(false)
+        .warnings_into_errors(true)
+        .try_get_compiler();
+    let compiler = match compiler {
+        Ok(compiler) if !compiler.is_like_msvc() => compiler,
+        _ => {
+            eprintln!("skipping: no gcc-like C compiler found");
+            return;
+        }
+    };
+    let exe = std::env::temp_dir().join(format!("todo-conformance-{}", std::process::id()));
+    let include = Path::new(env!("TODO_FFI_HEADER")).parent().unwrap();
+    let lib_dir = library_dir();
+    let compiled = compiler
+        .to_command()
+        .args(["-Wall", "-Wextra", "-I"])
+        .arg(include)
+        .arg(program())
+        .arg("-o")
+        .arg(&exe)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-ltodo_ffi")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .output()
+        .unwrap();
+    assert!(compiled.status.success(), "conformance.c failed to compile: {}", String::from_utf8_lossy(&compiled.stderr));
+
+    let output = Command::new(&exe).output().unwrap();
+    std::fs::remove_file(&exe).ok();
+    assert!(
+        output.status.success(),
+        "conformance checks failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,50 @@
+//! Keeps `bindings/dotnet` in sync with the .NET-facing FFI surface.
+//!
+//! # Design
+//! Same approach as `tests/go.rs`: regenerate and compare, then check that
+//! every `todo_net_*` function is declared. With a .NET SDK on `PATH` the
+//! project is also compiled; otherwise that test is skipped.
+
+use std::path::Path;
+use std::process::Command;
+
+use todo_bindgen::abi::Abi;
+use todo_bindgen::{csharp, HEADER};
+
+fn project_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../dotnet"))
+}
+
+fn generated() -> String {
+    csharp::generate(&Abi::parse(HEADER).unwrap()).unwrap()
+}
+
+#[test]
+fn checked_in_interop_is_up_to_date() {
+    let checked_in = std::fs::read_to_string(project_dir().join("TodoInterop.cs")).unwrap();
+    assert!(
+        checked_in == generated(),
+        "bindings/dotnet/TodoInterop.cs is stale; run `cargo run -p todo-bindgen -- csharp bindings/dotnet`"
+    );
+}
+
+#[test]
+fn every_net_function_is_declared() {
+    let source = generated();
+    let abi = Abi::parse(HEADER).unwrap();
+    let net: Vec<_> = abi.functions.iter().filter(|f| f.name.starts_with("todo_net_")).collect();
+    assert!(!net.is_empty());
+    for function in net {
+        assert!(source.contains(&format!(" {}(", function.name)), "{} is not declared", function.name);
+    }
+}
+
+#[test]
+fn dotnet_build_accepts_project() {
+    if !Command::new("dotnet").arg("--version").output().is_ok_and(|o| o.status.success()) {
+        eprintln!("skipping: no .NET SDK on PATH");
+        return;
+    }
+    let output = Command::new("dotnet").arg("build").current_dir(project_dir()).output().unwrap();
+    assert!(output.status.success(), "dotnet build failed: {}", String::from_utf8_lossy(&output.stdout));
+}
@@ -0,0 +1,67 @@
+//! Keeps `bindings/dart` in sync with the C header.
+//!
+//! # Design
+//! Same approach as `tests/go.rs`: regenerate and compare, then check that
+//! every exported function gets a lookup. The hand-written `lib/todo.dart`
+//! is checked to call only functions the header declares. With `dart` on
+//! `PATH` the package is also analyzed; otherwise that test is skipped.
+
+use std::path::Path;
+use std::process::Command;
+
+use todo_bindgen::abi::Abi;
+use todo_bindgen::{dart, HEADER};
+
+fn package_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../dart"))
+}
+
+fn generated() -> String {
+    dart::generate(&Abi::parse(HEADER).unwrap()).unwrap()
+}
+
+#[test]
+fn checked_in_bindings_are_up_to_date() {
+    let checked_in = std::fs::read_to_string(package_dir().join("lib/src/bindings.g.dart")).unwrap();
+    assert!(
+        checked_in == generated(),
+        "bindings/dart/lib/src/bindings.g.dart is stale; run `cargo run -p todo-bindgen -- dart bindings/dart/lib/src`"
+    );
+}
+
+#[test]
+fn every_function_is_looked_up() {
+    let source = generated();
+    for function in Abi::parse(HEADER).unwrap().functions {
+        assert!(source.contains(&format!("('{}')", function.name)), "{} is not looked up", function.name);
+    }
+}
+
+#[test]
+fn wrapper_calls_only_declared_functions() {
+    let abi = Abi::parse(HEADER).unwrap();
+    let wrapper = std::fs::read_to_string(package_dir().join("lib/todo.dart")).unwrap();
+    let mut calls = 0;
+    for (i, _) in wrapper.match_indices("_ffi.") {
+        // Skip file names such as `libtodo_ffi.so`.
+        if wrapper[..i].ends_with(|c: char| c.is_ascii_alphanumeric()) {
+            continue;
+        }
+        let rest = &wrapper[i + "_ffi.".len()..];
+        let name: String = rest.chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+        let name = name.strip_suffix("Ptr").unwrap_or(&name);
+        assert!(abi.function(name).is_ok(), "lib/todo.dart calls undeclared {name}");
+        calls += 1;
+    }
+    assert!(calls > 0);
+}
+
+#[test]
+fn dart_analyze_accepts_package() {
+    if !Command::new("dart").arg("--version").output().is_ok_and(|o| o.status.success()) {
+        eprintln!("skipping: no Dart SDK on PATH");
+        return;
+    }
+    let output = Command::new("dart").arg("analyze").current_dir(package_dir()).output().unwrap();
+    assert!(output.status.success(), "dart analyze failed: {}", String::from_utf8_lossy(&output.stdout));
+}
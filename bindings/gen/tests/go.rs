@@ -0,0 +1,53 @@
+//! Keeps `bindings/go` in sync with the FFI surface.
+//!
+//! # Design
+//! The Go package is generated and checked in, so these tests regenerate
+//! it from the header and compare, then check every exported function is
+//! called from Go. When a Go toolchain is on `PATH` the package is also
+//! type-checked with `go vet`; otherwise that test is skipped.
+
+use std::path::Path;
+use std::process::Command;
+
+use todo_bindgen::abi::Abi;
+use todo_bindgen::{go, HEADER};
+
+fn package_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../go"))
+}
+
+fn generated() -> String {
+    go::generate(&Abi::parse(HEADER).unwrap()).unwrap()
+}
+
+#[test]
+fn checked_in_package_is_up_to_date() {
+    let checked_in = std::fs::read_to_string(package_dir().join("todo.go")).unwrap();
+    assert!(
+        checked_in == generated(),
+        "bindings/go/todo.go is stale; run `cargo run -p todo-bindgen -- go bindings/go`"
+    );
+}
+
+#[test]
+fn every_exported_function_is_called() {
+    let source = generated();
+    for function in Abi::parse(HEADER).unwrap().functions {
+        let called = source.contains(&format!("C.{}(", function.name));
+        assert!(
+            called || go::skipped(&function.name),
+            "{} is not called from Go",
+            function.name
+        );
+    }
+}
+
+#[test]
+fn go_vet_accepts_package() {
+    if !Command::new("go").arg("version").output().is_ok_and(|o| o.status.success()) {
+        eprintln!("skipping: no Go toolchain on PATH");
+        return;
+    }
+    let output = Command::new("go").arg("vet").arg("./...").current_dir(package_dir()).output().unwrap();
+    assert!(output.status.success(), "go vet failed: {}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,111 @@
+//! Keeps `bindings/ruby` in sync with the C header.
+//!
+//! # Design
+//! Same approach as `tests/go.rs`: regenerate and compare, then check that
+//! every exported function is attached. With `ruby`, the `ffi` gem and a C
+//! compiler available, `test/conformance.rb` also runs against the built
+//! library: a probe compiled against the header prints each struct's size
+//! and field offsets, and the script checks the Ruby layouts against them
+//! before round-tripping a few calls. Otherwise that test is skipped.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use todo_bindgen::abi::Abi;
+use todo_bindgen::naming::type_name;
+use todo_bindgen::{ruby, HEADER};
+
+fn gem_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../ruby"))
+}
+
+fn generated() -> String {
+    ruby::generate(&Abi::parse(HEADER).unwrap()).unwrap()
+}
+
+#[test]
+fn checked_in_bindings_are_up_to_date() {
+    let checked_in = std::fs::read_to_string(gem_dir().join("lib/todo/native.rb")).unwrap();
+    assert!(
+        checked_in == generated(),
+        "bindings/ruby/lib/todo/native.rb is stale; run `cargo run -p todo-bindgen -- ruby bindings/ruby/lib/todo`"
+    );
+}
+
+#[test]
+fn every_function_is_attached() {
+    let source = generated();
+    for function in Abi::parse(HEADER).unwrap().functions {
+        assert!(source.contains(&format!("attach_function :{}, ", function.name)), "{} is not attached", function.name);
+    }
+}
+
+/// C source printing `Struct size` and `Struct.field offset` lines for
+/// every struct with fields, under the names the Ruby classes use.
+fn layout_probe(abi: &Abi) -> String {
+    let mut source = String::from("#include <stddef.h>\n#include <stdio.h>\n#include \"todo_client.h\"\n\nint main(void) {\n");
+    for s in &abi.structs {
+        let Some(fields) = &s.fields else { continue };
+        let name = type_name(&s.name);
+        source += &format!("    printf(\"{name} %zu\\n\", sizeof(struct {}));\n", s.name);
+        for field in fields {
+            source += &format!(
+                "    printf(\"{name}.{0} %zu\\n\", offsetof(struct {1}, {0}));\n",
+                field.name, s.name
+            );
+        }
+    }
+    source + "    return 0;\n}\n"
+}
+
+/// `target/<profile>`, where cargo put `libtodo_ffi` next to this test's `deps/`.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+fn succeeds(cmd: &mut Command) -> bool {
+    cmd.output().is_ok_and(|o| o.status.success())
+}
+
+#[test]
+fn conformance_script_passes() {
+    if !succeeds(Command::new("ruby").args(["-rffi", "-e", ""])) {
+        eprintln!("skipping: ruby with the ffi gem not found");
+        return;
+    }
+    let work = std::env::temp_dir().join(format!("todo-ruby-{}", std::process::id()));
+    std::fs::create_dir_all(&work).unwrap();
+    std::fs::write(work.join("probe.c"), layout_probe(&Abi::parse(HEADER).unwrap())).unwrap();
+    let include = Path::new(env!("TODO_FFI_HEADER")).parent().unwrap();
+    let compiled = Command::new("cc")
+        .arg("-I")
+        .arg(include)
+        .arg(work.join("probe.c"))
+        .arg("-o")
+        .arg(work.join("probe"))
+        .output();
+    let Ok(compiled) = compiled else {
+        eprintln!("skipping: cc not found");
+        return;
+    };
+    assert!(compiled.status.success(), "probe failed to compile: {}", String::from_utf8_lossy(&compiled.stderr));
+    let layout = Command::new(work.join("probe")).output().unwrap().stdout;
+
+    let mut script = Command::new("ruby")
+        .args(["-Ilib", "test/conformance.rb"])
+        .current_dir(gem_dir())
+        .env("TODO_FFI_LIB", library_dir().join(format!("{}todo_ffi{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    script.stdin.take().unwrap().write_all(&layout).unwrap();
+    let output = script.wait_with_output().unwrap();
+    std::fs::remove_dir_all(&work).ok();
+
+    assert!(output.status.success(), "conformance.rb failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+}
@@ -0,0 +1,475 @@
+// Code generated by todo-bindgen from todo_client.h. DO NOT EDIT.
+
+// Package todo wraps the todo_ffi C library for Go hosts.
+//
+// As with every binding, the host owns HTTP: Build* methods return a
+// Request to send, and Parse* methods decode the Response that came back.
+// Argument semantics are documented on the C functions in todo_client.h.
+// Build the library first with `cargo build -p todo-ffi --release`.
+package todo
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../../ffi/include
+#cgo LDFLAGS: -L${SRCDIR}/../../target/release -ltodo_ffi
+#include <stdlib.h>
+#include "todo_client.h"
+*/
+import "C"
+
+import (
+	"fmt"
+	"runtime"
+	"unsafe"
+)
+
+// DataTag mirrors the C enum FfiFfiDataTag.
+type DataTag int
+
+const DataTagNone DataTag = 0
+const DataTagTodo DataTag = 1
+const DataTagTodoList DataTag = 2
+const DataTagCount DataTag = 3
+
+func (v DataTag) String() string {
+	switch v {
+	case DataTagNone:
+		return "NONE"
+	case DataTagTodo:
+		return "TODO"
+	case DataTagTodoList:
+		return "TODO_LIST"
+	case DataTagCount:
+		return "COUNT"
+	}
+	return fmt.Sprintf("DataTag(%d)", int(v))
+}
+
+// ErrorCode mirrors the C enum FfiFfiErrorCode.
+type ErrorCode int
+
+const ErrorCodeOk ErrorCode = 0
+const ErrorCodeNotFound ErrorCode = 1
+const ErrorCodeHTTP ErrorCode = 2
+const ErrorCodeDeserialization ErrorCode = 3
+const ErrorCodeSerialization ErrorCode = 4
+const ErrorCodePanic ErrorCode = 5
+const ErrorCodeNullArg ErrorCode = 6
+const ErrorCodeConflict ErrorCode = 7
+const ErrorCodeValidation ErrorCode = 8
+const ErrorCodeRateLimited ErrorCode = 9
+const ErrorCodeTransport ErrorCode = 10
+const ErrorCodeSchemaMismatch ErrorCode = 11
+const ErrorCodeTLS ErrorCode = 12
+const ErrorCodePreconditionFailed ErrorCode = 13
+const ErrorCodeUnauthorized ErrorCode = 14
+
+func (v ErrorCode) String() string {
+	switch v {
+	case ErrorCodeOk:
+		return "OK"
+	case ErrorCodeNotFound:
+		return "NOT_FOUND"
+	case ErrorCodeHTTP:
+		return "HTTP"
+	case ErrorCodeDeserialization:
+		return "DESERIALIZATION"
+	case ErrorCodeSerialization:
+		return "SERIALIZATION"
+	case ErrorCodePanic:
+		return "PANIC"
+	case ErrorCodeNullArg:
+		return "NULL_ARG"
+	case ErrorCodeConflict:
+		return "CONFLICT"
+	case ErrorCodeValidation:
+		return "VALIDATION"
+	case ErrorCodeRateLimited:
+		return "RATE_LIMITED"
+	case ErrorCodeTransport:
+		return "TRANSPORT"
+	case ErrorCodeSchemaMismatch:
+		return "SCHEMA_MISMATCH"
+	case ErrorCodeTLS:
+		return "TLS"
+	case ErrorCodePreconditionFailed:
+		return "PRECONDITION_FAILED"
+	case ErrorCodeUnauthorized:
+		return "UNAUTHORIZED"
+	}
+	return fmt.Sprintf("ErrorCode(%d)", int(v))
+}
+
+// HttpMethod mirrors the C enum FfiFfiHttpMethod.
+type HttpMethod int
+
+const HttpMethodGet HttpMethod = 0
+const HttpMethodPost HttpMethod = 1
+const HttpMethodPut HttpMethod = 2
+const HttpMethodDelete HttpMethod = 3
+const HttpMethodOptions HttpMethod = 4
+const HttpMethodTrace HttpMethod = 5
+const HttpMethodPatch HttpMethod = 6
+const HttpMethodHead HttpMethod = 7
+
+func (v HttpMethod) String() string {
+	switch v {
+	case HttpMethodGet:
+		return "GET"
+	case HttpMethodPost:
+		return "POST"
+	case HttpMethodPut:
+		return "PUT"
+	case HttpMethodDelete:
+		return "DELETE"
+	case HttpMethodOptions:
+		return "OPTIONS"
+	case HttpMethodTrace:
+		return "TRACE"
+	case HttpMethodPatch:
+		return "PATCH"
+	case HttpMethodHead:
+		return "HEAD"
+	}
+	return fmt.Sprintf("HttpMethod(%d)", int(v))
+}
+
+// Error is a failed call. errors.Is matches it against the Err* values by
+// Code alone.
+type Error struct {
+	Code ErrorCode
+
+	// Status is the HTTP status, or 0 when no response was involved.
+	Status int
+
+	Message string
+}
+
+func (e *Error) Error() string {
+	if e.Status != 0 {
+		return fmt.Sprintf("todo: %s (HTTP %d): %s", e.Code, e.Status, e.Message)
+	}
+	return fmt.Sprintf("todo: %s: %s", e.Code, e.Message)
+}
+
+// Is reports whether target is an *Error with the same Code.
+func (e *Error) Is(target error) bool {
+	t, ok := target.(*Error)
+	return ok && t.Code == e.Code
+}
+
+// rejected reports a C function that returned null, which it does for
+// null, non-UTF-8 or otherwise invalid arguments.
+func rejected(function string) error {
+	return &Error{Code: ErrorCodeValidation, Message: function + " rejected its arguments"}
+}
+
+// Header is one request header.
+type Header struct {
+	Name  string
+	Value string
+}
+
+// Request describes an HTTP request for the host to send. Path is the full
+// URL, query string included.
+type Request struct {
+	Method  HttpMethod
+	Path    string
+	Headers []Header
+
+	// Body is nil when the request has none.
+	Body *string
+}
+
+// Response is what the host received after sending a Request.
+type Response struct {
+	Status int
+	Body   string
+}
+
+// Todo is a single todo item.
+type Todo struct {
+	ID        string
+	Title     string
+	Completed bool
+}
+
+// Result is a successful Parse* call. Which field is set depends on the
+// operation: Todo for single-item calls, Todos for lists and Count for
+// delete-all.
+type Result struct {
+	Todo  *Todo
+	Todos []Todo
+	Count uint64
+}
+
+// Client owns a todo_ffi client handle. Close releases it; a Client that is
+// never closed is released by a finalizer once unreachable. Calls made
+// after Close return an error.
+type Client struct {
+	ptr *C.FfiFfiTodoClient
+}
+
+func wrapClient(ptr *C.FfiFfiTodoClient, function string) (*Client, error) {
+	if ptr == nil {
+		return nil, rejected(function)
+	}
+	c := &Client{ptr: ptr}
+	runtime.SetFinalizer(c, (*Client).Close)
+	return c, nil
+}
+
+// handle is c's C pointer, or null for a nil Client.
+func handle(c *Client) *C.FfiFfiTodoClient {
+	if c == nil {
+		return nil
+	}
+	return c.ptr
+}
+
+// Close releases the handle. Calling it again does nothing.
+func (c *Client) Close() {
+	if c.ptr == nil {
+		return
+	}
+	C.todo_client_free(c.ptr)
+	c.ptr = nil
+	runtime.SetFinalizer(c, nil)
+}
+
+func takeRequest(req *C.FfiFfiHttpRequest, function string) (*Request, error) {
+	if req == nil {
+		return nil, rejected(function)
+	}
+	defer C.todo_free_request(req)
+	out := &Request{Method: HttpMethod(req.method), Path: C.GoString(req.path)}
+	for _, h := range unsafe.Slice(req.headers, req.headers_len) {
+		out.Headers = append(out.Headers, Header{Name: C.GoString(h.key), Value: C.GoString(h.value)})
+	}
+	if req.body != nil {
+		body := C.GoString(req.body)
+		out.Body = &body
+	}
+	return out, nil
+}
+
+func takeResult(res *C.FfiFfiTodoResult, function string) (*Result, error) {
+	if res == nil {
+		return nil, rejected(function)
+	}
+	defer C.todo_free_result(res)
+	if code := ErrorCode(res.error_code); code != ErrorCodeOk {
+		return nil, &Error{Code: code, Status: int(res.http_status), Message: C.GoString(res.error_message)}
+	}
+	out := &Result{}
+	switch DataTag(res.data_tag) {
+	case DataTagTodo:
+		todo := goTodo((*C.FfiFfiTodo)(res.data))
+		out.Todo = &todo
+	case DataTagTodoList:
+		list := (*C.FfiFfiTodoList)(res.data)
+		items := unsafe.Slice(list.items, list.len)
+		out.Todos = make([]Todo, 0, len(items))
+		for i := range items {
+			out.Todos = append(out.Todos, goTodo(&items[i]))
+		}
+	case DataTagCount:
+		out.Count = uint64(*(*C.uint64_t)(res.data))
+	}
+	return out, nil
+}
+
+func goTodo(t *C.FfiFfiTodo) Todo {
+	return Todo{ID: C.GoString(t.id), Title: C.GoString(t.title), Completed: bool(t.completed)}
+}
+
+// ErrNotFound matches any Error whose Code is ErrorCodeNotFound.
+var ErrNotFound = &Error{Code: ErrorCodeNotFound}
+
+// ErrHTTP matches any Error whose Code is ErrorCodeHTTP.
+var ErrHTTP = &Error{Code: ErrorCodeHTTP}
+
+// ErrDeserialization matches any Error whose Code is ErrorCodeDeserialization.
+var ErrDeserialization = &Error{Code: ErrorCodeDeserialization}
+
+// ErrSerialization matches any Error whose Code is ErrorCodeSerialization.
+var ErrSerialization = &Error{Code: ErrorCodeSerialization}
+
+// ErrPanic matches any Error whose Code is ErrorCodePanic.
+var ErrPanic = &Error{Code: ErrorCodePanic}
+
+// ErrNullArg matches any Error whose Code is ErrorCodeNullArg.
+var ErrNullArg = &Error{Code: ErrorCodeNullArg}
+
+// ErrConflict matches any Error whose Code is ErrorCodeConflict.
+var ErrConflict = &Error{Code: ErrorCodeConflict}
+
+// ErrValidation matches any Error whose Code is ErrorCodeValidation.
+var ErrValidation = &Error{Code: ErrorCodeValidation}
+
+// ErrRateLimited matches any Error whose Code is ErrorCodeRateLimited.
+var ErrRateLimited = &Error{Code: ErrorCodeRateLimited}
+
+// ErrTransport matches any Error whose Code is ErrorCodeTransport.
+var ErrTransport = &Error{Code: ErrorCodeTransport}
+
+// ErrSchemaMismatch matches any Error whose Code is ErrorCodeSchemaMismatch.
+var ErrSchemaMismatch = &Error{Code: ErrorCodeSchemaMismatch}
+
+// ErrTLS matches any Error whose Code is ErrorCodeTLS.
+var ErrTLS = &Error{Code: ErrorCodeTLS}
+
+// ErrPreconditionFailed matches any Error whose Code is ErrorCodePreconditionFailed.
+var ErrPreconditionFailed = &Error{Code: ErrorCodePreconditionFailed}
+
+// ErrUnauthorized matches any Error whose Code is ErrorCodeUnauthorized.
+var ErrUnauthorized = &Error{Code: ErrorCodeUnauthorized}
+
+// NewClient wraps todo_client_new.
+func NewClient(baseURL string) (*Client, error) {
+	cBaseURL := C.CString(baseURL)
+	defer C.free(unsafe.Pointer(cBaseURL))
+	ret := C.todo_client_new(cBaseURL)
+	return wrapClient(ret, "todo_client_new")
+}
+
+// NewClientBasicAuth wraps todo_client_new_basic_auth.
+func NewClientBasicAuth(baseURL string, user string, pass string) (*Client, error) {
+	cBaseURL := C.CString(baseURL)
+	defer C.free(unsafe.Pointer(cBaseURL))
+	cUser := C.CString(user)
+	defer C.free(unsafe.Pointer(cUser))
+	cPass := C.CString(pass)
+	defer C.free(unsafe.Pointer(cPass))
+	ret := C.todo_client_new_basic_auth(cBaseURL, cUser, cPass)
+	return wrapClient(ret, "todo_client_new_basic_auth")
+}
+
+// Clone wraps todo_client_clone.
+func (c *Client) Clone() (*Client, error) {
+	ret := C.todo_client_clone(handle(c))
+	runtime.KeepAlive(c)
+	return wrapClient(ret, "todo_client_clone")
+}
+
+// Equal wraps todo_client_eq.
+func (c *Client) Equal(b *Client) bool {
+	ret := C.todo_client_eq(handle(c), handle(b))
+	runtime.KeepAlive(c)
+	runtime.KeepAlive(b)
+	return bool(ret)
+}
+
+// BuildListTodos wraps todo_build_list_todos.
+func (c *Client) BuildListTodos() (*Request, error) {
+	ret := C.todo_build_list_todos(handle(c))
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_list_todos")
+}
+
+// BuildGetTodo wraps todo_build_get_todo.
+func (c *Client) BuildGetTodo(id string) (*Request, error) {
+	cID := C.CString(id)
+	defer C.free(unsafe.Pointer(cID))
+	ret := C.todo_build_get_todo(handle(c), cID)
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_get_todo")
+}
+
+// BuildCreateTodo wraps todo_build_create_todo.
+func (c *Client) BuildCreateTodo(title string, completed bool) (*Request, error) {
+	cTitle := C.CString(title)
+	defer C.free(unsafe.Pointer(cTitle))
+	ret := C.todo_build_create_todo(handle(c), cTitle, C.bool(completed))
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_create_todo")
+}
+
+// BuildUpdateTodo wraps todo_build_update_todo.
+func (c *Client) BuildUpdateTodo(id string, title *string, completed int32) (*Request, error) {
+	cID := C.CString(id)
+	defer C.free(unsafe.Pointer(cID))
+	var cTitle *C.char
+	if title != nil {
+		cTitle = C.CString(*title)
+		defer C.free(unsafe.Pointer(cTitle))
+	}
+	ret := C.todo_build_update_todo(handle(c), cID, cTitle, C.int32_t(completed))
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_update_todo")
+}
+
+// BuildDeleteTodo wraps todo_build_delete_todo.
+func (c *Client) BuildDeleteTodo(id string) (*Request, error) {
+	cID := C.CString(id)
+	defer C.free(unsafe.Pointer(cID))
+	ret := C.todo_build_delete_todo(handle(c), cID)
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_delete_todo")
+}
+
+// BuildDeleteAll wraps todo_build_delete_all.
+func (c *Client) BuildDeleteAll() (*Request, error) {
+	ret := C.todo_build_delete_all(handle(c))
+	runtime.KeepAlive(c)
+	return takeRequest(ret, "todo_build_delete_all")
+}
+
+// ParseListTodos wraps todo_parse_list_todos.
+func (c *Client) ParseListTodos(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_list_todos(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_list_todos")
+}
+
+// ParseGetTodo wraps todo_parse_get_todo.
+func (c *Client) ParseGetTodo(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_get_todo(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_get_todo")
+}
+
+// ParseCreateTodo wraps todo_parse_create_todo.
+func (c *Client) ParseCreateTodo(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_create_todo(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_create_todo")
+}
+
+// ParseUpdateTodo wraps todo_parse_update_todo.
+func (c *Client) ParseUpdateTodo(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_update_todo(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_update_todo")
+}
+
+// ParseDeleteTodo wraps todo_parse_delete_todo.
+func (c *Client) ParseDeleteTodo(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_delete_todo(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_delete_todo")
+}
+
+// ParseDeleteAll wraps todo_parse_delete_all.
+func (c *Client) ParseDeleteAll(response Response) (*Result, error) {
+	cResponseBody := C.CString(response.Body)
+	defer C.free(unsafe.Pointer(cResponseBody))
+	cResponse := C.FfiFfiHttpResponse{status: C.uint16_t(response.Status), body: cResponseBody}
+	ret := C.todo_parse_delete_all(handle(c), &cResponse)
+	runtime.KeepAlive(c)
+	return takeResult(ret, "todo_parse_delete_all")
+}
@@ -0,0 +1,111 @@
+package com.todo;
+
+import java.lang.ref.Cleaner;
+import java.lang.ref.Reference;
+import java.util.concurrent.locks.ReadWriteLock;
+import java.util.concurrent.locks.ReentrantReadWriteLock;
+
+/**
+ * Thin wrapper over the native todo client.
+ *
+ * <p>The client never touches the network. {@link #buildRequest} returns a
+ * JSON description of the HTTP request to send ({@code method}, {@code url},
+ * {@code headers}, {@code body}); after executing it, pass the response as
+ * {@code {"status", "headers", "body"}} to {@link #parseResponse} together with
+ * the same operation. Both return {@code {"ok": ...}} or
+ * {@code {"error": {"code", "status", "message"}}}.
+ *
+ * <p>Operations are JSON objects such as {@code {"op": "get_todo", "id": "..."}};
+ * the supported names are {@code list_todos}, {@code get_todo},
+ * {@code create_todo}, {@code update_todo} and {@code delete_todo}.
+ *
+ * <p>Native memory is released by a {@link Cleaner} once the client becomes
+ * unreachable, or immediately by {@link #close}. On Android this requires
+ * API level 33. Calls may run concurrently; {@link #close} waits for calls in
+ * flight before freeing the native client.
+ */
+public final class Client implements AutoCloseable {
+    static {
+        System.loadLibrary("todo_jni");
+    }
+
+    private static final Cleaner CLEANER = Cleaner.create();
+
+    private final long handle;
+    private final Cleaner.Cleanable cleanable;
+    /** Read-held by every native call, write-held by {@link #close}. */
+    private final ReadWriteLock lock = new ReentrantReadWriteLock();
+    private boolean closed;
+
+    /** @throws IllegalArgumentException if {@code baseUrl} is not a valid base URL */
+    public Client(String baseUrl) {
+        long handle = nativeNew(baseUrl);
+        if (handle == 0) {
+            throw new IllegalArgumentException("invalid base URL: " + baseUrl);
+        }
+        this.handle = handle;
+        this.cleanable = CLEANER.register(this, new Release(handle));
+    }
+
+    public String buildRequest(String operation) {
+        lock.readLock().lock();
+        try {
+            ensureOpen();
+            return nativeBuild(handle, operation);
+        } finally {
+            lock.readLock().unlock();
+            // Keeps the Cleaner from freeing the handle mid-call.
+            Reference.reachabilityFence(this);
+        }
+    }
+
+    public String parseResponse(String operation, String response) {
+        lock.readLock().lock();
+        try {
+            ensureOpen();
+            return nativeParse(handle, operation, response);
+        } finally {
+            lock.readLock().unlock();
+            Reference.reachabilityFence(this);
+        }
+    }
+
+    @Override
+    public void close() {
+        lock.writeLock().lock();
+        try {
+            closed = true;
+            cleanable.clean();
+        } finally {
+            lock.writeLock().unlock();
+        }
+    }
+
+    private void ensureOpen() {
+        if (closed) {
+            throw new IllegalStateException("client is closed");
+        }
+    }
+
+    /** Must not reference the client, or it would never become unreachable. */
+    private static final class Release implements Runnable {
+        private final long handle;
+
+        Release(long handle) {
+            this.handle = handle;
+        }
+
+        @Override
+        public void run() {
+            nativeFree(handle);
+        }
+    }
+
+    private static native long nativeNew(String baseUrl);
+
+    private static native void nativeFree(long handle);
+
+    private static native String nativeBuild(long handle, String operation);
+
+    private static native String nativeParse(long handle, String operation, String response);
+}
@@ -0,0 +1,192 @@
+//! JSON-in, JSON-out operations behind the JNI entry points.
+//!
+//! # Design
+//! Marshalling Java objects field by field through JNI is verbose and easy
+//! to get wrong, so the Java side speaks JSON strings instead and this
+//! module does all conversion in plain Rust, where it can be unit tested
+//! without a JVM.
+//!
+//! An operation is `{"op": "<name>", ...arguments}`. `build` returns the
+//! request as `{"method", "url", "headers": [[k, v]], "body"}`; the host
+//! executes it and hands the response back to `parse` as `{"status",
+//! "headers", "body"}`. Every result is either `{"ok": <value>}` or
+//! `{"error": {"code", "status", "message"}}`, where `code` is the
+//! snake_case name of the matching `FfiErrorCode`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use todo_core::{ApiError, CreateTodo, HttpRequest, HttpResponse, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+/// A client operation and its arguments.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Operation {
+    ListTodos,
+    GetTodo {
+        id: Uuid,
+    },
+    CreateTodo {
+        title: String,
+        #[serde(default)]
+        completed: bool,
+    },
+    UpdateTodo {
+        id: Uuid,
+        title: Option<String>,
+        completed: Option<bool>,
+    },
+    DeleteTodo {
+        id: Uuid,
+    },
+}
+
+#[derive(Serialize)]
+struct RequestJson {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl From<HttpRequest> for RequestJson {
+    fn from(req: HttpRequest) -> Self {
+        Self {
+            method: req.method.to_string(),
+            url: req.url(),
+            headers: req.headers,
+            body: req.body,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ResponseJson {
+    status: u16,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    #[serde(default)]
+    body: String,
+}
+
+/// Build the request for `operation`.
+pub fn build(client: &TodoClient, operation: &str) -> String {
+    render(build_request(client, operation).map(|req| json!(RequestJson::from(req))))
+}
+
+/// Parse `response`, the result of executing the request built for
+/// `operation`.
+pub fn parse(client: &TodoClient, operation: &str, response: &str) -> String {
+    render(parse_response(client, operation, response))
+}
+
+fn build_request(client: &TodoClient, operation: &str) -> Result<HttpRequest, ApiError> {
+    Ok(match decode::<Operation>(operation)? {
+        Operation::ListTodos => client.build_list_todos(),
+        Operation::GetTodo { id } => client.build_get_todo(id),
+        Operation::CreateTodo { title, completed } => client.build_create_todo(&CreateTodo { title, completed })?,
+        Operation::UpdateTodo { id, title, completed } => {
+            client.build_update_todo(id, &UpdateTodo { title, completed })?
+        }
+        Operation::DeleteTodo { id } => client.build_delete_todo(id),
+    })
+}
+
+fn parse_response(client: &TodoClient, operation: &str, response: &str) -> Result<Value, ApiError> {
+    let operation: Operation = decode(operation)?;
+    let response: ResponseJson = decode(response)?;
+    let response = HttpResponse {
+        status: response.status,
+        headers: response.headers,
+        body: response.body,
+    };
+    Ok(match operation {
+        Operation::ListTodos => json!(client.parse_list_todos(response)?),
+        Operation::GetTodo { .. } => json!(client.parse_get_todo(response)?),
+        Operation::CreateTodo { .. } => json!(client.parse_create_todo(response)?),
+        Operation::UpdateTodo { .. } => json!(client.parse_update_todo(response)?),
+        Operation::DeleteTodo { .. } => {
+            client.parse_delete_todo(response)?;
+            Value::Null
+        }
+    })
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(input: &str) -> Result<T, ApiError> {
+    serde_json::from_str(input).map_err(|e| ApiError::Validation(format!("malformed bridge JSON: {e}")))
+}
+
+fn render(result: Result<Value, ApiError>) -> String {
+    let envelope = match result {
+        Ok(value) => json!({ "ok": value }),
+        Err(err) => json!({
+            "error": {
+                "code": error_code(&err),
+                "status": err.status_code(),
+                "message": err.to_string(),
+            }
+        }),
+    };
+    envelope.to_string()
+}
+
+/// Same classification as `FfiErrorCode`, spelled for JSON.
+fn error_code(err: &ApiError) -> &'static str {
+    match err {
+        ApiError::NotFound | ApiError::NotFoundId(_) => "not_found",
+        ApiError::Unauthorized => "unauthorized",
+        ApiError::Conflict(_) => "conflict",
+        ApiError::PreconditionFailed => "precondition_failed",
+        ApiError::HttpError { .. } => "http",
+        ApiError::DeserializationError(_) => "deserialization",
+        ApiError::SerializationError(_) => "serialization",
+        ApiError::Validation(_) => "validation",
+        ApiError::RateLimited { .. } => "rate_limited",
+        ApiError::SchemaMismatch { .. } => "schema_mismatch",
+        ApiError::Transport(_) => "transport",
+        ApiError::Tls(_) => "tls",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> TodoClient {
+        TodoClient::new("http://localhost:3000")
+    }
+
+    fn value(s: &str) -> Value {
+        serde_json::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn build_create_renders_request() {
+        let out = value(&build(&client(), r#"{"op":"create_todo","title":"Buy milk"}"#));
+        let req = &out["ok"];
+        assert_eq!(req["method"], "POST");
+        assert_eq!(req["url"], "http://localhost:3000/todos");
+        assert_eq!(req["headers"], json!([["content-type", "application/json"]]));
+        assert_eq!(value(req["body"].as_str().unwrap()), json!({"title": "Buy milk", "completed": false}));
+    }
+
+    #[test]
+    fn parse_get_returns_todo_or_error() {
+        let op = r#"{"op":"get_todo","id":"00000000-0000-0000-0000-000000000001"}"#;
+        let ok = r#"{"status":200,"body":"{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"A\",\"completed\":true}"}"#;
+        let out = value(&parse(&client(), op, ok));
+        assert_eq!(out["ok"]["title"], "A");
+
+        let out = value(&parse(&client(), op, r#"{"status":404}"#));
+        assert_eq!(out["error"]["code"], "not_found");
+        assert_eq!(out["error"]["status"], 404);
+    }
+
+    #[test]
+    fn malformed_input_is_a_validation_error() {
+        let out = value(&build(&client(), r#"{"op":"rename_everything"}"#));
+        assert_eq!(out["error"]["code"], "validation");
+        let out = value(&parse(&client(), r#"{"op":"list_todos"}"#, "not json"));
+        assert_eq!(out["error"]["code"], "validation");
+    }
+}
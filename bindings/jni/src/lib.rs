@@ -0,0 +1,143 @@
+//! JNI bindings so JVM and Android hosts can reuse `todo-core`.
+//!
+//! # Overview
+//! Exports the `native` methods of `com.todo.Client` (see
+//! `java/com/todo/Client.java`). The Java class owns the HTTP round-trip,
+//! as every host does; Rust builds requests and parses responses.
+//!
+//! # Design
+//! - Data crosses as JSON strings in both directions; `bridge` defines the
+//!   format and does the conversion, keeping the JNI layer to string
+//!   marshalling.
+//! - Strings are read and created as UTF-16 (`GetStringChars`/`NewString`)
+//!   rather than JNI's modified UTF-8, so titles with characters outside
+//!   the BMP survive the trip.
+//! - A client is a boxed `TodoClient` whose pointer is handed to Java as a
+//!   `long`. Java releases it through `java.lang.ref.Cleaner`, or earlier
+//!   via `close()`; `nativeFree` is the only place the box is dropped.
+//! - Entry points never unwind into the JVM: panics are caught and become
+//!   a zero handle or an error envelope.
+
+// Names follow the JNI spec (`Java_<package>_<class>_<method>`, `jni.h`
+// field names), not Rust conventions.
+#![allow(non_snake_case, non_camel_case_types)]
+// Entry points check their pointers before use; the JVM calls them as C
+// functions, so marking them `unsafe` would not change anything.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+pub mod bridge;
+mod sys;
+
+use std::panic::catch_unwind;
+
+use sys::{jclass, jlong, jsize, jstring, JNIEnv};
+use todo_core::TodoClient;
+
+/// Envelope returned when a panic is caught inside an entry point.
+const PANIC_ENVELOPE: &str = r#"{"error":{"code":"panic","status":500,"message":"panic in native code"}}"#;
+
+/// Create a client for `base_url`. Returns 0 when the URL is null or fails
+/// `BaseUrl::parse`.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_todo_Client_nativeNew(env: *mut JNIEnv, _class: jclass, base_url: jstring) -> jlong {
+    catch_unwind(|| {
+        let Some(url) = (unsafe { read_string(env, base_url) }) else {
+            return 0;
+        };
+        match TodoClient::try_new(&url) {
+            Ok(client) => Box::into_raw(Box::new(client)) as jlong,
+            Err(_) => 0,
+        }
+    })
+    .unwrap_or(0)
+}
+
+/// Drop the client behind `handle`. Safe to call with 0.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_todo_Client_nativeFree(_env: *mut JNIEnv, _class: jclass, handle: jlong) {
+    if handle != 0 {
+        let _ = catch_unwind(|| drop(unsafe { Box::from_raw(handle as *mut TodoClient) }));
+    }
+}
+
+/// `bridge::build` for the client behind `handle`.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_todo_Client_nativeBuild(
+    env: *mut JNIEnv,
+    _class: jclass,
+    handle: jlong,
+    operation: jstring,
+) -> jstring {
+    let out = catch_unwind(|| {
+        let client = unsafe { client(handle) }?;
+        let operation = unsafe { read_string(env, operation) }?;
+        Some(bridge::build(client, &operation))
+    });
+    unsafe { respond(env, out) }
+}
+
+/// `bridge::parse` for the client behind `handle`.
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_com_todo_Client_nativeParse(
+    env: *mut JNIEnv,
+    _class: jclass,
+    handle: jlong,
+    operation: jstring,
+    response: jstring,
+) -> jstring {
+    let out = catch_unwind(|| {
+        let client = unsafe { client(handle) }?;
+        let operation = unsafe { read_string(env, operation) }?;
+        let response = unsafe { read_string(env, response) }?;
+        Some(bridge::parse(client, &operation, &response))
+    });
+    unsafe { respond(env, out) }
+}
+
+/// Borrow the client behind `handle`, or `None` for 0.
+///
+/// # Safety
+/// A non-zero `handle` must come from `nativeNew` and not yet be freed.
+unsafe fn client<'a>(handle: jlong) -> Option<&'a TodoClient> {
+    unsafe { (handle as *const TodoClient).as_ref() }
+}
+
+/// Convert an entry point's outcome to a Java string: `None` means a null
+/// argument, `Err` a caught panic.
+unsafe fn respond(env: *mut JNIEnv, out: std::thread::Result<Option<String>>) -> jstring {
+    let json = match out {
+        Ok(Some(json)) => json,
+        Ok(None) => r#"{"error":{"code":"null_arg","status":400,"message":"null handle or argument"}}"#.to_string(),
+        Err(_) => PANIC_ENVELOPE.to_string(),
+    };
+    unsafe { new_string(env, &json) }
+}
+
+/// Copy a Java string into Rust. Returns `None` for a null reference, a
+/// failed JVM allocation, or unpaired surrogates.
+unsafe fn read_string(env: *mut JNIEnv, string: jstring) -> Option<String> {
+    if env.is_null() || string.is_null() {
+        return None;
+    }
+    unsafe {
+        let table = &**env;
+        let len = (table.GetStringLength)(env, string);
+        let chars = (table.GetStringChars)(env, string, std::ptr::null_mut());
+        if chars.is_null() {
+            return None;
+        }
+        let decoded = String::from_utf16(std::slice::from_raw_parts(chars, len as usize)).ok();
+        (table.ReleaseStringChars)(env, string, chars);
+        decoded
+    }
+}
+
+/// Create a Java string from `s`. Returns null if the JVM is out of memory,
+/// with an `OutOfMemoryError` pending.
+unsafe fn new_string(env: *mut JNIEnv, s: &str) -> jstring {
+    if env.is_null() {
+        return std::ptr::null_mut();
+    }
+    let units: Vec<u16> = s.encode_utf16().collect();
+    unsafe { ((**env).NewString)(env, units.as_ptr(), units.len() as jsize) }
+}
@@ -0,0 +1,38 @@
+//! The slice of the JNI C interface these bindings call.
+//!
+//! # Design
+//! Declared by hand rather than pulled from a `jni` crate: four string
+//! functions are all that is needed. `JNINativeInterface` mirrors the
+//! function table in `jni.h` only up to the last slot used, with padding
+//! arrays standing in for the slots in between, so field offsets match the
+//! JVM's table. The slot numbers in the comments are `jni.h` indices.
+
+use std::ffi::c_void;
+
+pub type jint = i32;
+pub type jlong = i64;
+pub type jsize = jint;
+pub type jchar = u16;
+pub type jboolean = u8;
+pub type jobject = *mut c_void;
+pub type jclass = jobject;
+pub type jstring = jobject;
+
+/// `JNIEnv` is a pointer to the function table.
+pub type JNIEnv = *const JNINativeInterface;
+
+type Slot = *const c_void;
+
+#[repr(C)]
+pub struct JNINativeInterface {
+    _slots_0_to_162: [Slot; 163],
+    /// Slot 163.
+    pub NewString: unsafe extern "system" fn(env: *mut JNIEnv, chars: *const jchar, len: jsize) -> jstring,
+    /// Slot 164.
+    pub GetStringLength: unsafe extern "system" fn(env: *mut JNIEnv, string: jstring) -> jsize,
+    /// Slot 165.
+    pub GetStringChars:
+        unsafe extern "system" fn(env: *mut JNIEnv, string: jstring, is_copy: *mut jboolean) -> *const jchar,
+    /// Slot 166.
+    pub ReleaseStringChars: unsafe extern "system" fn(env: *mut JNIEnv, string: jstring, chars: *const jchar),
+}
@@ -0,0 +1,98 @@
+//! Loads the cdylib into a real JVM through `com.todo.Client`.
+//!
+//! # Design
+//! Compiles `Client.java` plus a small driver with `javac`, runs it with
+//! `java.library.path` pointing at the cargo output directory, and checks
+//! the JSON lines it prints. Skipped when no JDK is on `PATH`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::Value;
+
+const DRIVER: &str = r#"
+import com.todo.Client;
+import java.io.PrintStream;
+import java.nio.charset.StandardCharsets;
+
+public class Driver {
+    public static void main(String[] args) {
+        PrintStream out = new PrintStream(System.out, true, StandardCharsets.UTF_8);
+        try (Client client = new Client("http://localhost:3000")) {
+            String create = "{\"op\":\"create_todo\",\"title\":\"Milk 🥛\"}";
+            out.println(client.buildRequest(create));
+            String created = "{\"id\":\"00000000-0000-0000-0000-000000000001\",\"title\":\"Milk 🥛\",\"completed\":false}";
+            String response = "{\"status\":201,\"body\":" + quote(created) + "}";
+            out.println(client.parseResponse(create, response));
+            out.println(client.parseResponse("{\"op\":\"list_todos\"}", "{\"status\":503}"));
+        }
+        Client closed = new Client("http://localhost:3000");
+        closed.close();
+        closed.close();
+        try {
+            closed.buildRequest("{\"op\":\"list_todos\"}");
+        } catch (IllegalStateException e) {
+            out.println("{\"closed\":true}");
+        }
+        try {
+            new Client("localhost:3000");
+        } catch (IllegalArgumentException e) {
+            out.println("{\"rejected\":true}");
+        }
+    }
+
+    private static String quote(String s) {
+        return "\"" + s.replace("\\", "\\\\").replace("\"", "\\\"") + "\"";
+    }
+}
+"#;
+
+/// `target/<profile>`, where cargo put the cdylib next to this test's `deps/`.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+fn jdk_available() -> bool {
+    Command::new("javac").arg("-version").output().is_ok_and(|o| o.status.success())
+}
+
+fn run(cmd: &mut Command) -> String {
+    let output = cmd.output().unwrap();
+    assert!(output.status.success(), "{cmd:?} failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn client_round_trips_through_the_jvm() {
+    if !jdk_available() {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let work = std::env::temp_dir().join(format!("todo-jni-{}", std::process::id()));
+    std::fs::create_dir_all(&work).unwrap();
+    std::fs::write(work.join("Driver.java"), DRIVER).unwrap();
+    let client_src = Path::new(env!("CARGO_MANIFEST_DIR")).join("java/com/todo/Client.java");
+
+    run(Command::new("javac")
+        .args(["-encoding", "UTF-8", "-d"])
+        .arg(&work)
+        .arg(&client_src)
+        .arg(work.join("Driver.java")));
+    let stdout = run(Command::new("java")
+        .arg(format!("-Djava.library.path={}", library_dir().display()))
+        .arg("-cp")
+        .arg(&work)
+        .arg("Driver"));
+    std::fs::remove_dir_all(&work).ok();
+
+    let lines: Vec<Value> = stdout.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    let request = &lines[0]["ok"];
+    assert_eq!(request["method"], "POST");
+    assert!(request["body"].as_str().unwrap().contains("Milk 🥛"));
+    assert_eq!(lines[1]["ok"]["title"], "Milk 🥛");
+    assert_eq!(lines[2]["error"]["code"], "http");
+    assert_eq!(lines[2]["error"]["status"], 503);
+    assert_eq!(lines[3]["closed"], true);
+    assert_eq!(lines[4]["rejected"], true);
+}
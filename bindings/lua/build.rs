@@ -0,0 +1,9 @@
+fn main() {
+    // The Lua API is provided by the interpreter that loads the module, so
+    // its symbols stay undefined at link time. GNU ld allows that for shared
+    // objects by default; Apple's linker has to be told.
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        println!("cargo:rustc-cdylib-link-arg=-undefined");
+        println!("cargo:rustc-cdylib-link-arg=dynamic_lookup");
+    }
+}
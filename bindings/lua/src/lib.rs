@@ -0,0 +1,429 @@
+//! Lua C module exposing `todo-core` as `require("todo")`.
+//!
+//! # Overview
+//! Build with `cargo build -p todo-lua --release` and install
+//! `libtodo_lua.so` on `package.cpath` as `todo.so` (`todo.dll` on
+//! Windows). Enable the `lua51` feature for LuaJIT and OpenResty.
+//!
+//! ```lua
+//! local todo = require("todo")
+//! local client = assert(todo.new("http://localhost:3000"))
+//! local req = assert(client:build_create_todo("Buy milk"))
+//! -- req = { method = "POST", url = "...", headers = { ["content-type"] = "..." }, body = "..." }
+//! local res = http_send(req) -- the host's HTTP client
+//! local item, err, code = client:parse_create_todo({ status = res.status, body = res.body })
+//! ```
+//!
+//! # Design
+//! - The host does the I/O, as in every binding: `build_*` returns a
+//!   request table, `parse_*` takes a response table with `status`, `body`
+//!   and optional `headers`.
+//! - Failures follow the `nil, message, code` convention of OpenResty
+//!   libraries; `code` uses the JNI bridge's spelling (`not_found`,
+//!   `validation`, ...). Nothing raises a Lua error, because `lua_error`
+//!   unwinds with `longjmp`, which must not cross Rust frames.
+//! - A client is a plain table holding `base_url` and its methods, so the
+//!   module needs no userdata, metatables or registry, whose APIs differ
+//!   between Lua versions. Each call rebuilds the `TodoClient`, which
+//!   costs one URL parse.
+//! - Entry points catch panics and report them as code `panic`.
+//! - Lua raises memory errors from inside API calls; every Rust value that
+//!   owns memory is converted before the results are pushed, so such an
+//!   error skips no destructors that matter beyond leaking them.
+
+// Entry points check the stack before use; Lua calls them as C functions,
+// so marking them `unsafe` would not change anything for callers.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+mod sys;
+
+use std::ffi::{c_char, c_int, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use sys::*;
+use todo_core::{ApiError, CreateTodo, HttpRequest, HttpResponse, Todo, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+/// A Lua value produced by an entry point, built in Rust before anything
+/// is pushed.
+enum Value {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    List(Vec<Value>),
+    Map(Vec<(&'static str, Value)>),
+    /// A table with string keys chosen at runtime, e.g. headers.
+    Dict(Vec<(String, String)>),
+}
+
+impl From<Todo> for Value {
+    fn from(todo: Todo) -> Self {
+        Value::Map(vec![
+            ("id", Value::Str(todo.id.to_string())),
+            ("title", Value::Str(todo.title)),
+            ("completed", Value::Bool(todo.completed)),
+        ])
+    }
+}
+
+impl From<HttpRequest> for Value {
+    fn from(req: HttpRequest) -> Self {
+        let mut fields = vec![
+            ("method", Value::Str(req.method.to_string())),
+            ("url", Value::Str(req.url())),
+            ("headers", Value::Dict(req.headers)),
+        ];
+        if let Some(body) = req.body {
+            fields.push(("body", Value::Str(body)));
+        }
+        Value::Map(fields)
+    }
+}
+
+/// The `message, code` pair returned after `nil` on failure.
+struct Failure {
+    message: String,
+    code: &'static str,
+}
+
+impl From<ApiError> for Failure {
+    fn from(err: ApiError) -> Self {
+        Failure { code: error_code(&err), message: err.to_string() }
+    }
+}
+
+/// What an entry point returns.
+type Outcome = Result<Value, Failure>;
+
+fn invalid(message: impl Into<String>) -> Failure {
+    Failure { message: message.into(), code: "validation" }
+}
+
+/// Same spelling as the JNI bridge's error codes.
+fn error_code(err: &ApiError) -> &'static str {
+    match err {
+        ApiError::NotFound | ApiError::NotFoundId(_) => "not_found",
+        ApiError::Unauthorized => "unauthorized",
+        ApiError::Conflict(_) => "conflict",
+        ApiError::PreconditionFailed => "precondition_failed",
+        ApiError::HttpError { .. } => "http",
+        ApiError::DeserializationError(_) => "deserialization",
+        ApiError::SerializationError(_) => "serialization",
+        ApiError::Validation(_) => "validation",
+        ApiError::RateLimited { .. } => "rate_limited",
+        ApiError::SchemaMismatch { .. } => "schema_mismatch",
+        ApiError::Transport(_) => "transport",
+        ApiError::Tls(_) => "tls",
+    }
+}
+
+/// Client methods, in the order they are set on each client table.
+const METHODS: &[(&CStr, lua_CFunction)] = &[
+    (c"build_list_todos", build_list_todos),
+    (c"build_get_todo", build_get_todo),
+    (c"build_create_todo", build_create_todo),
+    (c"build_update_todo", build_update_todo),
+    (c"build_delete_todo", build_delete_todo),
+    (c"build_delete_all", build_delete_all),
+    (c"parse_list_todos", parse_list_todos),
+    (c"parse_get_todo", parse_get_todo),
+    (c"parse_create_todo", parse_create_todo),
+    (c"parse_update_todo", parse_update_todo),
+    (c"parse_delete_todo", parse_delete_todo),
+    (c"parse_delete_all", parse_delete_all),
+];
+
+/// Module entry point, called by `require("todo")`.
+#[unsafe(no_mangle)]
+pub extern "C" fn luaopen_todo(state: *mut lua_State) -> c_int {
+    unsafe {
+        lua_createtable(state, 0, 1);
+        lua_pushcclosure(state, new, 0);
+        lua_setfield(state, -2, c"new".as_ptr());
+    }
+    1
+}
+
+/// `todo.new(base_url)`: a client table, or `nil, message, "validation"`
+/// when `base_url` fails `BaseUrl::parse`.
+unsafe extern "C" fn new(state: *mut lua_State) -> c_int {
+    let outcome = guard(|| {
+        let url = unsafe { string_arg(state, 1) }.ok_or_else(|| invalid("base_url must be a string"))?;
+        TodoClient::try_new(&url)?;
+        Ok(Value::Str(url))
+    });
+    match outcome {
+        Ok(Value::Str(url)) => unsafe {
+            lua_createtable(state, 0, METHODS.len() as c_int + 1);
+            push_str(state, &url);
+            lua_setfield(state, -2, c"base_url".as_ptr());
+            for (name, method) in METHODS {
+                lua_pushcclosure(state, *method, 0);
+                lua_setfield(state, -2, name.as_ptr());
+            }
+            1
+        },
+        other => unsafe { respond(state, other) },
+    }
+}
+
+unsafe extern "C" fn build_list_todos(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.build_list_todos().into())) }
+}
+
+unsafe extern "C" fn build_get_todo(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.build_get_todo(id_arg(state, 2)?).into())) }
+}
+
+/// `client:build_create_todo(title [, completed])`.
+unsafe extern "C" fn build_create_todo(state: *mut lua_State) -> c_int {
+    unsafe {
+        method(state, |client| {
+            let title = string_arg(state, 2).ok_or_else(|| invalid("title must be a string"))?;
+            let completed = lua_toboolean(state, 3) != 0;
+            Ok(client.build_create_todo(&CreateTodo { title, completed })?.into())
+        })
+    }
+}
+
+/// `client:build_update_todo(id, { title = ..., completed = ... })`.
+unsafe extern "C" fn build_update_todo(state: *mut lua_State) -> c_int {
+    unsafe {
+        method(state, |client| {
+            let id = id_arg(state, 2)?;
+            if lua_type(state, 3) != LUA_TTABLE {
+                return Err(invalid("fields must be a table"));
+            }
+            let title = match field_type(state, 3, c"title") {
+                LUA_TNIL => None,
+                LUA_TSTRING => string_field(state, 3, c"title"),
+                _ => return Err(invalid("title must be a string")),
+            };
+            let completed = match field_type(state, 3, c"completed") {
+                LUA_TNIL => None,
+                LUA_TBOOLEAN => Some(bool_field(state, 3, c"completed")),
+                _ => return Err(invalid("completed must be a boolean")),
+            };
+            Ok(client.build_update_todo(id, &UpdateTodo { title, completed })?.into())
+        })
+    }
+}
+
+unsafe extern "C" fn build_delete_todo(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.build_delete_todo(id_arg(state, 2)?).into())) }
+}
+
+unsafe extern "C" fn build_delete_all(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.build_delete_all().into())) }
+}
+
+unsafe extern "C" fn parse_list_todos(state: *mut lua_State) -> c_int {
+    unsafe {
+        method(state, |client| {
+            let todos = client.parse_list_todos(response_arg(state, 2)?)?;
+            Ok(Value::List(todos.into_iter().map(Value::from).collect()))
+        })
+    }
+}
+
+unsafe extern "C" fn parse_get_todo(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.parse_get_todo(response_arg(state, 2)?)?.into())) }
+}
+
+unsafe extern "C" fn parse_create_todo(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.parse_create_todo(response_arg(state, 2)?)?.into())) }
+}
+
+unsafe extern "C" fn parse_update_todo(state: *mut lua_State) -> c_int {
+    unsafe { method(state, |client| Ok(client.parse_update_todo(response_arg(state, 2)?)?.into())) }
+}
+
+/// Returns `true` on success.
+unsafe extern "C" fn parse_delete_todo(state: *mut lua_State) -> c_int {
+    unsafe {
+        method(state, |client| {
+            client.parse_delete_todo(response_arg(state, 2)?)?;
+            Ok(Value::Bool(true))
+        })
+    }
+}
+
+/// Returns how many todos were deleted.
+unsafe extern "C" fn parse_delete_all(state: *mut lua_State) -> c_int {
+    unsafe {
+        method(state, |client| {
+            let count = client.parse_delete_all(response_arg(state, 2)?)?;
+            Ok(Value::Int(count as i64))
+        })
+    }
+}
+
+/// Run a client method: rebuild the client from `self.base_url` (argument
+/// 1), call `f`, and push its outcome.
+unsafe fn method(state: *mut lua_State, f: impl FnOnce(&TodoClient) -> Outcome) -> c_int {
+    let outcome = guard(|| {
+        if unsafe { lua_type(state, 1) } != LUA_TTABLE {
+            return Err(invalid("call methods with `client:method(...)`"));
+        }
+        let url = unsafe { string_field(state, 1, c"base_url") }.ok_or_else(|| invalid("client has no base_url"))?;
+        f(&TodoClient::try_new(&url)?)
+    });
+    unsafe { respond(state, outcome) }
+}
+
+fn guard(f: impl FnOnce() -> Outcome) -> Outcome {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err(Failure { message: "panic in native code".to_string(), code: "panic" }))
+}
+
+/// Push `value`, or `nil, message, code`; returns the number of results.
+unsafe fn respond(state: *mut lua_State, outcome: Outcome) -> c_int {
+    unsafe {
+        match outcome {
+            Ok(value) => {
+                push(state, value);
+                1
+            }
+            Err(failure) => {
+                lua_pushnil(state);
+                push_str(state, &failure.message);
+                push_str(state, failure.code);
+                3
+            }
+        }
+    }
+}
+
+unsafe fn push(state: *mut lua_State, value: Value) {
+    unsafe {
+        match value {
+            Value::Bool(b) => lua_pushboolean(state, b as c_int),
+            Value::Int(n) => lua_pushinteger(state, n as lua_Integer),
+            Value::Str(s) => push_str(state, &s),
+            Value::List(items) => {
+                lua_createtable(state, items.len() as c_int, 0);
+                for (i, item) in items.into_iter().enumerate() {
+                    lua_pushinteger(state, i as lua_Integer + 1);
+                    push(state, item);
+                    lua_settable(state, -3);
+                }
+            }
+            Value::Map(fields) => {
+                lua_createtable(state, 0, fields.len() as c_int);
+                for (key, item) in fields {
+                    push_str(state, key);
+                    push(state, item);
+                    lua_settable(state, -3);
+                }
+            }
+            Value::Dict(entries) => {
+                lua_createtable(state, 0, entries.len() as c_int);
+                for (key, item) in entries {
+                    push_str(state, &key);
+                    push_str(state, &item);
+                    lua_settable(state, -3);
+                }
+            }
+        }
+    }
+}
+
+unsafe fn push_str(state: *mut lua_State, s: &str) {
+    unsafe { lua_pushlstring(state, s.as_ptr().cast::<c_char>(), s.len()) }
+}
+
+/// The string at `index`, or `None` if it is not a UTF-8 string. Numbers
+/// are not coerced.
+unsafe fn string_arg(state: *mut lua_State, index: c_int) -> Option<String> {
+    unsafe {
+        if lua_type(state, index) != LUA_TSTRING {
+            return None;
+        }
+        let mut len = 0;
+        let ptr = lua_tolstring(state, index, &mut len);
+        String::from_utf8(std::slice::from_raw_parts(ptr.cast::<u8>(), len).to_vec()).ok()
+    }
+}
+
+unsafe fn id_arg(state: *mut lua_State, index: c_int) -> Result<Uuid, Failure> {
+    let id = unsafe { string_arg(state, index) }.ok_or_else(|| invalid("id must be a string"))?;
+    Uuid::parse_str(&id).map_err(|e| invalid(format!("invalid id {id:?}: {e}")))
+}
+
+/// Lua type of `table[key]`, for the table at absolute index `table`.
+unsafe fn field_type(state: *mut lua_State, table: c_int, key: &CStr) -> c_int {
+    unsafe {
+        lua_getfield(state, table, key.as_ptr());
+        let ty = lua_type(state, -1);
+        lua_settop(state, -2);
+        ty
+    }
+}
+
+unsafe fn string_field(state: *mut lua_State, table: c_int, key: &CStr) -> Option<String> {
+    unsafe {
+        lua_getfield(state, table, key.as_ptr());
+        let value = string_arg(state, -1);
+        lua_settop(state, -2);
+        value
+    }
+}
+
+unsafe fn bool_field(state: *mut lua_State, table: c_int, key: &CStr) -> bool {
+    unsafe {
+        lua_getfield(state, table, key.as_ptr());
+        let value = lua_toboolean(state, -1) != 0;
+        lua_settop(state, -2);
+        value
+    }
+}
+
+unsafe fn integer_field(state: *mut lua_State, table: c_int, key: &CStr) -> Option<i64> {
+    unsafe {
+        lua_getfield(state, table, key.as_ptr());
+        let value = to_integer(state, -1);
+        lua_settop(state, -2);
+        value
+    }
+}
+
+#[cfg(feature = "lua51")]
+unsafe fn to_integer(state: *mut lua_State, index: c_int) -> Option<i64> {
+    unsafe { (lua_type(state, index) == LUA_TNUMBER).then(|| lua_tointeger(state, index) as i64) }
+}
+
+#[cfg(not(feature = "lua51"))]
+unsafe fn to_integer(state: *mut lua_State, index: c_int) -> Option<i64> {
+    let mut is_num = 0;
+    let value = unsafe { lua_tointegerx(state, index, &mut is_num) };
+    (is_num != 0).then_some(value)
+}
+
+/// Read `{ status = ..., body = ..., headers = { [name] = value } }`.
+unsafe fn response_arg(state: *mut lua_State, index: c_int) -> Result<HttpResponse, Failure> {
+    unsafe {
+        if lua_type(state, index) != LUA_TTABLE {
+            return Err(invalid("response must be a table"));
+        }
+        let status = integer_field(state, index, c"status")
+            .and_then(|s| u16::try_from(s).ok())
+            .ok_or_else(|| invalid("response.status must be an HTTP status"))?;
+        let body = match field_type(state, index, c"body") {
+            LUA_TNIL => String::new(),
+            _ => string_field(state, index, c"body").ok_or_else(|| invalid("response.body must be a string"))?,
+        };
+        let mut headers = Vec::new();
+        lua_getfield(state, index, c"headers".as_ptr());
+        let table = lua_gettop(state);
+        if lua_type(state, table) == LUA_TTABLE {
+            lua_pushnil(state);
+            while lua_next(state, table) != 0 {
+                if let (Some(name), Some(value)) = (string_arg(state, -2), string_arg(state, -1)) {
+                    headers.push((name, value));
+                }
+                lua_settop(state, -2);
+            }
+        }
+        lua_settop(state, table - 1);
+        Ok(HttpResponse { status, headers, body })
+    }
+}
@@ -0,0 +1,64 @@
+//! The slice of the Lua C API this module calls.
+//!
+//! # Design
+//! Declared by hand, like the JNI bindings' `sys`, so the crate builds
+//! without Lua headers or a `lua-sys` dependency. Only functions exported
+//! under the same name from Lua 5.1 (and LuaJIT) through 5.4 are used;
+//! macros such as `lua_pop` or `lua_newtable` are spelled out by callers.
+//! Reading an integer is the one call that differs, selected by the
+//! `lua51` feature.
+//!
+//! Where versions disagree on a return type (`lua_getfield` returns `void`
+//! in 5.1 and the value type later, `lua_pushlstring` returns `void` or the
+//! interned string) the result is declared as `()` and never read.
+
+use std::ffi::{c_char, c_int};
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct lua_State {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+pub type lua_CFunction = unsafe extern "C" fn(state: *mut lua_State) -> c_int;
+
+/// `ptrdiff_t` in Lua 5.1, `long long` from 5.3 on (5.2 uses `ptrdiff_t`
+/// too, which is the same width on every 64-bit target).
+#[cfg(feature = "lua51")]
+#[allow(non_camel_case_types)]
+pub type lua_Integer = isize;
+#[cfg(not(feature = "lua51"))]
+#[allow(non_camel_case_types)]
+pub type lua_Integer = i64;
+
+pub const LUA_TNIL: c_int = 0;
+pub const LUA_TBOOLEAN: c_int = 1;
+#[cfg(feature = "lua51")]
+pub const LUA_TNUMBER: c_int = 3;
+pub const LUA_TSTRING: c_int = 4;
+pub const LUA_TTABLE: c_int = 5;
+
+unsafe extern "C" {
+    pub fn lua_gettop(state: *mut lua_State) -> c_int;
+    pub fn lua_settop(state: *mut lua_State, index: c_int);
+    pub fn lua_type(state: *mut lua_State, index: c_int) -> c_int;
+    pub fn lua_toboolean(state: *mut lua_State, index: c_int) -> c_int;
+    pub fn lua_tolstring(state: *mut lua_State, index: c_int, len: *mut usize) -> *const c_char;
+    #[cfg(feature = "lua51")]
+    pub fn lua_tointeger(state: *mut lua_State, index: c_int) -> lua_Integer;
+    #[cfg(not(feature = "lua51"))]
+    pub fn lua_tointegerx(state: *mut lua_State, index: c_int, is_num: *mut c_int) -> lua_Integer;
+
+    pub fn lua_pushnil(state: *mut lua_State);
+    pub fn lua_pushboolean(state: *mut lua_State, b: c_int);
+    pub fn lua_pushinteger(state: *mut lua_State, n: lua_Integer);
+    pub fn lua_pushlstring(state: *mut lua_State, s: *const c_char, len: usize);
+    pub fn lua_pushcclosure(state: *mut lua_State, f: lua_CFunction, upvalues: c_int);
+
+    pub fn lua_createtable(state: *mut lua_State, narr: c_int, nrec: c_int);
+    pub fn lua_getfield(state: *mut lua_State, index: c_int, key: *const c_char);
+    pub fn lua_setfield(state: *mut lua_State, index: c_int, key: *const c_char);
+    pub fn lua_settable(state: *mut lua_State, index: c_int);
+    pub fn lua_next(state: *mut lua_State, index: c_int) -> c_int;
+}
@@ -0,0 +1,73 @@
+//! Loads the module into a real Lua interpreter with `require("todo")`.
+//!
+//! # Design
+//! Copies the cdylib to `todo.so` in a scratch directory, points
+//! `package.cpath` at it and runs a script that asserts on the tables the
+//! module returns. Skipped when no `lua` is on `PATH`, or when its version
+//! does not match the C API the crate was built for (`lua51` feature).
+
+use std::path::PathBuf;
+use std::process::Command;
+
+const SCRIPT: &str = r#"
+local todo = require("todo")
+
+local client = assert(todo.new("http://localhost:3000"))
+local bad, msg, code = todo.new("localhost:3000")
+assert(bad == nil and code == "validation", msg)
+
+local req = assert(client:build_create_todo("Milk 🥛"))
+assert(req.method == "POST" and req.url == "http://localhost:3000/todos", req.url)
+assert(req.headers["content-type"] == "application/json")
+assert(req.body:find("Milk 🥛", 1, true))
+
+local id = "00000000-0000-0000-0000-000000000001"
+req = assert(client:build_update_todo(id, { completed = true }))
+assert(req.method == "PUT" and req.body == '{"completed":true}', req.body)
+local _, _, code = client:build_get_todo("42")
+assert(code == "validation")
+
+local body = '[{"id":"' .. id .. '","title":"A","completed":true}]'
+local items = assert(client:parse_list_todos({ status = 200, body = body }))
+assert(#items == 1 and items[1].title == "A" and items[1].completed == true)
+
+local none, msg, code = client:parse_get_todo({ status = 404 })
+assert(none == nil and code == "not_found", msg)
+assert(client:parse_delete_all({ status = 200, body = '{"deleted":3}' }) == 3)
+
+print("ok")
+"#;
+
+/// `target/<profile>`, where cargo put the cdylib next to this test's `deps/`.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+/// The interpreter's `_VERSION`, e.g. `Lua 5.4`, if one is installed.
+fn lua_version() -> Option<String> {
+    let output = Command::new("lua").args(["-e", "io.write(_VERSION)"]).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn module_round_trips_through_lua() {
+    let Some(version) = lua_version() else {
+        eprintln!("skipping: lua not found");
+        return;
+    };
+    if (version == "Lua 5.1") != cfg!(feature = "lua51") {
+        eprintln!("skipping: {version} does not match the C API this build targets");
+        return;
+    }
+    let work = std::env::temp_dir().join(format!("todo-lua-{}", std::process::id()));
+    std::fs::create_dir_all(&work).unwrap();
+    std::fs::copy(library_dir().join("libtodo_lua.so"), work.join("todo.so")).unwrap();
+
+    let cpath = format!("package.cpath = '{}/?.so;' .. package.cpath", work.display());
+    let output = Command::new("lua").args(["-e", &cpath, "-e", SCRIPT]).output().unwrap();
+    std::fs::remove_dir_all(&work).ok();
+
+    assert!(output.status.success(), "lua failed: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+}
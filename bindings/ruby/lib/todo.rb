@@ -0,0 +1,110 @@
+# frozen_string_literal: true
+
+require_relative "todo/native"
+
+# Ruby bindings for the todo client core.
+#
+# As with every binding, the host does the I/O: `build_*` returns a request
+# hash to send with any HTTP client, and `parse_*` decodes the response.
+#
+#   client = Todo::Client.new("http://localhost:3000")
+#   req = client.build_create_todo("Buy milk")
+#   res = Net::HTTP.new(...).send_request(req[:method], req[:url], req[:body], req[:headers].to_h)
+#   todo = client.parse_create_todo(res.code.to_i, res.body)
+module Todo
+  # A failed call. `code` is a `Native::ErrorCode` symbol such as `:not_found`.
+  class Error < StandardError
+    attr_reader :code, :status
+
+    def initialize(code, status, message)
+      super(message)
+      @code = code
+      @status = status
+    end
+  end
+
+  # Client for the todo API. The native client is freed when this object is
+  # garbage collected, or earlier by `close`.
+  class Client
+    def initialize(base_url)
+      @native = Native::TodoClient.take(Native.todo_client_new(base_url))
+      raise ArgumentError, "invalid base URL: #{base_url.inspect}" unless @native
+    end
+
+    def close
+      @native&.free
+      @native = nil
+    end
+
+    def build_list_todos = request(Native.todo_build_list_todos(native))
+    def build_get_todo(id) = request(Native.todo_build_get_todo(native, id))
+    def build_delete_todo(id) = request(Native.todo_build_delete_todo(native, id))
+    def build_delete_all = request(Native.todo_build_delete_all(native))
+
+    def build_create_todo(title, completed: false)
+      request(Native.todo_build_create_todo(native, title, completed))
+    end
+
+    # Pass only the fields to change.
+    def build_update_todo(id, title: nil, completed: nil)
+      flag = completed.nil? ? -1 : (completed ? 1 : 0)
+      request(Native.todo_build_update_todo(native, id, title, flag))
+    end
+
+    def parse_list_todos(status, body) = parse(:todo_parse_list_todos, status, body)
+    def parse_get_todo(status, body) = parse(:todo_parse_get_todo, status, body)
+    def parse_create_todo(status, body) = parse(:todo_parse_create_todo, status, body)
+    def parse_update_todo(status, body) = parse(:todo_parse_update_todo, status, body)
+    def parse_delete_todo(status, body) = parse(:todo_parse_delete_todo, status, body)
+
+    # Returns how many todos were deleted.
+    def parse_delete_all(status, body) = parse(:todo_parse_delete_all, status, body)
+
+    private
+
+    def native
+      @native or raise Error.new(:null_arg, 0, "client is closed")
+    end
+
+    def request(ptr)
+      req = Native::HttpRequest.take(ptr) or raise Error.new(:validation, 0, "request rejected: invalid arguments")
+      headers = Array.new(req[:headers_len]) do |i|
+        header = Native::Header.new(req[:headers] + i * Native::Header.size)
+        [string(header[:key]), string(header[:value])]
+      end
+      body = req[:body].null? ? nil : string(req[:body])
+      { method: req[:method].to_s.upcase, url: string(req[:path]), headers: headers, body: body }
+    ensure
+      req&.pointer&.free
+    end
+
+    def parse(function, status, body)
+      response = Native::HttpResponse.new
+      body_ptr = FFI::MemoryPointer.from_string(body.to_s)
+      response[:status] = status
+      response[:body] = body_ptr
+      result = Native::TodoResult.take(Native.public_send(function, native, response)) or
+        raise Error.new(:panic, 0, "no result")
+      unless result[:error_code] == :ok
+        raise Error.new(result[:error_code], result[:http_status], string(result[:error_message]))
+      end
+      data(result)
+    ensure
+      result&.pointer&.free
+    end
+
+    def data(result)
+      case result[:data_tag]
+      when :todo then todo(Native::Todo.new(result[:data]))
+      when :todo_list
+        list = Native::TodoList.new(result[:data])
+        Array.new(list[:len]) { |i| todo(Native::Todo.new(list[:items] + i * Native::Todo.size)) }
+      when :count then result[:data].read_uint64
+      end
+    end
+
+    def todo(native) = { id: string(native[:id]), title: string(native[:title]), completed: native[:completed] }
+
+    def string(ptr) = ptr.read_string.force_encoding(Encoding::UTF_8)
+  end
+end
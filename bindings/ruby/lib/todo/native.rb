@@ -0,0 +1,184 @@
+# frozen_string_literal: true
+
+# AUTO GENERATED FILE, DO NOT EDIT.
+#
+# Generated by todo-bindgen from todo_client.h. Regenerate with
+# `cargo run -p todo-bindgen -- ruby bindings/ruby/lib/todo`.
+
+require "ffi"
+
+module Todo
+  # Raw bindings to the todo_ffi C library, under the C names.
+  module Native
+    extend FFI::Library
+
+    ffi_lib ENV.fetch("TODO_FFI_LIB", "todo_ffi")
+
+    # Values of the C enum `FfiFfiDataTag`.
+    DataTag = enum(:data_tag, [:none, 0, :todo, 1, :todo_list, 2, :count, 3])
+
+    # Values of the C enum `FfiFfiErrorCode`.
+    ErrorCode = enum(:error_code, [:ok, 0, :not_found, 1, :http, 2, :deserialization, 3, :serialization, 4, :panic, 5, :null_arg, 6, :conflict, 7, :validation, 8, :rate_limited, 9, :transport, 10, :schema_mismatch, 11, :tls, 12, :precondition_failed, 13, :unauthorized, 14])
+
+    # Values of the C enum `FfiFfiHttpMethod`.
+    HttpMethod = enum(:http_method, [:get, 0, :post, 1, :put, 2, :delete, 3, :options, 4, :trace, 5, :patch, 6, :head, 7])
+
+    # `FfiFfiTodoClient`, released with `todo_client_free`.
+    class TodoClient < FFI::AutoPointer
+      def self.release(ptr)
+        Native.todo_client_free(ptr)
+      end
+
+      # Takes ownership of `ptr`; `nil` when it is null.
+      def self.take(ptr)
+        ptr.null? ? nil : new(ptr)
+      end
+    end
+
+    # `FfiFfiHeader`.
+    class Header < FFI::Struct
+      layout :key, :pointer,
+             :value, :pointer
+    end
+
+    # `FfiFfiHttpRequest`, released with `todo_free_request`.
+    class HttpRequest < FFI::ManagedStruct
+      layout :method, HttpMethod,
+             :path, :pointer,
+             :headers, :pointer,
+             :headers_len, :uint32,
+             :body, :pointer
+
+      def self.release(ptr)
+        Native.todo_free_request(ptr)
+      end
+
+      # Takes ownership of `ptr`; `nil` when it is null.
+      def self.take(ptr)
+        ptr.null? ? nil : new(ptr)
+      end
+    end
+
+    # `FfiFfiTodoResult`, released with `todo_free_result`.
+    class TodoResult < FFI::ManagedStruct
+      layout :error_code, ErrorCode,
+             :error_message, :pointer,
+             :http_status, :uint16,
+             :data_tag, DataTag,
+             :data, :pointer
+
+      def self.release(ptr)
+        Native.todo_free_result(ptr)
+      end
+
+      # Takes ownership of `ptr`; `nil` when it is null.
+      def self.take(ptr)
+        ptr.null? ? nil : new(ptr)
+      end
+    end
+
+    # `FfiFfiHttpResponse`.
+    class HttpResponse < FFI::Struct
+      layout :status, :uint16,
+             :body, :pointer
+    end
+
+    # `FfiFfiNetHttpRequest`, released with `todo_net_free_request`.
+    class NetHttpRequest < FFI::ManagedStruct
+      layout :method, :int32,
+             :path, :pointer,
+             :headers, :pointer,
+             :headers_len, :uint32,
+             :body, :pointer
+
+      def self.release(ptr)
+        Native.todo_net_free_request(ptr)
+      end
+
+      # Takes ownership of `ptr`; `nil` when it is null.
+      def self.take(ptr)
+        ptr.null? ? nil : new(ptr)
+      end
+    end
+
+    # `FfiFfiNetTodoResult`, released with `todo_net_free_result`.
+    class NetTodoResult < FFI::ManagedStruct
+      layout :error_code, :int32,
+             :error_message, :pointer,
+             :http_status, :uint16,
+             :data_tag, :int32,
+             :data, :pointer
+
+      def self.release(ptr)
+        Native.todo_net_free_result(ptr)
+      end
+
+      # Takes ownership of `ptr`; `nil` when it is null.
+      def self.take(ptr)
+        ptr.null? ? nil : new(ptr)
+      end
+    end
+
+    # `FfiFfiTodo`.
+    class Todo < FFI::Struct
+      layout :id, :pointer,
+             :title, :pointer,
+             :completed, :bool
+    end
+
+    # `FfiFfiTodoList`.
+    class TodoList < FFI::Struct
+      layout :items, :pointer,
+             :len, :uint32
+    end
+
+    # `FfiFfiNetTodo`.
+    class NetTodo < FFI::Struct
+      layout :id, :pointer,
+             :title, :pointer,
+             :completed, :uint8
+    end
+
+    # `FfiFfiNetTodoList`.
+    class NetTodoList < FFI::Struct
+      layout :items, :pointer,
+             :len, :uint32
+    end
+
+    attach_function :todo_client_new, [:string], :pointer
+    attach_function :todo_client_new_basic_auth, [:string, :string, :string], :pointer
+    attach_function :todo_client_free, [:pointer], :void
+    attach_function :todo_client_clone, [:pointer], :pointer
+    attach_function :todo_client_eq, [:pointer, :pointer], :bool
+    attach_function :todo_build_list_todos, [:pointer], :pointer
+    attach_function :todo_build_get_todo, [:pointer, :string], :pointer
+    attach_function :todo_build_create_todo, [:pointer, :string, :bool], :pointer
+    attach_function :todo_build_update_todo, [:pointer, :string, :string, :int32], :pointer
+    attach_function :todo_build_delete_todo, [:pointer, :string], :pointer
+    attach_function :todo_build_delete_all, [:pointer], :pointer
+    attach_function :todo_parse_list_todos, [:pointer, :pointer], :pointer
+    attach_function :todo_parse_get_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_parse_create_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_parse_update_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_parse_delete_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_parse_delete_all, [:pointer, :pointer], :pointer
+    attach_function :todo_free_request, [:pointer], :void
+    attach_function :todo_free_result, [:pointer], :void
+    attach_function :todo_free_string, [:pointer], :void
+    attach_function :todo_net_client_new, [:pointer], :pointer
+    attach_function :todo_net_build_list_todos, [:pointer], :pointer
+    attach_function :todo_net_build_get_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_net_build_create_todo, [:pointer, :pointer, :uint8], :pointer
+    attach_function :todo_net_build_update_todo, [:pointer, :pointer, :pointer, :int32], :pointer
+    attach_function :todo_net_build_delete_todo, [:pointer, :pointer], :pointer
+    attach_function :todo_net_build_delete_all, [:pointer], :pointer
+    attach_function :todo_net_parse_list_todos, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_parse_get_todo, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_parse_create_todo, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_parse_update_todo, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_parse_delete_todo, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_parse_delete_all, [:pointer, :uint16, :pointer], :pointer
+    attach_function :todo_net_free_request, [:pointer], :void
+    attach_function :todo_net_free_result, [:pointer], :void
+  end
+end
@@ -0,0 +1,60 @@
+# frozen_string_literal: true
+
+# Local conformance check for the gem, run by `cargo test -p todo-bindgen`
+# (see bindings/gen/tests/ruby.rs) or by hand:
+#
+#   TODO_FFI_LIB=target/debug/libtodo_ffi.so ruby -Ilib test/conformance.rb < layout.txt
+#
+# Standard input lists the C layout, as printed by a probe compiled against
+# todo_client.h: `Struct size` and `Struct.field offset` lines. Every struct
+# layout in Todo::Native must match it; then a client round-trips a few
+# calls through the library.
+
+require "todo"
+
+failures = []
+check = ->(what, actual, expected) { failures << "#{what}: ruby #{actual}, C #{expected}" unless actual == expected }
+
+$stdin.each_line do |line|
+  name, expected = line.split
+  struct, field = name.split(".")
+  klass = Todo::Native.const_get(struct)
+  if field
+    check.call(name, klass.offset_of(field.to_sym), Integer(expected))
+  else
+    check.call(name, klass.size, Integer(expected))
+  end
+end
+
+client = Todo::Client.new("http://localhost:3000")
+req = client.build_create_todo("Milk 🥛")
+check.call("create method", req[:method], "POST")
+check.call("create url", req[:url], "http://localhost:3000/todos")
+check.call("create body", req[:body], '{"title":"Milk 🥛","completed":false}')
+
+id = "00000000-0000-0000-0000-000000000001"
+req = client.build_update_todo(id, completed: true)
+check.call("update body", req[:body], '{"completed":true}')
+
+todos = client.parse_list_todos(200, %([{"id":"#{id}","title":"Milk 🥛","completed":true}]))
+check.call("list", todos, [{ id: id, title: "Milk 🥛", completed: true }])
+check.call("delete_all", client.parse_delete_all(200, '{"deleted":3}'), 3)
+
+begin
+  client.parse_get_todo(404, "")
+  failures << "404 did not raise"
+rescue Todo::Error => e
+  check.call("404 code", e.code, :not_found)
+  check.call("404 status", e.status, 404)
+end
+
+begin
+  Todo::Client.new("localhost:3000")
+  failures << "invalid base URL accepted"
+rescue ArgumentError
+  nil
+end
+client.close
+
+abort(failures.join("\n")) unless failures.empty?
+puts "ok"
@@ -0,0 +1,112 @@
+//! `todo`: a command-line host for the todo API.
+//!
+//! # Design
+//! A thin layer over `BlockingTodoClient` (the core's `blocking` feature),
+//! so it exercises the same build → execute → parse path as any other host
+//! and is handy for poking at a real server by hand. Commands map one to one
+//! onto client operations; rendering lives in [`output`]. Failures print
+//! the `ApiError` and exit with status 1; usage errors exit with 2, as clap
+//! does.
+
+mod output;
+
+use std::process::ExitCode;
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use todo_core::{ApiError, BlockingTodoClient, CreateTodo, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+use output::Format;
+
+fn command() -> Command {
+    let id = || Arg::new("id").required(true).value_parser(value_parser!(Uuid)).help("Todo id");
+    Command::new("todo")
+        .about("Manage todos on a todo API server")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("base-url")
+                .long("base-url")
+                .env("TODO_BASE_URL")
+                .default_value("http://localhost:3000")
+                .global(true)
+                .help("Server to talk to"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_parser(["table", "json"])
+                .default_value("table")
+                .global(true)
+                .help("Output format"),
+        )
+        .subcommand(Command::new("list").about("List every todo"))
+        .subcommand(
+            Command::new("add")
+                .about("Create a todo")
+                .arg(Arg::new("title").required(true).help("Title of the new todo"))
+                .arg(Arg::new("done").long("done").action(ArgAction::SetTrue).help("Create it already completed")),
+        )
+        .subcommand(Command::new("done").about("Mark a todo completed").arg(id()))
+        .subcommand(Command::new("rm").about("Delete a todo").arg(id()))
+}
+
+fn run(matches: &ArgMatches) -> Result<String, ApiError> {
+    let base_url = matches.get_one::<String>("base-url").unwrap();
+    let format = match matches.get_one::<String>("output").map(String::as_str) {
+        Some("json") => Format::Json,
+        _ => Format::Table,
+    };
+    let client = BlockingTodoClient::new(TodoClient::try_new(base_url)?);
+    let id = |args: &ArgMatches| *args.get_one::<Uuid>("id").unwrap();
+    match matches.subcommand() {
+        Some(("list", _)) => Ok(output::todos(format, &client.list_todos()?)),
+        Some(("add", args)) => {
+            let title = args.get_one::<String>("title").unwrap();
+            let input = CreateTodo::new(title, args.get_flag("done"));
+            Ok(output::todo(format, &client.create_todo(&input)?))
+        }
+        Some(("done", args)) => {
+            let input = UpdateTodo {
+                title: None,
+                completed: Some(true),
+            };
+            Ok(output::todo(format, &client.update_todo(id(args), &input)?))
+        }
+        Some(("rm", args)) => {
+            client.delete_todo(id(args))?;
+            Ok(output::deleted(format, id(args)))
+        }
+        _ => unreachable!("clap requires a subcommand"),
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+    match run(&matches) {
+        Ok(text) => {
+            print!("{text}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_is_well_formed() {
+        command().debug_assert();
+    }
+
+    #[test]
+    fn rejects_a_malformed_id() {
+        let err = command().try_get_matches_from(["todo", "rm", "not-a-uuid"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+}
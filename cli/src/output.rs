@@ -0,0 +1,84 @@
+//! Rendering command results as a table for people or JSON for scripts.
+//!
+//! JSON output is the todo's wire form, one document per command, so it can
+//! be piped to `jq`. Every rendering ends with a newline.
+
+use todo_core::Todo;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+pub fn todos(format: Format, todos: &[Todo]) -> String {
+    match format {
+        Format::Json => json(&todos),
+        Format::Table if todos.is_empty() => "no todos\n".to_string(),
+        Format::Table => table(todos),
+    }
+}
+
+pub fn todo(format: Format, todo: &Todo) -> String {
+    match format {
+        Format::Json => json(todo),
+        Format::Table => table(std::slice::from_ref(todo)),
+    }
+}
+
+pub fn deleted(format: Format, id: Uuid) -> String {
+    match format {
+        Format::Json => json(&serde_json::json!({ "deleted": id })),
+        Format::Table => format!("deleted {id}\n"),
+    }
+}
+
+fn json(value: &impl serde::Serialize) -> String {
+    let mut out = serde_json::to_string_pretty(value).expect("todos always serialize");
+    out.push('\n');
+    out
+}
+
+/// A header row, then one row per todo. Ids are fixed width, so only the
+/// title column is ragged.
+fn table(todos: &[Todo]) -> String {
+    let mut out = format!("{:<36}  {:<4}  TITLE\n", "ID", "DONE");
+    for todo in todos {
+        let done = if todo.completed { "[x]" } else { "[ ]" };
+        out.push_str(&format!("{:<36}  {done:<4}  {}\n", todo.id, todo.title));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(completed: bool) -> Todo {
+        Todo {
+            id: Uuid::from_u128(1),
+            title: "Buy milk".to_string(),
+            completed,
+            schema_version: None,
+        }
+    }
+
+    #[test]
+    fn table_lines_up_columns() {
+        assert_eq!(
+            todos(Format::Table, &[sample(false), sample(true)]),
+            "ID                                    DONE  TITLE\n\
+             00000000-0000-0000-0000-000000000001  [ ]   Buy milk\n\
+             00000000-0000-0000-0000-000000000001  [x]   Buy milk\n"
+        );
+        assert_eq!(todos(Format::Table, &[]), "no todos\n");
+    }
+
+    #[test]
+    fn json_is_the_wire_form() {
+        let parsed: serde_json::Value = serde_json::from_str(&todo(Format::Json, &sample(true))).unwrap();
+        assert_eq!(parsed, serde_json::json!({"id": Uuid::from_u128(1), "title": "Buy milk", "completed": true}));
+        assert_eq!(todos(Format::Json, &[]), "[]\n");
+    }
+}
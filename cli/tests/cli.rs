@@ -0,0 +1,59 @@
+//! Runs the `todo` binary against a live mock server.
+
+use std::process::{Command, Output};
+
+use todo_test_support::server::spawn_server;
+
+fn todo(base_url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_todo"))
+        .env_remove("TODO_BASE_URL")
+        .args(["--base-url", base_url])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn json(output: &Output) -> serde_json::Value {
+    serde_json::from_str(&stdout(output)).unwrap()
+}
+
+#[test]
+fn add_done_list_rm() {
+    let server = spawn_server();
+    let base_url = server.base_url.as_str();
+    assert_eq!(stdout(&todo(base_url, &["list"])), "no todos\n");
+
+    let created = json(&todo(base_url, &["add", "  Buy   milk ", "-o", "json"]));
+    assert_eq!(created["title"], "Buy milk");
+    assert_eq!(created["completed"], false);
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let done = json(&todo(base_url, &["--output", "json", "done", &id]));
+    assert_eq!(done["completed"], true);
+
+    let table = stdout(&todo(base_url, &["list"]));
+    assert_eq!(table.lines().nth(1), Some(format!("{id}  [x]   Buy milk").as_str()), "{table}");
+
+    assert_eq!(stdout(&todo(base_url, &["rm", &id])), format!("deleted {id}\n"));
+    assert_eq!(json(&todo(base_url, &["list", "-o", "json"])), serde_json::json!([]));
+}
+
+#[test]
+fn api_errors_exit_with_status_1() {
+    let server = spawn_server();
+    let base_url = server.base_url.as_str();
+    let output = todo(base_url, &["done", "00000000-0000-0000-0000-000000000001"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "error: resource not found\n");
+}
+
+#[test]
+fn usage_errors_exit_with_status_2() {
+    let output = todo("http://localhost:3000", &["rm", "not-a-uuid"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,274 @@
+//! Runs the shared test vectors through every surface of the client and
+//! compares what each one observed, field by field.
+//!
+//! # Design
+//! A [`Case`] splits a vector case into the [`Call`] a surface makes (inputs
+//! plus the simulated response) and the [`Observation`] the vector expects.
+//! Each [`surface::Surface`] turns a call into an observation in one
+//! canonical shape, so `todo-core`, the C ABI and out-of-process bindings
+//! are checked by the same [`diff`]:
+//! - the request's method name, full URL, headers in order, and body (as
+//!   parsed JSON when it parses, so key order does not matter);
+//! - the outcome: `{"result": ...}` with todos reduced to the
+//!   `id`/`title`/`completed` fields every surface exposes, or
+//!   `{"error": "<kind>"}` with the kind spelled like the C ABI's
+//!   `FfiErrorCode` variants (`NotFound`, `Http`, ...).
+//!
+//! `Call` and `Observation` are also the wire format of the subprocess
+//! protocol (see `surface`), one JSON object per line. Vector files are read
+//! with `todo-test-support`, which rejects malformed ones.
+
+pub mod surface;
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use todo_test_support::TestVector;
+
+pub use todo_test_support::SimulatedResponse;
+
+/// Base URL every call is made against, prefixed to vector paths.
+pub const BASE_URL: &str = "http://localhost:3000";
+
+/// The operations the vectors cover, named like the entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    ListTodos,
+    GetTodo,
+    CreateTodo,
+    UpdateTodo,
+    DeleteTodo,
+}
+
+impl Operation {
+    /// The operation a vector file covers, from its file stem.
+    pub fn from_stem(stem: &str) -> Option<Operation> {
+        Some(match stem {
+            "list" => Operation::ListTodos,
+            "get" => Operation::GetTodo,
+            "create" => Operation::CreateTodo,
+            "update" => Operation::UpdateTodo,
+            "delete" => Operation::DeleteTodo,
+            _ => return None,
+        })
+    }
+}
+
+/// What a surface is asked to do for one case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Call {
+    pub operation: Operation,
+    pub base_url: String,
+    /// The vector's `input`: a `CreateTodo` or `UpdateTodo` object.
+    #[serde(default)]
+    pub input: Value,
+    #[serde(default)]
+    pub input_id: Option<String>,
+    pub response: SimulatedResponse,
+}
+
+/// A built request in canonical form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservedRequest {
+    /// Upper-case method name, e.g. `POST`.
+    pub method: String,
+    /// Full URL, base included.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// How parsing the simulated response ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Result(Value),
+    Error(String),
+}
+
+/// Everything a surface observed for one call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Observation {
+    /// `None` when the surface refused to build the request.
+    pub request: Option<ObservedRequest>,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+/// One case from a vector file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    /// `<file stem>/<case name>`, e.g. `create/basic_create`.
+    pub name: String,
+    pub call: Call,
+    pub expected: Observation,
+}
+
+/// Load the cases of every `*.json` file in `dir`, in file name order.
+pub fn load_dir(dir: &Path) -> Result<Vec<Case>, String> {
+    let mut cases = Vec::new();
+    for (stem, vector) in todo_test_support::load_dir(dir)? {
+        cases.extend(cases_of(&stem, vector).map_err(|e| format!("{}: {e}", dir.join(format!("{stem}.json")).display()))?);
+    }
+    Ok(cases)
+}
+
+/// Parse one vector file, given its stem and contents.
+pub fn parse_vector(stem: &str, source: &str) -> Result<Vec<Case>, String> {
+    cases_of(stem, todo_test_support::parse(source)?)
+}
+
+fn cases_of(stem: &str, vector: TestVector) -> Result<Vec<Case>, String> {
+    let operation = Operation::from_stem(stem).ok_or_else(|| format!("no operation for vector file {stem}.json"))?;
+    vector
+        .cases
+        .into_iter()
+        .map(|case| {
+            let request = case.expected_request;
+            let call = Call {
+                operation,
+                base_url: BASE_URL.to_string(),
+                input: serde_json::to_value(&case.input).map_err(|e| e.to_string())?,
+                input_id: case.input_id,
+                response: case.simulated_response,
+            };
+            let outcome = match case.expected_error {
+                Some(kind) => Outcome::Error(kind.as_str().to_string()),
+                None => Outcome::Result(serde_json::to_value(&case.expected_result).map_err(|e| e.to_string())?),
+            };
+            let expected = Observation {
+                request: Some(ObservedRequest {
+                    method: request.method,
+                    path: format!("{BASE_URL}{}", request.path),
+                    headers: request.headers,
+                    body: request.body.map(|body| body.to_string()),
+                }),
+                outcome,
+            };
+            Ok(Case { name: format!("{stem}/{}", case.name), call, expected })
+        })
+        .collect()
+}
+
+/// Every field where `actual` differs from `expected`, as
+/// `field: expected X, got Y` lines. Empty when they agree.
+pub fn diff(expected: &Observation, actual: &Observation) -> Vec<String> {
+    let mut out = Vec::new();
+    match (&expected.request, &actual.request) {
+        (Some(want), Some(got)) => {
+            field(&mut out, "request.method", &want.method, &got.method);
+            field(&mut out, "request.path", &want.path, &got.path);
+            field(&mut out, "request.headers", &want.headers, &got.headers);
+            match (&want.body, &got.body) {
+                (Some(want), Some(got)) => diff_value(&mut out, "request.body", &as_json(want), &as_json(got)),
+                (want, got) => field(&mut out, "request.body", want, got),
+            }
+        }
+        (None, None) => {}
+        (want, got) => out.push(format!(
+            "request: expected {}, got {}",
+            if want.is_some() { "a request" } else { "none" },
+            if got.is_some() { "a request" } else { "none" }
+        )),
+    }
+    match (&expected.outcome, &actual.outcome) {
+        (Outcome::Result(want), Outcome::Result(got)) => diff_value(&mut out, "result", want, got),
+        (want, got) => field(&mut out, "outcome", want, got),
+    }
+    out
+}
+
+fn field<T: PartialEq + std::fmt::Debug>(out: &mut Vec<String>, name: &str, want: &T, got: &T) {
+    if want != got {
+        out.push(format!("{name}: expected {want:?}, got {got:?}"));
+    }
+}
+
+fn as_json(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()))
+}
+
+/// Recurse into objects and arrays so a mismatch names the exact field,
+/// e.g. `result[1].title`.
+fn diff_value(out: &mut Vec<String>, name: &str, want: &Value, got: &Value) {
+    match (want, got) {
+        (Value::Object(want), Value::Object(got)) => {
+            for (key, value) in want {
+                match got.get(key) {
+                    Some(other) => diff_value(out, &format!("{name}.{key}"), value, other),
+                    None => out.push(format!("{name}.{key}: expected {value}, got nothing")),
+                }
+            }
+            for (key, value) in got {
+                if !want.contains_key(key) {
+                    out.push(format!("{name}.{key}: expected nothing, got {value}"));
+                }
+            }
+        }
+        (Value::Array(want), Value::Array(got)) => {
+            if want.len() != got.len() {
+                out.push(format!("{name}: expected {} items, got {}", want.len(), got.len()));
+            }
+            for (i, (want, got)) in want.iter().zip(got).enumerate() {
+                diff_value(out, &format!("{name}[{i}]"), want, got);
+            }
+        }
+        (want, got) if want != got => out.push(format!("{name}: expected {want}, got {got}")),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn observation(body: &str, result: Value) -> Observation {
+        Observation {
+            request: Some(ObservedRequest {
+                method: "POST".to_string(),
+                path: format!("{BASE_URL}/todos"),
+                headers: vec![("content-type".to_string(), "application/json".to_string())],
+                body: Some(body.to_string()),
+            }),
+            outcome: Outcome::Result(result),
+        }
+    }
+
+    #[test]
+    fn body_key_order_does_not_matter() {
+        let want = observation(r#"{"title":"a","completed":false}"#, json!(null));
+        let got = observation(r#"{"completed":false,"title":"a"}"#, json!(null));
+        assert!(diff(&want, &got).is_empty());
+    }
+
+    #[test]
+    fn diff_names_the_field() {
+        let want = observation("{}", json!([{"title": "a"}, {"title": "b"}]));
+        let got = observation("{}", json!([{"title": "a"}, {"title": "c", "extra": 1}]));
+        assert_eq!(
+            diff(&want, &got),
+            [r#"result[1].title: expected "b", got "c""#, "result[1].extra: expected nothing, got 1"]
+        );
+        let err = Observation { request: None, outcome: Outcome::Error("NotFound".to_string()) };
+        assert_eq!(diff(&want, &err).len(), 2);
+    }
+
+    #[test]
+    fn observation_wire_format() {
+        let observation = Observation { request: None, outcome: Outcome::Error("NotFound".to_string()) };
+        let line = serde_json::to_string(&observation).unwrap();
+        assert_eq!(line, r#"{"request":null,"error":"NotFound"}"#);
+        assert_eq!(serde_json::from_str::<Observation>(&line).unwrap(), observation);
+    }
+
+    #[test]
+    fn parses_every_checked_in_vector() {
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-vectors"));
+        let cases = load_dir(dir).unwrap();
+        assert!(cases.iter().any(|c| c.name == "create/basic_create"));
+        assert!(cases.iter().any(|c| c.expected.outcome == Outcome::Error("NotFound".to_string())));
+    }
+}
@@ -0,0 +1,74 @@
+//! `todo-conformance [--vectors <dir>] [--binding <name>=<command>]...`:
+//! run the test vectors through `todo-core`, the C ABI and each binding
+//! adapter, printing every field that differs from the vector. `<command>`
+//! is split on whitespace.
+//!
+//! `todo-conformance --serve`: act as a protocol adapter backed by the C ABI.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use todo_conformance::surface::{self, Core, Ffi, Subprocess, Surface};
+use todo_conformance::{diff, load_dir};
+
+const USAGE: &str = "usage: todo-conformance [--vectors <dir>] [--binding <name>=<command>]...\n       todo-conformance --serve";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let mut vectors = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-vectors"));
+    let mut surfaces: Vec<Box<dyn Surface>> = vec![Box::new(Core), Box::new(Ffi)];
+    while let Some(arg) = args.next() {
+        let parsed = match arg.as_str() {
+            "--serve" => {
+                return match surface::serve(&mut Ffi, std::io::stdin().lock(), std::io::stdout().lock()) {
+                    Ok(()) => ExitCode::SUCCESS,
+                    Err(e) => fail(&e),
+                };
+            }
+            "--vectors" => args.next().map(|dir| vectors = PathBuf::from(dir)).ok_or_else(|| USAGE.to_string()),
+            "--binding" => args.next().ok_or_else(|| USAGE.to_string()).and_then(|spec| {
+                let (name, command) = spec.split_once('=').ok_or_else(|| format!("--binding {spec}: expected <name>=<command>"))?;
+                let mut words = command.split_whitespace().map(str::to_string);
+                let program = words.next().ok_or_else(|| format!("--binding {name}: empty command"))?;
+                let args: Vec<String> = words.collect();
+                surfaces.push(Box::new(Subprocess::spawn(name, &program, &args)?));
+                Ok(())
+            }),
+            _ => Err(USAGE.to_string()),
+        };
+        if let Err(e) = parsed {
+            return fail(&e);
+        }
+    }
+
+    let cases = match load_dir(&vectors) {
+        Ok(cases) => cases,
+        Err(e) => return fail(&e),
+    };
+    let mut failed = false;
+    for surface in &mut surfaces {
+        let mut passed = 0;
+        for case in &cases {
+            let problems = match surface.run(&case.call) {
+                Ok(observed) => diff(&case.expected, &observed),
+                Err(e) => vec![e],
+            };
+            for problem in &problems {
+                println!("FAIL {} {}: {problem}", surface.name(), case.name);
+            }
+            passed += usize::from(problems.is_empty());
+        }
+        println!("{}: {passed}/{} cases match", surface.name(), cases.len());
+        failed |= passed != cases.len();
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fail(message: &str) -> ExitCode {
+    eprintln!("todo-conformance: {message}");
+    ExitCode::FAILURE
+}
@@ -0,0 +1,365 @@
+//! The surfaces a [`Call`] can be driven through.
+//!
+//! # Design
+//! [`Core`] is the reference: it calls `todo-core` directly. [`Ffi`] goes
+//! through the `extern "C"` functions exactly as a C host would, C strings
+//! and all. [`Subprocess`] covers every other language without linking it:
+//! the runner starts the binding's adapter and speaks a line protocol over
+//! its stdin and stdout.
+//!
+//! # Subprocess protocol
+//! For each case the runner writes one [`Call`] as a JSON line, e.g.
+//!
+//! ```text
+//! {"operation":"get_todo","base_url":"http://localhost:3000","input":null,
+//!  "input_id":"00000000-0000-0000-0000-000000000001",
+//!  "response":{"status":200,"body":"{\"id\":...}"}}
+//! ```
+//!
+//! and reads back one [`Observation`] line:
+//!
+//! ```text
+//! {"request":{"method":"GET","path":"http://localhost:3000/todos/...",
+//!  "headers":[],"body":null},"result":{"id":"...","title":"Test","completed":false}}
+//! ```
+//!
+//! with `"error":"<kind>"` instead of `"result"` on failure. The adapter
+//! builds the request with the binding, then parses `response` with it;
+//! `request` is `null` if the binding refused to build one. It exits when
+//! stdin closes. `todo-conformance --serve` is a reference adapter backed by
+//! the C ABI.
+
+use std::ffi::{CStr, CString};
+use std::io::{BufRead, BufReader, Write};
+use std::os::raw::c_char;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{json, Value};
+use todo_core::{ApiError, CreateTodo, HttpResponse, Todo, TodoClient, UpdateTodo};
+use todo_ffi::types::{
+    FfiDataTag, FfiErrorCode, FfiHttpMethod, FfiHttpRequest, FfiHttpResponse, FfiTodo, FfiTodoClient, FfiTodoList,
+    FfiTodoResult,
+};
+use uuid::Uuid;
+
+use crate::{Call, Observation, ObservedRequest, Operation, Outcome};
+
+/// Something that can make a [`Call`] and report what it saw.
+pub trait Surface {
+    /// Short name used in reports, e.g. `ffi`.
+    fn name(&self) -> &str;
+
+    /// `Err` means the surface itself failed (crashed, spoke garbage), as
+    /// opposed to observing an error outcome.
+    fn run(&mut self, call: &Call) -> Result<Observation, String>;
+}
+
+/// `todo-core`, called directly.
+pub struct Core;
+
+impl Surface for Core {
+    fn name(&self) -> &str {
+        "core"
+    }
+
+    fn run(&mut self, call: &Call) -> Result<Observation, String> {
+        let client = TodoClient::new(&call.base_url);
+        let id = call.input_id.as_deref().and_then(|id| Uuid::parse_str(id).ok());
+        let request = match call.operation {
+            Operation::ListTodos => Some(client.build_list_todos()),
+            Operation::GetTodo => id.map(|id| client.build_get_todo(id)),
+            Operation::CreateTodo => serde_json::from_value::<CreateTodo>(call.input.clone())
+                .ok()
+                .and_then(|input| client.build_create_todo(&input).ok()),
+            Operation::UpdateTodo => id.and_then(|id| {
+                serde_json::from_value::<UpdateTodo>(call.input.clone())
+                    .ok()
+                    .and_then(|input| client.build_update_todo(id, &input).ok())
+            }),
+            Operation::DeleteTodo => id.map(|id| client.build_delete_todo(id)),
+        };
+        let response = HttpResponse {
+            status: call.response.status,
+            headers: call.response.headers.clone(),
+            body: call.response.body.clone(),
+        };
+        let result = match call.operation {
+            Operation::ListTodos => client
+                .parse_list_todos(response)
+                .map(|todos| Value::Array(todos.iter().map(todo_value).collect())),
+            Operation::GetTodo => client.parse_get_todo(response).map(|todo| todo_value(&todo)),
+            Operation::CreateTodo => client.parse_create_todo(response).map(|todo| todo_value(&todo)),
+            Operation::UpdateTodo => client.parse_update_todo(response).map(|todo| todo_value(&todo)),
+            Operation::DeleteTodo => client.parse_delete_todo(response).map(|()| Value::Null),
+        };
+        Ok(Observation {
+            request: request.map(|req| ObservedRequest {
+                method: req.method.as_str().to_string(),
+                path: req.path,
+                headers: req.headers,
+                body: req.body,
+            }),
+            outcome: match result {
+                Ok(value) => Outcome::Result(value),
+                Err(e) => Outcome::Error(error_kind(&e).to_string()),
+            },
+        })
+    }
+}
+
+fn todo_value(todo: &Todo) -> Value {
+    json!({ "id": todo.id.to_string(), "title": todo.title, "completed": todo.completed })
+}
+
+/// The `FfiErrorCode` spelling of an error, which every surface reports.
+pub fn error_kind(err: &ApiError) -> &'static str {
+    match err {
+        ApiError::Unauthorized => "Unauthorized",
+        ApiError::NotFound | ApiError::NotFoundId(_) => "NotFound",
+        ApiError::Conflict(_) => "Conflict",
+        ApiError::PreconditionFailed => "PreconditionFailed",
+        ApiError::HttpError { .. } => "Http",
+        ApiError::DeserializationError(_) => "Deserialization",
+        ApiError::SerializationError(_) => "Serialization",
+        ApiError::Validation(_) => "Validation",
+        ApiError::RateLimited { .. } => "RateLimited",
+        ApiError::SchemaMismatch { .. } => "SchemaMismatch",
+        ApiError::Transport(_) => "Transport",
+        ApiError::Tls(_) => "Tls",
+    }
+}
+
+/// The C ABI's UTF-8 entry points.
+pub struct Ffi;
+
+impl Surface for Ffi {
+    fn name(&self) -> &str {
+        "ffi"
+    }
+
+    fn run(&mut self, call: &Call) -> Result<Observation, String> {
+        let client = todo_ffi::todo_client_new(c_string(&call.base_url)?.as_ptr());
+        if client.is_null() {
+            return Err(format!("todo_client_new rejected {}", call.base_url));
+        }
+        let observation = ffi_call(client, call);
+        todo_ffi::todo_client_free(client);
+        observation
+    }
+}
+
+fn c_string(s: &str) -> Result<CString, String> {
+    CString::new(s).map_err(|_| format!("{s:?} contains a NUL byte"))
+}
+
+fn ffi_call(client: *mut FfiTodoClient, call: &Call) -> Result<Observation, String> {
+    let id = call.input_id.as_deref().map(c_string).transpose()?;
+    let id = id.as_ref().map_or(std::ptr::null(), |id| id.as_ptr());
+    let title = call.input["title"].as_str().map(c_string).transpose()?;
+    let title = title.as_ref().map_or(std::ptr::null(), |title| title.as_ptr());
+    let req = match call.operation {
+        Operation::ListTodos => todo_ffi::todo_build_list_todos(client),
+        Operation::GetTodo => todo_ffi::todo_build_get_todo(client, id),
+        Operation::CreateTodo => {
+            todo_ffi::todo_build_create_todo(client, title, call.input["completed"].as_bool().unwrap_or(false))
+        }
+        Operation::UpdateTodo => {
+            let completed = call.input["completed"].as_bool().map_or(-1, i32::from);
+            todo_ffi::todo_build_update_todo(client, id, title, completed)
+        }
+        Operation::DeleteTodo => todo_ffi::todo_build_delete_todo(client, id),
+    };
+    let request = unsafe { req.as_ref() }.map(|req| unsafe { ffi_request(req) });
+    todo_ffi::todo_free_request(req);
+
+    let body = c_string(&call.response.body)?;
+    let response = FfiHttpResponse { status: call.response.status, body: body.as_ptr() };
+    let result = match call.operation {
+        Operation::ListTodos => todo_ffi::todo_parse_list_todos(client, &response),
+        Operation::GetTodo => todo_ffi::todo_parse_get_todo(client, &response),
+        Operation::CreateTodo => todo_ffi::todo_parse_create_todo(client, &response),
+        Operation::UpdateTodo => todo_ffi::todo_parse_update_todo(client, &response),
+        Operation::DeleteTodo => todo_ffi::todo_parse_delete_todo(client, &response),
+    };
+    let outcome = unsafe { result.as_ref() }.map(|result| unsafe { ffi_outcome(result) });
+    todo_ffi::todo_free_result(result);
+    Ok(Observation { request, outcome: outcome.ok_or("parse returned null")? })
+}
+
+/// # Safety
+/// `s` is null or a valid C string.
+unsafe fn string(s: *const c_char) -> Option<String> {
+    (!s.is_null()).then(|| unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+}
+
+/// # Safety
+/// `req` came from a `todo_build_*` function and has not been freed.
+unsafe fn ffi_request(req: &FfiHttpRequest) -> ObservedRequest {
+    let headers = if req.headers.is_null() {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(req.headers, req.headers_len as usize) }
+    };
+    ObservedRequest {
+        method: match req.method {
+            FfiHttpMethod::Get => "GET",
+            FfiHttpMethod::Post => "POST",
+            FfiHttpMethod::Put => "PUT",
+            FfiHttpMethod::Delete => "DELETE",
+            FfiHttpMethod::Options => "OPTIONS",
+            FfiHttpMethod::Trace => "TRACE",
+            FfiHttpMethod::Patch => "PATCH",
+            FfiHttpMethod::Head => "HEAD",
+        }
+        .to_string(),
+        path: unsafe { string(req.path) }.unwrap_or_default(),
+        headers: headers
+            .iter()
+            .map(|h| unsafe { (string(h.key).unwrap_or_default(), string(h.value).unwrap_or_default()) })
+            .collect(),
+        body: unsafe { string(req.body) },
+    }
+}
+
+/// # Safety
+/// `todo` came from a `todo_parse_*` result that has not been freed.
+unsafe fn ffi_todo(todo: &FfiTodo) -> Value {
+    unsafe { json!({ "id": string(todo.id), "title": string(todo.title), "completed": todo.completed }) }
+}
+
+/// # Safety
+/// `result` came from a `todo_parse_*` function and has not been freed.
+unsafe fn ffi_outcome(result: &FfiTodoResult) -> Outcome {
+    let kind = match &result.error_code {
+        FfiErrorCode::Ok => {
+            return Outcome::Result(match &result.data_tag {
+                FfiDataTag::None => Value::Null,
+                FfiDataTag::Todo => unsafe { ffi_todo(&*(result.data as *const FfiTodo)) },
+                FfiDataTag::TodoList => {
+                    let list = unsafe { &*(result.data as *const FfiTodoList) };
+                    let items = if list.items.is_null() {
+                        &[][..]
+                    } else {
+                        unsafe { std::slice::from_raw_parts(list.items, list.len as usize) }
+                    };
+                    Value::Array(items.iter().map(|todo| unsafe { ffi_todo(todo) }).collect())
+                }
+                FfiDataTag::Count => json!(unsafe { *(result.data as *const u64) }),
+            });
+        }
+        FfiErrorCode::NotFound => "NotFound",
+        FfiErrorCode::Http => "Http",
+        FfiErrorCode::Deserialization => "Deserialization",
+        FfiErrorCode::Serialization => "Serialization",
+        FfiErrorCode::Panic => "Panic",
+        FfiErrorCode::NullArg => "NullArg",
+        FfiErrorCode::Conflict => "Conflict",
+        FfiErrorCode::Validation => "Validation",
+        FfiErrorCode::RateLimited => "RateLimited",
+        FfiErrorCode::Transport => "Transport",
+        FfiErrorCode::SchemaMismatch => "SchemaMismatch",
+        FfiErrorCode::Tls => "Tls",
+        FfiErrorCode::PreconditionFailed => "PreconditionFailed",
+        FfiErrorCode::Unauthorized => "Unauthorized",
+    };
+    Outcome::Error(kind.to_string())
+}
+
+/// A binding's adapter process speaking the line protocol.
+pub struct Subprocess {
+    name: String,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Subprocess {
+    /// Start `program` with `args`; stderr passes through.
+    pub fn spawn(name: &str, program: &str, args: &[String]) -> Result<Subprocess, String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("{name}: starting {program}: {e}"))?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        Ok(Subprocess { name: name.to_string(), child, stdin, stdout })
+    }
+}
+
+impl Surface for Subprocess {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&mut self, call: &Call) -> Result<Observation, String> {
+        let line = serde_json::to_string(call).map_err(|e| e.to_string())?;
+        writeln!(self.stdin, "{line}").and_then(|()| self.stdin.flush()).map_err(|e| format!("writing: {e}"))?;
+        let mut reply = String::new();
+        match self.stdout.read_line(&mut reply) {
+            Ok(0) => Err("adapter exited".to_string()),
+            Ok(_) => serde_json::from_str(&reply).map_err(|e| format!("bad reply {:?}: {e}", reply.trim_end())),
+            Err(e) => Err(format!("reading: {e}")),
+        }
+    }
+}
+
+impl Drop for Subprocess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Answer protocol lines from `input` with `surface` until EOF.
+pub fn serve(surface: &mut dyn Surface, input: impl BufRead, mut output: impl Write) -> Result<(), String> {
+    for line in input.lines() {
+        let line = line.map_err(|e| format!("reading: {e}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let call: Call = serde_json::from_str(&line).map_err(|e| format!("bad call {line:?}: {e}"))?;
+        let observation = surface.run(&call)?;
+        let reply = serde_json::to_string(&observation).map_err(|e| e.to_string())?;
+        writeln!(output, "{reply}").and_then(|()| output.flush()).map_err(|e| format!("writing: {e}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimulatedResponse;
+
+    fn call(operation: Operation, input_id: Option<&str>, status: u16, body: &str) -> Call {
+        Call {
+            operation,
+            base_url: crate::BASE_URL.to_string(),
+            input: Value::Null,
+            input_id: input_id.map(str::to_string),
+            response: SimulatedResponse { status, headers: Vec::new(), body: body.to_string() },
+        }
+    }
+
+    #[test]
+    fn core_and_ffi_agree_on_an_invalid_id() {
+        let call = call(Operation::GetTodo, Some("not-a-uuid"), 500, "oops");
+        let core = Core.run(&call).unwrap();
+        assert_eq!(core.request, None);
+        assert_eq!(core.outcome, Outcome::Error("Http".to_string()));
+        assert_eq!(Ffi.run(&call).unwrap(), core);
+    }
+
+    #[test]
+    fn serve_answers_each_line() {
+        let line = serde_json::to_string(&call(Operation::DeleteTodo, Some(&Uuid::nil().to_string()), 204, "")).unwrap();
+        let mut output = Vec::new();
+        serve(&mut Ffi, format!("{line}\n\n{line}\n").as_bytes(), &mut output).unwrap();
+        let replies: Vec<Observation> =
+            String::from_utf8(output).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0].outcome, Outcome::Result(Value::Null));
+        assert_eq!(replies[0].request.as_ref().unwrap().method, "DELETE");
+    }
+}
@@ -0,0 +1,43 @@
+//! Runs the `todo-conformance` binary end to end.
+
+use std::process::{Command, Output};
+
+const RUNNER: &str = env!("CARGO_BIN_EXE_todo-conformance");
+
+fn run(args: &[&str]) -> Output {
+    Command::new(RUNNER).args(args).output().unwrap()
+}
+
+#[test]
+fn checked_in_vectors_pass_on_every_surface() {
+    // The runner's own `--serve` adapter stands in for a foreign binding.
+    let binding = format!("self={RUNNER} --serve");
+    let output = run(&["--binding", &binding]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}{}", String::from_utf8_lossy(&output.stderr));
+    for surface in ["core", "ffi", "self"] {
+        assert!(stdout.lines().any(|l| l.starts_with(&format!("{surface}: ")) && l.contains("cases match")), "{stdout}");
+    }
+}
+
+#[test]
+fn mismatch_names_surface_case_and_field() {
+    let dir = tempfile::tempdir().unwrap();
+    let vector = include_str!("../../test-vectors/get.json").replace("\"title\": \"Test\"", "\"title\": \"Wrong\"");
+    std::fs::write(dir.path().join("get.json"), vector).unwrap();
+    let output = run(&["--vectors", dir.path().to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert!(stdout.contains(r#"FAIL core get/found: result.title: expected "Wrong", got "Test""#), "{stdout}");
+    assert!(stdout.contains(r#"FAIL ffi get/found: result.title"#), "{stdout}");
+}
+
+#[test]
+fn adapter_that_exits_fails_its_cases() {
+    let output = run(&["--binding", "broken=true"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    // Whether the write or the read notices first is a race.
+    assert!(stdout.contains("FAIL broken create/basic_create: "), "{stdout}");
+    assert!(stdout.contains("broken: 0/"), "{stdout}");
+}
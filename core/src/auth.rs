@@ -0,0 +1,90 @@
+//! Credentials attached to every request a `TodoClient` builds.
+//!
+//! # Design
+//! `Auth` describes how to authenticate, not how to obtain credentials:
+//! the host fetches or refreshes the secret and hands it to
+//! `TodoClient::with_auth`. `build_raw` turns it into a header, so every
+//! typed builder carries it without per-method code.
+//!
+//! `Debug` redacts the secret so clients can be logged safely.
+
+use core::fmt;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+
+use crate::prelude::*;
+
+/// How the client authenticates to the server.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// `Authorization: Basic <base64(user:pass)>` (RFC 7617).
+    Basic { user: String, pass: String },
+    /// `<header_name>: <value>`, e.g. `X-Api-Key: ...`.
+    ApiKey { header_name: String, value: String },
+}
+
+impl Auth {
+    /// The header this credential is sent as.
+    pub(crate) fn header(&self) -> (String, String) {
+        match self {
+            Auth::Bearer(token) => ("authorization".to_string(), format!("Bearer {token}")),
+            Auth::Basic { user, pass } => {
+                let credentials = BASE64_STANDARD.encode(format!("{user}:{pass}"));
+                ("authorization".to_string(), format!("Basic {credentials}"))
+            }
+            Auth::ApiKey { header_name, value } => (header_name.clone(), value.clone()),
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Bearer(_) => f.write_str("Bearer(<redacted>)"),
+            Auth::Basic { user, .. } => f
+                .debug_struct("Basic")
+                .field("user", user)
+                .field("pass", &"<redacted>")
+                .finish(),
+            Auth::ApiKey { header_name, .. } => f
+                .debug_struct("ApiKey")
+                .field("header_name", header_name)
+                .field("value", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_header_and_redacted_debug() {
+        let auth = Auth::Bearer("s3cret".to_string());
+        assert_eq!(auth.header(), ("authorization".to_string(), "Bearer s3cret".to_string()));
+        assert!(!format!("{auth:?}").contains("s3cret"));
+    }
+
+    #[test]
+    fn basic_header_matches_rfc_7617_example() {
+        let auth = Auth::Basic {
+            user: "Aladdin".to_string(),
+            pass: "open sesame".to_string(),
+        };
+        assert_eq!(auth.header().1, "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert!(!format!("{auth:?}").contains("sesame"));
+    }
+
+    #[test]
+    fn api_key_uses_configured_header() {
+        let auth = Auth::ApiKey {
+            header_name: "X-Api-Key".to_string(),
+            value: "k-123".to_string(),
+        };
+        assert_eq!(auth.header(), ("X-Api-Key".to_string(), "k-123".to_string()));
+        assert!(!format!("{auth:?}").contains("k-123"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Validated base URL for `TodoClient`.
+//!
+//! # Design
+//! `TodoClient::new` joins paths onto whatever string it is given, so a typo
+//! such as `htp://host` or `http://` only surfaces later as an unreachable
+//! request target. `BaseUrl::parse` checks the few properties the builders
+//! rely on up front: a supported scheme, a non-empty host, a numeric port,
+//! and no query or fragment that path joining would corrupt. It is not a
+//! general URL parser.
+
+use core::fmt;
+
+use crate::error::ApiError;
+use crate::prelude::*;
+
+/// A base URL accepted by `TodoClient::try_new`, without a trailing `/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUrl(String);
+
+impl BaseUrl {
+    /// Validate `input` as `http://host[:port][/prefix]`,
+    /// `https://host[:port][/prefix]`, or `unix:<socket-path>`.
+    pub fn parse(input: &str) -> Result<Self, ApiError> {
+        let invalid = |reason: &str| ApiError::Validation(format!("invalid base URL {input:?}: {reason}"));
+        if input.chars().any(char::is_whitespace) {
+            return Err(invalid("contains whitespace"));
+        }
+        let trimmed = input.trim_end_matches('/');
+        if let Some(socket) = trimmed.strip_prefix("unix:") {
+            if socket.is_empty() {
+                return Err(invalid("missing socket path"));
+            }
+            if socket.contains(':') {
+                return Err(invalid("socket path must not contain ':'"));
+            }
+            return Ok(Self(trimmed.to_string()));
+        }
+        let rest = trimmed
+            .strip_prefix("http://")
+            .or_else(|| trimmed.strip_prefix("https://"))
+            .ok_or_else(|| invalid("scheme must be http, https or unix"))?;
+        if rest.contains(['?', '#']) {
+            return Err(invalid("query and fragment are not allowed"));
+        }
+        let authority = rest.split('/').next().unwrap_or_default();
+        let host = match authority.rsplit_once(':') {
+            // A bracketed IPv6 literal without a port, e.g. `[::1]`.
+            Some((_, tail)) if tail.ends_with(']') => authority,
+            Some((host, port)) => {
+                if port.parse::<u16>().is_err() {
+                    return Err(invalid("port must be a number from 0 to 65535"));
+                }
+                host
+            }
+            None => authority,
+        };
+        if host.is_empty() || host.contains('@') {
+            return Err(invalid("missing or unsupported host"));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for BaseUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_supported_forms() {
+        for ok in [
+            "http://localhost:3000/",
+            "https://api.example.com/v1",
+            "http://[::1]:8080",
+            "http://[::1]",
+            "unix:/var/run/todo.sock",
+        ] {
+            assert!(BaseUrl::parse(ok).is_ok(), "{ok}");
+        }
+        assert_eq!(BaseUrl::parse("http://h/").unwrap().as_str(), "http://h");
+    }
+
+    #[test]
+    fn rejects_malformed_urls() {
+        for bad in [
+            "",
+            "localhost:3000",
+            "htp://host",
+            "http://",
+            "http://:80",
+            "http://host:port",
+            "http://host/a?b=c",
+            "http://user@host",
+            "http://ho st",
+            "unix:",
+            "unix:/a:b",
+        ] {
+            assert!(matches!(BaseUrl::parse(bad), Err(ApiError::Validation(_))), "{bad}");
+        }
+    }
+}
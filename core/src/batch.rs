@@ -0,0 +1,89 @@
+//! Accumulate built requests and collapse duplicate reads.
+//!
+//! # Design
+//! `RequestBatch` is a plain collector: callers `add` requests from the
+//! regular `build_*` methods and `finish` returns the requests to execute
+//! plus a fan-out map. Only GETs are deduplicated, since repeating any other
+//! method may have side effects the caller is relying on. Two GETs are
+//! duplicates when `HttpRequest::same_endpoint` says so.
+
+use crate::http::{HttpMethod, HttpRequest};
+use crate::prelude::*;
+
+/// Requests queued for execution as one batch.
+#[derive(Debug, Clone, Default)]
+pub struct RequestBatch {
+    requests: Vec<HttpRequest>,
+}
+
+impl RequestBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `req`, returning its position in the order requests were added.
+    pub fn add(&mut self, req: HttpRequest) -> usize {
+        self.requests.push(req);
+        self.requests.len() - 1
+    }
+
+    /// Collapse duplicate GETs and return `(unique, fan_out)`.
+    ///
+    /// `unique` holds the requests to execute, in first-seen order.
+    /// `fan_out[i]` is the index into `unique` whose response answers the
+    /// `i`-th added request.
+    pub fn finish(self) -> (Vec<HttpRequest>, Vec<usize>) {
+        let mut unique: Vec<HttpRequest> = Vec::new();
+        let mut fan_out = Vec::with_capacity(self.requests.len());
+        for req in self.requests {
+            let existing = (req.method == HttpMethod::Get)
+                .then(|| unique.iter().position(|u| u.same_endpoint(&req)))
+                .flatten();
+            match existing {
+                Some(index) => fan_out.push(index),
+                None => {
+                    fan_out.push(unique.len());
+                    unique.push(req);
+                }
+            }
+        }
+        (unique, fan_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::client::TodoClient;
+
+    #[test]
+    fn duplicate_gets_collapse() {
+        let client = TodoClient::new("http://localhost:3000");
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let mut batch = RequestBatch::new();
+        batch.add(client.build_get_todo(a));
+        batch.add(client.build_get_todo(b));
+        batch.add(client.build_get_todo(a));
+
+        let (unique, fan_out) = batch.finish();
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].path, "http://localhost:3000/todos/00000000-0000-0000-0000-000000000001");
+        assert_eq!(unique[1].path, "http://localhost:3000/todos/00000000-0000-0000-0000-000000000002");
+        assert_eq!(fan_out, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn non_get_requests_are_kept() {
+        let client = TodoClient::new("http://localhost:3000");
+        let id = Uuid::nil();
+        let mut batch = RequestBatch::new();
+        batch.add(client.build_delete_todo(id));
+        batch.add(client.build_delete_todo(id));
+
+        let (unique, fan_out) = batch.finish();
+        assert_eq!(unique.len(), 2);
+        assert_eq!(fan_out, vec![0, 1]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Optional blocking adapter that executes requests with ureq.
+//!
+//! # Design
+//! `BlockingTodoClient` is the one place in the crate that performs I/O, and
+//! only when the `blocking` feature is enabled. Each operation is the usual
+//! `build_*` → execute → `parse_*` sequence; the request is built once and
+//! re-sent on retry, with `RetrySession` deciding whether and how long to
+//! wait. Transport failures are reported as `ApiError::Transport` so they go
+//! through the same retry decision as HTTP errors, except TLS failures,
+//! which become `ApiError::Tls` and are never retried.
+//!
+//! Creates are not idempotent on their own. Pair retries with
+//! `TodoClient::with_idempotency_key_generator` when duplicate creates matter.
+
+use std::time::Instant;
+
+use uuid::Uuid;
+
+use crate::client::TodoClient;
+use crate::error::ApiError;
+use crate::http::{HttpMethod, HttpRequest, HttpResponse};
+use crate::retry::{RetryPolicy, RetrySession};
+use crate::types::{CreateTodo, Todo, UpdateTodo};
+
+/// A `TodoClient` paired with a ureq agent.
+#[derive(Debug, Clone)]
+pub struct BlockingTodoClient {
+    client: TodoClient,
+    agent: ureq::Agent,
+    retry: RetryPolicy,
+}
+
+impl BlockingTodoClient {
+    /// Wrap `client`. Retries are off until `with_retries` is called.
+    pub fn new(client: TodoClient) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .new_agent();
+        Self {
+            client,
+            agent,
+            retry: RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            },
+        }
+    }
+
+    /// Retry transport errors and retryable HTTP statuses under `policy`,
+    /// sleeping between attempts.
+    pub fn with_retries(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    pub fn list_todos(&self) -> Result<Vec<Todo>, ApiError> {
+        self.run(self.client.build_list_todos(), |r| self.client.parse_list_todos(r))
+    }
+
+    pub fn get_todo(&self, id: Uuid) -> Result<Todo, ApiError> {
+        self.run(self.client.build_get_todo(id), |r| self.client.parse_get_todo(r))
+    }
+
+    pub fn create_todo(&self, input: &CreateTodo) -> Result<Todo, ApiError> {
+        self.run(self.client.build_create_todo(input)?, |r| self.client.parse_create_todo(r))
+    }
+
+    pub fn update_todo(&self, id: Uuid, input: &UpdateTodo) -> Result<Todo, ApiError> {
+        self.run(self.client.build_update_todo(id, input)?, |r| self.client.parse_update_todo(r))
+    }
+
+    pub fn delete_todo(&self, id: Uuid) -> Result<(), ApiError> {
+        self.run(self.client.build_delete_todo(id), |r| self.client.parse_delete_todo(r))
+    }
+
+    /// Execute `req` and parse the response, retrying per the policy.
+    fn run<T>(&self, req: HttpRequest, parse: impl Fn(HttpResponse) -> Result<T, ApiError>) -> Result<T, ApiError> {
+        let mut session = RetrySession::new(self.retry);
+        let started = Instant::now();
+        loop {
+            let err = match self.execute(&req).and_then(&parse) {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            match session.on_failure_at(&err, started.elapsed(), jitter_sample()) {
+                Some(delay) => std::thread::sleep(delay),
+                None => return Err(err),
+            }
+        }
+    }
+
+    fn execute(&self, req: &HttpRequest) -> Result<HttpResponse, ApiError> {
+        let mut builder = ureq::http::Request::builder()
+            .method(req.method.as_str())
+            .uri(req.url());
+        for (name, value) in &req.headers {
+            builder = builder.header(name, value);
+        }
+        let result = match (&req.method, &req.body) {
+            (HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch, Some(body)) => {
+                let request = builder.body(body.clone()).map_err(|e| ApiError::Transport(e.to_string()))?;
+                self.agent.run(request)
+            }
+            _ => {
+                let request = builder.body(()).map_err(|e| ApiError::Transport(e.to_string()))?;
+                self.agent.run(request)
+            }
+        };
+        let mut response = result.map_err(transport_error)?;
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(transport_error)?;
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            headers,
+            body,
+        })
+    }
+}
+
+/// A uniform sample in `[0, 1)` for retry jitter, drawn from the OS RNG
+/// that backs v4 UUIDs. The low 53 bits of a v4 UUID are all random; the
+/// version and variant bits sit above them.
+fn jitter_sample() -> f64 {
+    let bits = Uuid::new_v4().as_u128() as u64 & ((1 << 53) - 1);
+    bits as f64 / (1u64 << 53) as f64
+}
+
+/// Classify a ureq failure, separating TLS problems from other transport
+/// errors. rustls handshake failures arrive wrapped in an `io::Error`, so the
+/// inner error is checked as well.
+fn transport_error(err: ureq::Error) -> ApiError {
+    match err {
+        ureq::Error::Tls(_) | ureq::Error::Pem(_) | ureq::Error::Rustls(_) | ureq::Error::TlsRequired => {
+            ApiError::Tls(err.to_string())
+        }
+        ureq::Error::Io(io) if io.get_ref().is_some_and(|inner| inner.is::<rustls::Error>()) => {
+            ApiError::Tls(io.to_string())
+        }
+        other => ApiError::Transport(other.to_string()),
+    }
+}
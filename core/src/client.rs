@@ -1,212 +1,1980 @@
 //! Stateless HTTP request builder and response parser for the todo API.
 //!
 //! # Design
-//! `TodoClient` holds only a `base_url` and carries no mutable state between
-//! calls. Each CRUD operation is split into a `build_*` method that produces
-//! an `HttpRequest` and a `parse_*` method that consumes an `HttpResponse`.
-//! The caller executes the actual HTTP round-trip, keeping the core
-//! deterministic and free of I/O dependencies.
+//! `TodoClient` holds a `base_url` plus options set once through `with_*`
+//! builders (or `TodoClient::builder()` for default headers), and carries no
+//! mutable state between calls. Each CRUD operation is split into a
+//! `build_*` method that produces an `HttpRequest` and a `parse_*` method
+//! that consumes an `HttpResponse`. The caller executes the actual HTTP
+//! round-trip, keeping the core deterministic and free of I/O dependencies.
+//!
+//! # Base URLs
+//! Besides `http://` and `https://`, a base of the form
+//! `unix:<socket-path>` targets a server listening on a unix domain socket.
+//! Built paths then take the form `unix:<socket-path>:<http-path>`, e.g.
+//! `unix:/var/run/todo.sock:/todos/{id}`. Executors split at the first `:`
+//! after the `unix:` prefix, connect to the socket, and send the remainder
+//! as the request target. Socket paths therefore must not contain `:`.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
 
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use serde::de::{Deserializer as _, IgnoredAny, SeqAccess, Visitor};
+use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::auth::Auth;
+use crate::base_url::BaseUrl;
 use crate::error::ApiError;
-use crate::http::{HttpMethod, HttpRequest, HttpResponse};
-use crate::types::{CreateTodo, Todo, UpdateTodo};
+use crate::http::{HttpMethod, HttpRequest, HttpResponse, RateLimitInfo};
+use crate::prelude::*;
+use crate::types::{
+    lenient_bool, Capabilities, CreateTodo, IdFormat, ListFilter, Page, PageParams, SearchHit, SearchOptions, SortBy, SortOrder, Todo, TodoCount, TodoFilter, TodoRef, TodoRevision, UpdateTodo,
+};
+
+/// Scheme prefix for unix-domain-socket base URLs.
+const UNIX_SCHEME: &str = "unix:";
 
 /// Synchronous, stateless client for the todo API.
 ///
 /// Builds `HttpRequest` values and parses `HttpResponse` values without
 /// touching the network. The caller is responsible for executing the HTTP
 /// round-trip between `build_*` and `parse_*`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TodoClient {
     base_url: String,
+    error_includes_request: bool,
+    reject_empty_updates: bool,
+    traceparent: Option<String>,
+    idempotency_key_generator: Option<HeaderFn>,
+    request_signer: Option<HeaderFn>,
+    content_md5: bool,
+    id_format: IdFormat,
+    default_headers: Vec<(String, String)>,
+    auth: Option<Auth>,
+    #[cfg(feature = "validate-schema")]
+    response_validation: bool,
+}
+
+/// Collects client-wide settings that must be fixed before the first
+/// request is built. Obtain one from `TodoClient::builder`.
+#[derive(Debug, Clone)]
+pub struct TodoClientBuilder {
+    client: TodoClient,
+}
+
+impl TodoClientBuilder {
+    /// Send `name: value` on every built request, e.g. `X-Tenant`. Setting
+    /// the same name again (case-insensitively) replaces the earlier value.
+    ///
+    /// Headers the client derives itself, such as `content-type`, take
+    /// precedence; per-call overrides go through `HttpRequest::set_header`.
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        let headers = &mut self.client.default_headers;
+        headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn build(self) -> TodoClient {
+        self.client
+    }
+}
+
+/// Shared closure deriving a header value (idempotency key, signature) from
+/// a fully built request.
+///
+/// Wrapped so `TodoClient` keeps its `Debug`/`Clone`/`PartialEq` derives:
+/// clones share the same closure, and two generators compare equal only
+/// when they are the same allocation.
+#[derive(Clone)]
+struct HeaderFn(Arc<dyn Fn(&HttpRequest) -> String + Send + Sync>);
+
+impl fmt::Debug for HeaderFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HeaderFn(..)")
+    }
+}
+
+impl PartialEq for HeaderFn {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
+impl Eq for HeaderFn {}
+
+// The client never mutates through the generator, so a panic inside it cannot
+// leave the client half-updated; FFI wrappers rely on this for `catch_unwind`.
+#[cfg(feature = "std")]
+impl std::panic::UnwindSafe for HeaderFn {}
+#[cfg(feature = "std")]
+impl std::panic::RefUnwindSafe for HeaderFn {}
+
 impl TodoClient {
+    /// Create a client without validating `base_url`; prefer `try_new` for
+    /// URLs that come from configuration or users.
     pub fn new(base_url: &str) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
+            error_includes_request: false,
+            reject_empty_updates: false,
+            traceparent: None,
+            idempotency_key_generator: None,
+            request_signer: None,
+            content_md5: false,
+            id_format: IdFormat::Uuid,
+            default_headers: Vec::new(),
+            auth: None,
+            #[cfg(feature = "validate-schema")]
+            response_validation: false,
+        }
+    }
+
+    /// Create a client after checking `base_url` with `BaseUrl::parse`, so a
+    /// malformed URL fails here with `Validation` instead of producing
+    /// unusable request targets.
+    pub fn try_new(base_url: &str) -> Result<Self, ApiError> {
+        Ok(Self::from_base_url(&BaseUrl::parse(base_url)?))
+    }
+
+    pub fn from_base_url(base_url: &BaseUrl) -> Self {
+        Self::new(base_url.as_str())
+    }
+
+    /// Start a client for `base_url` whose requests carry default headers.
+    /// The `with_*` options still apply to the built client.
+    pub fn builder(base_url: &str) -> TodoClientBuilder {
+        TodoClientBuilder {
+            client: Self::new(base_url),
+        }
+    }
+
+    /// Attach the originating request body to `HttpError`s returned by the
+    /// `*_with_request` parse variants. Off by default.
+    ///
+    /// Only the body is copied; headers (and with them any credentials) are
+    /// never included in the error.
+    pub fn with_error_includes_request(mut self, enabled: bool) -> Self {
+        self.error_includes_request = enabled;
+        self
+    }
+
+    /// Make `build_update_todo` fail with `Validation` when no fields are
+    /// set. Off by default because some servers accept the no-op PUT.
+    pub fn reject_empty_updates(mut self, enabled: bool) -> Self {
+        self.reject_empty_updates = enabled;
+        self
+    }
+
+    /// Set a W3C `traceparent` header on every built request.
+    ///
+    /// The value must match `00-<32 hex>-<16 hex>-<2 hex>` in lowercase, with
+    /// non-zero trace and parent ids, as the Trace Context spec requires.
+    pub fn with_traceparent(mut self, traceparent: &str) -> Result<Self, ApiError> {
+        if !is_valid_traceparent(traceparent) {
+            return Err(ApiError::Validation(format!("malformed traceparent: {traceparent}")));
+        }
+        self.traceparent = Some(traceparent.to_string());
+        Ok(self)
+    }
+
+    /// Attach a `content-md5` header (base64 of the body's MD5 digest, per
+    /// RFC 1864) to every request that carries a body. Off by default.
+    pub fn with_content_md5(mut self, enabled: bool) -> Self {
+        self.content_md5 = enabled;
+        self
+    }
+
+    /// Authenticate every built request with `auth`. A 401 response then
+    /// parses as `ApiError::Unauthorized`.
+    ///
+    /// Fails with `Validation` for `Auth::Basic` credentials whose user
+    /// contains `:`, which RFC 7617 cannot represent, and for an
+    /// `Auth::ApiKey` header name that is not a valid HTTP token.
+    pub fn with_auth(mut self, auth: Auth) -> Result<Self, ApiError> {
+        match &auth {
+            Auth::Basic { user, .. } if user.contains(':') => {
+                return Err(ApiError::Validation("basic auth user must not contain ':'".to_string()));
+            }
+            Auth::ApiKey { header_name, .. } if !is_token(header_name) => {
+                return Err(ApiError::Validation(format!("invalid api key header name: {header_name:?}")));
+            }
+            _ => {}
+        }
+        self.auth = Some(auth);
+        Ok(self)
+    }
+
+    /// Select how todo ids appear on the wire. Defaults to `IdFormat::Uuid`.
+    pub fn with_id_format(mut self, id_format: IdFormat) -> Self {
+        self.id_format = id_format;
+        self
+    }
+
+    /// Stamp every create with an `idempotency-key` header from `generator`.
+    ///
+    /// The generator runs after the request, body included, is fully built,
+    /// so it can derive the key from the payload (e.g. a content hash) and
+    /// retried creates of the same todo collapse server-side.
+    pub fn with_idempotency_key_generator(
+        mut self,
+        generator: Box<dyn Fn(&HttpRequest) -> String + Send + Sync>,
+    ) -> Self {
+        self.idempotency_key_generator = Some(HeaderFn(Arc::from(generator)));
+        self
+    }
+
+    /// Attach a `signature` header computed by `signer` (e.g. an HMAC over
+    /// the body) to every update.
+    ///
+    /// While a signer is set, update bodies are serialized with
+    /// `UpdateTodo::to_canonical_json` so the signed bytes are reproducible.
+    pub fn with_request_signer(mut self, signer: Box<dyn Fn(&HttpRequest) -> String + Send + Sync>) -> Self {
+        self.request_signer = Some(HeaderFn(Arc::from(signer)));
+        self
+    }
+
+    /// Check todo response bodies against the embedded JSON schema before
+    /// deserializing, so malformed bodies fail with `Validation` naming the
+    /// offending path. Off by default.
+    #[cfg(feature = "validate-schema")]
+    pub fn with_response_validation(mut self, enabled: bool) -> Self {
+        self.response_validation = enabled;
+        self
+    }
+
+    /// Render `id` as a path segment in the configured `IdFormat`.
+    fn id_segment(&self, id: Uuid) -> String {
+        match self.id_format {
+            IdFormat::Uuid => id.to_string(),
+            IdFormat::Integer => id.as_u128().to_string(),
+        }
+    }
+
+    /// Deserialize a single todo body in the configured `IdFormat`.
+    fn decode_todo(&self, body: &str) -> Result<Todo, ApiError> {
+        #[cfg(feature = "validate-schema")]
+        if self.response_validation {
+            crate::schema::validate_todo(body)?;
+        }
+        let todo = match self.id_format {
+            IdFormat::Uuid => serde_json::from_str(body),
+            IdFormat::Integer => serde_json::from_str::<IntegerIdTodo>(body).map(Todo::from),
+        };
+        todo.map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// Deserialize a todo array body in the configured `IdFormat`.
+    fn decode_todos(&self, body: &str) -> Result<Vec<Todo>, ApiError> {
+        #[cfg(feature = "validate-schema")]
+        if self.response_validation {
+            crate::schema::validate_todo_list(body)?;
+        }
+        let todos = match self.id_format {
+            IdFormat::Uuid => serde_json::from_str(body),
+            IdFormat::Integer => serde_json::from_str::<Vec<IntegerIdTodo>>(body)
+                .map(|todos| todos.into_iter().map(Todo::from).collect()),
+        };
+        todos.map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse a string-form id (JSON:API carries ids as strings) in the
+    /// configured `IdFormat`.
+    fn decode_id(&self, raw: &str) -> Result<Uuid, ApiError> {
+        match self.id_format {
+            IdFormat::Uuid => Uuid::parse_str(raw).map_err(|e| ApiError::DeserializationError(e.to_string())),
+            IdFormat::Integer => raw
+                .parse::<u64>()
+                .map(|id| Uuid::from_u128(u128::from(id)))
+                .map_err(|e| ApiError::DeserializationError(format!("integer id {raw:?}: {e}"))),
+        }
+    }
+
+    /// Join `path` onto the base URL, using the `unix:` separator when needed.
+    fn url(&self, path: &str) -> String {
+        if self.base_url.starts_with(UNIX_SCHEME) {
+            format!("{}:{path}", self.base_url)
+        } else {
+            format!("{}{path}", self.base_url)
+        }
+    }
+
+    pub fn build_list_todos(&self) -> HttpRequest {
+        self.build_raw(HttpMethod::Get, "/todos", None)
+    }
+
+    /// List only the todos matching `filter`. Parse with `parse_list_todos`.
+    pub fn build_list_todos_filtered(&self, filter: &ListFilter) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos", None);
+        req.query = filter.query();
+        req
+    }
+
+    /// List all todos ordered by `sort_by`. Parse with `parse_list_todos`.
+    pub fn build_list_todos_sorted(&self, sort_by: SortBy, order: SortOrder) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos", None);
+        req.query = vec![
+            ("sort".to_string(), sort_by.as_str().to_string()),
+            ("order".to_string(), order.as_str().to_string()),
+        ];
+        req
+    }
+
+    /// List one limit/offset window. Parse with `parse_list_todos_page`.
+    pub fn build_list_todos_page(&self, page: PageParams) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos", None);
+        req.query = vec![
+            ("limit".to_string(), page.limit.to_string()),
+            ("offset".to_string(), page.offset.to_string()),
+        ];
+        req
+    }
+
+    /// List the next page after `cursor`, or the first page when `None`.
+    ///
+    /// Cursors are opaque server tokens taken from the previous
+    /// `parse_list_todos_cursor` result and sent back unchanged.
+    pub fn build_list_todos_cursor(&self, cursor: Option<&str>) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos", None);
+        req.query.push(("cursor".to_string(), cursor.unwrap_or("").to_string()));
+        req
+    }
+
+    /// Full-text search over todo titles. `query` is sent as `q` and
+    /// percent-encoded by `HttpRequest::url`. Parse with `parse_search_todos`.
+    pub fn build_search_todos(&self, query: &str, opts: SearchOptions) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos/search", None);
+        req.query.push(("q".to_string(), query.to_string()));
+        req.query.extend(opts.query());
+        req
+    }
+
+    pub fn build_count_todos(&self) -> HttpRequest {
+        self.build_count_todos_filtered(TodoFilter::default())
+    }
+
+    /// Count only the todos matching `filter`, e.g. the pending ones.
+    pub fn build_count_todos_filtered(&self, filter: TodoFilter) -> HttpRequest {
+        let mut req = self.build_raw(HttpMethod::Get, "/todos/count", None);
+        req.query = filter.query();
+        req
+    }
+
+    pub fn build_get_todo(&self, id: Uuid) -> HttpRequest {
+        self.build_raw(HttpMethod::Get, &format!("/todos/{}", self.id_segment(id)), None)
+    }
+
+    /// Check whether `id` exists with a HEAD request, so no body is
+    /// transferred. Pair with `parse_todo_exists`.
+    pub fn build_todo_exists(&self, id: Uuid) -> HttpRequest {
+        self.build_raw(HttpMethod::Head, &format!("/todos/{}", self.id_segment(id)), None)
+    }
+
+    /// Like `build_get_todo`, but stamps a fresh `x-correlation-id` and
+    /// returns it so the caller can check the server's echo with
+    /// `HttpResponse::header` and catch mixed-up responses.
+    #[cfg(feature = "std")]
+    pub fn build_get_todo_traced(&self, id: Uuid) -> (HttpRequest, String) {
+        let correlation_id = Uuid::new_v4().to_string();
+        let mut req = self.build_get_todo(id);
+        req.headers.push(("x-correlation-id".to_string(), correlation_id.clone()));
+        (req, correlation_id)
+    }
+
+    pub fn build_create_todo(&self, input: &CreateTodo) -> Result<HttpRequest, ApiError> {
+        let mut req = self.create_request(input)?;
+        if let Some(HeaderFn(generate)) = &self.idempotency_key_generator {
+            let key = generate(&req);
+            req.headers.push(("idempotency-key".to_string(), key));
+        }
+        Ok(req)
+    }
+
+    /// Like `build_create_todo`, but stamps an `idempotency-key` header with
+    /// `key`, or a fresh UUID when `key` is `None`, and returns it for the
+    /// host to log. Re-sending the same request after a network failure
+    /// then cannot create a duplicate. Takes precedence over
+    /// `with_idempotency_key_generator`.
+    ///
+    /// Without the `std` feature there is no random source, and a `None`
+    /// key fails with `Validation`.
+    pub fn build_create_todo_idempotent(
+        &self,
+        input: &CreateTodo,
+        key: Option<&str>,
+    ) -> Result<(HttpRequest, String), ApiError> {
+        let key = match key {
+            Some(key) => key.to_string(),
+            #[cfg(feature = "std")]
+            None => Uuid::new_v4().to_string(),
+            #[cfg(not(feature = "std"))]
+            None => return Err(ApiError::Validation("an idempotency key is required without `std`".to_string())),
+        };
+        let mut req = self.create_request(input)?;
+        req.headers.push(("idempotency-key".to_string(), key.clone()));
+        Ok((req, key))
+    }
+
+    fn create_request(&self, input: &CreateTodo) -> Result<HttpRequest, ApiError> {
+        let body = serde_json::to_string(input).map_err(|e| ApiError::SerializationError(e.to_string()))?;
+        Ok(self.build_raw(HttpMethod::Post, "/todos", Some(body)))
+    }
+
+    /// Create all of `inputs` in one `POST /todos/batch`. Parse with
+    /// `parse_create_todos_batch`.
+    pub fn build_create_todos_batch(&self, inputs: &[CreateTodo]) -> Result<HttpRequest, ApiError> {
+        #[cfg(feature = "std")]
+        let body = {
+            let mut body = Vec::new();
+            CreateTodo::write_batch_json(inputs, &mut body)?;
+            String::from_utf8(body).map_err(|e| ApiError::SerializationError(e.to_string()))?
+        };
+        #[cfg(not(feature = "std"))]
+        let body = serde_json::to_string(inputs).map_err(|e| ApiError::SerializationError(e.to_string()))?;
+        Ok(self.build_raw(HttpMethod::Post, "/todos/batch", Some(body)))
+    }
+
+    /// Create a todo and ask for the refreshed list in the same response via
+    /// `Prefer: return=representation, embed=list`. Pair with
+    /// `parse_create_and_list`.
+    pub fn build_create_and_list(&self, input: &CreateTodo) -> Result<HttpRequest, ApiError> {
+        let mut req = self.build_create_todo(input)?;
+        let prefer = "return=representation, embed=list".to_string();
+        req.headers.push(("prefer".to_string(), prefer));
+        Ok(req)
+    }
+
+    pub fn build_update_todo(&self, id: Uuid, input: &UpdateTodo) -> Result<HttpRequest, ApiError> {
+        if self.reject_empty_updates {
+            input.clone().require_non_empty()?;
+        }
+        let path = format!("/todos/{}", self.id_segment(id));
+        if let Some(HeaderFn(sign)) = &self.request_signer {
+            let mut req = self.build_raw(HttpMethod::Put, &path, Some(input.to_canonical_json()?));
+            let signature = sign(&req);
+            req.headers.push(("signature".to_string(), signature));
+            return Ok(req);
+        }
+        let body = serde_json::to_string(input).map_err(|e| ApiError::SerializationError(e.to_string()))?;
+        Ok(self.build_raw(HttpMethod::Put, &path, Some(body)))
+    }
+
+    /// Update `id` only if it still matches `etag`, the `ETag` header from an
+    /// earlier read. A concurrent change makes `parse_update_todo` return
+    /// `ApiError::PreconditionFailed` instead of overwriting it.
+    pub fn build_update_todo_if_match(&self, id: Uuid, input: &UpdateTodo, etag: &str) -> Result<HttpRequest, ApiError> {
+        let mut req = self.build_update_todo(id, input)?;
+        req.headers.push(("if-match".to_string(), etag.to_string()));
+        Ok(req)
+    }
+
+    /// Partially update a todo with PATCH and JSON Merge Patch (RFC 7386)
+    /// semantics, for servers that do not accept partial PUTs. The body is
+    /// the same as for `build_update_todo`, signature included; only set
+    /// fields are sent.
+    pub fn build_patch_todo(&self, id: Uuid, input: &UpdateTodo) -> Result<HttpRequest, ApiError> {
+        if self.reject_empty_updates {
+            input.clone().require_non_empty()?;
+        }
+        let body = match &self.request_signer {
+            Some(_) => input.to_canonical_json()?,
+            None => serde_json::to_string(input).map_err(|e| ApiError::SerializationError(e.to_string()))?,
+        };
+        let mut req = self.build_raw(HttpMethod::Patch, &format!("/todos/{}", self.id_segment(id)), Some(body));
+        for (name, value) in &mut req.headers {
+            if name == "content-type" {
+                *value = "application/merge-patch+json".to_string();
+            }
+        }
+        if let Some(HeaderFn(sign)) = &self.request_signer {
+            let signature = sign(&req);
+            req.headers.push(("signature".to_string(), signature));
+        }
+        Ok(req)
+    }
+
+    pub fn build_delete_todo(&self, id: Uuid) -> HttpRequest {
+        self.build_raw(HttpMethod::Delete, &format!("/todos/{}", self.id_segment(id)), None)
+    }
+
+    /// Delete `id` and ask the server to echo the removed todo back via
+    /// `Prefer: return=representation` (RFC 7240). Pair with
+    /// `parse_delete_todo_returning`.
+    pub fn build_delete_todo_returning(&self, id: Uuid) -> HttpRequest {
+        let mut req = self.build_delete_todo(id);
+        req.headers.push(("prefer".to_string(), "return=representation".to_string()));
+        req
+    }
+
+    /// Delete `id` only if it still matches `etag`. See
+    /// `build_update_todo_if_match`.
+    pub fn build_delete_todo_if_match(&self, id: Uuid, etag: &str) -> HttpRequest {
+        let mut req = self.build_delete_todo(id);
+        req.headers.push(("if-match".to_string(), etag.to_string()));
+        req
+    }
+
+    /// Delete every todo in one call. Pair with `parse_delete_all`.
+    pub fn build_delete_all(&self) -> HttpRequest {
+        self.build_raw(HttpMethod::Delete, "/todos", None)
+    }
+
+    pub fn build_get_history(&self, id: Uuid) -> HttpRequest {
+        self.build_raw(HttpMethod::Get, &format!("/todos/{}/history", self.id_segment(id)), None)
+    }
+
+    pub fn build_capabilities(&self) -> HttpRequest {
+        self.build_raw(HttpMethod::Get, "/capabilities", None)
+    }
+
+    /// Build an arbitrary request against the base URL.
+    ///
+    /// Escape hatch for endpoints the typed builders do not cover, and the
+    /// single place every typed builder goes through, so client-wide headers
+    /// are applied uniformly. `path` is appended to the base URL as-is and
+    /// must start with `/`.
+    pub fn build_raw(&self, method: HttpMethod, path: &str, body: Option<String>) -> HttpRequest {
+        let mut headers = Vec::new();
+        if let Some(body) = &body {
+            headers.push(("content-type".to_string(), "application/json".to_string()));
+            if self.content_md5 {
+                let digest = md5::compute(body.as_bytes());
+                headers.push(("content-md5".to_string(), BASE64_STANDARD.encode(digest.0)));
+            }
+        }
+        if let Some(traceparent) = &self.traceparent {
+            headers.push(("traceparent".to_string(), traceparent.clone()));
+        }
+        if let Some(auth) = &self.auth {
+            headers.push(auth.header());
+        }
+        let mut req = HttpRequest {
+            method,
+            path: self.url(path),
+            query: Vec::new(),
+            headers: self.default_headers.clone(),
+            body,
+        };
+        for (name, value) in &headers {
+            req.set_header(name, value);
+        }
+        req
+    }
+
+    pub fn parse_list_todos(&self, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
+        check_response(&response, 200)?;
+        self.decode_todos(&response.body)
+    }
+
+    /// `parse_list_todos` plus the response's rate-limit headers, so hosts
+    /// can throttle before the server starts answering 429.
+    pub fn parse_list_todos_with_meta(
+        &self,
+        response: HttpResponse,
+    ) -> Result<(Vec<Todo>, Option<RateLimitInfo>), ApiError> {
+        let rate_limit = response.rate_limit();
+        Ok((self.parse_list_todos(response)?, rate_limit))
+    }
+
+    /// Parse a subset-fetch response and reorder it to match `requested`.
+    ///
+    /// Servers return the matching todos as an unordered set; this maps them
+    /// back onto the caller's id order and yields `None` for ids the server
+    /// omitted. Todos the caller did not request are dropped.
+    pub fn parse_get_todos_by_ids_ordered(
+        &self,
+        requested: &[Uuid],
+        response: HttpResponse,
+    ) -> Result<Vec<Option<Todo>>, ApiError> {
+        let todos = self.parse_list_todos(response)?;
+        let by_id: BTreeMap<Uuid, Todo> = todos.into_iter().map(|t| (t.id, t)).collect();
+        Ok(requested.iter().map(|id| by_id.get(id).cloned()).collect())
+    }
+
+    /// Parse a limit/offset page.
+    ///
+    /// Accepts either a bare array with `X-Total-Count` and `Link` headers or
+    /// a `{"items": [...], "total": N, "next": "...", "prev": "..."}` wrapper.
+    /// Wrapper fields win over headers when both are present.
+    pub fn parse_list_todos_page(&self, response: HttpResponse) -> Result<Page<Todo>, ApiError> {
+        check_response(&response, 200)?;
+        let total = response.header("x-total-count").and_then(|v| v.trim().parse().ok());
+        let links = response.header("link").map(parse_link_header).unwrap_or_default();
+        let header_link = |rel: &str| links.iter().find(|(r, _)| r == rel).map(|(_, url)| url.clone());
+
+        let body: serde_json::Value =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        if body.is_array() {
+            return Ok(Page {
+                items: self.decode_todos(&response.body)?,
+                total,
+                next: header_link("next"),
+                prev: header_link("prev"),
+            });
+        }
+        let wrapper: PageWrapper =
+            serde_json::from_value(body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        Ok(Page {
+            items: self.decode_todos(&wrapper.items.to_string())?,
+            total: wrapper.total.or(total),
+            next: wrapper.next.or_else(|| header_link("next")),
+            prev: wrapper.prev.or_else(|| header_link("prev")),
+        })
+    }
+
+    /// Parse one page of a cursor listing into its todos and the cursor for
+    /// the next page, which is `None` once the listing is exhausted.
+    pub fn parse_list_todos_cursor(&self, response: HttpResponse) -> Result<(Vec<Todo>, Option<String>), ApiError> {
+        check_response(&response, 200)?;
+        let page: CursorPage =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        Ok((self.decode_todos(&page.todos.to_string())?, page.next_cursor))
+    }
+
+    /// Parse a list and require every item to carry `schema_version ==
+    /// expected`, to catch servers mid-rollout serving mixed versions.
+    ///
+    /// Fails with `SchemaMismatch` on the first item that differs, including
+    /// items with no version at all.
+    pub fn parse_list_todos_versioned(&self, expected: u32, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
+        let todos = self.parse_list_todos(response)?;
+        if let Some(todo) = todos.iter().find(|t| t.schema_version != Some(expected)) {
+            return Err(ApiError::SchemaMismatch {
+                expected,
+                found: todo.schema_version,
+            });
+        }
+        Ok(todos)
+    }
+
+    /// Parse a list nested under a top-level key, e.g. `{"todos": [...]}`.
+    ///
+    /// Sibling keys are ignored. The extracted array goes through the same
+    /// decoding as `parse_list_todos`, so the configured `IdFormat` applies.
+    pub fn parse_list_todos_under(&self, key: &str, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
+        check_response(&response, 200)?;
+        let mut envelope: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        match envelope.remove(key) {
+            Some(list @ serde_json::Value::Array(_)) => self.decode_todos(&list.to_string()),
+            Some(_) => Err(ApiError::DeserializationError(format!("key `{key}` is not an array"))),
+            None => Err(ApiError::DeserializationError(format!("missing key `{key}`"))),
+        }
+    }
+
+    /// Parse a count response from either count builder.
+    pub fn parse_count_todos(&self, response: HttpResponse) -> Result<u64, ApiError> {
+        check_response(&response, 200)?;
+        serde_json::from_str::<TodoCount>(&response.body)
+            .map(|c| c.count)
+            .map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    pub fn parse_get_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
+        check_response(&response, 200)?;
+        self.decode_todo(&response.body)
+    }
+
+    /// Parse search hits, best match first as ranked by the server.
+    pub fn parse_search_todos(&self, response: HttpResponse) -> Result<Vec<SearchHit>, ApiError> {
+        check_response(&response, 200)?;
+        let hits = match self.id_format {
+            IdFormat::Uuid => serde_json::from_str(&response.body),
+            IdFormat::Integer => serde_json::from_str::<Vec<IntegerIdSearchHit>>(&response.body)
+                .map(|hits| hits.into_iter().map(SearchHit::from).collect()),
+        };
+        hits.map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// `true` for 200, `false` for 404; any other status is an error.
+    pub fn parse_todo_exists(&self, response: HttpResponse) -> Result<bool, ApiError> {
+        if response.status == 404 {
+            return Ok(false);
+        }
+        check_response(&response, 200)?;
+        Ok(true)
+    }
+
+    /// `parse_get_todo` plus the response's rate-limit headers.
+    pub fn parse_get_todo_with_meta(&self, response: HttpResponse) -> Result<(Todo, Option<RateLimitInfo>), ApiError> {
+        let rate_limit = response.rate_limit();
+        Ok((self.parse_get_todo(response)?, rate_limit))
+    }
+
+    /// Parse a get-todo response in JSON:API format.
+    ///
+    /// Expects `{"data": {"type": "todos", "id": "...", "attributes":
+    /// {"title": ..., "completed": ...}}}`. The id comes from the resource
+    /// object and the remaining fields from `attributes`.
+    pub fn parse_get_todo_jsonapi(&self, response: HttpResponse) -> Result<Todo, ApiError> {
+        check_response(&response, 200)?;
+        let doc: JsonApiDocument =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        let resource = doc.data;
+        if resource.kind != "todos" {
+            return Err(ApiError::DeserializationError(format!(
+                "JSON:API resource type is {:?}, expected \"todos\"",
+                resource.kind
+            )));
+        }
+        let Some(attributes) = resource.attributes else {
+            return Err(ApiError::DeserializationError(
+                "JSON:API resource has no attributes object".to_string(),
+            ));
+        };
+        Ok(Todo {
+            id: self.decode_id(&resource.id)?,
+            title: attributes.title,
+            completed: attributes.completed,
+            schema_version: None,
+        })
+    }
+
+    /// Parse a get-todo response for a known `id`.
+    ///
+    /// Behaves like `parse_get_todo`, except a 404 becomes
+    /// `ApiError::NotFoundId(id)` so the error names the missing todo.
+    pub fn parse_get_todo_expecting(&self, id: Uuid, response: HttpResponse) -> Result<Todo, ApiError> {
+        match self.parse_get_todo(response) {
+            Err(ApiError::NotFound) => Err(ApiError::NotFoundId(id)),
+            other => other,
+        }
+    }
+
+    pub fn parse_create_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
+        check_response(&response, 201)?;
+        self.decode_todo(&response.body)
+    }
+
+    /// Parse a batch create response: the created todos, in request order.
+    pub fn parse_create_todos_batch(&self, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
+        check_response(&response, 201)?;
+        self.decode_todos(&response.body)
+    }
+
+    /// Parse a `{"created": {...}, "list": [...]}` response into the new
+    /// todo and the full list after the insert.
+    pub fn parse_create_and_list(&self, response: HttpResponse) -> Result<(Todo, Vec<Todo>), ApiError> {
+        check_response(&response, 201)?;
+        let body: CreatedAndList =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        Ok((self.decode_todo(&body.created.to_string())?, self.decode_todos(&body.list.to_string())?))
+    }
+
+    /// Parse a create-todo response that may carry soft warnings.
+    ///
+    /// Warnings come from a `warnings` string array next to the todo fields
+    /// in the body, and from any `Warning` response headers. For headers in
+    /// the RFC 7234 `199 - "text"` form only the quoted text is kept. Body
+    /// warnings come first; the vec is empty when the server sent none.
+    pub fn parse_create_todo_with_warnings(&self, response: HttpResponse) -> Result<(Todo, Vec<String>), ApiError> {
+        check_response(&response, 201)?;
+        let todo = self.decode_todo(&response.body)?;
+        let body: WarningsField =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        let mut warnings = body.warnings;
+        for (name, value) in &response.headers {
+            if !name.eq_ignore_ascii_case("warning") {
+                continue;
+            }
+            let text = match (value.find('"'), value.rfind('"')) {
+                (Some(start), Some(end)) if end > start => &value[start + 1..end],
+                _ => value.as_str(),
+            };
+            warnings.push(text.to_string());
+        }
+        Ok((todo, warnings))
+    }
+
+    /// Parse a create-todo response, remembering the request that caused it.
+    ///
+    /// When `with_error_includes_request(true)` is set, an `HttpError` carries
+    /// `sent.body` in `request_body` so failed writes can be reproduced.
+    pub fn parse_create_todo_with_request(
+        &self,
+        sent: &HttpRequest,
+        response: HttpResponse,
+    ) -> Result<Todo, ApiError> {
+        let result = self.parse_create_todo(response);
+        if !self.error_includes_request {
+            return result;
+        }
+        result.map_err(|e| match e {
+            ApiError::HttpError { status, body, .. } => ApiError::HttpError {
+                status,
+                body,
+                request_body: sent.body.clone(),
+            },
+            other => other,
+        })
+    }
+
+    pub fn parse_update_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
+        check_response(&response, 200)?;
+        self.decode_todo(&response.body)
+    }
+
+    pub fn parse_patch_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
+        check_response(&response, 200)?;
+        self.decode_todo(&response.body)
+    }
+
+    pub fn parse_delete_todo(&self, response: HttpResponse) -> Result<(), ApiError> {
+        check_response(&response, 204)?;
+        Ok(())
+    }
+
+    /// Parse a delete with "ensure absent" semantics: 404 counts as success
+    /// because the end state is the same. Other statuses still fail.
+    pub fn parse_delete_todo_idempotent(&self, response: HttpResponse) -> Result<(), ApiError> {
+        if response.status == 404 {
+            return Ok(());
+        }
+        self.parse_delete_todo(response)
+    }
+
+    /// Parse a delete-all response, returning how many todos were removed.
+    pub fn parse_delete_all(&self, response: HttpResponse) -> Result<u64, ApiError> {
+        check_response(&response, 200)?;
+        serde_json::from_str::<DeletedCount>(&response.body)
+            .map(|d| d.deleted)
+            .map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse a delete response that may carry the deleted todo.
+    ///
+    /// A 200 with a todo body yields `Some(todo)`; a bodyless 204 from a
+    /// server that ignores the preference yields `None`.
+    pub fn parse_delete_todo_returning(&self, response: HttpResponse) -> Result<Option<Todo>, ApiError> {
+        if response.status == 204 {
+            return Ok(None);
+        }
+        check_response(&response, 200)?;
+        self.decode_todo(&response.body).map(Some)
+    }
+
+    /// Parse a revision history response, oldest revision first.
+    pub fn parse_history(&self, response: HttpResponse) -> Result<Vec<TodoRevision>, ApiError> {
+        check_response(&response, 200)?;
+        let revisions = match self.id_format {
+            IdFormat::Uuid => serde_json::from_str(&response.body),
+            IdFormat::Integer => serde_json::from_str::<Vec<IntegerIdRevision>>(&response.body)
+                .map(|revisions| revisions.into_iter().map(TodoRevision::from).collect()),
+        };
+        revisions.map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    pub fn parse_capabilities(&self, response: HttpResponse) -> Result<Capabilities, ApiError> {
+        check_response(&response, 200)?;
+        serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse a batch-create response where each element carries its own status.
+    ///
+    /// The envelope is `[{"status": 201, "body": {...}}, {"status": 409,
+    /// "body": "..."}]` behind a 200. Each element goes through the same
+    /// status mapping as single-item parses, so a 409 element becomes
+    /// `Conflict` and a 201 element becomes the parsed `Todo`. Elements have
+    /// no headers, so a 429 element's `RateLimited` has no `retry_after`.
+    /// The outer `Result` only fails when the envelope itself is unusable.
+    pub fn parse_batch_statuses(&self, response: HttpResponse) -> Result<Vec<Result<Todo, ApiError>>, ApiError> {
+        check_response(&response, 200)?;
+        let items: Vec<BatchItem> =
+            serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        let results = items
+            .into_iter()
+            .map(|item| {
+                let body = match item.body {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                check_status(item.status, &body, 201)?;
+                self.decode_todo(&body)
+            })
+            .collect();
+        Ok(results)
+    }
+
+    /// Parse a list response without copying titles out of `body`.
+    ///
+    /// For read-heavy paths that only inspect todos briefly. Always expects
+    /// UUID ids regardless of the configured `IdFormat`. Takes no headers,
+    /// so a 429 maps to `RateLimited` without `retry_after`.
+    pub fn parse_todos_borrowed<'a>(&self, status: u16, body: &'a str) -> Result<Vec<TodoRef<'a>>, ApiError> {
+        check_status(status, body, 200)?;
+        serde_json::from_str(body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse at most `max_items` todos from a list response.
+    ///
+    /// Elements past the cap are skipped with `IgnoredAny`, so they are
+    /// syntax-checked but never materialized into `Todo` values. The returned
+    /// `bool` is true when the array held more than `max_items` elements.
+    /// As with `parse_todos_borrowed`, a 429 carries no `retry_after`.
+    pub fn parse_list_todos_bounded(
+        &self,
+        status: u16,
+        body: &str,
+        max_items: usize,
+    ) -> Result<(Vec<Todo>, bool), ApiError> {
+        check_status(status, body, 200)?;
+        let mut de = serde_json::Deserializer::from_str(body);
+        let result = de
+            .deserialize_seq(BoundedTodos {
+                max_items,
+                id_format: self.id_format,
+            })
+            .and_then(|r| de.end().map(|()| r));
+        result.map_err(|e| ApiError::DeserializationError(e.to_string()))
+    }
+}
+
+/// One element of a batch response envelope.
+#[derive(Deserialize)]
+struct BatchItem {
+    status: u16,
+    body: serde_json::Value,
+}
+
+/// Body of a create-and-list response. Fields stay as raw JSON so they can
+/// go through the `IdFormat`-aware decoders.
+#[derive(Deserialize)]
+struct CreatedAndList {
+    created: serde_json::Value,
+    list: serde_json::Value,
+}
+
+/// Wrapper-body form of a limit/offset page.
+#[derive(Deserialize)]
+struct PageWrapper {
+    items: serde_json::Value,
+    total: Option<u64>,
+    next: Option<String>,
+    prev: Option<String>,
+}
+
+/// One page of a cursor listing.
+#[derive(Deserialize)]
+struct CursorPage {
+    todos: serde_json::Value,
+    next_cursor: Option<String>,
+}
+
+/// Split an RFC 8288 `Link` header into `(rel, url)` pairs.
+fn parse_link_header(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|link| {
+            let mut parts = link.split(';');
+            let url = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+            let rel = parts.find_map(|p| p.trim().strip_prefix("rel="))?.trim_matches('"');
+            Some((rel.to_string(), url.to_string()))
+        })
+        .collect()
+}
+
+/// Body of a delete-all response: `{"deleted": N}`.
+#[derive(Deserialize)]
+struct DeletedCount {
+    deleted: u64,
+}
+
+/// Wire shape of a todo from a server using `IdFormat::Integer`.
+#[derive(Deserialize)]
+struct IntegerIdTodo {
+    id: u64,
+    title: String,
+    #[serde(deserialize_with = "lenient_bool")]
+    completed: bool,
+    #[serde(default)]
+    schema_version: Option<u32>,
+}
+
+impl From<IntegerIdTodo> for Todo {
+    fn from(t: IntegerIdTodo) -> Self {
+        Todo {
+            id: Uuid::from_u128(u128::from(t.id)),
+            title: t.title,
+            completed: t.completed,
+            schema_version: t.schema_version,
+        }
+    }
+}
+
+/// Wire shape of a search hit from a server using `IdFormat::Integer`.
+#[derive(Deserialize)]
+struct IntegerIdSearchHit {
+    #[serde(flatten)]
+    todo: IntegerIdTodo,
+    score: f64,
+    #[serde(default)]
+    highlight: Option<String>,
+}
+
+impl From<IntegerIdSearchHit> for SearchHit {
+    fn from(hit: IntegerIdSearchHit) -> Self {
+        SearchHit {
+            todo: Todo::from(hit.todo),
+            score: hit.score,
+            highlight: hit.highlight,
         }
     }
+}
+
+/// Wire shape of a revision from a server using `IdFormat::Integer`.
+#[derive(Deserialize)]
+struct IntegerIdRevision {
+    id: u64,
+    title: String,
+    #[serde(deserialize_with = "lenient_bool")]
+    completed: bool,
+    revision: u64,
+    changed_at: u64,
+}
+
+impl From<IntegerIdRevision> for TodoRevision {
+    fn from(r: IntegerIdRevision) -> Self {
+        TodoRevision {
+            id: Uuid::from_u128(u128::from(r.id)),
+            title: r.title,
+            completed: r.completed,
+            revision: r.revision,
+            changed_at: r.changed_at,
+        }
+    }
+}
+
+/// The optional `warnings` array that rides alongside a todo body.
+#[derive(Deserialize)]
+struct WarningsField {
+    #[serde(default)]
+    warnings: Vec<String>,
+}
+
+/// Top-level JSON:API document carrying a single resource.
+#[derive(Deserialize)]
+struct JsonApiDocument {
+    data: JsonApiResource,
+}
+
+#[derive(Deserialize)]
+struct JsonApiResource {
+    #[serde(rename = "type")]
+    kind: String,
+    id: String,
+    attributes: Option<JsonApiAttributes>,
+}
+
+#[derive(Deserialize)]
+struct JsonApiAttributes {
+    title: String,
+    #[serde(deserialize_with = "lenient_bool")]
+    completed: bool,
+}
+
+/// Sequence visitor backing `parse_list_todos_bounded`.
+struct BoundedTodos {
+    max_items: usize,
+    id_format: IdFormat,
+}
+
+impl<'de> Visitor<'de> for BoundedTodos {
+    type Value = (Vec<Todo>, bool);
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON array of todos")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let capacity = self.max_items.min(seq.size_hint().unwrap_or(0));
+        let mut todos = Vec::with_capacity(capacity);
+        while todos.len() < self.max_items {
+            let todo = match self.id_format {
+                IdFormat::Uuid => seq.next_element::<Todo>()?,
+                IdFormat::Integer => seq.next_element::<IntegerIdTodo>()?.map(Todo::from),
+            };
+            match todo {
+                Some(todo) => todos.push(todo),
+                None => return Ok((todos, false)),
+            }
+        }
+        let mut truncated = false;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            truncated = true;
+        }
+        Ok((todos, truncated))
+    }
+}
+
+/// True if `name` is a non-empty RFC 9110 token, i.e. usable as a header
+/// name.
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Check the `version-traceid-parentid-flags` layout of a version-00
+/// `traceparent` value.
+fn is_valid_traceparent(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [version, trace_id, parent_id, flags] = parts.as_slice() else {
+        return false;
+    };
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len && s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    *version == "00"
+        && is_hex(trace_id, 32)
+        && is_hex(parent_id, 16)
+        && is_hex(flags, 2)
+        && trace_id.bytes().any(|b| b != b'0')
+        && parent_id.bytes().any(|b| b != b'0')
+}
+
+/// `check_status` for a full response, so header-derived details such as
+/// `Retry-After` make it into the error.
+fn check_response(response: &HttpResponse, expected: u16) -> Result<(), ApiError> {
+    check_status(response.status, &response.body, expected).map_err(|err| match err {
+        ApiError::RateLimited { .. } => ApiError::RateLimited {
+            retry_after: response.retry_after(),
+        },
+        other => other,
+    })
+}
+
+/// Map non-success status codes to the appropriate `ApiError` variant.
+///
+/// Only for callers that have no headers to offer (the `status`/`body`
+/// parses and batch items); a `RateLimited` from here never carries
+/// `retry_after`. Everything else goes through `check_response`.
+fn check_status(status: u16, body: &str, expected: u16) -> Result<(), ApiError> {
+    if status == expected {
+        return Ok(());
+    }
+    if status == 401 {
+        return Err(ApiError::Unauthorized);
+    }
+    if status == 404 {
+        return Err(ApiError::NotFound);
+    }
+    if status == 409 {
+        return Err(ApiError::Conflict(body.to_string()));
+    }
+    if status == 412 {
+        return Err(ApiError::PreconditionFailed);
+    }
+    if status == 429 {
+        return Err(ApiError::RateLimited { retry_after: None });
+    }
+    Err(ApiError::HttpError {
+        status,
+        body: body.to_string(),
+        request_body: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    fn client() -> TodoClient {
+        TodoClient::new("http://localhost:3000")
+    }
+
+    #[test]
+    fn build_list_todos_produces_correct_request() {
+        let req = client().build_list_todos();
+        assert_eq!(req.method, HttpMethod::Get);
+        assert_eq!(req.path, "http://localhost:3000/todos");
+        assert!(req.body.is_none());
+        assert!(req.headers.is_empty());
+    }
+
+    #[test]
+    fn build_get_todo_produces_correct_request() {
+        let id = Uuid::nil();
+        let req = client().build_get_todo(id);
+        assert_eq!(req.method, HttpMethod::Get);
+        assert_eq!(
+            req.path,
+            "http://localhost:3000/todos/00000000-0000-0000-0000-000000000000"
+        );
+        assert!(req.body.is_none());
+    }
+
+    #[test]
+    fn build_create_todo_produces_correct_request() {
+        let input = CreateTodo {
+            title: "Buy milk".to_string(),
+            completed: false,
+        };
+        let req = client().build_create_todo(&input).unwrap();
+        assert_eq!(req.method, HttpMethod::Post);
+        assert_eq!(req.path, "http://localhost:3000/todos");
+        assert_eq!(
+            req.headers,
+            vec![("content-type".to_string(), "application/json".to_string())]
+        );
+        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["title"], "Buy milk");
+        assert_eq!(body["completed"], false);
+    }
+
+    #[test]
+    fn idempotency_key_generator_hashes_payload() {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let client = client().with_idempotency_key_generator(Box::new(|req: &HttpRequest| {
+            let mut hasher = DefaultHasher::new();
+            req.body.hash(&mut hasher);
+            format!("{:016x}", hasher.finish())
+        }));
+        let key_for = |title: &str| {
+            let input = CreateTodo {
+                title: title.to_string(),
+                completed: false,
+            };
+            let req = client.build_create_todo(&input).unwrap();
+            req.headers
+                .into_iter()
+                .find(|(k, _)| k == "idempotency-key")
+                .map(|(_, v)| v)
+                .unwrap()
+        };
+        assert_eq!(key_for("Same"), key_for("Same"));
+        assert_ne!(key_for("Same"), key_for("Different"));
+        assert!(client.build_list_todos().headers.is_empty());
+    }
+
+    #[test]
+    fn default_headers_apply_to_every_request() {
+        let client = TodoClient::builder("http://localhost:3000")
+            .default_header("X-Tenant", "acme")
+            .default_header("content-type", "text/plain")
+            .build();
+        let list = client.build_list_todos();
+        assert_eq!(list.header("x-tenant"), Some("acme"));
+
+        let mut create = client.build_create_todo(&CreateTodo::new("Scoped", false)).unwrap();
+        assert_eq!(create.header("content-type"), Some("application/json"));
+        assert_eq!(create.headers.iter().filter(|(k, _)| k == "content-type").count(), 1);
+
+        create.set_header("x-tenant", "globex");
+        assert_eq!(create.header("X-Tenant"), Some("globex"));
+    }
+
+    #[test]
+    fn bearer_auth_on_every_request_and_401_is_unauthorized() {
+        let client = client().with_auth(Auth::Bearer("t0ken".to_string())).unwrap();
+        assert_eq!(client.build_list_todos().header("authorization"), Some("Bearer t0ken"));
+        assert_eq!(client.build_delete_todo(Uuid::nil()).header("authorization"), Some("Bearer t0ken"));
+
+        let response = HttpResponse {
+            status: 401,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        assert!(matches!(client.parse_list_todos(response), Err(ApiError::Unauthorized)));
+    }
+
+    #[test]
+    fn api_key_auth_rejects_invalid_header_names() {
+        let api_key = |name: &str| Auth::ApiKey {
+            header_name: name.to_string(),
+            value: "k".to_string(),
+        };
+        let client = client().with_auth(api_key("X-Api-Key")).unwrap();
+        assert_eq!(client.build_list_todos().header("x-api-key"), Some("k"));
+        for bad in ["", "X Api Key", "X-Api-Key:"] {
+            assert!(matches!(client.clone().with_auth(api_key(bad)), Err(ApiError::Validation(_))), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn list_with_meta_returns_rate_limit() {
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("X-RateLimit-Remaining".to_string(), "4".to_string())],
+            body: "[]".to_string(),
+        };
+        let (todos, rate_limit) = client().parse_list_todos_with_meta(response).unwrap();
+        assert!(todos.is_empty());
+        assert_eq!(rate_limit.unwrap().remaining, Some(4));
+    }
+
+    #[test]
+    fn rate_limited_carries_retry_after() {
+        let response = HttpResponse {
+            status: 429,
+            headers: vec![("Retry-After".to_string(), "3".to_string())],
+            body: String::new(),
+        };
+        let err = client().parse_get_todo(response).unwrap_err();
+        assert!(matches!(err, ApiError::RateLimited { retry_after: Some(d) } if d.as_secs() == 3));
+    }
+
+    #[test]
+    fn todo_exists_uses_head_and_maps_404_to_false() {
+        let req = client().build_todo_exists(Uuid::nil());
+        assert_eq!(req.method, HttpMethod::Head);
+        assert!(req.body.is_none());
+
+        let response = |status| HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        assert!(client().parse_todo_exists(response(200)).unwrap());
+        assert!(!client().parse_todo_exists(response(404)).unwrap());
+        assert!(matches!(client().parse_todo_exists(response(401)), Err(ApiError::Unauthorized)));
+    }
+
+    #[test]
+    fn search_encodes_query_and_parses_hits() {
+        let opts = SearchOptions {
+            limit: Some(5),
+            highlight: true,
+        };
+        let req = client().build_search_todos("milk & eggs", opts);
+        assert_eq!(
+            req.url(),
+            "http://localhost:3000/todos/search?q=milk%20%26%20eggs&limit=5&highlight=true"
+        );
+
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[
+                {"id":"00000000-0000-0000-0000-000000000001","title":"Buy milk","completed":false,"score":0.5,"highlight":"Buy <em>milk</em>"},
+                {"id":"00000000-0000-0000-0000-000000000002","title":"Milkshake","completed":1,"score":0.25}
+            ]"#
+            .to_string(),
+        };
+        let hits = client().parse_search_todos(response).unwrap();
+        assert_eq!(hits[0].todo.title, "Buy milk");
+        assert_eq!(hits[0].highlight.as_deref(), Some("Buy <em>milk</em>"));
+        assert!(hits[1].todo.completed);
+        assert_eq!(hits[1].highlight, None);
+    }
+
+    #[test]
+    fn idempotent_create_uses_supplied_or_fresh_key() {
+        let input = CreateTodo::new("Once", false);
+        let (req, key) = client().build_create_todo_idempotent(&input, Some("abc-123")).unwrap();
+        assert_eq!(key, "abc-123");
+        assert!(req.headers.contains(&("idempotency-key".to_string(), key)));
+
+        let client = client().with_idempotency_key_generator(Box::new(|_: &HttpRequest| "generated".to_string()));
+        let result = client.build_create_todo_idempotent(&input, None);
+        #[cfg(not(feature = "std"))]
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        #[cfg(feature = "std")]
+        {
+            let (req, key) = result.unwrap();
+            assert!(Uuid::parse_str(&key).is_ok());
+            let keys: Vec<_> = req.headers.iter().filter(|(k, _)| k == "idempotency-key").collect();
+            assert_eq!(keys, [&("idempotency-key".to_string(), key)]);
+        }
+    }
+
+    #[test]
+    fn content_md5_matches_known_digest() {
+        let client = client().with_content_md5(true);
+        let req = client.build_create_todo(&CreateTodo::new("Buy milk", false)).unwrap();
+        assert_eq!(req.body.as_deref(), Some(r#"{"title":"Buy milk","completed":false}"#));
+        assert!(req
+            .headers
+            .contains(&("content-md5".to_string(), "KsLWpwJ2kbLj+0uzPZVbUQ==".to_string())));
+        assert!(client.build_list_todos().headers.is_empty());
+    }
+
+    #[test]
+    fn build_update_todo_produces_correct_request() {
+        let id = Uuid::nil();
+        let input = UpdateTodo {
+            title: Some("Updated".to_string()),
+            completed: None,
+        };
+        let req = client().build_update_todo(id, &input).unwrap();
+        assert_eq!(req.method, HttpMethod::Put);
+        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["title"], "Updated");
+        assert!(body.get("completed").is_none());
+    }
+
+    #[test]
+    fn build_update_todo_empty_rejected_when_enabled() {
+        let empty = UpdateTodo {
+            title: None,
+            completed: None,
+        };
+        assert!(client().build_update_todo(Uuid::nil(), &empty).is_ok());
+        let err = client()
+            .reject_empty_updates(true)
+            .build_update_todo(Uuid::nil(), &empty)
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Validation(_)));
+    }
+
+    #[test]
+    fn traceparent_applied_to_all_requests() {
+        let tp = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let client = client().with_traceparent(tp).unwrap();
+        let expected = ("traceparent".to_string(), tp.to_string());
+        assert_eq!(client.build_list_todos().headers, vec![expected.clone()]);
+        let input = CreateTodo {
+            title: "Traced".to_string(),
+            completed: false,
+        };
+        let req = client.build_create_todo(&input).unwrap();
+        assert!(req.headers.contains(&expected));
+    }
+
+    #[test]
+    fn traceparent_malformed_rejected() {
+        let bad = [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+        ];
+        for tp in bad {
+            let err = client().with_traceparent(tp).unwrap_err();
+            assert!(matches!(err, ApiError::Validation(_)), "{tp}");
+        }
+    }
+
+    #[test]
+    fn build_delete_todo_produces_correct_request() {
+        let id = Uuid::nil();
+        let req = client().build_delete_todo(id);
+        assert_eq!(req.method, HttpMethod::Delete);
+        assert!(req.body.is_none());
+    }
+
+    #[test]
+    fn build_raw_options_and_trace() {
+        let req = client().build_raw(HttpMethod::Options, "/todos", None);
+        assert_eq!(req.method.as_str(), "OPTIONS");
+        assert_eq!(req.path, "http://localhost:3000/todos");
+        assert!(req.headers.is_empty());
+        let req = client().build_raw(HttpMethod::Trace, "/todos", None);
+        assert_eq!(req.method.as_str(), "TRACE");
+        assert!(req.body.is_none());
+    }
+
+    #[test]
+    fn build_get_history_produces_correct_request() {
+        let req = client().build_get_history(Uuid::nil());
+        assert_eq!(req.method, HttpMethod::Get);
+        assert_eq!(
+            req.path,
+            "http://localhost:3000/todos/00000000-0000-0000-0000-000000000000/history"
+        );
+    }
+
+    #[test]
+    fn capabilities_roundtrip() {
+        let req = client().build_capabilities();
+        assert_eq!(req.method, HttpMethod::Get);
+        assert_eq!(req.path, "http://localhost:3000/capabilities");
+
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"schema_version":1,"supported_fields":["id","title","completed"]}"#.to_string(),
+        };
+        let caps = client().parse_capabilities(response).unwrap();
+        assert_eq!(caps.schema_version, 1);
+        assert!(!caps.supported_fields.iter().any(|f| f == "priority"));
+
+        let input = CreateTodo {
+            title: "Ship".to_string(),
+            completed: false,
+        };
+        let req = caps.restrict_to_fields(client().build_create_todo(&input).unwrap()).unwrap();
+        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["title"], "Ship");
+    }
+
+    #[test]
+    fn parse_list_todos_success() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"Test","completed":false}]"#.to_string(),
+        };
+        let todos = client().parse_list_todos(response).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title, "Test");
+    }
+
+    #[test]
+    fn parse_get_todos_by_ids_ordered_reorders_and_fills_gaps() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[
+                {"id":"00000000-0000-0000-0000-000000000003","title":"C","completed":false},
+                {"id":"00000000-0000-0000-0000-000000000001","title":"A","completed":false}
+            ]"#
+            .to_string(),
+        };
+        let requested = [Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3)];
+        let todos = client().parse_get_todos_by_ids_ordered(&requested, response).unwrap();
+        let titles: Vec<Option<&str>> = todos.iter().map(|t| t.as_ref().map(|t| t.title.as_str())).collect();
+        assert_eq!(titles, vec![Some("A"), None, Some("C")]);
+    }
+
+    #[test]
+    fn parse_get_todos_by_ids_ordered_repeats_duplicated_ids() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"A","completed":false}]"#.to_string(),
+        };
+        let requested = [Uuid::from_u128(1), Uuid::from_u128(1)];
+        let todos = client().parse_get_todos_by_ids_ordered(&requested, response).unwrap();
+        let titles: Vec<Option<&str>> = todos.iter().map(|t| t.as_ref().map(|t| t.title.as_str())).collect();
+        assert_eq!(titles, vec![Some("A"), Some("A")]);
+    }
+
+    #[test]
+    fn parse_get_todo_not_found() {
+        let response = HttpResponse {
+            status: 404,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        let err = client().parse_get_todo(response).unwrap_err();
+        assert!(matches!(err, ApiError::NotFound));
+    }
+
+    #[test]
+    fn parse_get_todo_expecting_carries_id() {
+        let id = Uuid::from_u128(42);
+        let response = HttpResponse {
+            status: 404,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        let err = client().parse_get_todo_expecting(id, response).unwrap_err();
+        assert!(matches!(err, ApiError::NotFoundId(got) if got == id));
+        assert!(err.to_string().contains(&id.to_string()));
+    }
+
+    #[test]
+    fn parse_get_todo_jsonapi_success() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"data":{"type":"todos","id":"00000000-0000-0000-0000-000000000007",
+                "attributes":{"title":"From JSON:API","completed":true}}}"#
+                .to_string(),
+        };
+        let todo = client().parse_get_todo_jsonapi(response).unwrap();
+        assert_eq!(
+            todo,
+            Todo {
+                id: Uuid::from_u128(7),
+                title: "From JSON:API".to_string(),
+                completed: true,
+                schema_version: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_get_todo_jsonapi_missing_attributes() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"data":{"type":"todos","id":"00000000-0000-0000-0000-000000000007"}}"#.to_string(),
+        };
+        let err = client().parse_get_todo_jsonapi(response).unwrap_err();
+        assert!(matches!(err, ApiError::DeserializationError(ref msg) if msg.contains("attributes")));
+    }
+
+    #[test]
+    fn build_count_todos_filtered_adds_query() {
+        let pending = TodoFilter { completed: Some(false) };
+        assert_eq!(
+            client().build_count_todos_filtered(pending).url(),
+            "http://localhost:3000/todos/count?completed=false"
+        );
+        assert_eq!(client().build_count_todos().url(), "http://localhost:3000/todos/count");
+    }
+
+    #[test]
+    fn parse_count_todos_reads_count() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"count":3}"#.to_string(),
+        };
+        assert_eq!(client().parse_count_todos(response).unwrap(), 3);
+    }
 
-    pub fn build_list_todos(&self) -> HttpRequest {
-        HttpRequest {
-            method: HttpMethod::Get,
-            path: format!("{}/todos", self.base_url),
+    #[cfg(feature = "validate-schema")]
+    #[test]
+    fn response_validation_reports_missing_field_path() {
+        let client = client().with_response_validation(true);
+        let response = HttpResponse {
+            status: 200,
             headers: Vec::new(),
-            body: None,
-        }
+            body: format!(
+                r#"[{{"id":"{}","title":"A","completed":true}},{{"id":"{}","title":"B"}}]"#,
+                Uuid::nil(),
+                Uuid::nil()
+            ),
+        };
+        let err = client.parse_list_todos(response).unwrap_err();
+        assert!(
+            matches!(&err, ApiError::Validation(msg) if msg.starts_with("/1/completed: ")),
+            "{err:?}"
+        );
     }
 
-    pub fn build_get_todo(&self, id: Uuid) -> HttpRequest {
-        HttpRequest {
-            method: HttpMethod::Get,
-            path: format!("{}/todos/{id}", self.base_url),
+    #[test]
+    fn parse_delete_todo_returning_accepts_200_and_204() {
+        let todo = Todo {
+            id: Uuid::nil(),
+            title: "Gone".to_string(),
+            completed: true,
+            schema_version: None,
+        };
+        let response = HttpResponse {
+            status: 200,
             headers: Vec::new(),
-            body: None,
-        }
+            body: serde_json::to_string(&todo).unwrap(),
+        };
+        assert_eq!(client().parse_delete_todo_returning(response).unwrap(), Some(todo));
+
+        let response = HttpResponse {
+            status: 204,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        assert_eq!(client().parse_delete_todo_returning(response).unwrap(), None);
     }
 
-    pub fn build_create_todo(&self, input: &CreateTodo) -> Result<HttpRequest, ApiError> {
-        let body = serde_json::to_string(input).map_err(|e| ApiError::SerializationError(e.to_string()))?;
-        Ok(HttpRequest {
-            method: HttpMethod::Post,
-            path: format!("{}/todos", self.base_url),
-            headers: vec![("content-type".to_string(), "application/json".to_string())],
-            body: Some(body),
-        })
+    #[test]
+    fn signed_update_uses_canonical_body() {
+        let client = client().with_request_signer(Box::new(|req| format!("sig:{}", req.body.as_deref().unwrap())));
+        let update = UpdateTodo {
+            title: Some("B".to_string()),
+            completed: Some(true),
+        };
+        let req = client.build_update_todo(Uuid::nil(), &update).unwrap();
+        let canonical = update.to_canonical_json().unwrap();
+        assert_eq!(req.body.as_deref(), Some(canonical.as_str()));
+        assert!(req.headers.contains(&("signature".to_string(), format!("sig:{canonical}"))));
     }
 
-    pub fn build_update_todo(&self, id: Uuid, input: &UpdateTodo) -> Result<HttpRequest, ApiError> {
-        let body = serde_json::to_string(input).map_err(|e| ApiError::SerializationError(e.to_string()))?;
-        Ok(HttpRequest {
-            method: HttpMethod::Put,
-            path: format!("{}/todos/{id}", self.base_url),
-            headers: vec![("content-type".to_string(), "application/json".to_string())],
-            body: Some(body),
-        })
+    #[test]
+    fn signed_patch_uses_canonical_body() {
+        let client = client().with_request_signer(Box::new(|req| {
+            let content_type = &req.headers.iter().find(|(name, _)| name == "content-type").unwrap().1;
+            format!("sig:{content_type}:{}", req.body.as_deref().unwrap())
+        }));
+        let update = UpdateTodo {
+            title: Some("B".to_string()),
+            completed: Some(true),
+        };
+        let req = client.build_patch_todo(Uuid::nil(), &update).unwrap();
+        let canonical = update.to_canonical_json().unwrap();
+        assert_eq!(req.body.as_deref(), Some(canonical.as_str()));
+        let signature = format!("sig:application/merge-patch+json:{canonical}");
+        assert!(req.headers.contains(&("signature".to_string(), signature)));
     }
 
-    pub fn build_delete_todo(&self, id: Uuid) -> HttpRequest {
-        HttpRequest {
-            method: HttpMethod::Delete,
-            path: format!("{}/todos/{id}", self.base_url),
+    #[test]
+    fn parse_list_todos_under_extracts_key() {
+        let response = HttpResponse {
+            status: 200,
             headers: Vec::new(),
-            body: None,
-        }
+            body: format!(
+                r#"{{"todos":[{{"id":"{}","title":"A","completed":false}}],"next":null}}"#,
+                Uuid::nil()
+            ),
+        };
+        let todos = client().parse_list_todos_under("todos", response).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title, "A");
     }
 
-    pub fn parse_list_todos(&self, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
-        check_status(&response, 200)?;
-        serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    #[test]
+    fn parse_list_todos_under_rejects_missing_or_non_array() {
+        for body in [r#"{"items":[]}"#, r#"{"todos":{}}"#] {
+            let response = HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: body.to_string(),
+            };
+            let err = client().parse_list_todos_under("todos", response).unwrap_err();
+            assert!(matches!(err, ApiError::DeserializationError(_)), "{body}");
+        }
     }
 
-    pub fn parse_get_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
-        check_status(&response, 200)?;
-        serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    #[test]
+    fn parse_delete_todo_idempotent_accepts_204_and_404() {
+        for status in [204, 404] {
+            let response = HttpResponse {
+                status,
+                headers: Vec::new(),
+                body: String::new(),
+            };
+            assert!(client().parse_delete_todo_idempotent(response).is_ok(), "{status}");
+        }
+        let response = HttpResponse {
+            status: 500,
+            headers: Vec::new(),
+            body: "boom".to_string(),
+        };
+        let err = client().parse_delete_todo_idempotent(response).unwrap_err();
+        assert!(matches!(err, ApiError::HttpError { status: 500, .. }));
     }
 
-    pub fn parse_create_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
-        check_status(&response, 201)?;
-        serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    #[test]
+    fn delete_all_roundtrip() {
+        let req = client().build_delete_all();
+        assert_eq!(req.method, HttpMethod::Delete);
+        assert_eq!(req.path, "http://localhost:3000/todos");
+
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"deleted":4}"#.to_string(),
+        };
+        assert_eq!(client().parse_delete_all(response).unwrap(), 4);
     }
 
-    pub fn parse_update_todo(&self, response: HttpResponse) -> Result<Todo, ApiError> {
-        check_status(&response, 200)?;
-        serde_json::from_str(&response.body).map_err(|e| ApiError::DeserializationError(e.to_string()))
+    #[test]
+    fn parse_list_todos_versioned_detects_mixed_versions() {
+        let item = |version: u32| {
+            format!(
+                r#"{{"id":"{}","title":"t","completed":false,"schema_version":{version}}}"#,
+                Uuid::nil()
+            )
+        };
+        let response = |body: String| HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        };
+
+        let uniform = response(format!("[{},{}]", item(2), item(2)));
+        assert_eq!(client().parse_list_todos_versioned(2, uniform).unwrap().len(), 2);
+
+        let mixed = response(format!("[{},{}]", item(2), item(1)));
+        let err = client().parse_list_todos_versioned(2, mixed).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::SchemaMismatch {
+                expected: 2,
+                found: Some(1)
+            }
+        ));
     }
 
-    pub fn parse_delete_todo(&self, response: HttpResponse) -> Result<(), ApiError> {
-        check_status(&response, 204)?;
-        Ok(())
+    #[test]
+    fn parse_todos_borrowed_points_into_body() {
+        let body = format!(r#"[{{"id":"{}","title":"Borrowed","completed":false}}]"#, Uuid::nil());
+        let todos = client().parse_todos_borrowed(200, &body).unwrap();
+        let Cow::Borrowed(title) = todos[0].title else {
+            panic!("title was copied");
+        };
+        let body_range = body.as_bytes().as_ptr_range();
+        assert!(body_range.contains(&title.as_ptr()));
+        assert_eq!(todos[0].to_owned().title, "Borrowed");
     }
-}
 
-/// Map non-success status codes to the appropriate `ApiError` variant.
-fn check_status(response: &HttpResponse, expected: u16) -> Result<(), ApiError> {
-    if response.status == expected {
-        return Ok(());
+    #[test]
+    fn build_list_todos_filtered_encodes_query() {
+        let filter = ListFilter {
+            completed: Some(true),
+            title_contains: Some("a b".to_string()),
+        };
+        let req = client().build_list_todos_filtered(&filter);
+        assert_eq!(req.path, "http://localhost:3000/todos");
+        assert_eq!(req.url(), "http://localhost:3000/todos?completed=true&title_contains=a%20b");
     }
-    if response.status == 404 {
-        return Err(ApiError::NotFound);
+
+    #[test]
+    fn parse_list_todos_page_from_headers() {
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![
+                ("X-Total-Count".to_string(), "5".to_string()),
+                (
+                    "Link".to_string(),
+                    r#"</todos?limit=2&offset=4>; rel="next", </todos?limit=2&offset=0>; rel="prev""#.to_string(),
+                ),
+            ],
+            body: "[]".to_string(),
+        };
+        let page = client().parse_list_todos_page(response).unwrap();
+        assert_eq!(page.total, Some(5));
+        assert_eq!(page.next.as_deref(), Some("/todos?limit=2&offset=4"));
+        assert_eq!(page.prev.as_deref(), Some("/todos?limit=2&offset=0"));
     }
-    Err(ApiError::HttpError {
-        status: response.status,
-        body: response.body.clone(),
-    })
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn parse_list_todos_page_from_wrapper() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: format!(
+                r#"{{"items":[{{"id":"{}","title":"A","completed":false}}],"total":1,"next":null}}"#,
+                Uuid::nil()
+            ),
+        };
+        let page = client().parse_list_todos_page(response).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.total, Some(1));
+        assert_eq!(page.next, None);
+        assert_eq!(page.prev, None);
+    }
 
-    fn client() -> TodoClient {
-        TodoClient::new("http://localhost:3000")
+    #[test]
+    fn build_list_todos_sorted_emits_sort_and_order() {
+        let req = client().build_list_todos_sorted(SortBy::Title, SortOrder::Desc);
+        assert_eq!(req.url(), "http://localhost:3000/todos?sort=title&order=desc");
     }
 
     #[test]
-    fn build_list_todos_produces_correct_request() {
-        let req = client().build_list_todos();
-        assert_eq!(req.method, HttpMethod::Get);
-        assert_eq!(req.path, "http://localhost:3000/todos");
-        assert!(req.body.is_none());
-        assert!(req.headers.is_empty());
+    fn build_create_todos_batch_posts_array() {
+        let inputs = [CreateTodo::new("a", false), CreateTodo::new("b", true)];
+        let req = client().build_create_todos_batch(&inputs).unwrap();
+        assert_eq!(req.method, HttpMethod::Post);
+        assert_eq!(req.path, "http://localhost:3000/todos/batch");
+        assert_eq!(req.body.unwrap(), serde_json::to_string(&inputs).unwrap());
     }
 
     #[test]
-    fn build_get_todo_produces_correct_request() {
-        let id = Uuid::nil();
-        let req = client().build_get_todo(id);
-        assert_eq!(req.method, HttpMethod::Get);
+    fn build_list_todos_cursor_sends_token() {
+        assert_eq!(client().build_list_todos_cursor(None).url(), "http://localhost:3000/todos?cursor=");
         assert_eq!(
-            req.path,
-            "http://localhost:3000/todos/00000000-0000-0000-0000-000000000000"
+            client().build_list_todos_cursor(Some("MTI")).url(),
+            "http://localhost:3000/todos?cursor=MTI"
         );
-        assert!(req.body.is_none());
     }
 
     #[test]
-    fn build_create_todo_produces_correct_request() {
-        let input = CreateTodo {
-            title: "Buy milk".to_string(),
-            completed: false,
+    fn build_patch_todo_uses_merge_patch() {
+        let update = UpdateTodo {
+            title: None,
+            completed: Some(true),
         };
-        let req = client().build_create_todo(&input).unwrap();
-        assert_eq!(req.method, HttpMethod::Post);
-        assert_eq!(req.path, "http://localhost:3000/todos");
+        let req = client().build_patch_todo(Uuid::nil(), &update).unwrap();
+        assert_eq!(req.method, HttpMethod::Patch);
+        assert_eq!(req.path, "http://localhost:3000/todos/00000000-0000-0000-0000-000000000000");
+        assert_eq!(req.body.as_deref(), Some(r#"{"completed":true}"#));
         assert_eq!(
             req.headers,
-            vec![("content-type".to_string(), "application/json".to_string())]
+            vec![("content-type".to_string(), "application/merge-patch+json".to_string())]
         );
-        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
-        assert_eq!(body["title"], "Buy milk");
-        assert_eq!(body["completed"], false);
     }
 
     #[test]
-    fn build_update_todo_produces_correct_request() {
-        let id = Uuid::nil();
-        let input = UpdateTodo {
-            title: Some("Updated".to_string()),
-            completed: None,
+    fn integer_id_format_parses_and_builds() {
+        let client = client().with_id_format(IdFormat::Integer);
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"id":42,"title":"Legacy","completed":false}"#.to_string(),
         };
-        let req = client().build_update_todo(id, &input).unwrap();
-        assert_eq!(req.method, HttpMethod::Put);
-        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
-        assert_eq!(body["title"], "Updated");
-        assert!(body.get("completed").is_none());
+        let todo = client.parse_get_todo(response).unwrap();
+        assert_eq!(todo.id, Uuid::from_u128(42));
+        assert_eq!(todo.title, "Legacy");
+        assert_eq!(client.build_get_todo(todo.id).path, "http://localhost:3000/todos/42");
+
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[{"id":1,"title":"A","completed":true}]"#.to_string(),
+        };
+        let todos = client.parse_list_todos(response).unwrap();
+        assert_eq!(todos[0].id, Uuid::from_u128(1));
     }
 
     #[test]
-    fn build_delete_todo_produces_correct_request() {
-        let id = Uuid::nil();
-        let req = client().build_delete_todo(id);
-        assert_eq!(req.method, HttpMethod::Delete);
-        assert!(req.body.is_none());
+    fn integer_id_format_parses_history() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[{"id":42,"title":"Legacy","completed":false,"revision":1,"changed_at":1700000000000}]"#
+                .to_string(),
+        };
+        let history = client().with_id_format(IdFormat::Integer).parse_history(response).unwrap();
+        assert_eq!(history[0].id, Uuid::from_u128(42));
+        assert_eq!(history[0].revision, 1);
     }
 
     #[test]
-    fn parse_list_todos_success() {
+    fn integer_id_format_parses_search_hits() {
         let response = HttpResponse {
             status: 200,
             headers: Vec::new(),
-            body: r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"Test","completed":false}]"#.to_string(),
+            body: r#"[{"id":3,"title":"Buy milk","completed":0,"score":0.5,"highlight":"Buy <em>milk</em>"}]"#
+                .to_string(),
         };
-        let todos = client().parse_list_todos(response).unwrap();
-        assert_eq!(todos.len(), 1);
-        assert_eq!(todos[0].title, "Test");
+        let hits = client().with_id_format(IdFormat::Integer).parse_search_todos(response).unwrap();
+        assert_eq!(hits[0].todo.id, Uuid::from_u128(3));
+        assert!(!hits[0].todo.completed);
+        assert_eq!(hits[0].score, 0.5);
+        assert_eq!(hits[0].highlight.as_deref(), Some("Buy <em>milk</em>"));
     }
 
     #[test]
-    fn parse_get_todo_not_found() {
+    fn integer_id_format_parses_batch_statuses() {
         let response = HttpResponse {
-            status: 404,
+            status: 200,
             headers: Vec::new(),
-            body: String::new(),
+            body: r#"[{"status":201,"body":{"id":7,"title":"A","completed":false}}]"#.to_string(),
+        };
+        let results = client().with_id_format(IdFormat::Integer).parse_batch_statuses(response).unwrap();
+        assert_eq!(results[0].as_ref().unwrap().id, Uuid::from_u128(7));
+    }
+
+    #[test]
+    fn integer_id_format_parses_jsonapi() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"data":{"type":"todos","id":"7","attributes":{"title":"A","completed":true}}}"#.to_string(),
+        };
+        let todo = client().with_id_format(IdFormat::Integer).parse_get_todo_jsonapi(response).unwrap();
+        assert_eq!(todo.id, Uuid::from_u128(7));
+    }
+
+    #[test]
+    fn integer_id_format_parses_bounded_lists() {
+        let body = r#"[{"id":1,"title":"A","completed":false},{"id":2,"title":"B","completed":false}]"#;
+        let client = client().with_id_format(IdFormat::Integer);
+        let (todos, truncated) = client.parse_list_todos_bounded(200, body, 1).unwrap();
+        assert_eq!(todos[0].id, Uuid::from_u128(1));
+        assert!(truncated);
+    }
+
+    #[test]
+    fn integer_id_rejected_under_uuid_format() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"id":42,"title":"Legacy","completed":false}"#.to_string(),
         };
         let err = client().parse_get_todo(response).unwrap_err();
-        assert!(matches!(err, ApiError::NotFound));
+        assert!(matches!(err, ApiError::DeserializationError(_)));
     }
 
     #[test]
@@ -231,6 +1999,125 @@ mod tests {
         assert!(matches!(err, ApiError::HttpError { status: 500, .. }));
     }
 
+    #[test]
+    fn parse_create_todo_with_warnings_from_body_and_header() {
+        let response = HttpResponse {
+            status: 201,
+            headers: vec![("Warning".to_string(), r#"199 - "completed ignored""#.to_string())],
+            body: r#"{"id":"00000000-0000-0000-0000-000000000001","title":"Long","completed":false,
+                "warnings":["title truncated"]}"#
+                .to_string(),
+        };
+        let (todo, warnings) = client().parse_create_todo_with_warnings(response).unwrap();
+        assert_eq!(todo.title, "Long");
+        assert_eq!(warnings, vec!["title truncated", "completed ignored"]);
+    }
+
+    #[test]
+    fn parse_create_todo_with_warnings_none() {
+        let response = HttpResponse {
+            status: 201,
+            headers: Vec::new(),
+            body: r#"{"id":"00000000-0000-0000-0000-000000000001","title":"Ok","completed":false}"#.to_string(),
+        };
+        let (_, warnings) = client().parse_create_todo_with_warnings(response).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_create_todo_with_request_attaches_body() {
+        let client = client().with_error_includes_request(true);
+        let input = CreateTodo {
+            title: "Bad".to_string(),
+            completed: false,
+        };
+        let mut sent = client.build_create_todo(&input).unwrap();
+        sent.headers.push(("authorization".to_string(), "Bearer secret".to_string()));
+        let response = HttpResponse {
+            status: 400,
+            headers: Vec::new(),
+            body: "invalid title".to_string(),
+        };
+        let err = client.parse_create_todo_with_request(&sent, response).unwrap_err();
+        match &err {
+            ApiError::HttpError { status, request_body, .. } => {
+                assert_eq!(*status, 400);
+                assert_eq!(request_body.as_deref(), sent.body.as_deref());
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(!err.to_string().contains("secret"));
+    }
+
+    #[test]
+    fn parse_create_todo_with_request_off_by_default() {
+        let input = CreateTodo {
+            title: "Bad".to_string(),
+            completed: false,
+        };
+        let sent = client().build_create_todo(&input).unwrap();
+        let response = HttpResponse {
+            status: 400,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        let err = client().parse_create_todo_with_request(&sent, response).unwrap_err();
+        assert!(matches!(err, ApiError::HttpError { request_body: None, .. }));
+    }
+
+    #[test]
+    fn parse_create_todo_conflict() {
+        let response = HttpResponse {
+            status: 409,
+            headers: Vec::new(),
+            body: "duplicate title".to_string(),
+        };
+        let err = client().parse_create_todo(response).unwrap_err();
+        assert!(matches!(err, ApiError::Conflict(ref body) if body == "duplicate title"));
+    }
+
+    #[test]
+    fn if_match_builders_send_etag_and_map_412() {
+        let id = Uuid::nil();
+        let input = UpdateTodo {
+            title: None,
+            completed: Some(true),
+        };
+        let req = client().build_update_todo_if_match(id, &input, "\"3\"").unwrap();
+        assert_eq!(req.method, HttpMethod::Put);
+        assert!(req.headers.contains(&("if-match".to_string(), "\"3\"".to_string())));
+        let req = client().build_delete_todo_if_match(id, "\"3\"");
+        assert_eq!(req.method, HttpMethod::Delete);
+        assert!(req.headers.contains(&("if-match".to_string(), "\"3\"".to_string())));
+
+        let response = HttpResponse {
+            status: 412,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        let err = client().parse_delete_todo(response).unwrap_err();
+        assert!(matches!(err, ApiError::PreconditionFailed));
+    }
+
+    #[test]
+    fn parse_batch_statuses_mixed() {
+        let response = HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"[
+                {"status":201,"body":{"id":"00000000-0000-0000-0000-000000000001","title":"A","completed":false}},
+                {"status":409,"body":"title already exists"},
+                {"status":500,"body":{"error":"boom"}}
+            ]"#
+            .to_string(),
+        };
+        let results = client().parse_batch_statuses(response).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().title, "A");
+        assert!(matches!(&results[1], Err(ApiError::Conflict(body)) if body == "title already exists"));
+        assert!(matches!(&results[2], Err(ApiError::HttpError { status: 500, body, .. }) if body == r#"{"error":"boom"}"#));
+    }
+
     #[test]
     fn parse_update_todo_success() {
         let response = HttpResponse {
@@ -271,6 +2158,36 @@ mod tests {
         assert_eq!(req.path, "http://localhost:3000/todos");
     }
 
+    #[test]
+    fn parse_list_todos_bounded_truncates() {
+        let items: Vec<String> = (1..=10)
+            .map(|n| format!(r#"{{"id":"{}","title":"T{n}","completed":false}}"#, Uuid::from_u128(n)))
+            .collect();
+        let body = format!("[{}]", items.join(","));
+        let (todos, truncated) = client().parse_list_todos_bounded(200, &body, 3).unwrap();
+        assert_eq!(todos.len(), 3);
+        assert!(truncated);
+        assert_eq!(todos[2].title, "T3");
+    }
+
+    #[test]
+    fn parse_list_todos_bounded_under_cap() {
+        let body = r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"Only","completed":false}]"#;
+        let (todos, truncated) = client().parse_list_todos_bounded(200, body, 3).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn unix_base_separates_socket_and_path() {
+        let client = TodoClient::new("unix:/var/run/todo.sock/");
+        assert_eq!(client.build_list_todos().path, "unix:/var/run/todo.sock:/todos");
+        assert_eq!(
+            client.build_get_todo(Uuid::nil()).path,
+            "unix:/var/run/todo.sock:/todos/00000000-0000-0000-0000-000000000000"
+        );
+    }
+
     #[test]
     fn parse_list_todos_bad_json() {
         let response = HttpResponse {
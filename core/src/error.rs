@@ -3,42 +3,190 @@
 //! # Design
 //! `NotFound` gets a dedicated variant because callers frequently distinguish
 //! "the resource does not exist" from "the server returned an unexpected
-//! status." All other non-2xx responses land in `HttpError` with the raw
-//! status code and body for debugging.
+//! status." `NotFoundId` carries the requested id for parse paths that know
+//! it, so logs can name the missing resource. All other non-2xx responses
+//! land in `HttpError` with the raw status code and body for debugging.
+//!
+//! `status_code` is the single place that maps variants back to HTTP
+//! statuses; gateways should use it rather than matching variants.
+
+use core::fmt;
+use core::time::Duration;
 
-use std::fmt;
+use uuid::Uuid;
+
+use crate::prelude::*;
 
 /// Errors returned by `TodoClient` parse methods.
 #[derive(Debug)]
 pub enum ApiError {
+    /// The server returned 401 — credentials were missing, malformed, or
+    /// expired.
+    Unauthorized,
+
     /// The server returned 404 — the requested todo does not exist.
     NotFound,
 
-    /// The server returned a non-2xx status other than 404.
-    HttpError { status: u16, body: String },
+    /// The server returned 404 for a lookup whose id the caller supplied.
+    NotFoundId(Uuid),
+
+    /// The server returned 409 — the write conflicts with existing state.
+    /// Carries the response body, which usually explains the conflict.
+    Conflict(String),
+
+    /// The server returned 412 — an `If-Match` precondition did not hold,
+    /// meaning someone else changed the todo since it was read.
+    PreconditionFailed,
+
+    /// The server returned a non-2xx status other than 401, 404, 409 or 412.
+    ///
+    /// `request_body` is only populated by the `*_with_request` parse
+    /// variants when the client opted in via `with_error_includes_request`.
+    HttpError {
+        status: u16,
+        body: String,
+        request_body: Option<String>,
+    },
 
     /// The response body could not be deserialized into the expected type.
     DeserializationError(String),
 
     /// The request payload could not be serialized to JSON.
     SerializationError(String),
+
+    /// Caller-supplied input was rejected before any request was built.
+    Validation(String),
+
+    /// The server returned 429 — the caller should back off.
+    RateLimited { retry_after: Option<Duration> },
+
+    /// A listed todo was produced by a different schema version than the
+    /// caller expects. `found` is `None` when the item carries no version.
+    SchemaMismatch { expected: u32, found: Option<u32> },
+
+    /// The host's HTTP executor failed before a response arrived. The core
+    /// never produces this itself; host adapters report it.
+    Transport(String),
+
+    /// The TLS handshake failed (untrusted or invalid certificate, protocol
+    /// mismatch). Split from `Transport` so callers can report a certificate
+    /// problem specifically.
+    Tls(String),
+}
+
+impl ApiError {
+    /// Best-fit HTTP status for re-serving this error to a downstream client.
+    ///
+    /// Proxies built on the core use this to forward upstream failures.
+    /// Upstream responses that could not be understood map to 502, since from
+    /// the downstream client's view the gateway received a bad reply.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ApiError::Unauthorized => 401,
+            ApiError::NotFound | ApiError::NotFoundId(_) => 404,
+            ApiError::Conflict(_) => 409,
+            ApiError::PreconditionFailed => 412,
+            ApiError::HttpError { status, .. } => *status,
+            ApiError::DeserializationError(_) => 502,
+            ApiError::SerializationError(_) => 400,
+            ApiError::Validation(_) => 422,
+            ApiError::RateLimited { .. } => 429,
+            ApiError::SchemaMismatch { .. } => 502,
+            ApiError::Transport(_) => 502,
+            ApiError::Tls(_) => 502,
+        }
+    }
 }
 
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            ApiError::Unauthorized => write!(f, "unauthorized"),
             ApiError::NotFound => write!(f, "resource not found"),
-            ApiError::HttpError { status, body } => {
-                write!(f, "HTTP {status}: {body}")
-            }
+            ApiError::NotFoundId(id) => write!(f, "todo {id} not found"),
+            ApiError::Conflict(body) => write!(f, "conflict: {body}"),
+            ApiError::PreconditionFailed => write!(f, "precondition failed: todo was modified"),
+            ApiError::HttpError {
+                status,
+                body,
+                request_body: None,
+            } => write!(f, "HTTP {status}: {body}"),
+            ApiError::HttpError {
+                status,
+                body,
+                request_body: Some(sent),
+            } => write!(f, "HTTP {status}: {body} (request body: {sent})"),
             ApiError::DeserializationError(msg) => {
                 write!(f, "deserialization failed: {msg}")
             }
             ApiError::SerializationError(msg) => {
                 write!(f, "serialization failed: {msg}")
             }
+            ApiError::Validation(msg) => write!(f, "validation failed: {msg}"),
+            ApiError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            ApiError::RateLimited {
+                retry_after: Some(delay),
+            } => write!(f, "rate limited, retry after {}s", delay.as_secs()),
+            ApiError::SchemaMismatch {
+                expected,
+                found: Some(found),
+            } => write!(f, "schema version mismatch: expected {expected}, found {found}"),
+            ApiError::SchemaMismatch { expected, found: None } => {
+                write!(f, "schema version mismatch: expected {expected}, found none")
+            }
+            ApiError::Transport(msg) => write!(f, "transport error: {msg}"),
+            ApiError::Tls(msg) => write!(f, "TLS error: {msg}"),
+        }
+    }
+}
+
+impl core::error::Error for ApiError {}
+
+impl From<todo_schema::SchemaError> for ApiError {
+    fn from(err: todo_schema::SchemaError) -> Self {
+        match err {
+            todo_schema::SchemaError::Validation(msg) => ApiError::Validation(msg),
+            todo_schema::SchemaError::Serialization(msg) => ApiError::SerializationError(msg),
         }
     }
 }
 
-impl std::error::Error for ApiError {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_code_per_variant() {
+        let cases = [
+            (ApiError::Unauthorized, 401),
+            (ApiError::NotFound, 404),
+            (ApiError::NotFoundId(Uuid::nil()), 404),
+            (ApiError::Conflict(String::new()), 409),
+            (ApiError::PreconditionFailed, 412),
+            (
+                ApiError::HttpError {
+                    status: 503,
+                    body: String::new(),
+                    request_body: None,
+                },
+                503,
+            ),
+            (ApiError::DeserializationError(String::new()), 502),
+            (ApiError::SerializationError(String::new()), 400),
+            (ApiError::Validation(String::new()), 422),
+            (ApiError::RateLimited { retry_after: None }, 429),
+            (
+                ApiError::SchemaMismatch {
+                    expected: 2,
+                    found: Some(1),
+                },
+                502,
+            ),
+            (ApiError::Transport(String::new()), 502),
+            (ApiError::Tls(String::new()), 502),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(err.status_code(), expected, "{err:?}");
+        }
+    }
+}
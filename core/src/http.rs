@@ -9,14 +9,78 @@
 //!
 //! All fields use owned types (`String`, `Vec`) so values can cross FFI
 //! boundaries without lifetime concerns.
+//!
+//! Query parameters are kept as raw pairs on `HttpRequest` and only
+//! percent-encoded when `HttpRequest::url` renders the final target, so
+//! builders never deal with escaping.
+
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::prelude::*;
+
+/// Everything except RFC 3986 unreserved characters gets encoded.
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
 
 /// HTTP method for a request.
+///
+/// `Options` and `Trace` are never produced by the todo builders; they exist
+/// so `TodoClient::build_raw` can describe any request a proxy or debugging
+/// tool needs to issue.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HttpMethod {
     Get,
     Post,
     Put,
     Delete,
+    Options,
+    Trace,
+    Patch,
+    Head,
+}
+
+impl HttpMethod {
+    /// The method name as it appears on the wire.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Head => "HEAD",
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = String;
+
+    /// Parse a wire method name. Method names are case-sensitive per RFC 9110.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GET" => Ok(HttpMethod::Get),
+            "POST" => Ok(HttpMethod::Post),
+            "PUT" => Ok(HttpMethod::Put),
+            "DELETE" => Ok(HttpMethod::Delete),
+            "OPTIONS" => Ok(HttpMethod::Options),
+            "TRACE" => Ok(HttpMethod::Trace),
+            "PATCH" => Ok(HttpMethod::Patch),
+            "HEAD" => Ok(HttpMethod::Head),
+            other => Err(format!("unknown HTTP method: {other}")),
+        }
+    }
 }
 
 /// An HTTP request described as plain data.
@@ -24,14 +88,56 @@ pub enum HttpMethod {
 /// Built by `TodoClient::build_*` methods. The caller is responsible for
 /// executing this request against the network and returning the corresponding
 /// `HttpResponse`.
+///
+/// Hosts should send to `url()`, which appends the encoded `query`, rather
+/// than to `path` directly.
 #[derive(Debug, Clone)]
 pub struct HttpRequest {
     pub method: HttpMethod,
     pub path: String,
+    /// Unencoded query parameters, in order.
+    pub query: Vec<(String, String)>,
     pub headers: Vec<(String, String)>,
     pub body: Option<String>,
 }
 
+impl HttpRequest {
+    /// The full request target: `path` plus the percent-encoded query
+    /// string, if any.
+    pub fn url(&self) -> String {
+        let mut url = self.path.clone();
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            url.push(if i == 0 { '?' } else { '&' });
+            url.extend(utf8_percent_encode(key, QUERY_COMPONENT));
+            url.push('=');
+            url.extend(utf8_percent_encode(value, QUERY_COMPONENT));
+        }
+        url
+    }
+
+    /// True when both requests hit the same method and full URL (query
+    /// included). Headers and body are not compared.
+    pub fn same_endpoint(&self, other: &HttpRequest) -> bool {
+        self.method == other.method && self.path == other.path && self.query == other.query
+    }
+
+    /// First value of header `name`, matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set header `name` to `value`, replacing every existing value for it
+    /// (matched case-insensitively). Use this to override a client default
+    /// for a single call.
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        self.headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+}
+
 /// An HTTP response described as plain data.
 ///
 /// Constructed by the caller after executing an `HttpRequest`, then passed
@@ -42,3 +148,210 @@ pub struct HttpResponse {
     pub headers: Vec<(String, String)>,
     pub body: String,
 }
+
+impl HttpResponse {
+    /// First value of header `name`, matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The `X-RateLimit-*` headers, or `None` if the response carries none
+    /// of them. A header that is present but not a non-negative integer is
+    /// treated as absent.
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        let number = |name| self.header(name).and_then(|v| v.trim().parse().ok());
+        let info = RateLimitInfo {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset"),
+        };
+        (info != RateLimitInfo::default()).then_some(info)
+    }
+
+    /// How long the server asked the client to wait, from `Retry-After`
+    /// (RFC 9110 §10.2.3) in either delay-seconds or HTTP-date form.
+    ///
+    /// A date is measured against the response's own `Date` header when
+    /// present, so client clock skew does not distort the delay; otherwise
+    /// against the local clock. Dates in the past yield zero. Without the
+    /// `std` feature there is no clock, and only delay-seconds is understood.
+    pub fn retry_after(&self) -> Option<Duration> {
+        let value = self.header("retry-after")?.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        self.retry_after_date(value)
+    }
+
+    #[cfg(feature = "std")]
+    fn retry_after_date(&self, value: &str) -> Option<Duration> {
+        let at = httpdate::parse_http_date(value).ok()?;
+        let now = self
+            .header("date")
+            .and_then(|date| httpdate::parse_http_date(date.trim()).ok())
+            .unwrap_or_else(std::time::SystemTime::now);
+        Some(at.duration_since(now).unwrap_or(Duration::ZERO))
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn retry_after_date(&self, _value: &str) -> Option<Duration> {
+        None
+    }
+}
+
+/// Server-advertised request quota, from `X-RateLimit-*` response headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window (`X-RateLimit-Limit`).
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`X-RateLimit-Remaining`).
+    pub remaining: Option<u64>,
+    /// `X-RateLimit-Reset` as sent. Servers disagree on whether this is a
+    /// Unix timestamp or seconds until reset, so it is not interpreted.
+    pub reset: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [HttpMethod; 8] = [
+        HttpMethod::Get,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Delete,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+        HttpMethod::Patch,
+        HttpMethod::Head,
+    ];
+
+    #[test]
+    fn wire_strings() {
+        assert_eq!(HttpMethod::Options.to_string(), "OPTIONS");
+        assert_eq!(HttpMethod::Trace.to_string(), "TRACE");
+    }
+
+    #[test]
+    fn display_and_from_str_roundtrip() {
+        for method in ALL {
+            assert_eq!(method.to_string().parse::<HttpMethod>().unwrap(), method);
+        }
+    }
+
+    #[test]
+    fn same_endpoint_ignores_headers() {
+        let get = |path: &str, headers: Vec<(String, String)>| HttpRequest {
+            method: HttpMethod::Get,
+            path: path.to_string(),
+            query: Vec::new(),
+            headers,
+            body: None,
+        };
+        let traced = vec![("traceparent".to_string(), "x".to_string())];
+        assert!(get("/todos/1", Vec::new()).same_endpoint(&get("/todos/1", traced)));
+        assert!(!get("/todos/1", Vec::new()).same_endpoint(&get("/todos/2", Vec::new())));
+    }
+
+    #[test]
+    fn url_percent_encodes_query() {
+        let req = HttpRequest {
+            method: HttpMethod::Get,
+            path: "http://localhost/todos".to_string(),
+            query: vec![
+                ("title_contains".to_string(), "milk & eggs".to_string()),
+                ("completed".to_string(), "true".to_string()),
+            ],
+            headers: Vec::new(),
+            body: None,
+        };
+        assert_eq!(req.url(), "http://localhost/todos?title_contains=milk%20%26%20eggs&completed=true");
+    }
+
+    #[test]
+    fn set_header_replaces_case_insensitively() {
+        let mut req = HttpRequest {
+            method: HttpMethod::Get,
+            path: "/todos".to_string(),
+            query: Vec::new(),
+            headers: vec![
+                ("X-Tenant".to_string(), "a".to_string()),
+                ("accept".to_string(), "*/*".to_string()),
+            ],
+            body: None,
+        };
+        req.set_header("x-tenant", "b");
+        assert_eq!(req.header("X-TENANT"), Some("b"));
+        assert_eq!(req.headers.len(), 2);
+    }
+
+    #[test]
+    fn rate_limit_headers_parse_leniently() {
+        let response = |headers: &[(&str, &str)]| HttpResponse {
+            status: 200,
+            headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            body: String::new(),
+        };
+        let full = response(&[
+            ("X-RateLimit-Limit", "60"),
+            ("X-RateLimit-Remaining", "0"),
+            ("X-RateLimit-Reset", "1700000000"),
+        ]);
+        assert_eq!(
+            full.rate_limit(),
+            Some(RateLimitInfo {
+                limit: Some(60),
+                remaining: Some(0),
+                reset: Some(1_700_000_000),
+            })
+        );
+        let partial = response(&[("x-ratelimit-remaining", "7"), ("x-ratelimit-limit", "lots")]);
+        assert_eq!(partial.rate_limit().unwrap().remaining, Some(7));
+        assert_eq!(partial.rate_limit().unwrap().limit, None);
+        assert_eq!(response(&[]).rate_limit(), None);
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        let response = |headers: &[(&str, &str)]| HttpResponse {
+            status: 429,
+            headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            body: String::new(),
+        };
+        assert_eq!(response(&[("Retry-After", "120")]).retry_after(), Some(Duration::from_secs(120)));
+        let dated = response(&[
+            ("Retry-After", "Wed, 21 Oct 2015 07:28:30 GMT"),
+            ("Date", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ]);
+        #[cfg(not(feature = "std"))]
+        assert_eq!(dated.retry_after(), None);
+        #[cfg(feature = "std")]
+        {
+            assert_eq!(dated.retry_after(), Some(Duration::from_secs(30)));
+            let past = response(&[("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")]);
+            assert_eq!(past.retry_after(), Some(Duration::ZERO));
+        }
+        assert_eq!(response(&[("Retry-After", "soon")]).retry_after(), None);
+        assert_eq!(response(&[]).retry_after(), None);
+    }
+
+    #[test]
+    fn response_header_lookup_is_case_insensitive() {
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("X-Correlation-Id".to_string(), "abc".to_string())],
+            body: String::new(),
+        };
+        assert_eq!(response.header("x-correlation-id"), Some("abc"));
+        assert_eq!(response.header("traceparent"), None);
+    }
+
+    #[test]
+    fn from_str_rejects_unknown() {
+        assert!("options".parse::<HttpMethod>().is_err());
+        assert!("BREW".parse::<HttpMethod>().is_err());
+    }
+}
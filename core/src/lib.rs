@@ -6,19 +6,56 @@
 //! actual HTTP round-trip, making the core fully deterministic and testable.
 //!
 //! # Design
-//! - `TodoClient` is stateless — it holds only `base_url`.
+//! - `TodoClient` is stateless — it holds `base_url` and fixed options.
 //! - Each CRUD operation is split into `build_*` (produces request) and
 //!   `parse_*` (consumes response), so the I/O boundary is explicit.
 //! - Types use owned `String` / `Vec` fields to simplify future FFI mapping.
-//! - DTOs are defined independently from the mock-server crate; integration
-//!   tests catch schema drift.
+//! - The wire DTOs come from `todo-schema`, shared with the mock-server, so
+//!   a schema change breaks both builds rather than an integration test.
+//!
+//! # `no_std`
+//! With default features off the crate is `#![no_std]` and needs only
+//! `alloc`, for embedded hosts that do their I/O over raw sockets. The
+//! `std` feature (on by default) adds what needs an OS:
+//! - `Uuid::new_v4`, so `build_get_todo_traced` and generated idempotency
+//!   keys; pass keys explicitly without it.
+//! - `HttpResponse::retry_after` in HTTP-date form, which needs a clock;
+//!   delay-seconds is parsed either way.
+//! - `CreateTodo::write_batch_json`, which writes to `std::io::Write`.
+//! - `UnwindSafe` for clients holding header closures, for FFI wrappers
+//!   that use `catch_unwind`.
+//!
+//! `blocking` and `validate-schema` imply `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+pub mod auth;
+pub mod base_url;
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 pub mod error;
 pub mod http;
+pub mod paginator;
+mod prelude;
+pub mod reconcile;
+pub mod retry;
+#[cfg(feature = "validate-schema")]
+mod schema;
 pub mod types;
 
-pub use client::TodoClient;
+pub use auth::Auth;
+pub use base_url::BaseUrl;
+pub use batch::RequestBatch;
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingTodoClient;
+pub use client::{TodoClient, TodoClientBuilder};
 pub use error::ApiError;
-pub use http::{HttpMethod, HttpRequest, HttpResponse};
-pub use types::{CreateTodo, Todo, UpdateTodo};
+pub use http::{HttpMethod, HttpRequest, HttpResponse, RateLimitInfo};
+pub use paginator::ListTodosPaginator;
+pub use reconcile::{reconcile, ReconcilePlan};
+pub use retry::{is_retryable, RetryPolicy, RetrySession};
+pub use types::{Capabilities, CreateTodo, IdFormat, ListFilter, Page, PageParams, SearchHit, SearchOptions, SortBy, SortOrder, Todo, TodoFilter, TodoRef, TodoRevision, UpdateTodo};
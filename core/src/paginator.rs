@@ -0,0 +1,129 @@
+//! Sans-IO driver for cursor-paginated listing.
+//!
+//! # Design
+//! `ListTodosPaginator` owns the cursor bookkeeping so each host language
+//! only has to write the loop: ask for `next_request`, execute it, hand the
+//! response to `handle_response`, repeat until `next_request` returns
+//! `None`. It wraps `build_list_todos_cursor` / `parse_list_todos_cursor`
+//! and never performs I/O itself.
+//!
+//! A failed `handle_response` leaves the state untouched, so the host can
+//! re-execute the same request to retry the page.
+
+use crate::client::TodoClient;
+use crate::error::ApiError;
+use crate::http::{HttpRequest, HttpResponse};
+use crate::prelude::*;
+use crate::types::Todo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Start,
+    After(String),
+    Done,
+}
+
+/// Walks a cursor listing one page at a time.
+#[derive(Debug, Clone)]
+pub struct ListTodosPaginator {
+    client: TodoClient,
+    page_size: Option<u32>,
+    state: State,
+}
+
+impl ListTodosPaginator {
+    pub fn new(client: TodoClient) -> Self {
+        Self {
+            client,
+            page_size: None,
+            state: State::Start,
+        }
+    }
+
+    /// Ask the server for pages of at most `limit` todos instead of its
+    /// default size.
+    pub fn with_page_size(mut self, limit: u32) -> Self {
+        self.page_size = Some(limit);
+        self
+    }
+
+    /// The request for the next page, or `None` once the listing is
+    /// exhausted. Calling it again without `handle_response` returns the
+    /// same request.
+    pub fn next_request(&self) -> Option<HttpRequest> {
+        let cursor = match &self.state {
+            State::Start => None,
+            State::After(cursor) => Some(cursor.as_str()),
+            State::Done => return None,
+        };
+        let mut req = self.client.build_list_todos_cursor(cursor);
+        if let Some(limit) = self.page_size {
+            req.query.push(("limit".to_string(), limit.to_string()));
+        }
+        Some(req)
+    }
+
+    /// Consume the response to the last `next_request` and return its todos.
+    pub fn handle_response(&mut self, response: HttpResponse) -> Result<Vec<Todo>, ApiError> {
+        let (todos, next) = self.client.parse_list_todos_cursor(response)?;
+        self.state = match next {
+            Some(cursor) => State::After(cursor),
+            None => State::Done,
+        };
+        Ok(todos)
+    }
+
+    /// True once the final page has been handled.
+    pub fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn page(titles: &[&str], next: Option<&str>) -> HttpResponse {
+        let todos: Vec<_> = titles
+            .iter()
+            .map(|t| serde_json::json!({ "id": Uuid::nil(), "title": t, "completed": false }))
+            .collect();
+        HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: serde_json::json!({ "todos": todos, "next_cursor": next }).to_string(),
+        }
+    }
+
+    #[test]
+    fn walks_cursors_until_exhausted() {
+        let mut paginator = ListTodosPaginator::new(TodoClient::new("http://localhost:3000")).with_page_size(2);
+
+        let req = paginator.next_request().unwrap();
+        assert_eq!(req.url(), "http://localhost:3000/todos?cursor=&limit=2");
+        assert_eq!(paginator.handle_response(page(&["a", "b"], Some("Mg"))).unwrap().len(), 2);
+
+        let req = paginator.next_request().unwrap();
+        assert_eq!(req.url(), "http://localhost:3000/todos?cursor=Mg&limit=2");
+        assert_eq!(paginator.handle_response(page(&["c"], None)).unwrap().len(), 1);
+
+        assert!(paginator.is_done());
+        assert!(paginator.next_request().is_none());
+    }
+
+    #[test]
+    fn failed_page_can_be_retried() {
+        let mut paginator = ListTodosPaginator::new(TodoClient::new("http://localhost:3000"));
+        let before = paginator.next_request().unwrap().url();
+        let failure = HttpResponse {
+            status: 503,
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        assert!(paginator.handle_response(failure).is_err());
+        assert_eq!(paginator.next_request().unwrap().url(), before);
+        assert!(!paginator.is_done());
+    }
+}
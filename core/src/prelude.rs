@@ -0,0 +1,8 @@
+//! The `alloc` items `std`'s prelude would provide, imported by every
+//! module so the crate reads the same with and without `std`.
+
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
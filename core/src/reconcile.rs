@@ -0,0 +1,129 @@
+//! Declarative sync: diff a current todo list against a desired one.
+//!
+//! # Design
+//! `reconcile` is a pure function. It matches items by `id` and emits a
+//! `ReconcilePlan` describing the creates, updates, and deletes that would
+//! move the server from `current` to `desired`. The caller turns the plan
+//! into requests with the regular `build_*` methods, so the I/O boundary
+//! stays where it is for every other operation.
+//!
+//! Desired items whose id is absent from `current` become `CreateTodo`s; the
+//! server assigns a fresh id on create, so the desired id is not carried
+//! over. Updates only include fields that actually differ, and items that
+//! already match produce no update at all.
+
+use alloc::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::prelude::*;
+use crate::types::{CreateTodo, Todo, UpdateTodo};
+
+/// The set of operations needed to turn `current` into `desired`.
+///
+/// Each list preserves the order of the input slice it was derived from
+/// (`to_delete` follows `current`, the others follow `desired`).
+#[derive(Debug, Clone, Default)]
+pub struct ReconcilePlan {
+    pub to_create: Vec<CreateTodo>,
+    pub to_update: Vec<(Uuid, UpdateTodo)>,
+    pub to_delete: Vec<Uuid>,
+}
+
+impl ReconcilePlan {
+    /// True when `current` already matches `desired`.
+    pub fn is_empty(&self) -> bool {
+        self.to_create.is_empty() && self.to_update.is_empty() && self.to_delete.is_empty()
+    }
+}
+
+/// Compute the plan that moves the server from `current` to `desired`.
+pub fn reconcile(current: &[Todo], desired: &[Todo]) -> ReconcilePlan {
+    let current_by_id: BTreeMap<Uuid, &Todo> = current.iter().map(|t| (t.id, t)).collect();
+    let desired_by_id: BTreeMap<Uuid, &Todo> = desired.iter().map(|t| (t.id, t)).collect();
+
+    let mut plan = ReconcilePlan::default();
+
+    for want in desired {
+        let Some(have) = current_by_id.get(&want.id) else {
+            plan.to_create.push(CreateTodo {
+                title: want.title.clone(),
+                completed: want.completed,
+            });
+            continue;
+        };
+        let update = UpdateTodo {
+            title: (have.title != want.title).then(|| want.title.clone()),
+            completed: (have.completed != want.completed).then_some(want.completed),
+        };
+        if update.title.is_some() || update.completed.is_some() {
+            plan.to_update.push((want.id, update));
+        }
+    }
+
+    plan.to_delete = current
+        .iter()
+        .filter(|t| !desired_by_id.contains_key(&t.id))
+        .map(|t| t.id)
+        .collect();
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(n: u128, title: &str, completed: bool) -> Todo {
+        Todo {
+            id: Uuid::from_u128(n),
+            title: title.to_string(),
+            completed,
+            schema_version: None,
+        }
+    }
+
+    #[test]
+    fn identical_lists_produce_empty_plan() {
+        let list = vec![todo(1, "A", false), todo(2, "B", true)];
+        assert!(reconcile(&list, &list).is_empty());
+    }
+
+    #[test]
+    fn additions_become_creates() {
+        let current = vec![todo(1, "A", false)];
+        let desired = vec![todo(1, "A", false), todo(2, "New", true)];
+        let plan = reconcile(&current, &desired);
+        let created: Vec<_> = plan.to_create.iter().map(|c| (c.title.as_str(), c.completed)).collect();
+        assert_eq!(created, [("New", true)]);
+        assert!(plan.to_update.is_empty());
+        assert!(plan.to_delete.is_empty());
+    }
+
+    #[test]
+    fn removals_become_deletes() {
+        let current = vec![todo(1, "A", false), todo(2, "B", false), todo(3, "C", false)];
+        let desired = vec![todo(2, "B", false)];
+        let plan = reconcile(&current, &desired);
+        assert_eq!(plan.to_delete, vec![Uuid::from_u128(1), Uuid::from_u128(3)]);
+        assert!(plan.to_create.is_empty());
+        assert!(plan.to_update.is_empty());
+    }
+
+    #[test]
+    fn changed_fields_become_minimal_updates() {
+        let current = vec![todo(1, "A", false), todo(2, "B", false)];
+        let desired = vec![todo(1, "A renamed", false), todo(2, "B", true)];
+        let plan = reconcile(&current, &desired);
+        let updates: Vec<_> = plan.to_update.iter().map(|(id, u)| (*id, u.title.as_deref(), u.completed)).collect();
+        assert_eq!(
+            updates,
+            [
+                (Uuid::from_u128(1), Some("A renamed"), None),
+                (Uuid::from_u128(2), None, Some(true)),
+            ]
+        );
+        assert!(plan.to_create.is_empty());
+        assert!(plan.to_delete.is_empty());
+    }
+}
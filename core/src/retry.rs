@@ -0,0 +1,192 @@
+//! Pure retry bookkeeping, independent of any transport.
+//!
+//! # Design
+//! `RetrySession` is a small state machine: the host reports each failed
+//! attempt and gets back either the delay to wait before the next attempt or
+//! `None` to give up. It never sleeps or performs I/O itself, so the same
+//! logic serves the blocking adapter, async hosts, and C callers.
+//!
+//! Only failures that a later attempt could plausibly fix are retried:
+//! transport errors, 429, and 502/503/504. A server-supplied `Retry-After`
+//! takes precedence over the exponential backoff, capped at `max_delay`.
+//!
+//! Randomness and time come from the caller: `on_failure_at` takes a uniform
+//! sample in `[0, 1)` for jitter and the time elapsed since the first
+//! attempt, so the core needs no RNG or clock and stays deterministic under
+//! test.
+
+use core::time::Duration;
+
+use crate::error::ApiError;
+
+/// How many times to try an operation and how long to wait in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the second attempt; doubled for each one after.
+    pub base_delay: Duration,
+    /// Upper bound on any single delay.
+    pub max_delay: Duration,
+    /// Apply "full jitter": wait a uniformly random fraction of the
+    /// backoff instead of all of it, so many clients failing together do
+    /// not retry in lockstep. Never applied to a server's `Retry-After`.
+    pub jitter: bool,
+    /// Give up once the next attempt would start later than this after the
+    /// first one. `None` bounds retries by `max_attempts` alone.
+    pub max_elapsed: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            jitter: false,
+            max_elapsed: None,
+        }
+    }
+}
+
+/// Tracks attempts for one logical operation under a `RetryPolicy`.
+#[derive(Debug, Clone)]
+pub struct RetrySession {
+    policy: RetryPolicy,
+    attempts: u32,
+}
+
+impl RetrySession {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy, attempts: 0 }
+    }
+
+    /// Attempts recorded so far.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Record a failed attempt. Returns how long to wait before trying
+    /// again, or `None` if `err` is not retryable or the budget is spent.
+    ///
+    /// Ignores `jitter` and `max_elapsed`; use `on_failure_at` for those.
+    pub fn on_failure(&mut self, err: &ApiError) -> Option<Duration> {
+        self.on_failure_at(err, Duration::ZERO, 1.0)
+    }
+
+    /// Like `on_failure`, with caller-supplied time and randomness.
+    ///
+    /// `elapsed` is the time since the first attempt started, measured on
+    /// the host's monotonic clock. `random` is a uniform sample in `[0, 1)`
+    /// that scales the backoff when the policy enables jitter; values
+    /// outside that range are clamped.
+    pub fn on_failure_at(&mut self, err: &ApiError, elapsed: Duration, random: f64) -> Option<Duration> {
+        self.attempts += 1;
+        if self.attempts >= self.policy.max_attempts || !is_retryable(err) {
+            return None;
+        }
+        let delay = match err {
+            ApiError::RateLimited {
+                retry_after: Some(delay),
+            } => (*delay).min(self.policy.max_delay),
+            _ => {
+                let backoff = self.policy.base_delay.saturating_mul(1 << (self.attempts - 1).min(16));
+                let backoff = backoff.min(self.policy.max_delay);
+                if self.policy.jitter {
+                    backoff.mul_f64(random.clamp(0.0, 1.0))
+                } else {
+                    backoff
+                }
+            }
+        };
+        match self.policy.max_elapsed {
+            Some(budget) if elapsed.saturating_add(delay) > budget => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+/// True for failures a later attempt could plausibly fix: transport
+/// errors, 429, and 502/503/504. Everything else, including TLS and auth
+/// failures, is final.
+pub fn is_retryable(err: &ApiError) -> bool {
+    match err {
+        ApiError::Transport(_) | ApiError::RateLimited { .. } => true,
+        ApiError::HttpError { status, .. } => matches!(status, 502..=504),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unavailable() -> ApiError {
+        ApiError::HttpError {
+            status: 503,
+            body: String::new(),
+            request_body: None,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap_until_budget_spent() {
+        let mut session = RetrySession::new(RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(25),
+            ..RetryPolicy::default()
+        });
+        assert_eq!(session.on_failure(&unavailable()), Some(Duration::from_millis(10)));
+        assert_eq!(session.on_failure(&unavailable()), Some(Duration::from_millis(20)));
+        assert_eq!(session.on_failure(&unavailable()), Some(Duration::from_millis(25)));
+        assert_eq!(session.on_failure(&unavailable()), None);
+        assert_eq!(session.attempts(), 4);
+    }
+
+    #[test]
+    fn non_retryable_errors_stop_immediately() {
+        let mut session = RetrySession::new(RetryPolicy::default());
+        assert_eq!(session.on_failure(&ApiError::NotFound), None);
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff() {
+        let mut session = RetrySession::new(RetryPolicy::default());
+        let err = ApiError::RateLimited {
+            retry_after: Some(Duration::from_millis(700)),
+        };
+        assert_eq!(session.on_failure(&err), Some(Duration::from_millis(700)));
+    }
+
+    #[test]
+    fn jitter_scales_backoff_by_caller_sample() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            jitter: true,
+            ..RetryPolicy::default()
+        };
+        let mut session = RetrySession::new(policy);
+        assert_eq!(session.on_failure_at(&unavailable(), Duration::ZERO, 0.25), Some(Duration::from_millis(25)));
+        assert_eq!(session.on_failure_at(&unavailable(), Duration::ZERO, 7.0), Some(Duration::from_millis(200)));
+
+        let mut session = RetrySession::new(policy);
+        let err = ApiError::RateLimited {
+            retry_after: Some(Duration::from_millis(700)),
+        };
+        assert_eq!(session.on_failure_at(&err, Duration::ZERO, 0.0), Some(Duration::from_millis(700)));
+    }
+
+    #[test]
+    fn elapsed_budget_stops_retries() {
+        let mut session = RetrySession::new(RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_elapsed: Some(Duration::from_millis(250)),
+            ..RetryPolicy::default()
+        });
+        let at = Duration::from_millis;
+        assert_eq!(session.on_failure_at(&unavailable(), at(100), 0.5), Some(at(100)));
+        assert_eq!(session.on_failure_at(&unavailable(), at(200), 0.5), None);
+    }
+}
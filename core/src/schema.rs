@@ -0,0 +1,97 @@
+//! Pre-deserialization JSON Schema check for todo response bodies.
+//!
+//! # Design
+//! serde reports the first mismatch as a line/column into the body, which is
+//! hard to act on for large lists. Validating against the schema first
+//! yields a JSON Pointer to the offending value instead, e.g.
+//! `/3/completed`. Validators are compiled once and cached for the life of
+//! the process.
+//!
+//! The schema describes the canonical wire shape, so lenient forms the
+//! deserializer would accept (such as `"completed": 1`) are rejected while
+//! validation is on. `id` may be a string or an integer to cover both
+//! `IdFormat`s.
+
+use std::sync::OnceLock;
+
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use serde_json::{json, Value};
+
+use crate::error::ApiError;
+
+fn todo_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["id", "title", "completed"],
+        "properties": {
+            "id": { "type": ["string", "integer"] },
+            "title": { "type": "string" },
+            "completed": { "type": "boolean" }
+        }
+    })
+}
+
+fn todo_validator() -> &'static Validator {
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| jsonschema::validator_for(&todo_schema()).expect("embedded todo schema is valid"))
+}
+
+fn todo_list_validator() -> &'static Validator {
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let schema = json!({ "type": "array", "items": todo_schema() });
+        jsonschema::validator_for(&schema).expect("embedded todo list schema is valid")
+    })
+}
+
+/// Check `body` against the single-todo schema.
+pub(crate) fn validate_todo(body: &str) -> Result<(), ApiError> {
+    validate(todo_validator(), body)
+}
+
+/// Check `body` against the todo-array schema.
+pub(crate) fn validate_todo_list(body: &str) -> Result<(), ApiError> {
+    validate(todo_list_validator(), body)
+}
+
+/// Report the first schema violation as `Validation("<pointer>: <reason>")`.
+///
+/// A missing property is reported at the property's own path rather than at
+/// the enclosing object, so callers see `/completed` and not `/`.
+fn validate(validator: &Validator, body: &str) -> Result<(), ApiError> {
+    let instance: Value = serde_json::from_str(body).map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+    let Err(err) = validator.validate(&instance) else {
+        return Ok(());
+    };
+    let mut path = err.instance_path().to_string();
+    if let ValidationErrorKind::Required {
+        property: Value::String(property),
+    } = err.kind()
+    {
+        path.push('/');
+        path.push_str(property);
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    Err(ApiError::Validation(format!("{path}: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_todo_passes() {
+        let body = r#"{"id":"00000000-0000-0000-0000-000000000000","title":"ok","completed":false}"#;
+        assert!(validate_todo(body).is_ok());
+    }
+
+    #[test]
+    fn wrong_type_reports_field_path() {
+        let body = r#"{"id":1,"title":7,"completed":false}"#;
+        let err = validate_todo(body).unwrap_err();
+        assert!(matches!(&err, ApiError::Validation(msg) if msg.starts_with("/title: ")), "{err:?}");
+    }
+}
@@ -1,36 +1,253 @@
 //! Domain DTOs for the todo API.
 //!
 //! # Design
-//! These types mirror the mock-server's schema but are defined independently.
-//! The core crate will gain `#[repr(C)]` and FFI attributes in Phase 3;
-//! keeping the types separate avoids coupling the FFI surface to Axum internals.
-//! Integration tests catch any schema drift between the two crates.
+//! The payloads both sides of the wire read and write (`Todo`,
+//! `CreateTodo`, `UpdateTodo`, `TodoRevision`) come from `todo-schema`,
+//! which the mock-server also uses, so the two cannot drift apart; they are
+//! re-exported here. The rest are client-side views (`TodoRef`, `Page`) and
+//! query options.
+
+use alloc::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-/// A single todo item returned by the API.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Todo {
+pub use todo_schema::{normalize_title, CreateTodo, Todo, TodoRevision, UpdateTodo};
+pub(crate) use todo_schema::lenient_bool;
+
+use crate::error::ApiError;
+use crate::http::HttpRequest;
+use crate::prelude::*;
+
+/// A `Todo` whose title borrows from the response body.
+///
+/// Produced by `TodoClient::parse_todos_borrowed`. The title is a
+/// `Cow::Borrowed` slice of the body unless it contains JSON escapes, in
+/// which case serde has to unescape it into an owned `String`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct TodoRef<'a> {
     pub id: Uuid,
-    pub title: String,
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+    #[serde(deserialize_with = "lenient_bool")]
     pub completed: bool,
+    #[serde(default)]
+    pub schema_version: Option<u32>,
 }
 
-/// Request payload for creating a new todo.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateTodo {
-    pub title: String,
-    #[serde(default)]
-    pub completed: bool,
+impl TodoRef<'_> {
+    /// Copy into an owned `Todo` that no longer borrows the body.
+    pub fn to_owned(&self) -> Todo {
+        Todo {
+            id: self.id,
+            title: self.title.clone().into_owned(),
+            completed: self.completed,
+            schema_version: self.schema_version,
+        }
+    }
+}
+
+/// Criteria for narrowing a todo query. `None` fields match everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TodoFilter {
+    pub completed: Option<bool>,
+}
+
+impl TodoFilter {
+    /// Query parameters for the set fields; empty when nothing is filtered.
+    pub fn query(&self) -> Vec<(String, String)> {
+        self.completed
+            .map(|completed| ("completed".to_string(), completed.to_string()))
+            .into_iter()
+            .collect()
+    }
 }
 
-/// Request payload for updating an existing todo. Only the fields present in
-/// the JSON are applied; omitted fields remain unchanged on the server.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UpdateTodo {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub title: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+/// Filters for `TodoClient::build_list_todos_filtered`. `None` fields match
+/// everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFilter {
     pub completed: Option<bool>,
+    /// Case-sensitive substring the title must contain.
+    pub title_contains: Option<String>,
+}
+
+impl ListFilter {
+    /// Query parameters for the set fields, unencoded.
+    pub fn query(&self) -> Vec<(String, String)> {
+        let mut query = TodoFilter {
+            completed: self.completed,
+        }
+        .query();
+        if let Some(needle) = &self.title_contains {
+            query.push(("title_contains".to_string(), needle.clone()));
+        }
+        query
+    }
+}
+
+/// Options for `TodoClient::build_search_todos`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Cap on the number of hits; the server default when `None`.
+    pub limit: Option<u32>,
+    /// Ask the server for `SearchHit::highlight` snippets.
+    pub highlight: bool,
+}
+
+impl SearchOptions {
+    /// Query parameters for the set options, unencoded.
+    pub fn query(&self) -> Vec<(String, String)> {
+        let mut query = Vec::new();
+        if let Some(limit) = self.limit {
+            query.push(("limit".to_string(), limit.to_string()));
+        }
+        if self.highlight {
+            query.push(("highlight".to_string(), "true".to_string()));
+        }
+        query
+    }
+}
+
+/// One search result: the todo plus its relevance. The wire form is the
+/// todo object with extra `score` and optional `highlight` keys.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub todo: Todo,
+    /// Server-assigned relevance; higher is better.
+    pub score: f64,
+    /// Title with matches wrapped in `<em>`, when highlighting was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+/// Field to order a listing by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Title,
+    Completed,
+    /// Server-side creation time; todos carry no timestamp of their own.
+    CreatedAt,
+}
+
+impl SortBy {
+    /// Value of the `sort` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortBy::Title => "title",
+            SortBy::Completed => "completed",
+            SortBy::CreatedAt => "created_at",
+        }
+    }
+}
+
+/// Direction for `SortBy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    /// Value of the `order` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+/// Limit/offset window for `TodoClient::build_list_todos_page`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageParams {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// One page of a limit/offset listing.
+///
+/// `total`, `next` and `prev` are whatever the server reported, either in
+/// a wrapper body or via `X-Total-Count` and `Link` headers. `next` and
+/// `prev` are links as sent, typically paths relative to the base URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: Option<u64>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+/// Response body for `GET /todos/count`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TodoCount {
+    pub count: u64,
+}
+
+/// How todo ids appear on the wire.
+///
+/// `Todo::id` stays a `Uuid` under both formats. An integer id `n` maps to
+/// `Uuid::from_u128(n)`, which is deterministic and reversible, so builders
+/// render it back as `n` in request paths and the rest of the crate (and the
+/// FFI layer) never has to know which format the server speaks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdFormat {
+    #[default]
+    Uuid,
+    Integer,
+}
+
+/// What the server understands, as reported by `GET /capabilities`.
+///
+/// Lets a newer client degrade gracefully against an older server by
+/// dropping fields the server would reject or silently ignore.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Capabilities {
+    pub schema_version: u32,
+    pub supported_fields: Vec<String>,
+}
+
+impl Capabilities {
+    /// Drop top-level JSON body fields the server does not support.
+    ///
+    /// Requests without a body, or with a non-object body, pass through
+    /// unchanged.
+    pub fn restrict_to_fields(&self, mut req: HttpRequest) -> Result<HttpRequest, ApiError> {
+        let Some(body) = req.body.as_deref() else {
+            return Ok(req);
+        };
+        let mut value: serde_json::Value =
+            serde_json::from_str(body).map_err(|e| ApiError::SerializationError(e.to_string()))?;
+        let Some(fields) = value.as_object_mut() else {
+            return Ok(req);
+        };
+        fields.retain(|key, _| self.supported_fields.iter().any(|f| f == key));
+        req.body = Some(value.to_string());
+        Ok(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restrict_to_fields_drops_unsupported() {
+        let caps = Capabilities {
+            schema_version: 1,
+            supported_fields: vec!["id".to_string(), "title".to_string(), "completed".to_string()],
+        };
+        let req = HttpRequest {
+            query: Vec::new(),
+            method: crate::http::HttpMethod::Post,
+            path: "http://localhost:3000/todos".to_string(),
+            headers: Vec::new(),
+            body: Some(r#"{"title":"Ship","completed":false,"priority":3}"#.to_string()),
+        };
+        let req = caps.restrict_to_fields(req).unwrap();
+        let body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({"title": "Ship", "completed": false}));
+    }
 }
@@ -0,0 +1,63 @@
+//! `BlockingTodoClient` against the live mock server.
+//!
+//! # Design
+//! Only built with the `blocking` feature. Uses the mock server's
+//! error-injection mode to produce transient failures on demand.
+#![cfg(feature = "blocking")]
+
+use std::time::Duration;
+
+use todo_core::{ApiError, BlockingTodoClient, CreateTodo, HttpMethod, RetryPolicy, TodoClient};
+use todo_test_support::server::spawn_server;
+
+/// Make the next `count` requests fail with 503.
+fn inject_503s(client: &TodoClient, count: u32) {
+    let req = client.build_raw(HttpMethod::Post, "/_faults", Some(format!(r#"{{"count":{count},"status":503}}"#)));
+    ureq::post(&req.path)
+        .content_type("application/json")
+        .send(req.body.unwrap().as_bytes())
+        .unwrap();
+}
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(5),
+        max_delay: Duration::from_millis(20),
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn transient_503_is_retried_to_success() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    let blocking = BlockingTodoClient::new(client.clone()).with_retries(fast_policy(3));
+
+    let created = blocking.create_todo(&CreateTodo::new("Retry me", false)).unwrap();
+    inject_503s(&client, 2);
+    assert_eq!(blocking.get_todo(created.id).unwrap(), created);
+}
+
+#[test]
+fn tls_handshake_failure_is_tls_error() {
+    // The mock server only speaks plain HTTP, so an https:// client fails
+    // the handshake the same way it would against a misconfigured server.
+    let server = spawn_server();
+    let base_url = server.base_url.replacen("http://", "https://", 1);
+    let blocking = BlockingTodoClient::new(TodoClient::new(&base_url)).with_retries(fast_policy(3));
+
+    let err = blocking.list_todos().unwrap_err();
+    assert!(matches!(err, ApiError::Tls(_)), "{err:?}");
+}
+
+#[test]
+fn retries_stop_at_policy_limit() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    let blocking = BlockingTodoClient::new(client.clone()).with_retries(fast_policy(2));
+
+    inject_503s(&client, 2);
+    let err = blocking.list_todos().unwrap_err();
+    assert!(matches!(err, ApiError::HttpError { status: 503, .. }));
+}
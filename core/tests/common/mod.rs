@@ -0,0 +1,22 @@
+//! Helpers shared by the vector and contract tests.
+
+use todo_core::ApiError;
+use todo_test_support::ErrorKind;
+
+/// The vector spelling of an error, named like the `FfiErrorCode` variants.
+pub fn error_kind(err: &ApiError) -> ErrorKind {
+    match err {
+        ApiError::Unauthorized => ErrorKind::Unauthorized,
+        ApiError::NotFound | ApiError::NotFoundId(_) => ErrorKind::NotFound,
+        ApiError::Conflict(_) => ErrorKind::Conflict,
+        ApiError::PreconditionFailed => ErrorKind::PreconditionFailed,
+        ApiError::HttpError { .. } => ErrorKind::Http,
+        ApiError::DeserializationError(_) => ErrorKind::Deserialization,
+        ApiError::SerializationError(_) => ErrorKind::Serialization,
+        ApiError::Validation(_) => ErrorKind::Validation,
+        ApiError::RateLimited { .. } => ErrorKind::RateLimited,
+        ApiError::SchemaMismatch { .. } => ErrorKind::SchemaMismatch,
+        ApiError::Transport(_) => ErrorKind::Transport,
+        ApiError::Tls(_) => ErrorKind::Tls,
+    }
+}
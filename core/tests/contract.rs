@@ -0,0 +1,82 @@
+//! Replay recorded server traffic through the `parse_*` methods.
+//!
+//! # Design
+//! The HAR files in `test-vectors/captures/` are meant to hold what real
+//! deployments answered, quirks included (extra fields, `0`/`1` booleans,
+//! HTML error pages from a proxy), so the client is checked against more
+//! than the mock server. The only one so far, `example.har`, is a
+//! hand-written stand-in with such quirks until a real capture is checked
+//! in. Only responses are replayed; the recorded requests came from
+//! whatever client made the capture and are not compared.
+//!
+//! An entry passes when a 2xx parses and any other status fails with an
+//! error that maps back to that status, unless it names the error it is
+//! known to produce in `_expect_error`. Entries outside the API are
+//! skipped. Add a capture by exporting HAR from a browser or proxy and
+//! dropping it in the directory; `todo-test-support` rejects files that
+//! still carry credentials.
+
+mod common;
+
+use common::error_kind;
+use todo_core::{ApiError, HttpResponse, TodoClient};
+use todo_test_support::har::{self, Entry, Route};
+
+/// Run the entry's response through the parser for its route, keeping only
+/// whether it parsed.
+fn replay(client: &TodoClient, route: Route, entry: &Entry) -> Result<(), ApiError> {
+    let response = HttpResponse {
+        status: entry.response.status,
+        headers: entry.response_headers(),
+        body: entry.response_body().to_string(),
+    };
+    match route {
+        Route::ListTodos => client.parse_list_todos(response).map(drop),
+        Route::CreateTodo => client.parse_create_todo(response).map(drop),
+        Route::DeleteAll => client.parse_delete_all(response).map(drop),
+        Route::CreateTodosBatch => client.parse_create_todos_batch(response).map(drop),
+        Route::CountTodos => client.parse_count_todos(response).map(drop),
+        Route::SearchTodos => client.parse_search_todos(response).map(drop),
+        Route::GetTodo => client.parse_get_todo(response).map(drop),
+        Route::UpdateTodo => client.parse_update_todo(response).map(drop),
+        Route::PatchTodo => client.parse_patch_todo(response).map(drop),
+        Route::DeleteTodo => client.parse_delete_todo(response),
+        Route::History => client.parse_history(response).map(drop),
+        Route::Capabilities => client.parse_capabilities(response).map(drop),
+    }
+}
+
+/// Why `entry` did not replay as expected, or `None` if it did.
+fn mismatch(route: Route, entry: &Entry, outcome: Result<(), ApiError>) -> Option<String> {
+    let status = entry.response.status;
+    match (outcome, entry.expect_error) {
+        (Err(err), Some(kind)) if error_kind(&err) == kind => None,
+        (Err(err), Some(kind)) => Some(format!("expected {kind:?}, got {err:?}")),
+        (Ok(()), Some(kind)) => Some(format!("expected {kind:?}, but {route:?} parsed")),
+        (Ok(()), None) if (200..300).contains(&status) => None,
+        (Ok(()), None) => Some(format!("{route:?} accepted status {status}")),
+        (Err(err), None) if (200..300).contains(&status) => Some(format!("{route:?} rejected it: {err}")),
+        (Err(err), None) if err.status_code() == status => None,
+        (Err(err), None) => Some(format!("{err:?} does not map back to status {status}")),
+    }
+}
+
+#[test]
+fn captured_traffic_replays() {
+    let client = TodoClient::new("http://localhost:3000");
+    let mut failures = Vec::new();
+    let mut replayed = 0;
+    for (file, capture) in har::load_dir(&har::captures_dir()).unwrap() {
+        for (i, entry) in capture.log.entries.iter().enumerate() {
+            let Some(route) = entry.route() else {
+                continue;
+            };
+            replayed += 1;
+            if let Some(problem) = mismatch(route, entry, replay(&client, route, entry)) {
+                failures.push(format!("{file} entry {i} ({} {}): {problem}", entry.request.method, entry.request.url));
+            }
+        }
+    }
+    assert!(replayed > 0, "no capture entry matched an API route");
+    assert!(failures.is_empty(), "{} entries failed:\n{}", failures.len(), failures.join("\n"));
+}
@@ -5,7 +5,11 @@
 //! operation over real HTTP using ureq. Validates that the core's request
 //! building and response parsing work end-to-end with the actual server.
 
-use todo_core::{ApiError, CreateTodo, HttpMethod, HttpResponse, TodoClient, UpdateTodo};
+use todo_core::{
+    ApiError, Auth, CreateTodo, HttpMethod, HttpResponse, ListFilter, ListTodosPaginator, PageParams, SearchOptions, SortBy, SortOrder,
+    TodoClient, UpdateTodo,
+};
+use todo_test_support::server::{spawn_server, spawn_server_with};
 
 /// Execute an `HttpRequest` using ureq and return an `HttpResponse`.
 ///
@@ -18,50 +22,81 @@ fn execute(req: todo_core::HttpRequest) -> HttpResponse {
         .build()
         .new_agent();
 
+    let url = req.url();
+    let headers = &req.headers;
     let mut response = match (req.method, req.body) {
-        (HttpMethod::Get, _) => agent.get(&req.path).call(),
-        (HttpMethod::Delete, _) => agent.delete(&req.path).call(),
-        (HttpMethod::Post, Some(body)) => {
-            agent.post(&req.path).content_type("application/json").send(body.as_bytes())
-        }
-        (HttpMethod::Post, None) => agent.post(&req.path).send_empty(),
-        (HttpMethod::Put, Some(body)) => {
-            agent.put(&req.path).content_type("application/json").send(body.as_bytes())
-        }
-        (HttpMethod::Put, None) => agent.put(&req.path).send_empty(),
+        (HttpMethod::Get, _) => with_headers(agent.get(&url), headers).call(),
+        (HttpMethod::Delete, _) => with_headers(agent.delete(&url), headers).call(),
+        (HttpMethod::Head, _) => with_headers(agent.head(&url), headers).call(),
+        (HttpMethod::Options, _) => with_headers(agent.options(&url), headers).call(),
+        (HttpMethod::Trace, _) => with_headers(agent.trace(&url), headers).call(),
+        (HttpMethod::Post, Some(body)) => with_headers(agent.post(&url), headers).send(body.as_bytes()),
+        (HttpMethod::Post, None) => with_headers(agent.post(&url), headers).send_empty(),
+        (HttpMethod::Put, Some(body)) => with_headers(agent.put(&url), headers).send(body.as_bytes()),
+        (HttpMethod::Put, None) => with_headers(agent.put(&url), headers).send_empty(),
+        (HttpMethod::Patch, Some(body)) => with_headers(agent.patch(&url), headers).send(body.as_bytes()),
+        (HttpMethod::Patch, None) => with_headers(agent.patch(&url), headers).send_empty(),
     }
     .expect("HTTP transport error");
 
     let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
     let body = response.body_mut().read_to_string().unwrap_or_default();
 
-    HttpResponse {
-        status,
-        headers: Vec::new(),
-        body,
+    HttpResponse { status, headers, body }
+}
+
+/// Copy the core-built headers (content-type included) onto a ureq request.
+fn with_headers<B>(mut builder: ureq::RequestBuilder<B>, headers: &[(String, String)]) -> ureq::RequestBuilder<B> {
+    for (name, value) in headers {
+        builder = builder.header(name, value);
     }
+    builder
 }
 
 #[test]
-fn crud_lifecycle() {
-    // Step 1: start mock server on a random port.
-    let std_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
-    let addr = std_listener.local_addr().unwrap();
-    std_listener.set_nonblocking(true).unwrap();
-
-    std::thread::spawn(move || {
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap();
-        rt.block_on(async {
-            let listener = tokio::net::TcpListener::from_std(std_listener).unwrap();
-            mock_server::run(listener).await
-        })
-        .unwrap();
+fn bearer_token_is_required_when_configured() {
+    let server = spawn_server_with(mock_server::Config {
+        auth_tokens: Some(vec!["s3cret".to_string()]),
+        ..mock_server::Config::default()
+    });
+
+    let anonymous = TodoClient::new(&server.base_url);
+    let err = anonymous.parse_list_todos(execute(anonymous.build_list_todos())).unwrap_err();
+    assert!(matches!(err, ApiError::Unauthorized), "{err:?}");
+
+    let client = TodoClient::new(&server.base_url).with_auth(Auth::Bearer("s3cret".to_string())).unwrap();
+    assert!(client.parse_list_todos(execute(client.build_list_todos())).unwrap().is_empty());
+}
+
+#[test]
+fn duplicate_title_is_a_conflict_when_titles_are_unique() {
+    let server = spawn_server_with(mock_server::Config {
+        unique_titles: true,
+        ..mock_server::Config::default()
     });
+    let client = TodoClient::new(&server.base_url);
+    let create = |title: &str| client.parse_create_todo(execute(client.build_create_todo(&CreateTodo::new(title, false)).unwrap()));
 
-    let client = TodoClient::new(&format!("http://{addr}"));
+    let first = create("Buy milk").unwrap();
+    let err = create("  Buy   milk ").unwrap_err();
+    let ApiError::Conflict(body) = err else {
+        panic!("expected a conflict, got {err:?}");
+    };
+    let problem: mock_server::Problem = serde_json::from_str(&body).unwrap();
+    assert_eq!((problem.status, problem.conflicts_with), (409, Some(first.id)));
+    assert!(create("Buy bread").is_ok());
+}
+
+#[test]
+fn crud_lifecycle() {
+    // Step 1: start mock server on a random port.
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
 
     // Step 2: list — should be empty.
     let req = client.build_list_todos();
@@ -128,3 +163,288 @@ fn crud_lifecycle() {
     let todos = client.parse_list_todos(execute(req)).unwrap();
     assert!(todos.is_empty(), "expected empty list after delete");
 }
+
+#[test]
+fn delete_returning_echoes_removed_todo() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Short-lived", true)).unwrap();
+    let created = client.parse_create_todo(execute(req)).unwrap();
+
+    let req = client.build_delete_todo_returning(created.id);
+    let deleted = client.parse_delete_todo_returning(execute(req)).unwrap();
+    assert_eq!(deleted, Some(created.clone()));
+
+    let req = client.build_get_todo(created.id);
+    let err = client.parse_get_todo(execute(req)).unwrap_err();
+    assert!(matches!(err, ApiError::NotFound));
+}
+
+#[test]
+fn stale_etag_is_rejected() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Contended", false)).unwrap();
+    let created = client.parse_create_todo(execute(req)).unwrap();
+
+    let response = execute(client.build_get_todo(created.id));
+    let etag = response.header("etag").unwrap().to_string();
+
+    let rename = UpdateTodo {
+        title: Some("Renamed".to_string()),
+        completed: None,
+    };
+    let req = client.build_update_todo_if_match(created.id, &rename, &etag).unwrap();
+    let response = execute(req);
+    let fresh_etag = response.header("etag").unwrap().to_string();
+    assert_ne!(fresh_etag, etag);
+    assert_eq!(client.parse_update_todo(response).unwrap().title, "Renamed");
+
+    let req = client.build_update_todo_if_match(created.id, &rename, &etag).unwrap();
+    let err = client.parse_update_todo(execute(req)).unwrap_err();
+    assert!(matches!(err, ApiError::PreconditionFailed));
+
+    let req = client.build_delete_todo_if_match(created.id, &etag);
+    let err = client.parse_delete_todo(execute(req)).unwrap_err();
+    assert!(matches!(err, ApiError::PreconditionFailed));
+
+    let req = client.build_delete_todo_if_match(created.id, &fresh_etag);
+    client.parse_delete_todo(execute(req)).unwrap();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn repeated_idempotency_key_creates_once() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let (req, _key) = client
+        .build_create_todo_idempotent(&CreateTodo::new("Exactly once", false), None)
+        .unwrap();
+    let first = client.parse_create_todo(execute(req.clone())).unwrap();
+    let retried = client.parse_create_todo(execute(req)).unwrap();
+    assert_eq!(retried, first);
+
+    let todos = client.parse_list_todos(execute(client.build_list_todos())).unwrap();
+    assert_eq!(todos, vec![first]);
+}
+
+#[test]
+fn head_reports_existence_without_body() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Present", false)).unwrap();
+    let created = client.parse_create_todo(execute(req)).unwrap();
+
+    let response = execute(client.build_todo_exists(created.id));
+    assert!(response.body.is_empty());
+    assert!(client.parse_todo_exists(response).unwrap());
+
+    let response = execute(client.build_todo_exists(uuid::Uuid::new_v4()));
+    assert!(!client.parse_todo_exists(response).unwrap());
+}
+
+#[test]
+fn search_ranks_and_highlights_matches() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    for title in ["Buy milk", "Buy oat milk", "Walk dog"] {
+        let req = client.build_create_todo(&CreateTodo::new(title, false)).unwrap();
+        client.parse_create_todo(execute(req)).unwrap();
+    }
+
+    let opts = SearchOptions {
+        limit: None,
+        highlight: true,
+    };
+    let hits = client
+        .parse_search_todos(execute(client.build_search_todos("OAT milk", opts)))
+        .unwrap();
+    let titles: Vec<_> = hits.iter().map(|h| h.todo.title.as_str()).collect();
+    assert_eq!(titles, ["Buy oat milk", "Buy milk"]);
+    assert!(hits[0].score > hits[1].score);
+    assert_eq!(hits[0].highlight.as_deref(), Some("Buy <em>oat</em> <em>milk</em>"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn correlation_id_is_echoed() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Traced", false)).unwrap();
+    let created = client.parse_create_todo(execute(req)).unwrap();
+
+    let (req, correlation_id) = client.build_get_todo_traced(created.id);
+    let response = execute(req);
+    assert_eq!(response.header("x-correlation-id"), Some(correlation_id.as_str()));
+    assert_eq!(client.parse_get_todo(response).unwrap(), created);
+}
+
+#[test]
+fn create_and_list_returns_both() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("First", false)).unwrap();
+    let first = client.parse_create_todo(execute(req)).unwrap();
+
+    let req = client.build_create_and_list(&CreateTodo::new("Second", true)).unwrap();
+    let (created, list) = client.parse_create_and_list(execute(req)).unwrap();
+    assert_eq!(created.title, "Second");
+    assert!(created.completed);
+    assert_eq!(list, vec![first, created]);
+}
+
+#[test]
+fn cursor_pagination_walks_to_exhaustion() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let mut created = Vec::new();
+    for title in ["one", "two", "three", "four", "five"] {
+        let req = client.build_create_todo(&CreateTodo::new(title, false)).unwrap();
+        created.push(client.parse_create_todo(execute(req)).unwrap());
+    }
+
+    let mut paginator = ListTodosPaginator::new(client).with_page_size(2);
+    let mut seen = Vec::new();
+    let mut pages = 0;
+    while let Some(req) = paginator.next_request() {
+        seen.extend(paginator.handle_response(execute(req)).unwrap());
+        pages += 1;
+    }
+
+    assert_eq!(pages, 3);
+    assert_eq!(seen, created, "pages should follow insertion order");
+}
+
+#[test]
+fn patch_updates_only_sent_fields() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Keep title", false)).unwrap();
+    let created = client.parse_create_todo(execute(req)).unwrap();
+
+    let update = UpdateTodo {
+        title: None,
+        completed: Some(true),
+    };
+    let req = client.build_patch_todo(created.id, &update).unwrap();
+    let patched = client.parse_patch_todo(execute(req)).unwrap();
+    assert_eq!(patched.title, "Keep title");
+    assert!(patched.completed);
+}
+
+#[test]
+fn filtered_list_matches_completed_and_title() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    for (title, completed) in [("Buy milk & eggs", true), ("Buy bread", true), ("Walk dog", false)] {
+        let req = client.build_create_todo(&CreateTodo::new(title, completed)).unwrap();
+        client.parse_create_todo(execute(req)).unwrap();
+    }
+
+    let filter = ListFilter {
+        completed: Some(true),
+        title_contains: Some("milk & eggs".to_string()),
+    };
+    let todos = client.parse_list_todos(execute(client.build_list_todos_filtered(&filter))).unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].title, "Buy milk & eggs");
+}
+
+#[test]
+fn offset_pages_report_total_and_links() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    for title in ["one", "two", "three"] {
+        let req = client.build_create_todo(&CreateTodo::new(title, false)).unwrap();
+        client.parse_create_todo(execute(req)).unwrap();
+    }
+
+    let req = client.build_list_todos_page(PageParams { limit: 2, offset: 0 });
+    let first = client.parse_list_todos_page(execute(req)).unwrap();
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.total, Some(3));
+    assert_eq!(first.next.as_deref(), Some("/todos?limit=2&offset=2"));
+    assert_eq!(first.prev, None);
+
+    let req = client.build_list_todos_page(PageParams { limit: 2, offset: 2 });
+    let last = client.parse_list_todos_page(execute(req)).unwrap();
+    assert_eq!(last.items[0].title, "three");
+    assert_eq!(last.next, None);
+    assert_eq!(last.prev.as_deref(), Some("/todos?limit=2&offset=0"));
+}
+
+#[test]
+fn sorted_list_follows_requested_order() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    for title in ["banana", "cherry", "apple"] {
+        let req = client.build_create_todo(&CreateTodo::new(title, false)).unwrap();
+        client.parse_create_todo(execute(req)).unwrap();
+    }
+
+    let titles = |sort_by, order| -> Vec<String> {
+        let req = client.build_list_todos_sorted(sort_by, order);
+        client.parse_list_todos(execute(req)).unwrap().into_iter().map(|t| t.title).collect()
+    };
+    assert_eq!(titles(SortBy::Title, SortOrder::Desc), ["cherry", "banana", "apple"]);
+    assert_eq!(titles(SortBy::CreatedAt, SortOrder::Asc), ["banana", "cherry", "apple"]);
+}
+
+#[test]
+fn batch_create_returns_todos_in_order() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+    let inputs: Vec<_> = (0..50).map(|i| CreateTodo::new(&format!("todo {i}"), i % 2 == 0)).collect();
+
+    let req = client.build_create_todos_batch(&inputs).unwrap();
+    let created = client.parse_create_todos_batch(execute(req)).unwrap();
+    assert_eq!(created.len(), 50);
+    for (input, todo) in inputs.iter().zip(&created) {
+        assert_eq!(todo.title, input.title);
+        assert_eq!(todo.completed, input.completed);
+    }
+
+    let listed = client.parse_list_todos(execute(client.build_list_todos())).unwrap();
+    assert_eq!(listed.len(), 50);
+}
+
+#[test]
+fn history_records_every_revision() {
+    let server = spawn_server();
+    let client = TodoClient::new(&server.base_url);
+
+    let req = client.build_create_todo(&CreateTodo::new("Draft", false)).unwrap();
+    let id = client.parse_create_todo(execute(req)).unwrap().id;
+
+    for update in [
+        UpdateTodo {
+            title: Some("Final".to_string()),
+            completed: None,
+        },
+        UpdateTodo {
+            title: None,
+            completed: Some(true),
+        },
+    ] {
+        let req = client.build_update_todo(id, &update).unwrap();
+        client.parse_update_todo(execute(req)).unwrap();
+    }
+
+    let history = client.parse_history(execute(client.build_get_history(id))).unwrap();
+    let revisions: Vec<(u64, &str, bool)> =
+        history.iter().map(|r| (r.revision, r.title.as_str(), r.completed)).collect();
+    assert_eq!(
+        revisions,
+        vec![(1, "Draft", false), (2, "Final", false), (3, "Final", true)]
+    );
+    assert!(history.windows(2).all(|w| w[0].changed_at <= w[1].changed_at));
+}
@@ -0,0 +1,100 @@
+//! Property tests: values survive build → serialize → parse unchanged.
+//!
+//! # Design
+//! The server side of each round trip is played by `serde_json`, the same
+//! serializer the mock server uses, so a failure points at the client's
+//! encoding rather than at a disagreement between JSON libraries. Titles
+//! are arbitrary Unicode, with control characters, quotes, backslashes and
+//! astral-plane characters weighted in; ids include the nil and max UUIDs.
+
+use proptest::prelude::*;
+use todo_core::{CreateTodo, HttpMethod, HttpResponse, Todo, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+const BASE_URL: &str = "http://localhost:3000";
+
+fn uuid() -> impl Strategy<Value = Uuid> {
+    prop_oneof![
+        Just(Uuid::nil()),
+        Just(Uuid::max()),
+        any::<u128>().prop_map(Uuid::from_u128),
+        any::<[u8; 16]>().prop_map(|bytes| uuid::Builder::from_random_bytes(bytes).into_uuid()),
+    ]
+}
+
+fn title() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "[\"\\\\\u{0}-\u{1f}\u{7f}\u{2028}\u{feff}\u{1f600}a ]{0,16}",
+        Just(String::new()),
+    ]
+}
+
+fn todo() -> impl Strategy<Value = Todo> {
+    (uuid(), title(), any::<bool>()).prop_map(|(id, title, completed)| Todo { id, title, completed, schema_version: None })
+}
+
+fn create_todo() -> impl Strategy<Value = CreateTodo> {
+    (title(), any::<bool>()).prop_map(|(title, completed)| CreateTodo { title, completed })
+}
+
+fn update_todo() -> impl Strategy<Value = UpdateTodo> {
+    (proptest::option::of(title()), proptest::option::of(any::<bool>()))
+        .prop_map(|(title, completed)| UpdateTodo { title, completed })
+}
+
+fn response(status: u16, body: String) -> HttpResponse {
+    HttpResponse { status, headers: Vec::new(), body }
+}
+
+proptest! {
+    #[test]
+    fn create_body_decodes_to_the_input(input in create_todo()) {
+        let req = TodoClient::new(BASE_URL).build_create_todo(&input).unwrap();
+        prop_assert_eq!(req.method, HttpMethod::Post);
+        let decoded: CreateTodo = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        prop_assert_eq!((decoded.title, decoded.completed), (input.title, input.completed));
+    }
+
+    #[test]
+    fn update_body_decodes_to_the_input(id in uuid(), input in update_todo()) {
+        let req = TodoClient::new(BASE_URL).build_update_todo(id, &input).unwrap();
+        prop_assert_eq!(req.path, format!("{BASE_URL}/todos/{id}"));
+        let decoded: UpdateTodo = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        prop_assert_eq!((decoded.title, decoded.completed), (input.title, input.completed));
+    }
+
+    #[test]
+    fn get_and_delete_paths_carry_the_id(id in uuid()) {
+        let client = TodoClient::new(BASE_URL);
+        let path = format!("{BASE_URL}/todos/{id}");
+        prop_assert_eq!(client.build_get_todo(id).path, path.clone());
+        prop_assert_eq!(client.build_delete_todo(id).path, path);
+    }
+
+    #[test]
+    fn served_todo_parses_back(todo in todo()) {
+        let client = TodoClient::new(BASE_URL);
+        let body = serde_json::to_string(&todo).unwrap();
+        prop_assert_eq!(client.parse_get_todo(response(200, body.clone())).unwrap(), todo.clone());
+        prop_assert_eq!(client.parse_create_todo(response(201, body.clone())).unwrap(), todo.clone());
+        prop_assert_eq!(client.parse_update_todo(response(200, body)).unwrap(), todo);
+    }
+
+    #[test]
+    fn served_list_parses_back(todos in proptest::collection::vec(todo(), 0..8)) {
+        let body = serde_json::to_string(&todos).unwrap();
+        prop_assert_eq!(TodoClient::new(BASE_URL).parse_list_todos(response(200, body)).unwrap(), todos);
+    }
+
+    #[test]
+    fn created_todo_round_trips_through_a_server(input in create_todo(), id in uuid()) {
+        // Build, let a server echo the body back with an id, parse.
+        let client = TodoClient::new(BASE_URL);
+        let req = client.build_create_todo(&input).unwrap();
+        let stored: CreateTodo = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
+        let served = Todo { id, title: stored.title, completed: stored.completed, schema_version: None };
+        let parsed = client.parse_create_todo(response(201, serde_json::to_string(&served).unwrap())).unwrap();
+        prop_assert_eq!((parsed.id, parsed.title, parsed.completed), (id, input.title, input.completed));
+    }
+}
@@ -1,10 +1,15 @@
 //! Verify build/parse methods against JSON test vectors stored in `test-vectors/`.
 //!
 //! Each vector file describes inputs, expected requests, simulated responses,
-//! and expected parse results. Comparing parsed JSON (not raw strings) avoids
-//! false negatives from field-ordering differences.
+//! and expected parse results; `todo-test-support` loads and validates them.
+//! Comparing parsed JSON (not raw strings) avoids false negatives from
+//! field-ordering differences.
 
-use todo_core::{ApiError, CreateTodo, HttpMethod, HttpResponse, Todo, TodoClient, UpdateTodo};
+mod common;
+
+use common::error_kind;
+use todo_core::{ApiError, CreateTodo, HttpRequest, HttpResponse, Todo, TodoClient, UpdateTodo};
+use todo_test_support::{ExpectedResult, ExpectedTodo, TestCase, TestVector};
 use uuid::Uuid;
 
 const BASE_URL: &str = "http://localhost:3000";
@@ -13,14 +18,66 @@ fn client() -> TodoClient {
     TodoClient::new(BASE_URL)
 }
 
-/// Parse the method string from test vectors into `HttpMethod`.
-fn parse_method(s: &str) -> HttpMethod {
-    match s {
-        "GET" => HttpMethod::Get,
-        "POST" => HttpMethod::Post,
-        "PUT" => HttpMethod::Put,
-        "DELETE" => HttpMethod::Delete,
-        other => panic!("unknown method: {other}"),
+fn vector(source: &str) -> TestVector {
+    todo_test_support::parse(source).unwrap()
+}
+
+fn input_id(case: &TestCase) -> Uuid {
+    case.input_id.as_deref().unwrap().parse().unwrap()
+}
+
+fn check_request(case: &TestCase, req: &HttpRequest) {
+    let name = &case.name;
+    let expected = &case.expected_request;
+    assert_eq!(req.method, expected.method.parse().unwrap(), "{name}: method");
+    assert_eq!(req.url(), format!("{BASE_URL}{}", expected.path), "{name}: path");
+    assert_eq!(req.headers, expected.headers, "{name}: headers");
+    let body = req.body.as_deref().map(|body| serde_json::from_str(body).unwrap());
+    assert_eq!(body, expected.body, "{name}: body");
+}
+
+fn response(case: &TestCase) -> HttpResponse {
+    HttpResponse {
+        status: case.simulated_response.status,
+        headers: case.simulated_response.headers.clone(),
+        body: case.simulated_response.body.clone(),
+    }
+}
+
+/// The status an error came from, as the C ABI reports it in `http_status`.
+fn http_status(err: &ApiError) -> Option<u16> {
+    Some(match err {
+        ApiError::Unauthorized => 401,
+        ApiError::NotFound | ApiError::NotFoundId(_) => 404,
+        ApiError::Conflict(_) => 409,
+        ApiError::PreconditionFailed => 412,
+        ApiError::RateLimited { .. } => 429,
+        ApiError::HttpError { status, .. } => *status,
+        _ => return None,
+    })
+}
+
+fn fields(todo: &Todo) -> ExpectedTodo {
+    ExpectedTodo {
+        id: todo.id.to_string(),
+        title: todo.title.clone(),
+        completed: todo.completed,
+    }
+}
+
+/// Check a parse outcome against the case's `expected_error` or
+/// `expected_result`, with `result` mapping the success value into the
+/// vector's shape.
+fn check_outcome<T>(case: &TestCase, outcome: Result<T, ApiError>, result: impl FnOnce(T) -> Option<ExpectedResult>) {
+    let name = &case.name;
+    match (outcome, case.expected_error) {
+        (Err(err), Some(kind)) => {
+            assert_eq!(error_kind(&err), kind, "{name}: error {err:?}");
+            assert_eq!(http_status(&err), case.expected_http_status, "{name}: status of {err:?}");
+        }
+        (Err(err), None) => panic!("{name}: unexpected error {err:?}"),
+        (Ok(_), Some(kind)) => panic!("{name}: expected {kind:?}"),
+        (Ok(value), None) => assert_eq!(result(value), case.expected_result, "{name}: parsed result"),
     }
 }
 
@@ -30,44 +87,16 @@ fn parse_method(s: &str) -> HttpMethod {
 
 #[test]
 fn create_test_vectors() {
-    let raw = include_str!("../../test-vectors/create.json");
-    let vectors: serde_json::Value = serde_json::from_str(raw).unwrap();
-
     let c = client();
-    for case in vectors["cases"].as_array().unwrap() {
-        let name = case["name"].as_str().unwrap();
-        let input: CreateTodo = serde_json::from_value(case["input"].clone()).unwrap();
-        let expected_req = &case["expected_request"];
-
-        // Verify build
-        let req = c.build_create_todo(&input).unwrap();
-        assert_eq!(req.method, parse_method(expected_req["method"].as_str().unwrap()), "{name}: method");
-        assert_eq!(req.path, format!("{BASE_URL}{}", expected_req["path"].as_str().unwrap()), "{name}: path");
-
-        let expected_headers: Vec<(String, String)> = expected_req["headers"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|h| {
-                let arr = h.as_array().unwrap();
-                (arr[0].as_str().unwrap().to_string(), arr[1].as_str().unwrap().to_string())
-            })
-            .collect();
-        assert_eq!(req.headers, expected_headers, "{name}: headers");
-
-        let req_body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
-        assert_eq!(req_body, expected_req["body"], "{name}: body");
-
-        // Verify parse
-        let sim = &case["simulated_response"];
-        let response = HttpResponse {
-            status: sim["status"].as_u64().unwrap() as u16,
-            headers: Vec::new(),
-            body: sim["body"].as_str().unwrap().to_string(),
+    for case in vector(include_str!("../../test-vectors/create.json")).cases {
+        let input = case.input.clone().unwrap();
+        let input = CreateTodo {
+            title: input.title.unwrap(),
+            completed: input.completed.unwrap_or(false),
         };
-        let todo = c.parse_create_todo(response).unwrap();
-        let expected: Todo = serde_json::from_value(case["expected_result"].clone()).unwrap();
-        assert_eq!(todo, expected, "{name}: parsed result");
+        check_request(&case, &c.build_create_todo(&input).unwrap());
+        let outcome = c.parse_create_todo(response(&case));
+        check_outcome(&case, outcome, |todo| Some(ExpectedResult::Todo(fields(&todo))));
     }
 }
 
@@ -77,30 +106,11 @@ fn create_test_vectors() {
 
 #[test]
 fn list_test_vectors() {
-    let raw = include_str!("../../test-vectors/list.json");
-    let vectors: serde_json::Value = serde_json::from_str(raw).unwrap();
-
     let c = client();
-    for case in vectors["cases"].as_array().unwrap() {
-        let name = case["name"].as_str().unwrap();
-        let expected_req = &case["expected_request"];
-
-        // Verify build
-        let req = c.build_list_todos();
-        assert_eq!(req.method, parse_method(expected_req["method"].as_str().unwrap()), "{name}: method");
-        assert_eq!(req.path, format!("{BASE_URL}{}", expected_req["path"].as_str().unwrap()), "{name}: path");
-        assert!(req.body.is_none(), "{name}: body should be None");
-
-        // Verify parse
-        let sim = &case["simulated_response"];
-        let response = HttpResponse {
-            status: sim["status"].as_u64().unwrap() as u16,
-            headers: Vec::new(),
-            body: sim["body"].as_str().unwrap().to_string(),
-        };
-        let todos = c.parse_list_todos(response).unwrap();
-        let expected: Vec<Todo> = serde_json::from_value(case["expected_result"].clone()).unwrap();
-        assert_eq!(todos, expected, "{name}: parsed result");
+    for case in vector(include_str!("../../test-vectors/list.json")).cases {
+        check_request(&case, &c.build_list_todos());
+        let outcome = c.parse_list_todos(response(&case));
+        check_outcome(&case, outcome, |todos| Some(ExpectedResult::List(todos.iter().map(fields).collect())));
     }
 }
 
@@ -110,41 +120,11 @@ fn list_test_vectors() {
 
 #[test]
 fn get_test_vectors() {
-    let raw = include_str!("../../test-vectors/get.json");
-    let vectors: serde_json::Value = serde_json::from_str(raw).unwrap();
-
     let c = client();
-    for case in vectors["cases"].as_array().unwrap() {
-        let name = case["name"].as_str().unwrap();
-        let id: Uuid = case["input_id"].as_str().unwrap().parse().unwrap();
-        let expected_req = &case["expected_request"];
-
-        // Verify build
-        let req = c.build_get_todo(id);
-        assert_eq!(req.method, parse_method(expected_req["method"].as_str().unwrap()), "{name}: method");
-        assert_eq!(req.path, format!("{BASE_URL}{}", expected_req["path"].as_str().unwrap()), "{name}: path");
-        assert!(req.body.is_none(), "{name}: body should be None");
-
-        // Verify parse
-        let sim = &case["simulated_response"];
-        let response = HttpResponse {
-            status: sim["status"].as_u64().unwrap() as u16,
-            headers: Vec::new(),
-            body: sim["body"].as_str().unwrap().to_string(),
-        };
-        let result = c.parse_get_todo(response);
-
-        if let Some(expected_error) = case.get("expected_error") {
-            let err = result.unwrap_err();
-            match expected_error.as_str().unwrap() {
-                "NotFound" => assert!(matches!(err, ApiError::NotFound), "{name}: expected NotFound"),
-                other => panic!("{name}: unknown expected_error: {other}"),
-            }
-        } else {
-            let todo = result.unwrap();
-            let expected: Todo = serde_json::from_value(case["expected_result"].clone()).unwrap();
-            assert_eq!(todo, expected, "{name}: parsed result");
-        }
+    for case in vector(include_str!("../../test-vectors/get.json")).cases {
+        check_request(&case, &c.build_get_todo(input_id(&case)));
+        let outcome = c.parse_get_todo(response(&case));
+        check_outcome(&case, outcome, |todo| Some(ExpectedResult::Todo(fields(&todo))));
     }
 }
 
@@ -154,34 +134,16 @@ fn get_test_vectors() {
 
 #[test]
 fn update_test_vectors() {
-    let raw = include_str!("../../test-vectors/update.json");
-    let vectors: serde_json::Value = serde_json::from_str(raw).unwrap();
-
     let c = client();
-    for case in vectors["cases"].as_array().unwrap() {
-        let name = case["name"].as_str().unwrap();
-        let id: Uuid = case["input_id"].as_str().unwrap().parse().unwrap();
-        let input: UpdateTodo = serde_json::from_value(case["input"].clone()).unwrap();
-        let expected_req = &case["expected_request"];
-
-        // Verify build
-        let req = c.build_update_todo(id, &input).unwrap();
-        assert_eq!(req.method, parse_method(expected_req["method"].as_str().unwrap()), "{name}: method");
-        assert_eq!(req.path, format!("{BASE_URL}{}", expected_req["path"].as_str().unwrap()), "{name}: path");
-
-        let req_body: serde_json::Value = serde_json::from_str(req.body.as_deref().unwrap()).unwrap();
-        assert_eq!(req_body, expected_req["body"], "{name}: body");
-
-        // Verify parse
-        let sim = &case["simulated_response"];
-        let response = HttpResponse {
-            status: sim["status"].as_u64().unwrap() as u16,
-            headers: Vec::new(),
-            body: sim["body"].as_str().unwrap().to_string(),
+    for case in vector(include_str!("../../test-vectors/update.json")).cases {
+        let input = case.input.clone().unwrap();
+        let input = UpdateTodo {
+            title: input.title,
+            completed: input.completed,
         };
-        let todo = c.parse_update_todo(response).unwrap();
-        let expected: Todo = serde_json::from_value(case["expected_result"].clone()).unwrap();
-        assert_eq!(todo, expected, "{name}: parsed result");
+        check_request(&case, &c.build_update_todo(input_id(&case), &input).unwrap());
+        let outcome = c.parse_update_todo(response(&case));
+        check_outcome(&case, outcome, |todo| Some(ExpectedResult::Todo(fields(&todo))));
     }
 }
 
@@ -191,38 +153,10 @@ fn update_test_vectors() {
 
 #[test]
 fn delete_test_vectors() {
-    let raw = include_str!("../../test-vectors/delete.json");
-    let vectors: serde_json::Value = serde_json::from_str(raw).unwrap();
-
     let c = client();
-    for case in vectors["cases"].as_array().unwrap() {
-        let name = case["name"].as_str().unwrap();
-        let id: Uuid = case["input_id"].as_str().unwrap().parse().unwrap();
-        let expected_req = &case["expected_request"];
-
-        // Verify build
-        let req = c.build_delete_todo(id);
-        assert_eq!(req.method, parse_method(expected_req["method"].as_str().unwrap()), "{name}: method");
-        assert_eq!(req.path, format!("{BASE_URL}{}", expected_req["path"].as_str().unwrap()), "{name}: path");
-        assert!(req.body.is_none(), "{name}: body should be None");
-
-        // Verify parse
-        let sim = &case["simulated_response"];
-        let response = HttpResponse {
-            status: sim["status"].as_u64().unwrap() as u16,
-            headers: Vec::new(),
-            body: sim["body"].as_str().unwrap().to_string(),
-        };
-        let result = c.parse_delete_todo(response);
-
-        if let Some(expected_error) = case.get("expected_error") {
-            let err = result.unwrap_err();
-            match expected_error.as_str().unwrap() {
-                "NotFound" => assert!(matches!(err, ApiError::NotFound), "{name}: expected NotFound"),
-                other => panic!("{name}: unknown expected_error: {other}"),
-            }
-        } else {
-            assert!(result.is_ok(), "{name}: expected success");
-        }
+    for case in vector(include_str!("../../test-vectors/delete.json")).cases {
+        check_request(&case, &c.build_delete_todo(input_id(&case)));
+        let outcome = c.parse_delete_todo(response(&case));
+        check_outcome(&case, outcome, |()| None);
     }
 }
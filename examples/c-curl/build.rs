@@ -0,0 +1,10 @@
+use std::env;
+
+fn main() {
+    let ffi_include = env::var("DEP_TODO_FFI_INCLUDE").expect("todo-ffi exports its include dir");
+    println!("cargo:rustc-env=TODO_FFI_INCLUDE={ffi_include}");
+    // For configuring `cc` in tests/c_curl.rs.
+    for var in ["TARGET", "HOST"] {
+        println!("cargo:rustc-env={var}={}", env::var(var).unwrap());
+    }
+}
@@ -0,0 +1,202 @@
+/* The host-does-IO loop from C: the Rust core builds each request through
+ * the C ABI, libcurl executes it, and the core parses the response.
+ *
+ * Usage: todo-curl <base-url>
+ *
+ * Creates a todo, finds it in the list, marks it completed, deletes it and
+ * checks it is gone, printing one line per step. Exits non-zero at the
+ * first step that does not go as expected. tests/c_curl.rs builds this
+ * file and runs it against the mock server.
+ *
+ * Build by hand (Linux; use the profile directory you built):
+ *   cc -std=c11 -I ffi/include examples/c-curl/main.c -L target/debug \
+ *      -ltodo_ffi -lcurl -Wl,-rpath,target/debug -o todo-curl */
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include <curl/curl.h>
+
+#include "todo_client.h"
+
+typedef FfiFfiTodoResult *(*Parse)(const FfiFfiTodoClient *, const FfiFfiHttpResponse *);
+
+/* A response body, kept NUL-terminated as `todo_parse_*` expects. */
+typedef struct {
+    char *data;
+    size_t len;
+} Buffer;
+
+static size_t on_body(char *chunk, size_t size, size_t count, void *userdata) {
+    Buffer *buf = userdata;
+    size_t n = size * count;
+    char *grown = realloc(buf->data, buf->len + n + 1);
+    if (!grown) {
+        return 0; /* makes curl_easy_perform fail with CURLE_WRITE_ERROR */
+    }
+    memcpy(grown + buf->len, chunk, n);
+    buf->data = grown;
+    buf->len += n;
+    buf->data[buf->len] = '\0';
+    return n;
+}
+
+static const char *method_name(FfiFfiHttpMethod method) {
+    switch (method) {
+    case FFI_FFI_HTTP_METHOD_GET: return "GET";
+    case FFI_FFI_HTTP_METHOD_POST: return "POST";
+    case FFI_FFI_HTTP_METHOD_PUT: return "PUT";
+    case FFI_FFI_HTTP_METHOD_DELETE: return "DELETE";
+    case FFI_FFI_HTTP_METHOD_OPTIONS: return "OPTIONS";
+    case FFI_FFI_HTTP_METHOD_TRACE: return "TRACE";
+    case FFI_FFI_HTTP_METHOD_PATCH: return "PATCH";
+    case FFI_FFI_HTTP_METHOD_HEAD: return "HEAD";
+    }
+    return "GET";
+}
+
+/* Execute `req` with libcurl and hand the response to `parse`. Takes
+ * ownership of `req`, which may be null if building it failed. Returns
+ * null when there is no result to parse: the request could not be built
+ * or the transfer failed. */
+static FfiFfiTodoResult *roundtrip(CURL *curl, const FfiFfiTodoClient *client, FfiFfiHttpRequest *req, Parse parse) {
+    if (!req) {
+        fprintf(stderr, "building the request failed\n");
+        return NULL;
+    }
+    struct curl_slist *headers = NULL;
+    for (uint32_t i = 0; i < req->headers_len; i++) {
+        const FfiFfiHeader *h = &req->headers[i];
+        size_t size = strlen(h->key) + strlen(h->value) + 3;
+        char *line = malloc(size);
+        if (!line) {
+            break;
+        }
+        snprintf(line, size, "%s: %s", h->key, h->value);
+        headers = curl_slist_append(headers, line);
+        free(line);
+    }
+
+    Buffer body = {0};
+    curl_easy_reset(curl);
+    curl_easy_setopt(curl, CURLOPT_URL, req->path);
+    curl_easy_setopt(curl, CURLOPT_CUSTOMREQUEST, method_name(req->method));
+    curl_easy_setopt(curl, CURLOPT_HTTPHEADER, headers);
+    if (req->body) {
+        curl_easy_setopt(curl, CURLOPT_POSTFIELDS, req->body);
+    }
+    curl_easy_setopt(curl, CURLOPT_WRITEFUNCTION, on_body);
+    curl_easy_setopt(curl, CURLOPT_WRITEDATA, &body);
+    CURLcode rc = curl_easy_perform(curl);
+    long status = 0;
+    curl_easy_getinfo(curl, CURLINFO_RESPONSE_CODE, &status);
+    curl_slist_free_all(headers);
+
+    FfiFfiTodoResult *result = NULL;
+    if (rc != CURLE_OK) {
+        fprintf(stderr, "%s %s: %s\n", method_name(req->method), req->path, curl_easy_strerror(rc));
+    } else {
+        FfiFfiHttpResponse response = {(uint16_t)status, body.data ? body.data : ""};
+        result = parse(client, &response);
+    }
+    todo_free_request(req);
+    free(body.data);
+    return result;
+}
+
+/* True if `result` has error code `want`; otherwise reports `step` and
+ * frees it. */
+static bool expect(FfiFfiTodoResult *result, const char *step, FfiFfiErrorCode want) {
+    if (!result) {
+        fprintf(stderr, "%s: no result\n", step);
+        return false;
+    }
+    if (result->error_code != want) {
+        fprintf(stderr, "%s: error %d (HTTP %u): %s\n", step, (int)result->error_code, result->http_status,
+                result->error_message ? result->error_message : "");
+        todo_free_result(result);
+        return false;
+    }
+    return true;
+}
+
+static bool run(CURL *curl, const FfiFfiTodoClient *client) {
+    FfiFfiTodoResult *result =
+        roundtrip(curl, client, todo_build_create_todo(client, "Buy milk", false), todo_parse_create_todo);
+    if (!expect(result, "create", FFI_FFI_ERROR_CODE_OK)) {
+        return false;
+    }
+    const FfiFfiTodo *todo = result->data;
+    char id[64];
+    snprintf(id, sizeof id, "%s", todo->id);
+    printf("created %s \"%s\"\n", todo->id, todo->title);
+    todo_free_result(result);
+
+    result = roundtrip(curl, client, todo_build_list_todos(client), todo_parse_list_todos);
+    if (!expect(result, "list", FFI_FFI_ERROR_CODE_OK)) {
+        return false;
+    }
+    const FfiFfiTodoList *list = result->data;
+    bool found = false;
+    for (uint32_t i = 0; i < list->len; i++) {
+        found = found || strcmp(list->items[i].id, id) == 0;
+    }
+    printf("listed %u todo(s)\n", list->len);
+    todo_free_result(result);
+    if (!found) {
+        fprintf(stderr, "list: %s is missing\n", id);
+        return false;
+    }
+
+    result = roundtrip(curl, client, todo_build_update_todo(client, id, NULL, 1), todo_parse_update_todo);
+    if (!expect(result, "update", FFI_FFI_ERROR_CODE_OK)) {
+        return false;
+    }
+    todo = result->data;
+    bool completed = todo->completed;
+    printf("updated %s: completed=%s\n", todo->id, completed ? "true" : "false");
+    todo_free_result(result);
+    if (!completed) {
+        fprintf(stderr, "update: still not completed\n");
+        return false;
+    }
+
+    result = roundtrip(curl, client, todo_build_delete_todo(client, id), todo_parse_delete_todo);
+    if (!expect(result, "delete", FFI_FFI_ERROR_CODE_OK)) {
+        return false;
+    }
+    printf("deleted %s\n", id);
+    todo_free_result(result);
+
+    result = roundtrip(curl, client, todo_build_get_todo(client, id), todo_parse_get_todo);
+    if (!expect(result, "get after delete", FFI_FFI_ERROR_CODE_NOT_FOUND)) {
+        return false;
+    }
+    printf("get %s: not found (HTTP %u)\n", id, result->http_status);
+    todo_free_result(result);
+    return true;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <base-url>\n", argv[0]);
+        return 2;
+    }
+    if (curl_global_init(CURL_GLOBAL_DEFAULT) != CURLE_OK) {
+        fprintf(stderr, "curl_global_init failed\n");
+        return 1;
+    }
+    CURL *curl = curl_easy_init();
+    FfiFfiTodoClient *client = todo_client_new(argv[1]);
+    if (!client) {
+        fprintf(stderr, "invalid base URL: %s\n", argv[1]);
+    }
+    bool ok = curl && client && run(curl, client);
+    todo_client_free(client);
+    curl_easy_cleanup(curl);
+    curl_global_cleanup();
+    return ok ? 0 : 1;
+}
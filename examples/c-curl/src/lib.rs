@@ -0,0 +1,8 @@
+//! Build harness for `main.c`, a C program that drives the todo API through
+//! the C ABI with libcurl as its HTTP executor.
+//!
+//! # Design
+//! The example is the documented C usage pattern, so it is kept as working
+//! code: `tests/c_curl.rs` compiles it with the `cc` crate's compiler,
+//! links it against the `libtodo_ffi` cargo built and libcurl, and runs it
+//! against a live mock server. The crate has no Rust API of its own.
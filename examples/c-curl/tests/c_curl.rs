@@ -0,0 +1,86 @@
+//! Compiles `main.c` and runs it against a live mock server.
+//!
+//! # Design
+//! Same approach as the C conformance program's test: the compiler the `cc`
+//! crate picks for this target, linked against the `libtodo_ffi` cargo
+//! built next to this test. libcurl comes from the system (`-lcurl`); a
+//! small probe program checks for its headers and library first, and the
+//! test is skipped without them, as it is without a gcc-like compiler.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use todo_test_support::server::spawn_server;
+
+fn program() -> PathBuf {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/main.c")).to_path_buf()
+}
+
+/// `target/<profile>`, where cargo put `libtodo_ffi` next to this test's `deps/`.
+fn library_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+/// Compile `source` into `exe`, linking libcurl and `libtodo_ffi`.
+fn compile(compiler: &cc::Tool, source: &Path, exe: &Path) -> Output {
+    let lib_dir = library_dir();
+    compiler
+        .to_command()
+        .args(["-Wall", "-Wextra", "-I", env!("TODO_FFI_INCLUDE")])
+        .arg(source)
+        .arg("-o")
+        .arg(exe)
+        .arg("-L")
+        .arg(&lib_dir)
+        .args(["-ltodo_ffi", "-lcurl"])
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn curl_example_runs_against_the_mock_server() {
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("HOST"))
+        .opt_level(0)
+        .std("c11")
+        .cargo_metadata(false)
+        .cargo_warnings(false)
+        .warnings_into_errors(true)
+        .try_get_compiler();
+    let compiler = match compiler {
+        Ok(compiler) if !compiler.is_like_msvc() => compiler,
+        _ => {
+            eprintln!("skipping: no gcc-like C compiler found");
+            return;
+        }
+    };
+    let scratch = std::env::temp_dir().join(format!("todo-c-curl-{}", std::process::id()));
+    std::fs::create_dir_all(&scratch).unwrap();
+
+    let probe = scratch.join("probe.c");
+    std::fs::write(&probe, "#include <curl/curl.h>\nint main(void) { return curl_global_init(CURL_GLOBAL_DEFAULT); }\n").unwrap();
+    if !compile(&compiler, &probe, &scratch.join("probe")).status.success() {
+        std::fs::remove_dir_all(&scratch).ok();
+        eprintln!("skipping: libcurl headers or library not found");
+        return;
+    }
+
+    let exe = scratch.join("todo-curl");
+    let compiled = compile(&compiler, &program(), &exe);
+    assert!(compiled.status.success(), "main.c failed to compile: {}", String::from_utf8_lossy(&compiled.stderr));
+
+    let server = spawn_server();
+    let output = Command::new(&exe).arg(&server.base_url).output().unwrap();
+    std::fs::remove_dir_all(&scratch).ok();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "the example failed:\n{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let steps: Vec<&str> = stdout.lines().map(|line| line.split(' ').next().unwrap()).collect();
+    assert_eq!(steps, ["created", "listed", "updated", "deleted", "get"], "{stdout}");
+}
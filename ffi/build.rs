@@ -1,14 +1,56 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Clang module map so Swift packages can `import TodoFFI` directly.
+const MODULE_MAP: &str = r#"module TodoFFI {
+    header "todo_client.h"
+    link "todo_ffi"
+    export *
+}
+"#;
 
 fn main() {
     let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
-    let output_file = PathBuf::from(&crate_dir).join("include").join("todo_client.h");
+    let include_dir = PathBuf::from(&crate_dir).join("include");
 
+    let mut header = Vec::new();
     cbindgen::Builder::new()
         .with_crate(&crate_dir)
         .with_config(cbindgen::Config::from_file("cbindgen.toml").unwrap())
         .generate()
         .expect("cbindgen failed to generate header")
-        .write_to_file(output_file);
+        .write(&mut header);
+    let header = annotate_nullability(&String::from_utf8(header).unwrap());
+
+    fs::create_dir_all(&include_dir).unwrap();
+    write_if_changed(&include_dir.join("todo_client.h"), &header);
+    write_if_changed(&include_dir.join("module.modulemap"), MODULE_MAP);
+    println!("cargo:include={}", include_dir.display());
+}
+
+/// Qualify every pointer declarator with `TODO_NULLABLE`. cbindgen has no
+/// option for nullable raw pointers, and clang expects either all pointers
+/// in a header annotated or none. Comments and preprocessor lines are left
+/// alone.
+fn annotate_nullability(header: &str) -> String {
+    let mut out = String::with_capacity(header.len());
+    for line in header.lines() {
+        let code = line.trim_start();
+        let is_code = !(code.starts_with("/*") || code.starts_with('*') || code.starts_with('#'));
+        if is_code {
+            out.push_str(&line.replace('*', "*TODO_NULLABLE "));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Leave unchanged files untouched so dependents are not rebuilt.
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).ok().as_deref() != Some(contents) {
+        fs::write(path, contents).unwrap();
+    }
 }
@@ -0,0 +1,457 @@
+//! Entry points shaped for .NET P/Invoke (`todo_net_*`).
+//!
+//! # Design
+//! - Strings go in as NUL-terminated UTF-16, so C# passes `string` as
+//!   `LPWStr` without transcoding. Strings coming out stay UTF-8 and are
+//!   read with `Marshal.PtrToStringUTF8`.
+//! - Every struct is blittable: enums travel as `int32_t` and flags as
+//!   `uint8_t`, because C enum size is implementation-defined and .NET
+//!   marshals `bool` as a 4-byte `BOOL`.
+//! - Each owning return has exactly one release function taking only the
+//!   pointer, which is the shape `SafeHandle.ReleaseHandle` needs: clients
+//!   use `todo_client_free`, requests `todo_net_free_request` and results
+//!   `todo_net_free_result`.
+//! - The functions delegate to the UTF-8 entry points and move the
+//!   returned pointers into the blittable layouts, so validation and error
+//!   mapping are shared with every other host.
+
+use std::ffi::{c_void, CString};
+use std::os::raw::c_char;
+
+use crate::tracking;
+use crate::types::*;
+use crate::unwind::catch_unwind;
+use crate::{
+    todo_build_create_todo, todo_build_delete_all, todo_build_delete_todo, todo_build_get_todo,
+    todo_build_list_todos, todo_build_update_todo, todo_client_new, todo_free_string, todo_parse_create_todo,
+    todo_parse_delete_all, todo_parse_delete_todo, todo_parse_get_todo, todo_parse_list_todos,
+    todo_parse_update_todo,
+};
+
+const TAG_TODO: i32 = FfiDataTag::Todo as i32;
+const TAG_TODO_LIST: i32 = FfiDataTag::TodoList as i32;
+const TAG_COUNT: i32 = FfiDataTag::Count as i32;
+
+/// `FfiHttpRequest` with `method` as an `FfiHttpMethod` value in an
+/// `int32_t`.
+#[repr(C)]
+pub struct FfiNetHttpRequest {
+    pub method: i32,
+    pub path: *mut c_char,
+    pub headers: *mut FfiHeader,
+    pub headers_len: u32,
+    pub body: *mut c_char,
+}
+
+/// `FfiTodo` with `completed` as 0 or 1.
+#[repr(C)]
+pub struct FfiNetTodo {
+    pub id: *mut c_char,
+    pub title: *mut c_char,
+    pub completed: u8,
+}
+
+/// A list of `FfiNetTodo`.
+#[repr(C)]
+pub struct FfiNetTodoList {
+    pub items: *mut FfiNetTodo,
+    pub len: u32,
+}
+
+/// `FfiTodoResult` with `error_code` and `data_tag` as `int32_t`. `data`
+/// points to an `FfiNetTodo`, `FfiNetTodoList` or `uint64_t` per the tag.
+#[repr(C)]
+pub struct FfiNetTodoResult {
+    pub error_code: i32,
+    pub error_message: *mut c_char,
+    pub http_status: u16,
+    pub data_tag: i32,
+    pub data: *mut c_void,
+}
+
+/// Read a NUL-terminated UTF-16 string. `None` for null or for unpaired
+/// surrogates.
+fn utf16_arg(s: *const u16) -> Option<CString> {
+    if s.is_null() {
+        return None;
+    }
+    let len = (0..).take_while(|&i| unsafe { *s.add(i) } != 0).count();
+    let units = unsafe { std::slice::from_raw_parts(s, len) };
+    // A UTF-16 string cannot contain NUL before its terminator, so neither
+    // can the CString.
+    CString::new(String::from_utf16(units).ok()?).ok()
+}
+
+/// Move a built request into the blittable layout.
+fn net_request(req: *mut FfiHttpRequest) -> *mut FfiNetHttpRequest {
+    if req.is_null() {
+        return std::ptr::null_mut();
+    }
+    tracking::release(req, "net_request");
+    let req = unsafe { Box::from_raw(req) };
+    tracking::track(Box::into_raw(Box::new(FfiNetHttpRequest {
+        method: req.method as i32,
+        path: req.path,
+        headers: req.headers,
+        headers_len: req.headers_len,
+        body: req.body,
+    })))
+}
+
+fn net_todo(todo: FfiTodo) -> FfiNetTodo {
+    FfiNetTodo { id: todo.id, title: todo.title, completed: todo.completed as u8 }
+}
+
+/// Move a parse result, payload included, into the blittable layouts.
+fn net_result(result: *mut FfiTodoResult) -> *mut FfiNetTodoResult {
+    if result.is_null() {
+        return std::ptr::null_mut();
+    }
+    tracking::release(result, "net_result");
+    let result = unsafe { Box::from_raw(result) };
+    let data = match result.data_tag {
+        _ if result.data.is_null() => result.data,
+        FfiDataTag::Todo => {
+            let todo = unsafe { Box::from_raw(result.data as *mut FfiTodo) };
+            Box::into_raw(Box::new(net_todo(*todo))) as *mut c_void
+        }
+        FfiDataTag::TodoList => {
+            let list = unsafe { Box::from_raw(result.data as *mut FfiTodoList) };
+            let items = if list.items.is_null() {
+                std::ptr::null_mut()
+            } else {
+                let items = unsafe { Vec::from_raw_parts(list.items, list.len as usize, list.len as usize) };
+                let items: Box<[FfiNetTodo]> = items.into_iter().map(net_todo).collect();
+                Box::into_raw(items) as *mut FfiNetTodo
+            };
+            Box::into_raw(Box::new(FfiNetTodoList { items, len: list.len })) as *mut c_void
+        }
+        FfiDataTag::Count | FfiDataTag::None => result.data,
+    };
+    tracking::track(Box::into_raw(Box::new(FfiNetTodoResult {
+        error_code: result.error_code as i32,
+        error_message: result.error_message,
+        http_status: result.http_status,
+        data_tag: result.data_tag as i32,
+        data,
+    })))
+}
+
+type ParseFn = extern "C" fn(*const FfiTodoClient, *const FfiHttpResponse) -> *mut FfiTodoResult;
+
+/// Run `parse` on a response whose body is UTF-16. A null or malformed body
+/// is treated as empty, as `todo_parse_*` does for bad UTF-8.
+fn net_parse(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+    parse: ParseFn,
+    panic_message: &str,
+) -> *mut FfiNetTodoResult {
+    catch_unwind(|| {
+        let body = utf16_arg(body).unwrap_or_default();
+        let response = FfiHttpResponse { status, body: body.as_ptr() };
+        net_result(parse(client, &response))
+    })
+    .unwrap_or_else(|_| net_result(FfiTodoResult::panic(panic_message)))
+}
+
+/// Create a client from a UTF-16 base URL. Returns null where
+/// `todo_client_new` would, or if `base_url` is not valid UTF-16.
+/// Release with `todo_client_free`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_client_new(base_url: *const u16) -> *mut FfiTodoClient {
+    catch_unwind(|| match utf16_arg(base_url) {
+        Some(url) => todo_client_new(url.as_ptr()),
+        None => std::ptr::null_mut(),
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_list_todos` in the blittable layout. Release with
+/// `todo_net_free_request`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_list_todos(client: *const FfiTodoClient) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| net_request(todo_build_list_todos(client))).unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_get_todo` with a UTF-16 `id`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_get_todo(client: *const FfiTodoClient, id: *const u16) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| match utf16_arg(id) {
+        Some(id) => net_request(todo_build_get_todo(client, id.as_ptr())),
+        None => std::ptr::null_mut(),
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_create_todo` with a UTF-16 `title`; any non-zero
+/// `completed` is true.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_create_todo(
+    client: *const FfiTodoClient,
+    title: *const u16,
+    completed: u8,
+) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| match utf16_arg(title) {
+        Some(title) => net_request(todo_build_create_todo(client, title.as_ptr(), completed != 0)),
+        None => std::ptr::null_mut(),
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_update_todo` with UTF-16 strings. `title` may be null;
+/// `completed` is tri-state as there.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_update_todo(
+    client: *const FfiTodoClient,
+    id: *const u16,
+    title: *const u16,
+    completed: i32,
+) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| {
+        let Some(id) = utf16_arg(id) else {
+            return std::ptr::null_mut();
+        };
+        let title = match (title.is_null(), utf16_arg(title)) {
+            (true, _) => None,
+            (false, Some(title)) => Some(title),
+            (false, None) => return std::ptr::null_mut(),
+        };
+        let title_ptr = title.as_ref().map_or(std::ptr::null(), |t| t.as_ptr());
+        net_request(todo_build_update_todo(client, id.as_ptr(), title_ptr, completed))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_delete_todo` with a UTF-16 `id`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_delete_todo(client: *const FfiTodoClient, id: *const u16) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| match utf16_arg(id) {
+        Some(id) => net_request(todo_build_delete_todo(client, id.as_ptr())),
+        None => std::ptr::null_mut(),
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_build_delete_all` in the blittable layout.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_build_delete_all(client: *const FfiTodoClient) -> *mut FfiNetHttpRequest {
+    catch_unwind(|| net_request(todo_build_delete_all(client))).unwrap_or(std::ptr::null_mut())
+}
+
+/// `todo_parse_list_todos` taking the response status and UTF-16 body directly.
+/// A null or malformed body is treated as empty. Release with
+/// `todo_net_free_result`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_list_todos(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_list_todos, "panic in todo_net_parse_list_todos")
+}
+
+/// `todo_parse_get_todo` taking the status and UTF-16 body directly.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_get_todo(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_get_todo, "panic in todo_net_parse_get_todo")
+}
+
+/// `todo_parse_create_todo` taking the status and UTF-16 body directly.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_create_todo(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_create_todo, "panic in todo_net_parse_create_todo")
+}
+
+/// `todo_parse_update_todo` taking the status and UTF-16 body directly.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_update_todo(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_update_todo, "panic in todo_net_parse_update_todo")
+}
+
+/// `todo_parse_delete_todo` taking the status and UTF-16 body directly.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_delete_todo(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_delete_todo, "panic in todo_net_parse_delete_todo")
+}
+
+/// `todo_parse_delete_all` taking the status and UTF-16 body directly.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_parse_delete_all(
+    client: *const FfiTodoClient,
+    status: u16,
+    body: *const u16,
+) -> *mut FfiNetTodoResult {
+    net_parse(client, status, body, todo_parse_delete_all, "panic in todo_net_parse_delete_all")
+}
+
+/// Free a request returned by any `todo_net_build_*` function. Safe to call
+/// with null.
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_free_request(req: *mut FfiNetHttpRequest) {
+    if req.is_null() {
+        return;
+    }
+    tracking::release(req, "todo_net_free_request");
+    let _ = catch_unwind(|| {
+        let req = unsafe { Box::from_raw(req) };
+        todo_free_string(req.path);
+        todo_free_string(req.body);
+        if !req.headers.is_null() {
+            let len = req.headers_len as usize;
+            for header in unsafe { Vec::from_raw_parts(req.headers, len, len) } {
+                todo_free_string(header.key);
+                todo_free_string(header.value);
+            }
+        }
+    });
+}
+
+/// Free a result returned by any `todo_net_parse_*` function. Safe to call
+/// with null.
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_net_free_result(result: *mut FfiNetTodoResult) {
+    if result.is_null() {
+        return;
+    }
+    tracking::release(result, "todo_net_free_result");
+    let _ = catch_unwind(|| {
+        let result = unsafe { Box::from_raw(result) };
+        todo_free_string(result.error_message);
+        if result.data.is_null() {
+            return;
+        }
+        match result.data_tag {
+            TAG_TODO => {
+                let todo = unsafe { Box::from_raw(result.data as *mut FfiNetTodo) };
+                todo_free_string(todo.id);
+                todo_free_string(todo.title);
+            }
+            TAG_TODO_LIST => {
+                let list = unsafe { Box::from_raw(result.data as *mut FfiNetTodoList) };
+                if !list.items.is_null() {
+                    let len = list.len as usize;
+                    for todo in unsafe { Vec::from_raw_parts(list.items, len, len) } {
+                        todo_free_string(todo.id);
+                        todo_free_string(todo.title);
+                    }
+                }
+            }
+            TAG_COUNT => drop(unsafe { Box::from_raw(result.data as *mut u64) }),
+            _ => {}
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+    use crate::todo_client_free;
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain([0]).collect()
+    }
+
+    fn client() -> *mut FfiTodoClient {
+        todo_net_client_new(utf16("http://localhost:3000").as_ptr())
+    }
+
+    fn text(ptr: *const c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn utf16_title_outside_the_bmp_reaches_the_body() {
+        let client = client();
+        let req = todo_net_build_create_todo(client, utf16("Milk 🥛").as_ptr(), 1);
+        assert!(!req.is_null());
+        let r = unsafe { &*req };
+        assert_eq!(r.method, FfiHttpMethod::Post as i32);
+        assert_eq!(text(r.path), "http://localhost:3000/todos");
+        let body: serde_json::Value = serde_json::from_str(&text(r.body)).unwrap();
+        assert_eq!(body["title"], "Milk 🥛");
+        assert_eq!(body["completed"], true);
+        todo_net_free_request(req);
+        todo_client_free(client);
+    }
+
+    #[test]
+    fn invalid_utf16_and_null_are_rejected() {
+        assert!(todo_net_client_new(std::ptr::null()).is_null());
+        let client = client();
+        let lone_surrogate = [0xD83Eu16, 0];
+        assert!(todo_net_build_get_todo(client, lone_surrogate.as_ptr()).is_null());
+        assert!(todo_net_build_delete_todo(client, std::ptr::null()).is_null());
+        let id = utf16("00000000-0000-0000-0000-000000000001");
+        assert!(todo_net_build_update_todo(client, id.as_ptr(), lone_surrogate.as_ptr(), -1).is_null());
+        let req = todo_net_build_update_todo(client, id.as_ptr(), std::ptr::null(), 0);
+        assert_eq!(text(unsafe { &*req }.body), r#"{"completed":false}"#);
+        todo_net_free_request(req);
+        todo_client_free(client);
+    }
+
+    #[test]
+    fn list_result_uses_blittable_layouts() {
+        let client = client();
+        let body = utf16(r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"Café","completed":true}]"#);
+        let result = todo_net_parse_list_todos(client, 200, body.as_ptr());
+        let r = unsafe { &*result };
+        assert_eq!(r.error_code, FfiErrorCode::Ok as i32);
+        assert_eq!(r.data_tag, FfiDataTag::TodoList as i32);
+        let list = unsafe { &*(r.data as *const FfiNetTodoList) };
+        assert_eq!(list.len, 1);
+        let todo = unsafe { &*list.items };
+        assert_eq!(text(todo.title), "Café");
+        assert_eq!(todo.completed, 1);
+        todo_net_free_result(result);
+        todo_client_free(client);
+    }
+
+    #[test]
+    fn error_result_carries_code_and_status() {
+        let client = client();
+        let result = todo_net_parse_get_todo(client, 404, utf16("").as_ptr());
+        let r = unsafe { &*result };
+        assert_eq!(r.error_code, FfiErrorCode::NotFound as i32);
+        assert_eq!(r.http_status, 404);
+        assert!(r.data.is_null());
+        todo_net_free_result(result);
+
+        let result = todo_net_parse_delete_all(client, 200, std::ptr::null());
+        assert_eq!(unsafe { &*result }.error_code, FfiErrorCode::Deserialization as i32);
+        todo_net_free_result(result);
+        todo_client_free(client);
+    }
+}
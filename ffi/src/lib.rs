@@ -7,23 +7,43 @@
 //!
 //! # Design
 //! - Every `extern "C"` function wraps its body in `catch_unwind` so panics
-//!   never cross the FFI boundary.
+//!   never cross the FFI boundary. The `panic-abort` feature turns that into
+//!   abort-on-panic at compile time (see `unwind`).
 //! - Per-operation `build_*` / `parse_*` mirrors the core API 1:1.
 //! - A single `FfiTodoResult` envelope with `FfiDataTag` + `void* data`
 //!   conveys success payloads and errors uniformly.
 //! - The C caller owns all returned pointers and must call the matching
 //!   `todo_free_*` function to release them.
-
+//! - Release functions take one pointer, accept null, never unwind and keep
+//!   no thread-local state, so hosts can register them directly as
+//!   finalizers (Dart `NativeFinalizer`, .NET `SafeHandle`) that run on
+//!   whatever thread the collector picks.
+//! - Debug builds track every pointer they hand out, so a double free or a
+//!   pointer passed to the wrong release function aborts with a message
+//!   (see `tracking`).
+//! - `build.rs` annotates the generated header for Swift: every pointer is
+//!   `_Nullable` under clang, owning returns warn when discarded, and a
+//!   `module.modulemap` next to it exposes the library as module `TodoFFI`.
+//! - `dotnet` repeats the surface as `todo_net_*` for P/Invoke: UTF-16
+//!   strings in, blittable structs out.
+
+// Every entry point null-checks its pointers before dereferencing them, and
+// marking them `unsafe` would not change anything for C callers.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+pub mod dotnet;
+mod tracking;
 pub mod types;
+mod unwind;
 
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
-use std::panic::catch_unwind;
 
 use todo_core::http::HttpResponse;
 use todo_core::types::{CreateTodo, UpdateTodo};
 
 use types::*;
+use unwind::catch_unwind;
 
 // ---------------------------------------------------------------------------
 // Client lifecycle
@@ -31,8 +51,10 @@ use types::*;
 
 /// Create a new `TodoClient` bound to `base_url`.
 ///
-/// Returns null if `base_url` is null or if an internal panic occurs.
+/// Returns null if `base_url` is null, not UTF-8, or not a valid base URL
+/// (see `BaseUrl::parse`), or if an internal panic occurs.
 /// The caller must free the returned pointer with `todo_client_free`.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_client_new(base_url: *const c_char) -> *mut FfiTodoClient {
     catch_unwind(|| {
@@ -40,8 +62,42 @@ pub extern "C" fn todo_client_new(base_url: *const c_char) -> *mut FfiTodoClient
             return std::ptr::null_mut();
         }
         let url = unsafe { CStr::from_ptr(base_url) }.to_str().unwrap_or("");
-        let client = todo_core::TodoClient::new(url);
-        Box::into_raw(Box::new(FfiTodoClient { inner: client }))
+        match todo_core::TodoClient::try_new(url) {
+            Ok(client) => tracking::track(Box::into_raw(Box::new(FfiTodoClient { inner: client }))),
+            Err(_) => std::ptr::null_mut(),
+        }
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Create a `TodoClient` that sends HTTP Basic credentials on every request.
+///
+/// Returns null if any argument is null or not UTF-8, if `base_url` is
+/// invalid, if `user` contains `:`, or if an internal panic occurs. The
+/// caller must free the returned pointer with `todo_client_free`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_client_new_basic_auth(
+    base_url: *const c_char,
+    user: *const c_char,
+    pass: *const c_char,
+) -> *mut FfiTodoClient {
+    catch_unwind(|| {
+        if base_url.is_null() || user.is_null() || pass.is_null() {
+            return std::ptr::null_mut();
+        }
+        let args = unsafe { (CStr::from_ptr(base_url), CStr::from_ptr(user), CStr::from_ptr(pass)) };
+        let (Ok(url), Ok(user), Ok(pass)) = (args.0.to_str(), args.1.to_str(), args.2.to_str()) else {
+            return std::ptr::null_mut();
+        };
+        let auth = todo_core::Auth::Basic {
+            user: user.to_string(),
+            pass: pass.to_string(),
+        };
+        match todo_core::TodoClient::try_new(url).and_then(|client| client.with_auth(auth)) {
+            Ok(client) => tracking::track(Box::into_raw(Box::new(FfiTodoClient { inner: client }))),
+            Err(_) => std::ptr::null_mut(),
+        }
     })
     .unwrap_or(std::ptr::null_mut())
 }
@@ -50,12 +106,45 @@ pub extern "C" fn todo_client_new(base_url: *const c_char) -> *mut FfiTodoClient
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_client_free(client: *mut FfiTodoClient) {
     if !client.is_null() {
+        tracking::release(client, "todo_client_free");
         let _ = catch_unwind(|| {
             drop(unsafe { Box::from_raw(client) });
         });
     }
 }
 
+/// Clone a `TodoClient`, including its configuration.
+///
+/// Returns null if `client` is null. The caller must free the returned
+/// pointer with `todo_client_free`.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_client_clone(client: *const FfiTodoClient) -> *mut FfiTodoClient {
+    catch_unwind(|| {
+        if client.is_null() {
+            return std::ptr::null_mut();
+        }
+        let client = unsafe { &*client };
+        tracking::track(Box::into_raw(Box::new(FfiTodoClient {
+            inner: client.inner.clone(),
+        })))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Compare two clients' base URLs and configuration.
+///
+/// Two null pointers compare equal; a null and a non-null pointer do not.
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_client_eq(a: *const FfiTodoClient, b: *const FfiTodoClient) -> bool {
+    catch_unwind(|| match (a.is_null(), b.is_null()) {
+        (true, true) => true,
+        (false, false) => unsafe { (*a).inner == (*b).inner },
+        _ => false,
+    })
+    .unwrap_or(false)
+}
+
 // ---------------------------------------------------------------------------
 // Build request functions
 // ---------------------------------------------------------------------------
@@ -64,6 +153,7 @@ pub extern "C" fn todo_client_free(client: *mut FfiTodoClient) {
 ///
 /// Returns null if `client` is null.
 /// The caller must free the returned pointer with `todo_free_request`.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_build_list_todos(client: *const FfiTodoClient) -> *mut FfiHttpRequest {
     catch_unwind(|| {
@@ -80,6 +170,7 @@ pub extern "C" fn todo_build_list_todos(client: *const FfiTodoClient) -> *mut Ff
 /// Build an HTTP request for fetching a single todo by id.
 ///
 /// Returns null if `client` or `id` is null, or if `id` is not a valid UUID.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_build_get_todo(
     client: *const FfiTodoClient,
@@ -104,6 +195,7 @@ pub extern "C" fn todo_build_get_todo(
 /// Build an HTTP request for creating a new todo.
 ///
 /// Returns null if `client` or `title` is null, or if serialization fails.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_build_create_todo(
     client: *const FfiTodoClient,
@@ -136,6 +228,7 @@ pub extern "C" fn todo_build_create_todo(
 /// `title` may be null (skip update). `completed` uses tri-state:
 /// -1 = skip, 0 = false, 1 = true.
 /// Returns null if `client` or `id` is null, or if `id` is not a valid UUID.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_build_update_todo(
     client: *const FfiTodoClient,
@@ -183,6 +276,7 @@ pub extern "C" fn todo_build_update_todo(
 /// Build an HTTP request for deleting a todo by id.
 ///
 /// Returns null if `client` or `id` is null, or if `id` is not a valid UUID.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_build_delete_todo(
     client: *const FfiTodoClient,
@@ -204,6 +298,23 @@ pub extern "C" fn todo_build_delete_todo(
     .unwrap_or(std::ptr::null_mut())
 }
 
+/// Build an HTTP request that deletes every todo.
+///
+/// Returns null if `client` is null.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_build_delete_all(client: *const FfiTodoClient) -> *mut FfiHttpRequest {
+    catch_unwind(|| {
+        if client.is_null() {
+            return std::ptr::null_mut();
+        }
+        let client = unsafe { &*client };
+        let req = client.inner.build_delete_all();
+        FfiHttpRequest::from_core(req)
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
 // ---------------------------------------------------------------------------
 // Parse response functions
 // ---------------------------------------------------------------------------
@@ -231,6 +342,7 @@ fn ffi_response_to_core(resp: &FfiHttpResponse) -> HttpResponse {
 /// Parse an HTTP response from a list-todos request.
 ///
 /// Returns a result with `data_tag = TodoList` on success.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_parse_list_todos(
     client: *const FfiTodoClient,
@@ -257,6 +369,7 @@ pub extern "C" fn todo_parse_list_todos(
 /// Parse an HTTP response from a get-todo request.
 ///
 /// Returns a result with `data_tag = Todo` on success.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_parse_get_todo(
     client: *const FfiTodoClient,
@@ -283,6 +396,7 @@ pub extern "C" fn todo_parse_get_todo(
 /// Parse an HTTP response from a create-todo request.
 ///
 /// Returns a result with `data_tag = Todo` on success (status 201).
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_parse_create_todo(
     client: *const FfiTodoClient,
@@ -309,6 +423,7 @@ pub extern "C" fn todo_parse_create_todo(
 /// Parse an HTTP response from an update-todo request.
 ///
 /// Returns a result with `data_tag = Todo` on success.
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_parse_update_todo(
     client: *const FfiTodoClient,
@@ -335,6 +450,7 @@ pub extern "C" fn todo_parse_update_todo(
 /// Parse an HTTP response from a delete-todo request.
 ///
 /// Returns a result with `data_tag = None` on success (status 204).
+#[must_use]
 #[unsafe(no_mangle)]
 pub extern "C" fn todo_parse_delete_todo(
     client: *const FfiTodoClient,
@@ -358,6 +474,34 @@ pub extern "C" fn todo_parse_delete_todo(
     .unwrap_or_else(|_| FfiTodoResult::panic("panic in todo_parse_delete_todo"))
 }
 
+/// Parse an HTTP response from a delete-all request.
+///
+/// Returns a result with `data_tag = Count` on success; `data` points to the
+/// number of todos deleted.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_parse_delete_all(
+    client: *const FfiTodoClient,
+    response: *const FfiHttpResponse,
+) -> *mut FfiTodoResult {
+    catch_unwind(|| {
+        if client.is_null() {
+            return FfiTodoResult::null_arg("client");
+        }
+        if response.is_null() {
+            return FfiTodoResult::null_arg("response");
+        }
+        let client = unsafe { &*client };
+        let resp = unsafe { &*response };
+        let core_resp = ffi_response_to_core(resp);
+        match client.inner.parse_delete_all(core_resp) {
+            Ok(deleted) => FfiTodoResult::ok_count(deleted),
+            Err(e) => FfiTodoResult::from_error(e),
+        }
+    })
+    .unwrap_or_else(|_| FfiTodoResult::panic("panic in todo_parse_delete_all"))
+}
+
 // ---------------------------------------------------------------------------
 // Free functions
 // ---------------------------------------------------------------------------
@@ -369,6 +513,7 @@ pub extern "C" fn todo_free_request(req: *mut FfiHttpRequest) {
     if req.is_null() {
         return;
     }
+    tracking::release(req, "todo_free_request");
     let _ = catch_unwind(|| {
         let req = unsafe { Box::from_raw(req) };
         if !req.path.is_null() {
@@ -400,6 +545,7 @@ pub extern "C" fn todo_free_result(result: *mut FfiTodoResult) {
     if result.is_null() {
         return;
     }
+    tracking::release(result, "todo_free_result");
     let _ = catch_unwind(|| {
         let result = unsafe { Box::from_raw(result) };
         if !result.error_message.is_null() {
@@ -426,6 +572,9 @@ pub extern "C" fn todo_free_result(result: *mut FfiTodoResult) {
                         }
                     }
                 }
+                FfiDataTag::Count => {
+                    drop(unsafe { Box::from_raw(result.data as *mut u64) });
+                }
                 FfiDataTag::None => {}
             }
         }
@@ -460,6 +609,29 @@ pub extern "C" fn todo_free_string(s: *mut c_char) {
 mod tests {
     use super::*;
     use std::ffi::CString;
+    use todo_test_support::server::spawn_server;
+
+    /// The generated header must compile on its own. Skipped without `cc`.
+    #[test]
+    fn generated_header_compiles() {
+        let include = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("include");
+        let module_map = std::fs::read_to_string(include.join("module.modulemap")).unwrap();
+        assert!(module_map.contains(r#"header "todo_client.h""#));
+
+        let source = std::env::temp_dir().join(format!("todo-header-{}.c", std::process::id()));
+        std::fs::write(&source, "#include \"todo_client.h\"\n").unwrap();
+        let output = std::process::Command::new("cc")
+            .args(["-fsyntax-only", "-Wall", "-Wextra", "-Werror", "-I"])
+            .arg(&include)
+            .arg(&source)
+            .output();
+        std::fs::remove_file(&source).ok();
+        let Ok(output) = output else {
+            eprintln!("skipping: cc not found");
+            return;
+        };
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
 
     #[test]
     fn client_new_and_free() {
@@ -475,11 +647,51 @@ mod tests {
         assert!(client.is_null());
     }
 
+    #[test]
+    fn client_new_invalid_url_returns_null() {
+        let url = CString::new("localhost:3000").unwrap();
+        assert!(todo_client_new(url.as_ptr()).is_null());
+    }
+
     #[test]
     fn client_free_null_is_safe() {
         todo_client_free(std::ptr::null_mut());
     }
 
+    #[test]
+    fn client_free_on_another_thread() {
+        let url = CString::new("http://localhost:3000").unwrap();
+        let client = todo_client_new(url.as_ptr()) as usize;
+        std::thread::spawn(move || todo_client_free(client as *mut FfiTodoClient)).join().unwrap();
+    }
+
+    #[test]
+    fn client_clone_eq_and_mutation() {
+        let url = CString::new("http://localhost:3000").unwrap();
+        let client = todo_client_new(url.as_ptr());
+        let clone = todo_client_clone(client);
+        assert!(!clone.is_null());
+        assert!(todo_client_eq(client, clone));
+
+        let clone_ref = unsafe { &mut *clone };
+        clone_ref.inner = clone_ref.inner.clone().with_error_includes_request(true);
+        assert!(!todo_client_eq(client, clone));
+
+        todo_client_free(clone);
+        todo_client_free(client);
+    }
+
+    #[test]
+    fn client_eq_null_handling() {
+        let url = CString::new("http://localhost:3000").unwrap();
+        let client = todo_client_new(url.as_ptr());
+        assert!(todo_client_eq(std::ptr::null(), std::ptr::null()));
+        assert!(!todo_client_eq(client, std::ptr::null()));
+        assert!(!todo_client_eq(std::ptr::null(), client));
+        assert!(todo_client_clone(std::ptr::null()).is_null());
+        todo_client_free(client);
+    }
+
     #[test]
     fn build_list_todos_returns_correct_request() {
         let url = CString::new("http://localhost:3000").unwrap();
@@ -500,6 +712,31 @@ mod tests {
         todo_client_free(client);
     }
 
+    #[test]
+    fn basic_auth_client_stamps_authorization() {
+        let url = CString::new("http://localhost:3000").unwrap();
+        let user = CString::new("Aladdin").unwrap();
+        let pass = CString::new("open sesame").unwrap();
+        let client = todo_client_new_basic_auth(url.as_ptr(), user.as_ptr(), pass.as_ptr());
+        assert!(!client.is_null());
+
+        let req = todo_build_list_todos(client);
+        let req_ref = unsafe { &*req };
+        assert_eq!(req_ref.headers_len, 1);
+        let header = unsafe { &*req_ref.headers };
+        assert_eq!(unsafe { CStr::from_ptr(header.key) }.to_str().unwrap(), "authorization");
+        assert_eq!(
+            unsafe { CStr::from_ptr(header.value) }.to_str().unwrap(),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+        todo_free_request(req);
+        todo_client_free(client);
+
+        let bad_user = CString::new("a:b").unwrap();
+        assert!(todo_client_new_basic_auth(url.as_ptr(), bad_user.as_ptr(), pass.as_ptr()).is_null());
+        assert!(todo_client_new_basic_auth(url.as_ptr(), std::ptr::null(), pass.as_ptr()).is_null());
+    }
+
     #[test]
     fn build_list_todos_null_client_returns_null() {
         let req = todo_build_list_todos(std::ptr::null());
@@ -708,6 +945,72 @@ mod tests {
         todo_client_free(client);
     }
 
+    #[test]
+    fn build_delete_all_null_client_returns_null() {
+        assert!(todo_build_delete_all(std::ptr::null()).is_null());
+    }
+
+    fn agent() -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .new_agent()
+    }
+
+    /// Execute a bodyless FFI-built request, returning the status and body.
+    fn execute(req: &FfiHttpRequest) -> (u16, CString) {
+        let path = unsafe { CStr::from_ptr(req.path) }.to_str().unwrap();
+        let mut response = match req.method {
+            FfiHttpMethod::Get => agent().get(path).call(),
+            FfiHttpMethod::Delete => agent().delete(path).call(),
+            _ => panic!("unexpected method"),
+        }
+        .expect("HTTP transport error");
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string().unwrap_or_default();
+        (status, CString::new(body).unwrap())
+    }
+
+    #[test]
+    fn delete_all_reports_count() {
+        let server = spawn_server();
+        let base_url = server.base_url.clone();
+        for title in ["a", "b", "c"] {
+            let body = format!(r#"{{"title":"{title}"}}"#);
+            agent()
+                .post(&format!("{base_url}/todos"))
+                .content_type("application/json")
+                .send(body.as_bytes())
+                .unwrap();
+        }
+
+        let url = CString::new(base_url).unwrap();
+        let client = todo_client_new(url.as_ptr());
+
+        let req = todo_build_delete_all(client);
+        let (status, body) = execute(unsafe { &*req });
+        todo_free_request(req);
+
+        let resp = FfiHttpResponse {
+            status,
+            body: body.as_ptr(),
+        };
+        let result = todo_parse_delete_all(client, &resp);
+        let r = unsafe { &*result };
+        assert!(matches!(r.error_code, FfiErrorCode::Ok));
+        assert!(matches!(r.data_tag, FfiDataTag::Count));
+        assert_eq!(unsafe { *(r.data as *const u64) }, 3);
+        todo_free_result(result);
+
+        let req = todo_build_list_todos(client);
+        let (status, body) = execute(unsafe { &*req });
+        todo_free_request(req);
+        assert_eq!(status, 200);
+        assert_eq!(body.to_str().unwrap(), "[]");
+
+        todo_client_free(client);
+    }
+
     #[test]
     fn parse_null_client_returns_null_arg() {
         let body = CString::new("[]").unwrap();
@@ -760,6 +1063,27 @@ mod tests {
         todo_client_free(client);
     }
 
+    #[test]
+    fn parse_title_with_nul_is_deserialization_error() {
+        let url = CString::new("http://localhost:3000").unwrap();
+        let client = todo_client_new(url.as_ptr());
+        let body = CString::new(
+            r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"a\u0000b","completed":false}]"#,
+        )
+        .unwrap();
+        let resp = FfiHttpResponse {
+            status: 200,
+            body: body.as_ptr(),
+        };
+        let result = todo_parse_list_todos(client, &resp);
+        let r = unsafe { &*result };
+        assert!(matches!(r.error_code, FfiErrorCode::Deserialization));
+        assert!(matches!(r.data_tag, FfiDataTag::None));
+
+        todo_free_result(result);
+        todo_client_free(client);
+    }
+
     #[test]
     fn parse_get_todo_not_found() {
         let url = CString::new("http://localhost:3000").unwrap();
@@ -0,0 +1,81 @@
+//! Debug-build bookkeeping of the pointers handed to hosts.
+//!
+//! # Design
+//! With `debug_assertions`, every client, request and result the library
+//! returns is recorded with its type, and each release function checks it
+//! off before freeing. Releasing a pointer twice, releasing one the library
+//! never returned, or passing a result to `todo_free_request` then aborts
+//! with a message naming the function, instead of corrupting the heap
+//! somewhere far from the bug. Hosts hit this in their debug builds; in
+//! release builds both functions are no-ops.
+//!
+//! Payloads hanging off a result (`data`, strings) are not tracked: they
+//! are only ever freed through their owner.
+
+#[cfg(debug_assertions)]
+use std::collections::BTreeMap;
+#[cfg(debug_assertions)]
+use std::sync::{Mutex, MutexGuard};
+
+/// Live pointer addresses and the type each was allocated as.
+#[cfg(debug_assertions)]
+static LIVE: Mutex<BTreeMap<usize, &'static str>> = Mutex::new(BTreeMap::new());
+
+#[cfg(debug_assertions)]
+fn live() -> MutexGuard<'static, BTreeMap<usize, &'static str>> {
+    // A panic while holding the lock cannot leave the map inconsistent.
+    LIVE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Record `ptr` as handed out; returns it for chaining. Null is ignored.
+pub(crate) fn track<T>(ptr: *mut T) -> *mut T {
+    #[cfg(debug_assertions)]
+    if !ptr.is_null() {
+        live().insert(ptr as usize, std::any::type_name::<T>());
+    }
+    ptr
+}
+
+/// Check `ptr` off before `function` frees it. Aborts in debug builds if
+/// it is not a live `T`. Null is ignored.
+pub(crate) fn release<T>(ptr: *mut T, function: &str) {
+    #[cfg(debug_assertions)]
+    if !ptr.is_null() {
+        let expected = std::any::type_name::<T>();
+        let found = live().remove(&(ptr as usize));
+        if found != Some(expected) {
+            let problem = match found {
+                Some(actual) => {
+                    // Put it back so the abort message is the only effect.
+                    live().insert(ptr as usize, actual);
+                    format!("points to a {actual}, not a {expected}")
+                }
+                None => "was already freed or did not come from this library".to_string(),
+            };
+            eprintln!("{function}: {ptr:p} {problem}");
+            std::process::abort();
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = (ptr, function);
+}
+
+#[cfg(all(test, debug_assertions))]
+pub(crate) fn is_live<T>(ptr: *mut T) -> bool {
+    live().contains_key(&(ptr as usize))
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_then_release() {
+        let ptr = track(Box::into_raw(Box::new(5u32)));
+        assert!(is_live(ptr));
+        release(ptr, "test");
+        assert!(!is_live(ptr));
+        drop(unsafe { Box::from_raw(ptr) });
+        release(std::ptr::null_mut::<u32>(), "test");
+    }
+}
@@ -29,6 +29,10 @@ pub enum FfiHttpMethod {
     Post = 1,
     Put = 2,
     Delete = 3,
+    Options = 4,
+    Trace = 5,
+    Patch = 6,
+    Head = 7,
 }
 
 impl From<HttpMethod> for FfiHttpMethod {
@@ -38,6 +42,10 @@ impl From<HttpMethod> for FfiHttpMethod {
             HttpMethod::Post => FfiHttpMethod::Post,
             HttpMethod::Put => FfiHttpMethod::Put,
             HttpMethod::Delete => FfiHttpMethod::Delete,
+            HttpMethod::Options => FfiHttpMethod::Options,
+            HttpMethod::Trace => FfiHttpMethod::Trace,
+            HttpMethod::Patch => FfiHttpMethod::Patch,
+            HttpMethod::Head => FfiHttpMethod::Head,
         }
     }
 }
@@ -65,7 +73,7 @@ pub struct FfiHttpRequest {
 impl FfiHttpRequest {
     /// Convert a core `HttpRequest` into a heap-allocated `FfiHttpRequest`.
     pub(crate) fn from_core(req: todo_core::HttpRequest) -> *mut Self {
-        let path = CString::new(req.path).unwrap().into_raw();
+        let path = CString::new(req.url()).unwrap().into_raw();
         let body = match req.body {
             Some(b) => CString::new(b).unwrap().into_raw(),
             None => std::ptr::null_mut(),
@@ -95,7 +103,7 @@ impl FfiHttpRequest {
             headers_len,
             body,
         });
-        Box::into_raw(ffi_req)
+        crate::tracking::track(Box::into_raw(ffi_req))
     }
 }
 
@@ -128,6 +136,14 @@ pub enum FfiErrorCode {
     Serialization = 4,
     Panic = 5,
     NullArg = 6,
+    Conflict = 7,
+    Validation = 8,
+    RateLimited = 9,
+    Transport = 10,
+    SchemaMismatch = 11,
+    Tls = 12,
+    PreconditionFailed = 13,
+    Unauthorized = 14,
 }
 
 /// Tag that tells `todo_free_result` what `FfiTodoResult::data` points to.
@@ -136,6 +152,8 @@ pub enum FfiDataTag {
     None = 0,
     Todo = 1,
     TodoList = 2,
+    /// `data` points to a `uint64_t`.
+    Count = 3,
 }
 
 /// A single todo item exposed to C.
@@ -168,9 +186,22 @@ pub struct FfiTodoResult {
     pub data: *mut std::ffi::c_void,
 }
 
+/// Titles become C strings, which end at the first NUL. A server sending
+/// one in a title gets a `Deserialization` error rather than a truncated
+/// title (or a panic in `CString::new`).
+fn check_c_strings(todos: &[todo_core::Todo]) -> Result<(), ApiError> {
+    match todos.iter().find(|todo| todo.title.contains('\0')) {
+        Some(todo) => Err(ApiError::DeserializationError(format!("title of todo {} contains a NUL byte", todo.id))),
+        None => Ok(()),
+    }
+}
+
 impl FfiTodoResult {
     /// Build a success result carrying a single `FfiTodo`.
     pub(crate) fn ok_todo(todo: todo_core::Todo) -> *mut Self {
+        if let Err(e) = check_c_strings(std::slice::from_ref(&todo)) {
+            return Self::from_error(e);
+        }
         let ffi_todo = Box::new(FfiTodo {
             id: CString::new(todo.id.to_string()).unwrap().into_raw(),
             title: CString::new(todo.title).unwrap().into_raw(),
@@ -183,11 +214,15 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::Todo,
             data: Box::into_raw(ffi_todo) as *mut std::ffi::c_void,
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
     }
 
-    /// Build a success result carrying a `FfiTodoList`.
-    pub(crate) fn ok_todo_list(todos: Vec<todo_core::Todo>) -> *mut Self {
+    /// Build a success result carrying a `FfiTodoList`. Public so `benches/`
+    /// can measure the conversion apart from parsing.
+    pub fn ok_todo_list(todos: Vec<todo_core::Todo>) -> *mut Self {
+        if let Err(e) = check_c_strings(&todos) {
+            return Self::from_error(e);
+        }
         let len = todos.len() as u32;
         let mut ffi_todos: Vec<FfiTodo> = todos
             .into_iter()
@@ -214,7 +249,19 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::TodoList,
             data: Box::into_raw(ffi_list) as *mut std::ffi::c_void,
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
+    }
+
+    /// Build a success result carrying a count (e.g. delete-all).
+    pub(crate) fn ok_count(count: u64) -> *mut Self {
+        let result = Box::new(FfiTodoResult {
+            error_code: FfiErrorCode::Ok,
+            error_message: std::ptr::null_mut(),
+            http_status: 0,
+            data_tag: FfiDataTag::Count,
+            data: Box::into_raw(Box::new(count)) as *mut std::ffi::c_void,
+        });
+        crate::tracking::track(Box::into_raw(result))
     }
 
     /// Build a success result with no data payload (e.g. delete).
@@ -226,13 +273,18 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::None,
             data: std::ptr::null_mut(),
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
     }
 
     /// Build an error result from an `ApiError`.
     pub(crate) fn from_error(err: ApiError) -> *mut Self {
         let (error_code, http_status, msg) = match &err {
-            ApiError::NotFound => (FfiErrorCode::NotFound, 404u16, err.to_string()),
+            ApiError::NotFound | ApiError::NotFoundId(_) => {
+                (FfiErrorCode::NotFound, 404u16, err.to_string())
+            }
+            ApiError::Conflict(_) => (FfiErrorCode::Conflict, 409, err.to_string()),
+            ApiError::Unauthorized => (FfiErrorCode::Unauthorized, 401, err.to_string()),
+            ApiError::PreconditionFailed => (FfiErrorCode::PreconditionFailed, 412, err.to_string()),
             ApiError::HttpError { status, .. } => {
                 (FfiErrorCode::Http, *status, err.to_string())
             }
@@ -242,6 +294,11 @@ impl FfiTodoResult {
             ApiError::SerializationError(_) => {
                 (FfiErrorCode::Serialization, 0, err.to_string())
             }
+            ApiError::Validation(_) => (FfiErrorCode::Validation, 0, err.to_string()),
+            ApiError::RateLimited { .. } => (FfiErrorCode::RateLimited, 429, err.to_string()),
+            ApiError::SchemaMismatch { .. } => (FfiErrorCode::SchemaMismatch, 0, err.to_string()),
+            ApiError::Transport(_) => (FfiErrorCode::Transport, 0, err.to_string()),
+            ApiError::Tls(_) => (FfiErrorCode::Tls, 0, err.to_string()),
         };
 
         let result = Box::new(FfiTodoResult {
@@ -251,7 +308,7 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::None,
             data: std::ptr::null_mut(),
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
     }
 
     /// Build an error result for a null argument.
@@ -264,7 +321,7 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::None,
             data: std::ptr::null_mut(),
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
     }
 
     /// Build an error result for a caught panic.
@@ -276,6 +333,6 @@ impl FfiTodoResult {
             data_tag: FfiDataTag::None,
             data: std::ptr::null_mut(),
         });
-        Box::into_raw(result)
+        crate::tracking::track(Box::into_raw(result))
     }
 }
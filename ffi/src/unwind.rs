@@ -0,0 +1,79 @@
+//! Panic containment for the `extern "C"` entry points.
+//!
+//! # Design
+//! Entry points call [`catch_unwind`] from here instead of `std::panic`, so
+//! the `panic-abort` feature can switch every one of them at compile time:
+//! - Default: the body runs under `std::panic::catch_unwind` and a panic
+//!   becomes a null return or an `FfiErrorCode::Panic` result.
+//! - `panic-abort`: the body runs directly and a panic aborts the process
+//!   before any unwind reaches the caller's frames. The `Err` branches at
+//!   the call sites are then dead code. Combined with the `ffi-release`
+//!   profile (`panic = "abort"`), the library carries no unwinding
+//!   machinery at all, for hosts whose toolchains forbid it.
+
+use std::panic::UnwindSafe;
+
+/// Run `f`, turning a panic into `Err` (the default).
+#[cfg(not(feature = "panic-abort"))]
+pub(crate) fn catch_unwind<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> std::thread::Result<R> {
+    std::panic::catch_unwind(f)
+}
+
+/// Run `f`, aborting the process if it panics (`panic-abort`).
+///
+/// Keeps the `UnwindSafe` bound so both modes accept the same bodies.
+#[cfg(feature = "panic-abort")]
+pub(crate) fn catch_unwind<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> std::thread::Result<R> {
+    /// Only dropped while unwinding out of `f`; with `panic = "abort"`
+    /// that never happens and the guard compiles away.
+    struct AbortOnUnwind;
+
+    impl Drop for AbortOnUnwind {
+        fn drop(&mut self) {
+            std::process::abort();
+        }
+    }
+
+    let guard = AbortOnUnwind;
+    let value = f();
+    std::mem::forget(guard);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_value() {
+        assert_eq!(catch_unwind(|| 7).unwrap(), 7);
+    }
+
+    #[cfg(not(feature = "panic-abort"))]
+    #[test]
+    fn panic_becomes_err() {
+        let result = catch_unwind(|| -> u8 { panic!("boom") });
+        assert!(result.is_err());
+    }
+
+    /// Runs in a child process spawned by `panic_aborts`.
+    #[cfg(feature = "panic-abort")]
+    #[test]
+    #[ignore = "aborts the process; run by panic_aborts"]
+    fn abort_child() {
+        let _ = catch_unwind(|| -> u8 { panic!("boom") });
+    }
+
+    #[cfg(all(feature = "panic-abort", unix))]
+    #[test]
+    fn panic_aborts() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "unwind::tests::abort_child", "--ignored", "--nocapture"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.signal(), Some(6), "{output:?}");
+        assert!(String::from_utf8_lossy(&output.stderr).contains("boom"));
+    }
+}
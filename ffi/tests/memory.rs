@@ -0,0 +1,305 @@
+//! Allocate/free cycles over every owning pointer the C ABI hands out,
+//! meant to be run under a memory checker.
+//!
+//! # Design
+//! Plain `cargo test` only proves the cycles do not crash. The point is to
+//! run this binary where leaks, double frees and use-after-free are errors:
+//!
+//! ```text
+//! cargo +nightly miri test -p todo-ffi --test memory
+//! RUSTFLAGS=-Zsanitizer=address cargo +nightly test -Zbuild-std \
+//!     --target x86_64-unknown-linux-gnu -p todo-ffi --test memory
+//! ```
+//!
+//! Every cycle frees what it allocated through the public release
+//! functions only, reading each payload first so checkers see the reads.
+//! Miri runs far slower, so it gets fewer rounds and a shorter huge list.
+//!
+//! The misuse tests check the debug-build pointer tracking: freeing twice
+//! or through the wrong release function must abort with a message, not
+//! corrupt the heap. They re-run this binary on an ignored child test and
+//! look at how it died, so they are skipped under Miri.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use todo_ffi::dotnet::{self, FfiNetHttpRequest, FfiNetTodoList, FfiNetTodoResult};
+use todo_ffi::types::{FfiDataTag, FfiHttpRequest, FfiHttpResponse, FfiTodoClient, FfiTodoList, FfiTodoResult};
+
+const ID: &str = "00000000-0000-0000-0000-000000000001";
+const TODO: &str = r#"{"id":"00000000-0000-0000-0000-000000000001","title":"milk","completed":false}"#;
+
+fn rounds() -> usize {
+    if cfg!(miri) { 2 } else { 50 }
+}
+
+fn huge_len() -> usize {
+    if cfg!(miri) { 64 } else { 20_000 }
+}
+
+fn utf16(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain([0]).collect()
+}
+
+fn new_client() -> *mut FfiTodoClient {
+    let client = todo_ffi::todo_client_new(c"http://localhost:3000".as_ptr());
+    assert!(!client.is_null());
+    client
+}
+
+/// Read every string a request owns, then free it.
+fn consume_request(req: *mut FfiHttpRequest) {
+    assert!(!req.is_null());
+    let r = unsafe { &*req };
+    let mut bytes = unsafe { CStr::from_ptr(r.path) }.to_bytes().len();
+    if !r.body.is_null() {
+        bytes += unsafe { CStr::from_ptr(r.body) }.to_bytes().len();
+    }
+    for i in 0..r.headers_len as usize {
+        let header = unsafe { &*r.headers.add(i) };
+        bytes += unsafe { CStr::from_ptr(header.key) }.to_bytes().len();
+        bytes += unsafe { CStr::from_ptr(header.value) }.to_bytes().len();
+    }
+    assert!(bytes > 0);
+    todo_ffi::todo_free_request(req);
+}
+
+fn consume_net_request(req: *mut FfiNetHttpRequest) {
+    assert!(!req.is_null());
+    let r = unsafe { &*req };
+    assert!(!unsafe { CStr::from_ptr(r.path) }.to_bytes().is_empty());
+    for i in 0..r.headers_len as usize {
+        let header = unsafe { &*r.headers.add(i) };
+        assert!(!unsafe { CStr::from_ptr(header.key) }.to_bytes().is_empty());
+    }
+    dotnet::todo_net_free_request(req);
+}
+
+/// Read a result's message and payload, then free it. Returns the number
+/// of todos it carried.
+fn consume_result(result: *mut FfiTodoResult) -> usize {
+    assert!(!result.is_null());
+    let r = unsafe { &*result };
+    if !r.error_message.is_null() {
+        let _ = unsafe { CStr::from_ptr(r.error_message) }.to_bytes();
+    }
+    let todos = match r.data_tag {
+        _ if r.data.is_null() => 0,
+        FfiDataTag::Todo => 1,
+        FfiDataTag::TodoList => {
+            let list = unsafe { &*(r.data as *const FfiTodoList) };
+            for i in 0..list.len as usize {
+                let todo = unsafe { &*list.items.add(i) };
+                let _ = unsafe { CStr::from_ptr(todo.title) }.to_bytes();
+            }
+            list.len as usize
+        }
+        FfiDataTag::Count => unsafe { *(r.data as *const u64) as usize },
+        FfiDataTag::None => 0,
+    };
+    todo_ffi::todo_free_result(result);
+    todos
+}
+
+fn consume_net_result(result: *mut FfiNetTodoResult) -> usize {
+    assert!(!result.is_null());
+    let r = unsafe { &*result };
+    if !r.error_message.is_null() {
+        let _ = unsafe { CStr::from_ptr(r.error_message) }.to_bytes();
+    }
+    let todos = if r.data_tag == FfiDataTag::TodoList as i32 && !r.data.is_null() {
+        let list = unsafe { &*(r.data as *const FfiNetTodoList) };
+        for i in 0..list.len as usize {
+            let todo = unsafe { &*list.items.add(i) };
+            let _ = unsafe { CStr::from_ptr(todo.title) }.to_bytes();
+        }
+        list.len as usize
+    } else {
+        0
+    };
+    dotnet::todo_net_free_result(result);
+    todos
+}
+
+fn response(status: u16, body: &CString) -> FfiHttpResponse {
+    FfiHttpResponse { status, body: body.as_ptr() }
+}
+
+#[test]
+fn client_cycles() {
+    for _ in 0..rounds() {
+        let client = new_client();
+        let clone = todo_ffi::todo_client_clone(client);
+        assert!(todo_ffi::todo_client_eq(client, clone));
+        todo_ffi::todo_client_free(client);
+        // The clone owns its own state.
+        consume_request(todo_ffi::todo_build_list_todos(clone));
+        todo_ffi::todo_client_free(clone);
+
+        let auth = todo_ffi::todo_client_new_basic_auth(c"http://localhost:3000".as_ptr(), c"u".as_ptr(), c"p".as_ptr());
+        todo_ffi::todo_client_free(auth);
+        let net = dotnet::todo_net_client_new(utf16("http://localhost:3000").as_ptr());
+        todo_ffi::todo_client_free(net);
+
+        assert!(todo_ffi::todo_client_new(c"not a url".as_ptr()).is_null());
+        todo_ffi::todo_client_free(std::ptr::null_mut());
+    }
+}
+
+#[test]
+fn request_cycles() {
+    let client = new_client();
+    let id = CString::new(ID).unwrap();
+    for _ in 0..rounds() {
+        consume_request(todo_ffi::todo_build_list_todos(client));
+        consume_request(todo_ffi::todo_build_get_todo(client, id.as_ptr()));
+        consume_request(todo_ffi::todo_build_create_todo(client, c"milk".as_ptr(), true));
+        consume_request(todo_ffi::todo_build_update_todo(client, id.as_ptr(), c"eggs".as_ptr(), 1));
+        consume_request(todo_ffi::todo_build_update_todo(client, id.as_ptr(), std::ptr::null(), -1));
+        consume_request(todo_ffi::todo_build_delete_todo(client, id.as_ptr()));
+        consume_request(todo_ffi::todo_build_delete_all(client));
+        assert!(todo_ffi::todo_build_get_todo(client, c"nope".as_ptr()).is_null());
+        todo_ffi::todo_free_request(std::ptr::null_mut());
+
+        let net_id = utf16(ID);
+        consume_net_request(dotnet::todo_net_build_list_todos(client));
+        consume_net_request(dotnet::todo_net_build_get_todo(client, net_id.as_ptr()));
+        consume_net_request(dotnet::todo_net_build_create_todo(client, utf16("milk").as_ptr(), 1));
+        consume_net_request(dotnet::todo_net_build_update_todo(client, net_id.as_ptr(), utf16("eggs").as_ptr(), 0));
+        consume_net_request(dotnet::todo_net_build_delete_todo(client, net_id.as_ptr()));
+        consume_net_request(dotnet::todo_net_build_delete_all(client));
+        dotnet::todo_net_free_request(std::ptr::null_mut());
+    }
+    todo_ffi::todo_client_free(client);
+}
+
+#[test]
+fn result_cycles() {
+    let client = new_client();
+    let todo = CString::new(TODO).unwrap();
+    let list = CString::new(format!("[{TODO},{TODO}]")).unwrap();
+    let deleted = CString::new(r#"{"deleted":3}"#).unwrap();
+    let empty = CString::new("").unwrap();
+    let garbage = CString::new("{").unwrap();
+    for _ in 0..rounds() {
+        assert_eq!(consume_result(todo_ffi::todo_parse_list_todos(client, &response(200, &list))), 2);
+        assert_eq!(consume_result(todo_ffi::todo_parse_get_todo(client, &response(200, &todo))), 1);
+        assert_eq!(consume_result(todo_ffi::todo_parse_create_todo(client, &response(201, &todo))), 1);
+        assert_eq!(consume_result(todo_ffi::todo_parse_update_todo(client, &response(200, &todo))), 1);
+        assert_eq!(consume_result(todo_ffi::todo_parse_delete_todo(client, &response(204, &empty))), 0);
+        assert_eq!(consume_result(todo_ffi::todo_parse_delete_all(client, &response(200, &deleted))), 3);
+        // Error results own a message instead of a payload.
+        consume_result(todo_ffi::todo_parse_get_todo(client, &response(404, &empty)));
+        consume_result(todo_ffi::todo_parse_list_todos(client, &response(200, &garbage)));
+        consume_result(todo_ffi::todo_parse_list_todos(std::ptr::null(), &response(200, &list)));
+        todo_ffi::todo_free_result(std::ptr::null_mut());
+
+        let net_list = utf16(list.to_str().unwrap());
+        let net_todo = utf16(TODO);
+        assert_eq!(consume_net_result(dotnet::todo_net_parse_list_todos(client, 200, net_list.as_ptr())), 2);
+        consume_net_result(dotnet::todo_net_parse_get_todo(client, 200, net_todo.as_ptr()));
+        consume_net_result(dotnet::todo_net_parse_create_todo(client, 201, net_todo.as_ptr()));
+        consume_net_result(dotnet::todo_net_parse_update_todo(client, 200, net_todo.as_ptr()));
+        consume_net_result(dotnet::todo_net_parse_delete_todo(client, 204, std::ptr::null()));
+        consume_net_result(dotnet::todo_net_parse_delete_all(client, 200, utf16(r#"{"deleted":3}"#).as_ptr()));
+        consume_net_result(dotnet::todo_net_parse_get_todo(client, 500, std::ptr::null()));
+        dotnet::todo_net_free_result(std::ptr::null_mut());
+    }
+    todo_ffi::todo_client_free(client);
+}
+
+#[test]
+fn huge_list_cycles() {
+    let client = new_client();
+    let items: Vec<String> = (0..huge_len())
+        .map(|i| format!(r#"{{"id":"00000000-0000-0000-0000-{i:012}","title":"todo {i}","completed":{}}}"#, i % 2 == 0))
+        .collect();
+    let body = format!("[{}]", items.join(","));
+    let c_body = CString::new(body.as_str()).unwrap();
+    let net_body = utf16(&body);
+    for _ in 0..2 {
+        let parsed = consume_result(todo_ffi::todo_parse_list_todos(client, &response(200, &c_body)));
+        assert_eq!(parsed, huge_len());
+        let parsed = consume_net_result(dotnet::todo_net_parse_list_todos(client, 200, net_body.as_ptr()));
+        assert_eq!(parsed, huge_len());
+    }
+    todo_ffi::todo_client_free(client);
+}
+
+#[test]
+fn strings_free_cleanly() {
+    let s = CString::new("owned by the caller").unwrap().into_raw();
+    todo_ffi::todo_free_string(s);
+    todo_ffi::todo_free_string(std::ptr::null_mut::<c_char>());
+}
+
+// ---------------------------------------------------------------------------
+// Misuse (debug builds only)
+// ---------------------------------------------------------------------------
+
+/// Runs `child` alone in a fresh copy of this binary and returns its
+/// stderr after checking it died of `SIGABRT`.
+#[cfg(all(debug_assertions, unix))]
+fn abort_message(child: &str) -> String {
+    use std::os::unix::process::ExitStatusExt;
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", child, "--ignored", "--nocapture"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.signal(), Some(6), "{output:?}");
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[ignore = "aborts the process; run by double_free_aborts"]
+fn double_free_child() {
+    let client = new_client();
+    let result = todo_ffi::todo_parse_get_todo(client, &response(404, &CString::new("").unwrap()));
+    todo_ffi::todo_free_result(result);
+    todo_ffi::todo_free_result(result);
+}
+
+#[cfg(all(debug_assertions, unix))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn double_free_aborts() {
+    let stderr = abort_message("double_free_child");
+    assert!(stderr.contains("todo_free_result: 0x"), "{stderr}");
+    assert!(stderr.contains("was already freed or did not come from this library"), "{stderr}");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[ignore = "aborts the process; run by wrong_release_function_aborts"]
+fn wrong_release_function_child() {
+    let client = new_client();
+    let req = todo_ffi::todo_build_list_todos(client);
+    todo_ffi::todo_free_result(req.cast::<FfiTodoResult>());
+}
+
+#[cfg(all(debug_assertions, unix))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn wrong_release_function_aborts() {
+    let stderr = abort_message("wrong_release_function_child");
+    assert!(stderr.contains("todo_free_result: 0x"), "{stderr}");
+    assert!(stderr.contains("FfiHttpRequest, not a todo_ffi::types::FfiTodoResult"), "{stderr}");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[ignore = "aborts the process; run by foreign_pointer_aborts"]
+fn foreign_pointer_child() {
+    let client = Box::into_raw(Box::new(0u64));
+    todo_ffi::todo_client_free(client.cast::<FfiTodoClient>());
+}
+
+#[cfg(all(debug_assertions, unix))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn foreign_pointer_aborts() {
+    let stderr = abort_message("foreign_pointer_child");
+    assert!(stderr.contains("todo_client_free: 0x"), "{stderr}");
+}
@@ -0,0 +1,157 @@
+//! Property tests: values survive the C ABI's string and struct conversions.
+//!
+//! # Design
+//! Mirrors `core/tests/roundtrip.rs` one layer out. Inputs go in as C
+//! strings (UTF-8) or UTF-16 as a C or .NET host would pass them, and
+//! results are read back out of the `FfiTodo` structs, so the properties
+//! cover `CString`/UTF-16 conversion and struct construction on top of the
+//! core's JSON handling.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use proptest::prelude::*;
+use todo_core::{CreateTodo, Todo};
+use todo_ffi::dotnet::{self, FfiNetTodo, FfiNetTodoList};
+use todo_ffi::types::{FfiDataTag, FfiErrorCode, FfiHttpResponse, FfiTodo, FfiTodoClient, FfiTodoList, FfiTodoResult};
+use uuid::Uuid;
+
+fn uuid() -> impl Strategy<Value = Uuid> {
+    prop_oneof![Just(Uuid::nil()), Just(Uuid::max()), any::<u128>().prop_map(Uuid::from_u128)]
+}
+
+fn title() -> impl Strategy<Value = String> {
+    prop_oneof![any::<String>(), "[\"\\\\\u{0}-\u{1f}\u{2028}\u{feff}\u{1f600}a ]{0,16}", Just(String::new())]
+}
+
+fn todo() -> impl Strategy<Value = Todo> {
+    (uuid(), title(), any::<bool>()).prop_map(|(id, title, completed)| Todo { id, title, completed, schema_version: None })
+}
+
+fn utf16(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain([0]).collect()
+}
+
+unsafe fn string(s: *const c_char) -> String {
+    unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string()
+}
+
+/// A client for the duration of `f`.
+fn with_client<T>(f: impl FnOnce(*mut FfiTodoClient) -> T) -> T {
+    let client = todo_ffi::todo_client_new(c"http://localhost:3000".as_ptr());
+    let out = f(client);
+    todo_ffi::todo_client_free(client);
+    out
+}
+
+unsafe fn ffi_todo(todo: &FfiTodo) -> (String, String, bool) {
+    unsafe { (string(todo.id), string(todo.title), todo.completed) }
+}
+
+unsafe fn net_todo(todo: &FfiNetTodo) -> (String, String, bool) {
+    unsafe { (string(todo.id), string(todo.title), todo.completed != 0) }
+}
+
+fn expected(todo: &Todo) -> (String, String, bool) {
+    (todo.id.to_string(), todo.title.clone(), todo.completed)
+}
+
+/// Parse `body` with `parse` and read the single todo back out.
+fn parse_todo(
+    parse: extern "C" fn(*const FfiTodoClient, *const FfiHttpResponse) -> *mut FfiTodoResult,
+    status: u16,
+    body: &str,
+) -> Result<(String, String, bool), String> {
+    with_client(|client| {
+        let body = CString::new(body).unwrap();
+        let result = parse(client, &FfiHttpResponse { status, body: body.as_ptr() });
+        let r = unsafe { &*result };
+        let out = match (&r.error_code, &r.data_tag) {
+            (FfiErrorCode::Ok, FfiDataTag::Todo) => Ok(unsafe { ffi_todo(&*(r.data as *const FfiTodo)) }),
+            (FfiErrorCode::Deserialization, _) => Err(unsafe { string(r.error_message) }),
+            _ => panic!("unexpected error: {}", unsafe { string(r.error_message) }),
+        };
+        todo_ffi::todo_free_result(result);
+        out
+    })
+}
+
+proptest! {
+    #[test]
+    fn create_title_survives_c_strings(title in title().prop_filter("C strings end at NUL", |t| !t.contains('\0')), completed in any::<bool>()) {
+        let body = with_client(|client| {
+            let c_title = CString::new(title.as_str()).unwrap();
+            let req = todo_ffi::todo_build_create_todo(client, c_title.as_ptr(), completed);
+            let body = unsafe { string((*req).body) };
+            todo_ffi::todo_free_request(req);
+            body
+        });
+        let decoded: CreateTodo = serde_json::from_str(&body).unwrap();
+        prop_assert_eq!((decoded.title, decoded.completed), (title, completed));
+    }
+
+    #[test]
+    fn create_title_survives_utf16(title in title().prop_filter("C strings end at NUL", |t| !t.contains('\0')), completed in any::<bool>()) {
+        let body = with_client(|client| {
+            let req = dotnet::todo_net_build_create_todo(client, utf16(&title).as_ptr(), u8::from(completed));
+            let body = unsafe { string((*req).body) };
+            dotnet::todo_net_free_request(req);
+            body
+        });
+        let decoded: CreateTodo = serde_json::from_str(&body).unwrap();
+        prop_assert_eq!((decoded.title, decoded.completed), (title, completed));
+    }
+
+    #[test]
+    fn served_todo_parses_back(todo in todo()) {
+        let body = serde_json::to_string(&todo).unwrap();
+        for (parse, status) in [
+            (todo_ffi::todo_parse_get_todo as extern "C" fn(_, _) -> _, 200),
+            (todo_ffi::todo_parse_create_todo, 201),
+            (todo_ffi::todo_parse_update_todo, 200),
+        ] {
+            let parsed = parse_todo(parse, status, &body);
+            if todo.title.contains('\0') {
+                // A C string cannot carry it; the host gets an error, not a panic.
+                prop_assert!(parsed.unwrap_err().contains("NUL"));
+            } else {
+                prop_assert_eq!(parsed.unwrap(), expected(&todo));
+            }
+        }
+    }
+
+    #[test]
+    fn served_list_parses_back(todos in proptest::collection::vec(todo(), 0..8)) {
+        let body = serde_json::to_string(&todos).unwrap();
+        let representable = !todos.iter().any(|t| t.title.contains('\0'));
+        let (utf8, net) = with_client(|client| {
+            let c_body = CString::new(body.as_str()).unwrap();
+            let result = todo_ffi::todo_parse_list_todos(client, &FfiHttpResponse { status: 200, body: c_body.as_ptr() });
+            let r = unsafe { &*result };
+            let utf8 = match (&r.error_code, &r.data_tag) {
+                (FfiErrorCode::Ok, FfiDataTag::TodoList) => {
+                    let list = unsafe { &*(r.data as *const FfiTodoList) };
+                    let items = if list.len == 0 { &[][..] } else { unsafe { std::slice::from_raw_parts(list.items, list.len as usize) } };
+                    Some(items.iter().map(|t| unsafe { ffi_todo(t) }).collect::<Vec<_>>())
+                }
+                (FfiErrorCode::Deserialization, _) => None,
+                _ => panic!("unexpected error: {}", unsafe { string(r.error_message) }),
+            };
+            todo_ffi::todo_free_result(result);
+
+            let result = dotnet::todo_net_parse_list_todos(client, 200, utf16(&body).as_ptr());
+            let r = unsafe { &*result };
+            assert!([FfiErrorCode::Ok as i32, FfiErrorCode::Deserialization as i32].contains(&r.error_code));
+            let net = (r.error_code == FfiErrorCode::Ok as i32).then(|| {
+                let list = unsafe { &*(r.data as *const FfiNetTodoList) };
+                let items = if list.len == 0 { &[][..] } else { unsafe { std::slice::from_raw_parts(list.items, list.len as usize) } };
+                items.iter().map(|t| unsafe { net_todo(t) }).collect::<Vec<_>>()
+            });
+            dotnet::todo_net_free_result(result);
+            (utf8, net)
+        });
+        let want = representable.then(|| todos.iter().map(expected).collect::<Vec<_>>());
+        prop_assert_eq!(&utf8, &want);
+        prop_assert_eq!(&net, &want);
+    }
+}
@@ -0,0 +1,129 @@
+//! Arbitrary responses into every `todo_parse_*` and `todo_net_parse_*`
+//! entry point, as a C or .NET host would pass them.
+//!
+//! Input layout: parser index, flags (bit 0: UTF-16 entry point, bit 1:
+//! null body), status as little-endian `u16`, then the body. Bodies are raw
+//! bytes cut at the first NUL, as C would see them, so
+//! invalid UTF-8 and UTF-16 (unpaired surrogates) reach the decoders. Each
+//! result is walked in full before it is freed, so under a sanitizer a bad
+//! pointer or length in any payload is caught, not only in the envelope.
+//! A caught panic is reported as `FfiErrorCode::Panic`, which fails here.
+
+#![no_main]
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use libfuzzer_sys::fuzz_target;
+use todo_ffi::dotnet::{self, FfiNetTodo, FfiNetTodoList, FfiNetTodoResult};
+use todo_ffi::types::{FfiDataTag, FfiErrorCode, FfiHttpResponse, FfiTodo, FfiTodoClient, FfiTodoList, FfiTodoResult};
+
+type Parse = extern "C" fn(*const FfiTodoClient, *const FfiHttpResponse) -> *mut FfiTodoResult;
+type NetParse = extern "C" fn(*const FfiTodoClient, u16, *const u16) -> *mut FfiNetTodoResult;
+
+const PARSERS: [Parse; 6] = [
+    todo_ffi::todo_parse_list_todos,
+    todo_ffi::todo_parse_get_todo,
+    todo_ffi::todo_parse_create_todo,
+    todo_ffi::todo_parse_update_todo,
+    todo_ffi::todo_parse_delete_todo,
+    todo_ffi::todo_parse_delete_all,
+];
+
+const NET_PARSERS: [NetParse; 6] = [
+    dotnet::todo_net_parse_list_todos,
+    dotnet::todo_net_parse_get_todo,
+    dotnet::todo_net_parse_create_todo,
+    dotnet::todo_net_parse_update_todo,
+    dotnet::todo_net_parse_delete_todo,
+    dotnet::todo_net_parse_delete_all,
+];
+
+fuzz_target!(|data: &[u8]| {
+    let [parser, flags, status_lo, status_hi, body @ ..] = data else {
+        return;
+    };
+    let index = usize::from(*parser) % PARSERS.len();
+    let status = u16::from_le_bytes([*status_lo, *status_hi]);
+    let null_body = flags & 2 != 0;
+    let client = todo_ffi::todo_client_new(c"http://localhost:3000".as_ptr());
+    assert!(!client.is_null());
+    if flags & 1 != 0 {
+        let mut units: Vec<u16> = body.chunks(2).map(|c| u16::from_le_bytes([c[0], *c.get(1).unwrap_or(&0)])).collect();
+        units.push(0);
+        let body = if null_body { std::ptr::null() } else { units.as_ptr() };
+        let result = NET_PARSERS[index](client, status, body);
+        walk_net(result);
+        dotnet::todo_net_free_result(result);
+    } else {
+        let mut bytes = body.to_vec();
+        bytes.push(0);
+        let body = if null_body { std::ptr::null() } else { bytes.as_ptr().cast::<c_char>() };
+        let response = FfiHttpResponse { status, body };
+        let result = PARSERS[index](client, &response);
+        walk(result);
+        todo_ffi::todo_free_result(result);
+    }
+    todo_ffi::todo_client_free(client);
+});
+
+/// Read every string a result owns.
+fn touch(s: *const c_char) {
+    if !s.is_null() {
+        let _ = unsafe { CStr::from_ptr(s) }.to_bytes().len();
+    }
+}
+
+fn walk(result: *mut FfiTodoResult) {
+    let result = unsafe { result.as_ref() }.expect("parse functions never return null");
+    assert!(!matches!(result.error_code, FfiErrorCode::Panic), "panicked: {:?}", unsafe { CStr::from_ptr(result.error_message) });
+    touch(result.error_message);
+    let is_ok = matches!(result.error_code, FfiErrorCode::Ok);
+    assert_eq!(is_ok, result.error_message.is_null());
+    match result.data_tag {
+        FfiDataTag::None => {}
+        FfiDataTag::Todo => walk_todo(unsafe { &*(result.data as *const FfiTodo) }),
+        FfiDataTag::TodoList => {
+            let list = unsafe { &*(result.data as *const FfiTodoList) };
+            if list.len > 0 {
+                for todo in unsafe { std::slice::from_raw_parts(list.items, list.len as usize) } {
+                    walk_todo(todo);
+                }
+            }
+        }
+        FfiDataTag::Count => {
+            let _ = unsafe { *(result.data as *const u64) };
+        }
+    }
+}
+
+fn walk_todo(todo: &FfiTodo) {
+    touch(todo.id);
+    touch(todo.title);
+}
+
+fn walk_net(result: *mut FfiNetTodoResult) {
+    let result = unsafe { result.as_ref() }.expect("parse functions never return null");
+    assert_ne!(result.error_code, FfiErrorCode::Panic as i32, "panicked: {:?}", unsafe { CStr::from_ptr(result.error_message) });
+    touch(result.error_message);
+    match result.data_tag {
+        tag if tag == FfiDataTag::Todo as i32 => walk_net_todo(unsafe { &*(result.data as *const FfiNetTodo) }),
+        tag if tag == FfiDataTag::TodoList as i32 => {
+            let list = unsafe { &*(result.data as *const FfiNetTodoList) };
+            if list.len > 0 {
+                for todo in unsafe { std::slice::from_raw_parts(list.items, list.len as usize) } {
+                    walk_net_todo(todo);
+                }
+            }
+        }
+        tag if tag == FfiDataTag::Count as i32 => {
+            let _ = unsafe { *(result.data as *const u64) };
+        }
+        _ => {}
+    }
+}
+
+fn walk_net_todo(todo: &FfiNetTodo) {
+    touch(todo.id);
+    touch(todo.title);
+}
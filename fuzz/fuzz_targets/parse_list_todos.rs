@@ -0,0 +1,29 @@
+//! Arbitrary responses into `TodoClient::parse_list_todos`.
+//!
+//! The list body is the largest untrusted input the client decodes, and
+//! headers feed the rate-limit and `Retry-After` parsing on error paths.
+//! Any outcome is fine as long as it is an `Err` rather than a panic.
+//! Status and headers are decoded from the front of the input; the rest is
+//! the body.
+
+#![no_main]
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::fuzz_target;
+use todo_core::{HttpResponse, TodoClient};
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let (Ok(status), Ok(headers)) = (u.arbitrary::<u16>(), u.arbitrary::<Vec<(String, String)>>()) else {
+        return;
+    };
+    let body = String::from_utf8_lossy(u.take_rest()).into_owned();
+    let client = TodoClient::new("http://localhost:3000");
+    if let Ok(todos) = client.parse_list_todos(HttpResponse { status, headers, body }) {
+        assert!(status / 100 == 2, "accepted status {status}");
+        for todo in todos {
+            // What was accepted must serialize back.
+            serde_json::to_string(&todo).unwrap();
+        }
+    }
+});
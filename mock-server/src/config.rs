@@ -0,0 +1,355 @@
+//! Server settings that are not data: where state lives, what failures to
+//! simulate, how hard clients may push and who may call at all, browsers
+//! included.
+//!
+//! # Design
+//! [`Config::from_vars`] reads every setting through one lookup function,
+//! so the binary passes the process environment while tests pass a map and
+//! never touch global state. Unset or empty variables keep the default;
+//! values that do not parse are an `InvalidInput` error, so a typo stops
+//! the server at startup instead of silently running without the mode.
+
+use std::{io, path::PathBuf, str::FromStr};
+
+use axum::http::{HeaderName, HeaderValue, Method};
+
+use crate::Entropy;
+
+/// Where the server keeps its state.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Storage {
+    /// Nothing outlives the process.
+    #[default]
+    Memory,
+    /// A JSON snapshot of the [`Store`](crate::Store) at this path, read at
+    /// startup (a missing file is an empty store) and replaced after every
+    /// write.
+    JsonFile(PathBuf),
+}
+
+/// Random 5xx responses, for soak-testing retry policies and error paths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chaos {
+    /// Chance, from 0 to 1, that a request fails.
+    pub rate: f64,
+    /// Seconds to send as `Retry-After` on 503s; omitted when `None`.
+    pub retry_after: Option<u64>,
+    /// Seed for the failure draws, to replay a run; `None` seeds randomly.
+    pub seed: Option<u64>,
+}
+
+/// Per-client token buckets, answering 429 with `Retry-After` once a
+/// client's bucket is empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    /// Requests a client can make back to back; also `X-RateLimit-Limit`.
+    pub burst: u32,
+    /// Requests a client earns back per second.
+    pub per_second: f64,
+}
+
+/// Cross-origin access for browser clients, such as the WASM binding
+/// served from a dev server on another port.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cors {
+    /// `Origin`s that may call; `None` allows any.
+    pub origins: Option<Vec<HeaderValue>>,
+    /// Methods a preflight may ask for.
+    pub methods: Vec<Method>,
+    /// Request headers a preflight may ask for.
+    pub headers: Vec<HeaderName>,
+}
+
+/// Methods allowed cross-origin unless `CORS_METHODS` says otherwise:
+/// every method the API routes.
+pub const DEFAULT_CORS_METHODS: [Method; 5] = [Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE];
+
+/// Request headers allowed cross-origin unless `CORS_HEADERS` says
+/// otherwise: every header the server reads.
+pub const DEFAULT_CORS_HEADERS: [&str; 9] = [
+    "authorization",
+    "content-md5",
+    "content-type",
+    "idempotency-key",
+    "if-match",
+    "prefer",
+    "x-api-key",
+    "x-correlation-id",
+    "x-tenant",
+];
+
+/// Everything [`app_with_config`](crate::app_with_config) needs besides
+/// the data itself.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub entropy: Entropy,
+    pub storage: Storage,
+    /// Todos to start with, as a JSON array or one JSON todo per line, ids
+    /// included. Only used when the store starts out empty, so a saved
+    /// store is not overwritten on restart.
+    pub seed_file: Option<PathBuf>,
+    /// Off when `None`.
+    pub chaos: Option<Chaos>,
+    /// Unlimited when `None`.
+    pub rate_limit: Option<RateLimit>,
+    /// When set, every route needs `Authorization: Bearer <token>` with one
+    /// of these tokens and answers 401 otherwise.
+    pub auth_tokens: Option<Vec<String>>,
+    /// Browsers may only call from the server's own origin when `None`.
+    pub cors: Option<Cors>,
+    /// Longest title accepted, in characters after normalization;
+    /// unlimited when `None`.
+    pub max_title_len: Option<usize>,
+    /// Refuse a title another todo already has with a 409.
+    pub unique_titles: bool,
+}
+
+impl Config {
+    /// [`Config::from_vars`] over the process environment.
+    pub fn from_env() -> io::Result<Self> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Settings from variables looked up with `var`:
+    ///
+    /// * `STORE_PATH`: use [`Storage::JsonFile`] at this path.
+    /// * `SEED_FILE`: start with the todos in this file.
+    /// * `CHAOS_RATE`: enable [`Chaos`] with this failure rate; a rate of 0
+    ///   leaves it off. `CHAOS_RETRY_AFTER` and `CHAOS_SEED` fill in the
+    ///   rest and are ignored without it.
+    /// * `RATE_LIMIT_PER_SEC`: enable [`RateLimit`] refilling at this rate;
+    ///   0 leaves it off. `RATE_LIMIT_BURST` sets the bucket size, by
+    ///   default the rate rounded up.
+    /// * `REQUIRE_AUTH`: `1` or `true` to require one of the comma-separated
+    ///   `AUTH_TOKENS` as a bearer token; `0` or `false` leaves it off.
+    /// * `CORS_ORIGINS`: enable [`Cors`] for these comma-separated origins,
+    ///   or `*` for any. `CORS_METHODS` and `CORS_HEADERS` replace
+    ///   [`DEFAULT_CORS_METHODS`] and [`DEFAULT_CORS_HEADERS`] and are
+    ///   ignored without it.
+    /// * `MAX_TITLE_LEN`: refuse longer titles with a 422.
+    /// * `UNIQUE_TITLES`: `1` or `true` to refuse duplicate titles with a
+    ///   409.
+    ///
+    /// Ids and timestamps are always [`Entropy::Random`].
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> io::Result<Self> {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        let storage = var("STORE_PATH").map_or(Storage::Memory, |path| Storage::JsonFile(path.into()));
+        let chaos = match parse::<f64>("CHAOS_RATE", var("CHAOS_RATE"))? {
+            Some(rate) if !(0.0..=1.0).contains(&rate) => {
+                return Err(invalid("CHAOS_RATE", "must be between 0 and 1"));
+            }
+            Some(rate) if rate > 0.0 => Some(Chaos {
+                rate,
+                retry_after: parse("CHAOS_RETRY_AFTER", var("CHAOS_RETRY_AFTER"))?,
+                seed: parse("CHAOS_SEED", var("CHAOS_SEED"))?,
+            }),
+            _ => None,
+        };
+        let rate_limit = match parse::<f64>("RATE_LIMIT_PER_SEC", var("RATE_LIMIT_PER_SEC"))? {
+            Some(rate) if !rate.is_finite() || rate < 0.0 => {
+                return Err(invalid("RATE_LIMIT_PER_SEC", "must be a positive number"));
+            }
+            Some(per_second) if per_second > 0.0 => Some(RateLimit {
+                burst: match parse("RATE_LIMIT_BURST", var("RATE_LIMIT_BURST"))? {
+                    Some(0) => return Err(invalid("RATE_LIMIT_BURST", "must be at least 1")),
+                    Some(burst) => burst,
+                    None => per_second.ceil() as u32,
+                },
+                per_second,
+            }),
+            _ => None,
+        };
+        let auth_tokens = match flag("REQUIRE_AUTH", var("REQUIRE_AUTH"))? {
+            false => None,
+            true => {
+                let tokens = list(var("AUTH_TOKENS"));
+                if tokens.is_empty() {
+                    return Err(invalid("AUTH_TOKENS", "REQUIRE_AUTH needs at least one token"));
+                }
+                Some(tokens)
+            }
+        };
+        let cors = match list(var("CORS_ORIGINS")) {
+            origins if origins.is_empty() => None,
+            origins => Some(Cors {
+                origins: match origins.iter().any(|origin| origin == "*") {
+                    true if origins.len() > 1 => return Err(invalid("CORS_ORIGINS", "* cannot be combined with origins")),
+                    true => None,
+                    false => Some(parse_each("CORS_ORIGINS", origins, |o| HeaderValue::from_str(&o))?),
+                },
+                methods: match var("CORS_METHODS") {
+                    None => DEFAULT_CORS_METHODS.to_vec(),
+                    Some(methods) => parse_each("CORS_METHODS", list(Some(methods)), |m| {
+                        Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                    })?,
+                },
+                headers: match var("CORS_HEADERS") {
+                    None => DEFAULT_CORS_HEADERS.map(HeaderName::from_static).to_vec(),
+                    Some(headers) => parse_each("CORS_HEADERS", list(Some(headers)), |h| HeaderName::from_str(&h))?,
+                },
+            }),
+        };
+        let max_title_len = match parse("MAX_TITLE_LEN", var("MAX_TITLE_LEN"))? {
+            Some(0) => return Err(invalid("MAX_TITLE_LEN", "must be at least 1")),
+            max => max,
+        };
+        Ok(Config {
+            entropy: Entropy::Random,
+            storage,
+            seed_file: var("SEED_FILE").map(PathBuf::from),
+            chaos,
+            rate_limit,
+            auth_tokens,
+            cors,
+            max_title_len,
+            unique_titles: flag("UNIQUE_TITLES", var("UNIQUE_TITLES"))?,
+        })
+    }
+}
+
+fn parse<T: FromStr>(name: &str, value: Option<String>) -> io::Result<Option<T>>
+where
+    T::Err: std::fmt::Display,
+{
+    value.map(|v| v.trim().parse().map_err(|e| invalid(name, e))).transpose()
+}
+
+/// An on/off switch: `1` or `true`, `0` or `false`; off when unset.
+fn flag(name: &str, value: Option<String>) -> io::Result<bool> {
+    match value.as_deref().map(str::trim) {
+        None | Some("0" | "false") => Ok(false),
+        Some("1" | "true") => Ok(true),
+        Some(other) => Err(invalid(name, format!("expected 1 or 0, got {other:?}"))),
+    }
+}
+
+/// The non-empty, trimmed items of a comma-separated value.
+fn list(value: Option<String>) -> Vec<String> {
+    let value = value.unwrap_or_default();
+    value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect()
+}
+
+fn parse_each<T, E: std::fmt::Display>(
+    name: &str,
+    items: Vec<String>,
+    parse: impl Fn(String) -> Result<T, E>,
+) -> io::Result<Vec<T>> {
+    items.into_iter().map(|item| parse(item).map_err(|e| invalid(name, e))).collect()
+}
+
+fn invalid(name: &str, reason: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("{name}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn from(vars: &[(&str, &str)]) -> io::Result<Config> {
+        let vars: HashMap<_, _> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn defaults_when_nothing_is_set() {
+        assert_eq!(from(&[]).unwrap(), Config::default());
+        assert_eq!(from(&[("STORE_PATH", ""), ("CHAOS_RATE", "0")]).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn reads_storage_and_chaos() {
+        let config = from(&[
+            ("STORE_PATH", "todos.json"),
+            ("CHAOS_RATE", "0.25"),
+            ("CHAOS_RETRY_AFTER", "2"),
+        ])
+        .unwrap();
+        assert_eq!(config.storage, Storage::JsonFile("todos.json".into()));
+        assert_eq!(config.seed_file, None);
+        assert_eq!(config.max_title_len, None);
+        assert!(!config.unique_titles);
+        assert_eq!(
+            config.chaos,
+            Some(Chaos {
+                rate: 0.25,
+                retry_after: Some(2),
+                seed: None,
+            })
+        );
+    }
+
+    #[test]
+    fn rate_limit_burst_defaults_to_the_rate() {
+        let limit = |vars| from(vars).unwrap().rate_limit;
+        assert_eq!(
+            limit(&[("RATE_LIMIT_PER_SEC", "2.5")]),
+            Some(RateLimit {
+                burst: 3,
+                per_second: 2.5,
+            })
+        );
+        assert_eq!(limit(&[("RATE_LIMIT_PER_SEC", "1"), ("RATE_LIMIT_BURST", "10")]).unwrap().burst, 10);
+        assert_eq!(limit(&[("RATE_LIMIT_BURST", "10")]), None);
+    }
+
+    #[test]
+    fn auth_tokens_are_comma_separated() {
+        let config = from(&[("REQUIRE_AUTH", "1"), ("AUTH_TOKENS", "alpha, beta,")]).unwrap();
+        assert_eq!(config.auth_tokens, Some(vec!["alpha".to_string(), "beta".to_string()]));
+        assert_eq!(from(&[("REQUIRE_AUTH", "0"), ("AUTH_TOKENS", "alpha")]).unwrap().auth_tokens, None);
+    }
+
+    #[test]
+    fn unique_titles_is_a_switch() {
+        for (value, on) in [("1", true), ("true", true), ("0", false), ("false", false)] {
+            assert_eq!(from(&[("UNIQUE_TITLES", value)]).unwrap().unique_titles, on, "{value}");
+        }
+    }
+
+    #[test]
+    fn cors_defaults_to_every_routed_method_and_read_header() {
+        let cors = from(&[("CORS_ORIGINS", "http://localhost:5173, http://127.0.0.1:5173")]).unwrap().cors.unwrap();
+        assert_eq!(
+            cors.origins,
+            Some(vec![
+                HeaderValue::from_static("http://localhost:5173"),
+                HeaderValue::from_static("http://127.0.0.1:5173")
+            ])
+        );
+        assert_eq!(cors.methods, DEFAULT_CORS_METHODS);
+        assert_eq!(cors.headers.len(), DEFAULT_CORS_HEADERS.len());
+
+        let cors = from(&[("CORS_ORIGINS", "*"), ("CORS_METHODS", "get,post"), ("CORS_HEADERS", "X-Custom")])
+            .unwrap()
+            .cors
+            .unwrap();
+        assert_eq!(cors.origins, None);
+        assert_eq!(cors.methods, [Method::GET, Method::POST]);
+        assert_eq!(cors.headers, [HeaderName::from_static("x-custom")]);
+        assert_eq!(from(&[("CORS_METHODS", "GET")]).unwrap().cors, None);
+    }
+
+    #[test]
+    fn rejects_values_that_do_not_parse() {
+        for vars in [
+            [("CHAOS_RATE", "often"), ("CHAOS_SEED", "")],
+            [("CHAOS_RATE", "1.5"), ("CHAOS_SEED", "")],
+            [("CHAOS_RATE", "0.5"), ("CHAOS_SEED", "-1")],
+            [("RATE_LIMIT_PER_SEC", "-1"), ("RATE_LIMIT_BURST", "")],
+            [("RATE_LIMIT_PER_SEC", "1"), ("RATE_LIMIT_BURST", "0")],
+            [("REQUIRE_AUTH", "1"), ("AUTH_TOKENS", " , ")],
+            [("REQUIRE_AUTH", "yes"), ("AUTH_TOKENS", "alpha")],
+            [("CORS_ORIGINS", "*, http://localhost"), ("CORS_METHODS", "")],
+            [("CORS_ORIGINS", "*"), ("CORS_METHODS", "GET POST")],
+            [("CORS_ORIGINS", "*"), ("CORS_HEADERS", "bad header")],
+            [("MAX_TITLE_LEN", "0"), ("CHAOS_SEED", "")],
+            [("MAX_TITLE_LEN", "long"), ("CHAOS_SEED", "")],
+            [("UNIQUE_TITLES", "on"), ("CHAOS_SEED", "")],
+        ] {
+            let err = from(&vars).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{vars:?}");
+        }
+    }
+}
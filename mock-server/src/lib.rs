@@ -1,131 +1,1594 @@
 //! In-memory CRUD todo API built on Axum.
 //!
 //! # Design
-//! State lives in a `HashMap<Uuid, Todo>` behind an `Arc<RwLock<..>>`, shared
-//! across all handlers. Each call to [`app`] creates a fresh, empty store so
-//! integration tests get isolation for free.
+//! State lives in a `Store` (todos plus per-todo revision logs) behind an
+//! `Arc<RwLock<..>>`, shared across all handlers. Each call to [`app`]
+//! creates a fresh, empty store so integration tests get isolation for
+//! free. Suites sharing one server get it from tenants instead: requests
+//! with an `X-Tenant` or `X-Api-Key` header each see a store of their own
+//! (see the `tenants` module). [`app_with`] can swap random ids and the
+//! wall clock for deterministic ones, so recorded responses are
+//! byte-for-byte reproducible (see `todo-vectorgen`). Every change is also
+//! published as a [`TodoEvent`] on the store's [`ChangeFeed`], which
+//! `GET /todos/events` relays as Server-Sent Events and `GET /todos/ws` as
+//! WebSocket messages.
 //!
-//! No persistence — this crate exists as a reference server for the rust-to-c
-//! translation project.
+//! [`app_with_config`] takes the rest of the settings as a [`Config`],
+//! which the binary reads from the environment. State is in memory unless
+//! [`Storage::JsonFile`] is chosen: the store is then loaded from a JSON
+//! file at startup and rewritten after every successful write, so the
+//! server survives restarts. [`Chaos`] fails a random share of requests,
+//! [`RateLimit`] answers 429 to clients that send too many, and
+//! `auth_tokens` turns away callers without a known bearer token. [`Cors`]
+//! lets browser apps on other origins call the server.
+//! This crate exists as a reference server for the rust-to-c translation
+//! project.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    future::Future,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::get,
+    body::{Body, Bytes},
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, Query, Request, State,
+    },
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
     Json, Router,
 };
+use base64::prelude::{Engine as _, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
-use tokio::{net::TcpListener, sync::RwLock};
+use tokio::{
+    net::TcpListener,
+    sync::{
+        broadcast::{self, error::RecvError},
+        watch, RwLock,
+    },
+};
+use tower_http::{
+    cors::{AllowOrigin, CorsLayer},
+    trace::{DefaultOnResponse, TraceLayer},
+    LatencyUnit,
+};
+use tracing::{Level, Span};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-/// A single todo item, the core domain type for every endpoint.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Todo {
-    pub id: Uuid,
-    pub title: String,
-    pub completed: bool,
+mod config;
+mod openapi;
+mod rate_limit;
+mod tenants;
+
+pub use config::{Chaos, Config, Cors, RateLimit, Storage, DEFAULT_CORS_HEADERS, DEFAULT_CORS_METHODS};
+pub use openapi::ApiDoc;
+pub use tenants::{TenantSummary, MAX_TENANTS};
+use tenants::{Tenant, Tenants};
+
+/// The request and response bodies, shared with `todo-core` so the two
+/// cannot drift apart.
+pub use todo_schema::{normalize_title, CreateTodo, Todo, TodoRevision, UpdateTodo};
+
+/// Query parameters accepted by `GET /todos/count`. Omitted fields match
+/// every todo.
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoFilter {
+    pub completed: Option<bool>,
 }
 
-/// Request body for `POST /todos`. The `completed` field defaults to `false`
-/// when omitted, so clients only need to send a title.
-#[derive(Deserialize)]
-pub struct CreateTodo {
-    pub title: String,
+/// Query parameters for `GET /todos/search`.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    /// Words to look for in titles.
+    pub q: String,
+    pub limit: Option<usize>,
+    /// Return titles with the matches wrapped in `<em>`.
     #[serde(default)]
-    pub completed: bool,
+    pub highlight: bool,
+}
+
+/// One element of the `GET /todos/search` response.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub todo: Todo,
+    pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+/// Response body for `GET /todos/count`.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TodoCount {
+    pub count: u64,
+}
+
+/// Response body for `POST /todos` with `Prefer: embed=list`: the new todo
+/// plus the full list after the insert, in creation order.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CreatedAndList {
+    pub created: Todo,
+    pub list: Vec<Todo>,
 }
 
-/// Request body for `PUT /todos/{id}`. All fields are optional; only the
-/// fields present in the JSON payload are applied, leaving the rest unchanged.
-#[derive(Deserialize)]
-pub struct UpdateTodo {
-    pub title: Option<String>,
+/// Query parameters for `GET /todos`. Supplying `cursor` (even empty)
+/// switches the response to a `CursorPage`; the filters apply either way.
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
     pub completed: Option<bool>,
+    pub title_contains: Option<String>,
+    /// Accepted so it can be refused: todos carry no tags, and silently
+    /// ignoring the filter would return todos the caller did not ask for.
+    pub tag: Option<String>,
+    /// `title`, `completed` or `created_at`.
+    pub sort: Option<String>,
+    /// `asc` (default) or `desc`.
+    pub order: Option<String>,
+}
+
+impl ListParams {
+    fn matches(&self, todo: &Todo) -> bool {
+        self.completed.is_none_or(|c| todo.completed == c)
+            && self.title_contains.as_deref().is_none_or(|s| todo.title.contains(s))
+    }
+}
+
+/// An RFC 9457 `application/problem+json` body, sent when a request
+/// cannot be honoured.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// On a 409 for a duplicate title, the todo that already has it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflicts_with: Option<Uuid>,
+}
+
+fn problem(status: StatusCode, detail: impl Into<String>) -> Response {
+    problem_with(status, detail, None)
+}
+
+/// The 409 for a title [`Store::title_taken`] found on `existing`.
+fn duplicate_title(existing: &Todo) -> Response {
+    let detail = format!("a todo titled {:?} already exists", existing.title);
+    problem_with(StatusCode::CONFLICT, detail, Some(existing.id))
+}
+
+fn problem_with(status: StatusCode, detail: impl Into<String>, conflicts_with: Option<Uuid>) -> Response {
+    let body = Problem {
+        kind: "about:blank".to_string(),
+        title: status.canonical_reason().unwrap_or_default().to_string(),
+        status: status.as_u16(),
+        detail: detail.into(),
+        conflicts_with,
+    };
+    let mut response = (status, Json(body)).into_response();
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static("application/problem+json"));
+    response
+}
+
+/// Why a JSON body was refused, as a problem: 400 for malformed JSON, 415
+/// without a JSON content type and 422 for a missing field or a value of
+/// the wrong type.
+fn rejected(rejection: JsonRejection) -> Response {
+    problem(rejection.status(), rejection.body_text())
+}
+
+/// One page of a cursor-paginated list. `next_cursor` is `None` on the last
+/// page.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CursorPage {
+    pub todos: Vec<Todo>,
+    pub next_cursor: Option<String>,
+}
+
+/// Page size when a cursor request does not set `limit`.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Response body for `DELETE /todos`.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DeletedCount {
+    pub deleted: u64,
+}
+
+/// Response body for `GET /capabilities`. Lists the schema version and the
+/// todo fields this server understands, so newer clients can drop the rest.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Capabilities {
+    pub schema_version: u32,
+    pub supported_fields: Vec<String>,
+}
+
+/// Request body for `POST /_faults`: fail the next `count` requests with
+/// `status` before handling any normally.
+#[derive(Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct InjectFaults {
+    pub count: u32,
+    pub status: u16,
+}
+
+/// Where the server gets new todo ids and revision timestamps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Entropy {
+    /// v4 ids and the system clock.
+    #[default]
+    Random,
+    /// Ids `00000000-0000-0000-0000-000000000001`, `...0002`, ... in
+    /// creation order, and every revision stamped at the Unix epoch.
+    Deterministic,
+}
+
+/// Todos plus their revision logs. Both maps are keyed by todo id and
+/// updated under the same lock, so a todo and its history never disagree.
+///
+/// The serialized form is one tenant's part of the [`Storage::JsonFile`]
+/// snapshot; injected faults and the server's configuration are not part
+/// of it.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Store {
+    pub todos: HashMap<Uuid, Todo>,
+    pub history: HashMap<Uuid, Vec<TodoRevision>>,
+    /// Pending injected failures, consumed one per request.
+    #[serde(skip)]
+    pub faults: Option<InjectFaults>,
+    /// Todo ids keyed by insertion sequence number; backs list cursors.
+    pub insertion_order: BTreeMap<u64, Uuid>,
+    pub next_seq: u64,
+    /// Todo created for each `Idempotency-Key` seen on a create.
+    pub idempotency_keys: HashMap<String, Uuid>,
+    #[serde(skip)]
+    pub entropy: Entropy,
+    #[serde(skip)]
+    pub changes: ChangeFeed,
+    /// See [`Config::max_title_len`].
+    #[serde(skip)]
+    pub max_title_len: Option<usize>,
+    /// See [`Config::unique_titles`].
+    #[serde(skip)]
+    pub unique_titles: bool,
+}
+
+/// A change to one todo, as pushed to change feed subscribers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TodoEvent {
+    Created { todo: Todo },
+    Updated { todo: Todo },
+    Deleted { id: Uuid },
+}
+
+impl TodoEvent {
+    /// The `type` tag, also used as the SSE event name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TodoEvent::Created { .. } => "created",
+            TodoEvent::Updated { .. } => "updated",
+            TodoEvent::Deleted { .. } => "deleted",
+        }
+    }
+}
+
+/// Events a subscriber may fall behind by before it starts missing some.
+const CHANGE_FEED_BUFFER: usize = 256;
+
+/// Fan-out of [`TodoEvent`]s: each subscriber gets every event sent after
+/// it subscribed, unless it falls more than [`CHANGE_FEED_BUFFER`] behind.
+/// Closing the feed ends every subscription, so streaming responses finish
+/// and a graceful shutdown can drain their connections.
+#[derive(Clone)]
+pub struct ChangeFeed {
+    events: broadcast::Sender<TodoEvent>,
+    closed: Arc<watch::Sender<bool>>,
+}
+
+/// One subscriber's view of a [`ChangeFeed`].
+pub struct Subscription {
+    events: broadcast::Receiver<TodoEvent>,
+    closed: watch::Receiver<bool>,
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        ChangeFeed {
+            events: broadcast::channel(CHANGE_FEED_BUFFER).0,
+            closed: Arc::new(watch::channel(false).0),
+        }
+    }
+}
+
+impl ChangeFeed {
+    pub fn subscribe(&self) -> Subscription {
+        Subscription {
+            events: self.events.subscribe(),
+            closed: self.closed.subscribe(),
+        }
+    }
+
+    /// A feed with its own events that closes along with this one.
+    fn sibling(&self) -> ChangeFeed {
+        ChangeFeed {
+            events: broadcast::channel(CHANGE_FEED_BUFFER).0,
+            closed: self.closed.clone(),
+        }
+    }
+
+    /// End every subscription, current and future.
+    pub fn close(&self) {
+        self.closed.send_replace(true);
+    }
+
+    /// Publish `event`; with no subscribers it is dropped.
+    fn send(&self, event: TodoEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+impl Subscription {
+    /// The next change, `Err` with the number of changes missed after
+    /// falling behind, or `None` once the feed is closed.
+    pub async fn next(&mut self) -> Option<Result<TodoEvent, u64>> {
+        tokio::select! {
+            _ = self.closed.wait_for(|closed| *closed) => None,
+            event = self.events.recv() => match event {
+                Ok(event) => Some(Ok(event)),
+                Err(RecvError::Lagged(missed)) => Some(Err(missed)),
+                Err(RecvError::Closed) => None,
+            },
+        }
+    }
+}
+
+impl Store {
+    /// The todos and bookkeeping of `data` under this store's settings,
+    /// with a change feed of its own that closes along with this one's.
+    fn with_data(&self, data: Store) -> Store {
+        Store {
+            faults: None,
+            entropy: self.entropy,
+            changes: self.changes.sibling(),
+            max_title_len: self.max_title_len,
+            unique_titles: self.unique_titles,
+            ..data
+        }
+    }
+
+    /// Strong ETag for `id`, derived from its revision count, or `None` if
+    /// the todo does not exist.
+    fn etag(&self, id: &Uuid) -> Option<String> {
+        self.todos.get(id)?;
+        let revision = self.history.get(id).map_or(0, Vec::len);
+        Some(format!("\"{revision}\""))
+    }
+
+    /// Enforce an `If-Match` precondition, if the request carries one.
+    fn check_if_match(&self, id: &Uuid, headers: &HeaderMap) -> Result<(), StatusCode> {
+        let Some(expected) = headers.get("if-match") else {
+            return Ok(());
+        };
+        let current = self.etag(id).ok_or(StatusCode::NOT_FOUND)?;
+        if expected == "*" || expected.as_bytes() == current.as_bytes() {
+            Ok(())
+        } else {
+            Err(StatusCode::PRECONDITION_FAILED)
+        }
+    }
+
+    /// Id for the next todo. Deterministic ids follow `next_seq`, which
+    /// only grows until a reset, so they are not reused; one already taken
+    /// by a seeded todo is skipped. Of the `todos.len() + 1` ids from
+    /// `next_seq` on, at least one is free, so the search stops there.
+    fn new_id(&self) -> Uuid {
+        match self.entropy {
+            Entropy::Random => Uuid::new_v4(),
+            Entropy::Deterministic => {
+                let first = u128::from(self.next_seq) + 1;
+                let last = first + self.todos.len() as u128;
+                (first..last)
+                    .map(Uuid::from_u128)
+                    .find(|id| !self.todos.contains_key(id))
+                    .unwrap_or(Uuid::from_u128(last))
+            }
+        }
+    }
+
+    /// Drop every todo, its history and the idempotency keys, and start the
+    /// sequence over, as if the server had just started with no saved
+    /// state. Injected faults and the configuration stay.
+    fn reset(&mut self) {
+        for id in self.insertion_order.values() {
+            self.changes.send(TodoEvent::Deleted { id: *id });
+        }
+        *self = Store {
+            faults: self.faults.take(),
+            changes: self.changes.clone(),
+            ..self.with_data(Store::default())
+        };
+    }
+
+    /// Refuse a title that is empty once normalized or longer than
+    /// [`Store::max_title_len`], with the reason.
+    fn check_title(&self, title: &str) -> Result<(), String> {
+        let length = normalize_title(title).chars().count();
+        if length == 0 {
+            return Err("title must not be empty".to_string());
+        }
+        match self.max_title_len {
+            Some(max) if length > max => Err(format!("title is {length} characters, more than the {max} allowed")),
+            _ => Ok(()),
+        }
+    }
+
+    /// With [`Store::unique_titles`] on, the todo other than `except` whose
+    /// title normalizes to the same text as `title`.
+    fn title_taken(&self, title: &str, except: Option<Uuid>) -> Option<&Todo> {
+        if !self.unique_titles {
+            return None;
+        }
+        let title = normalize_title(title);
+        self.todos
+            .values()
+            .find(|todo| Some(todo.id) != except && normalize_title(&todo.title) == title)
+    }
+
+    /// Store `todo` as given. A todo with a new id goes to the end of the
+    /// insertion order; one replacing an existing todo keeps its place and
+    /// gets a new revision.
+    fn upsert(&mut self, todo: Todo) {
+        self.record_revision(&todo);
+        match self.todos.get_mut(&todo.id) {
+            Some(existing) => *existing = todo,
+            None => self.insert(todo),
+        }
+    }
+
+    /// Insert a new todo at the end of the insertion order.
+    fn insert(&mut self, todo: Todo) {
+        self.insertion_order.insert(self.next_seq, todo.id);
+        self.next_seq += 1;
+        self.todos.insert(todo.id, todo);
+    }
+
+    /// Append the current state of `todo` to its revision log and announce
+    /// it on the change feed: the first revision is a create, the rest are
+    /// updates. Every change to a todo short of deleting it goes through
+    /// here.
+    fn record_revision(&mut self, todo: &Todo) {
+        let log = self.history.entry(todo.id).or_default();
+        let changed_at = match self.entropy {
+            Entropy::Random => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64),
+            Entropy::Deterministic => 0,
+        };
+        log.push(TodoRevision {
+            id: todo.id,
+            title: todo.title.clone(),
+            completed: todo.completed,
+            revision: log.len() as u64 + 1,
+            changed_at,
+        });
+        let event = match log.len() {
+            1 => TodoEvent::Created { todo: todo.clone() },
+            _ => TodoEvent::Updated { todo: todo.clone() },
+        };
+        self.changes.send(event);
+    }
 }
 
-/// Shared in-memory store. `RwLock` allows concurrent reads from `GET`/`LIST`
-/// handlers while serializing writes from `POST`/`PUT`/`DELETE`.
-pub type Db = Arc<RwLock<HashMap<Uuid, Todo>>>;
+/// One tenant's in-memory store. `RwLock` allows concurrent reads from
+/// `GET`/`LIST` handlers while serializing writes from `POST`/`PUT`/`DELETE`.
+pub type Db = Arc<RwLock<Store>>;
 
 /// Build a fresh Axum router with an empty todo store.
 ///
 /// Each call creates independent state, so tests can run in parallel without
 /// shared-mutable-state conflicts.
 pub fn app() -> Router {
-    let db: Db = Arc::new(RwLock::new(HashMap::new()));
+    app_with(Entropy::Random)
+}
+
+/// [`app`], taking ids and timestamps from `entropy`.
+pub fn app_with(entropy: Entropy) -> Router {
+    app_with_config(Config {
+        entropy,
+        ..Config::default()
+    })
+    .expect("an in-memory store always opens")
+}
+
+/// A server set up by `config`. Fails if a saved store or the seed file
+/// cannot be read.
+pub fn app_with_config(config: Config) -> io::Result<Router> {
+    build(config).map(|(router, _)| router)
+}
+
+/// [`app_with_config`], also returning the store's change feed so a
+/// shutdown can close it.
+fn build(config: Config) -> io::Result<(Router, ChangeFeed)> {
+    let template = Store {
+        entropy: config.entropy,
+        max_title_len: config.max_title_len,
+        unique_titles: config.unique_titles,
+        ..Store::default()
+    };
+    let seed_file = config.seed_file;
+    let tenants = Tenants::open(template, config.storage, |store| {
+        let Some(path) = seed_file else {
+            return Ok(());
+        };
+        let text = std::fs::read_to_string(&path)?;
+        let todos = parse_seed(&text)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display())))?;
+        for todo in todos {
+            store.upsert(todo);
+        }
+        Ok(())
+    })?;
+    let changes = tenants.changes().clone();
+    let mut router = router(tenants);
+    if let Some(chaos) = config.chaos {
+        router = router.layer(middleware::from_fn_with_state(Arc::new(ChaosState::new(chaos)), chaos_faults));
+    }
+    if let Some(tokens) = config.auth_tokens {
+        router = router.layer(middleware::from_fn_with_state(Arc::new(tokens), require_bearer));
+    }
+    // Outside the rest, so a client over its limit is refused before
+    // anything else happens.
+    if let Some(limit) = config.rate_limit {
+        let limiter = Arc::new(rate_limit::Limiter::new(limit));
+        router = router.layer(middleware::from_fn_with_state(limiter, rate_limit::rate_limit));
+    }
+    // Outside auth and the limiter, so preflights are answered without a
+    // token and refusals still carry the headers a browser needs to read
+    // them.
+    if let Some(cors) = config.cors {
+        router = router.layer(cors_layer(cors));
+    }
+    // Outermost, so refused requests are logged too.
+    let router = router.layer(
+        TraceLayer::new_for_http()
+            .make_span_with(request_span)
+            .on_request(())
+            .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Micros))
+            .on_failure(()),
+    );
+    Ok((router, changes))
+}
+
+/// Response headers browsers may show to cross-origin scripts, beyond the
+/// always-visible few: everything the server sets for clients to read.
+const EXPOSED_HEADERS: [&str; 9] = [
+    "etag",
+    "link",
+    "retry-after",
+    "www-authenticate",
+    "x-correlation-id",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    "x-total-count",
+];
+
+fn cors_layer(cors: Cors) -> CorsLayer {
+    let origins = match cors.origins {
+        Some(origins) => AllowOrigin::list(origins),
+        None => AllowOrigin::any(),
+    };
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(cors.methods)
+        .allow_headers(cors.headers)
+        .expose_headers(EXPOSED_HEADERS.map(HeaderName::from_static))
+}
+
+/// The span every request is logged in: method, path and the
+/// `x-correlation-id` the client sent (which [`echo_correlation_id`]
+/// returns), if any. Each response adds an event with its status and
+/// latency; the binary picks text or JSON output.
+fn request_span(request: &Request) -> Span {
+    let correlation_id = request.headers().get("x-correlation-id").and_then(|v| v.to_str().ok());
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = request.uri().path(),
+        correlation_id,
+    )
+}
+
+/// Serve `router`, recording each connection's peer address for the
+/// per-client rate limiter, until `shutdown` resolves. Then stop
+/// accepting, close `changes` so event streams end, and return once every
+/// open request has been answered.
+async fn serve(
+    listener: TcpListener,
+    router: Router,
+    changes: ChangeFeed,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), std::io::Error> {
+    let stopping = async move {
+        shutdown.await;
+        tracing::info!("shutting down, draining open requests");
+        changes.close();
+    };
+    axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(stopping)
+        .await
+}
+
+fn router(tenants: Tenants) -> Router {
+    let tenants = Arc::new(tenants);
     Router::new()
-        .route("/todos", get(list_todos).post(create_todo))
-        .route("/todos/{id}", get(get_todo).put(update_todo).delete(delete_todo))
-        .with_state(db)
+        .route("/_faults", post(inject_faults))
+        .route("/_admin/reset", post(admin_reset))
+        .route("/_admin/seed", post(admin_seed))
+        .route("/_admin/tenants", get(tenants::list_tenants))
+        .route("/capabilities", get(capabilities))
+        .route("/openapi.json", get(openapi::document))
+        .route("/todos", get(list_todos).post(create_todo).delete(delete_all))
+        .route("/todos/batch", post(create_todos_batch))
+        .route("/todos/count", get(count_todos))
+        .route("/todos/events", get(todo_events))
+        .route("/todos/ws", get(todo_socket))
+        .route("/todos/search", get(search_todos))
+        .route("/todos/{id}", get(get_todo).put(update_todo).patch(patch_todo).delete(delete_todo))
+        .route("/todos/{id}/history", get(get_history))
+        .layer(middleware::from_fn_with_state(tenants.clone(), persist))
+        .layer(middleware::from_fn(verify_content_md5))
+        .layer(middleware::from_fn(echo_correlation_id))
+        .layer(middleware::from_fn_with_state(tenants.clone(), injected_faults))
+        .with_state(tenants)
 }
 
-/// Serve the todo API on the given listener until the process is stopped.
-pub async fn run(listener: TcpListener) -> Result<(), std::io::Error> {
-    axum::serve(listener, app()).await
+/// Serve the todo API on the given listener until `shutdown` resolves,
+/// then drain open requests and return. Pass
+/// [`std::future::pending()`] to serve until the process is stopped.
+pub async fn run(
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), std::io::Error> {
+    run_with(listener, Entropy::Random, shutdown).await
 }
 
-async fn list_todos(State(db): State<Db>) -> Json<Vec<Todo>> {
-    let todos = db.read().await;
-    Json(todos.values().cloned().collect())
+/// [`run`], taking ids and timestamps from `entropy`.
+pub async fn run_with(
+    listener: TcpListener,
+    entropy: Entropy,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), std::io::Error> {
+    let config = Config {
+        entropy,
+        ..Config::default()
+    };
+    run_with_config(listener, config, shutdown).await
+}
+
+/// [`run`], with the server set up by `config`.
+pub async fn run_with_config(
+    listener: TcpListener,
+    config: Config,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), std::io::Error> {
+    let (router, changes) = build(config)?;
+    serve(listener, router, changes, shutdown).await
 }
 
+/// True when any `Prefer` header (RFC 7240) lists `preference`.
+fn prefers(headers: &HeaderMap, preference: &str) -> bool {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|pref| pref.trim() == preference)
+}
+
+/// Todos from a seed file: a JSON array, or else one todo per line (JSON
+/// Lines; blank lines are skipped).
+fn parse_seed(text: &str) -> Result<Vec<Todo>, String> {
+    if text.trim_start().starts_with('[') {
+        return serde_json::from_str(text).map_err(|e| e.to_string());
+    }
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+/// `POST /_admin/reset`: empty the store; see [`Store::reset`].
+#[utoipa::path(post, path = "/_admin/reset", tag = "testing", responses((status = 204, description = "Store emptied")))]
+async fn admin_reset(Tenant(db): Tenant) -> StatusCode {
+    db.write().await.reset();
+    StatusCode::NO_CONTENT
+}
+
+/// `POST /_admin/seed`: store a JSON array of complete todos, ids included,
+/// and echo them back. Todos are stored in array order, each replacing any
+/// todo with its id, so seeding after a reset gives a known listing.
+#[utoipa::path(
+    post,
+    path = "/_admin/seed",
+    tag = "testing",
+    request_body = Vec<Todo>,
+    responses((status = 200, description = "The todos as stored", body = Vec<Todo>))
+)]
+async fn admin_seed(Tenant(db): Tenant, Json(todos): Json<Vec<Todo>>) -> Json<Vec<Todo>> {
+    let mut store = db.write().await;
+    for todo in &todos {
+        store.upsert(todo.clone());
+    }
+    Json(todos)
+}
+
+/// Error-injection mode for exercising client retries: arm with
+/// `POST /_faults`, after which the next `count` requests to any other route
+/// get `status` with an empty body.
+#[utoipa::path(
+    post,
+    path = "/_faults",
+    tag = "testing",
+    request_body = InjectFaults,
+    responses((status = 204, description = "Faults armed"))
+)]
+async fn inject_faults(Tenant(db): Tenant, Json(faults): Json<InjectFaults>) -> StatusCode {
+    db.write().await.faults = (faults.count > 0).then_some(faults);
+    StatusCode::NO_CONTENT
+}
+
+/// Faults are armed per tenant, and a tenant that does not exist yet has
+/// none to fire.
+async fn injected_faults(State(tenants): State<Arc<Tenants>>, request: Request, next: Next) -> Response {
+    let db = match request.uri().path() {
+        "/_faults" => None,
+        _ => tenants.find(tenants::tenant_name(request.headers()).as_deref()).await,
+    };
+    if let Some(db) = db {
+        let mut store = db.write().await;
+        if let Some(faults) = store.faults.as_mut() {
+            let status = StatusCode::from_u16(faults.status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+            faults.count -= 1;
+            if faults.count == 0 {
+                store.faults = None;
+            }
+            return status.into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// [`Chaos`] settings and the generator its draws come from.
+struct ChaosState {
+    chaos: Chaos,
+    rng: Mutex<fastrand::Rng>,
+}
+
+impl ChaosState {
+    fn new(chaos: Chaos) -> Self {
+        let rng = chaos.seed.map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed);
+        ChaosState {
+            chaos,
+            rng: Mutex::new(rng),
+        }
+    }
+}
+
+/// Fail each request with probability `rate`, as a 500 or (equally likely)
+/// a 503 with an empty body; 503s carry `Retry-After` when configured.
+/// Control routes (`/_…`) are spared so tests can still steer the server.
+async fn chaos_faults(State(state): State<Arc<ChaosState>>, request: Request, next: Next) -> Response {
+    if request.uri().path().starts_with("/_") {
+        return next.run(request).await;
+    }
+    let status = {
+        let mut rng = state.rng.lock().unwrap_or_else(|e| e.into_inner());
+        if rng.f64() >= state.chaos.rate {
+            None
+        } else if rng.bool() {
+            Some(StatusCode::INTERNAL_SERVER_ERROR)
+        } else {
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        }
+    };
+    match (status, state.chaos.retry_after) {
+        (None, _) => next.run(request).await,
+        (Some(StatusCode::SERVICE_UNAVAILABLE), Some(secs)) => {
+            (StatusCode::SERVICE_UNAVAILABLE, [(header::RETRY_AFTER, secs.to_string())]).into_response()
+        }
+        (Some(status), _) => status.into_response(),
+    }
+}
+
+/// 401 unless `Authorization` carries one of `tokens` as a bearer token
+/// (RFC 6750). The challenge says `invalid_token` when a token was sent
+/// but is not one of them.
+async fn require_bearer(State(tokens): State<Arc<Vec<String>>>, request: Request, next: Next) -> Response {
+    let sent = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim());
+    let challenge = match sent {
+        Some(token) if tokens.iter().any(|t| t == token) => return next.run(request).await,
+        Some(_) => r#"Bearer realm="todos", error="invalid_token""#,
+        None => r#"Bearer realm="todos""#,
+    };
+    let mut response = problem(StatusCode::UNAUTHORIZED, "a valid bearer token is required");
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static(challenge));
+    response
+}
+
+/// Save every tenant after each successful write; see [`Tenants::save`].
+/// If the save fails the client gets a 500: the write is applied in
+/// memory but would not survive a restart.
+async fn persist(State(tenants): State<Arc<Tenants>>, request: Request, next: Next) -> Response {
+    let writes = !matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    let response = next.run(request).await;
+    if !writes || !response.status().is_success() {
+        return response;
+    }
+    match tenants.save().await {
+        Ok(()) => response,
+        Err(e) => problem(StatusCode::INTERNAL_SERVER_ERROR, format!("saving the store: {e}")),
+    }
+}
+
+/// Copy the request's `x-correlation-id` onto the response, so clients can
+/// match responses to requests.
+async fn echo_correlation_id(request: Request, next: Next) -> Response {
+    let correlation_id = request.headers().get("x-correlation-id").cloned();
+    let mut response = next.run(request).await;
+    if let Some(value) = correlation_id {
+        response.headers_mut().insert("x-correlation-id", value);
+    }
+    response
+}
+
+/// Largest request body the `Content-MD5` check will buffer.
+const MAX_VERIFIED_BODY: usize = 1024 * 1024;
+
+/// Reject requests whose `Content-MD5` header does not match the body.
+///
+/// Requests without the header pass through untouched. The body has to be
+/// buffered to hash it, then handed on to the route unchanged.
+async fn verify_content_md5(request: Request, next: Next) -> Response {
+    let Some(expected) = request.headers().get("content-md5").cloned() else {
+        return next.run(request).await;
+    };
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_VERIFIED_BODY).await else {
+        return problem(StatusCode::PAYLOAD_TOO_LARGE, "body too large to verify its Content-MD5");
+    };
+    let actual = BASE64_STANDARD.encode(md5::compute(&bytes).0);
+    if expected.as_bytes() != actual.as_bytes() {
+        return problem(StatusCode::BAD_REQUEST, "Content-MD5 mismatch");
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes))).await
+}
+
+#[utoipa::path(get, path = "/capabilities", tag = "todos", responses((status = 200, body = Capabilities)))]
+async fn capabilities() -> Json<Capabilities> {
+    Json(Capabilities {
+        schema_version: 1,
+        supported_fields: ["id", "title", "completed"].map(String::from).to_vec(),
+    })
+}
+
+/// Plain array by default. With `cursor`, a `CursorPage`; with only
+/// `limit`/`offset`, an array slice. Both paged modes add `X-Total-Count`
+/// and, when there are neighbouring pages, `Link` headers. Plain and
+/// offset listings follow `sort`/`order`; cursor pages are always in
+/// insertion order, since the cursor is a position in it. Parameters that
+/// do not parse, and `tag`, are a 400 problem.
+#[utoipa::path(
+    get,
+    path = "/todos",
+    tag = "todos",
+    params(ListParams),
+    responses(
+        (
+            status = 200,
+            description = "Matching todos; a `CursorPage` instead when `cursor` is given",
+            body = Vec<Todo>,
+            headers(
+                ("x-total-count" = u64, description = "Matching todos across all pages (paged modes only)"),
+                ("link" = String, description = "RFC 8288 links to the next and previous pages"),
+            )
+        ),
+        (status = 400, description = "A parameter does not parse, or `tag` was given", content_type = "application/problem+json", body = Problem),
+    )
+)]
+async fn list_todos(Tenant(db): Tenant, params: Result<Query<ListParams>, QueryRejection>) -> Response {
+    let params = match params {
+        Ok(Query(params)) => params,
+        Err(rejection) => return problem(StatusCode::BAD_REQUEST, rejection.body_text()),
+    };
+    if params.tag.is_some() {
+        return problem(StatusCode::BAD_REQUEST, "todos have no tags to filter by");
+    }
+    let store = db.read().await;
+    if let Some(cursor) = params.cursor.as_deref() {
+        if params.sort.is_some() || params.order.is_some() {
+            return problem(StatusCode::BAD_REQUEST, "cursor pages cannot be sorted");
+        }
+        return cursor_page(&store, &params, cursor);
+    }
+    let todos = match sorted(&store, &params) {
+        Ok(todos) => todos,
+        Err(detail) => return problem(StatusCode::BAD_REQUEST, detail),
+    };
+    if params.limit.is_some() || params.offset.is_some() {
+        return offset_page(todos, &params);
+    }
+    Json(todos).into_response()
+}
+
+/// Matching todos in the order `params` asks for. `created_at` is insertion
+/// order and the default. Equal keys break by id, ascending in either
+/// direction, so a listing is the same on every request. Errors are the
+/// problem detail for an unknown `sort` or `order`.
+fn sorted<'a>(store: &'a Store, params: &'a ListParams) -> Result<Vec<&'a Todo>, &'static str> {
+    type Key = fn(&(u64, &Todo), &(u64, &Todo)) -> Ordering;
+    let key: Key = match params.sort.as_deref() {
+        None | Some("created_at") => |a, b| a.0.cmp(&b.0),
+        Some("title") => |a, b| a.1.title.cmp(&b.1.title),
+        Some("completed") => |a, b| a.1.completed.cmp(&b.1.completed),
+        Some(_) => return Err("sort must be title, completed or created_at"),
+    };
+    let descending = match params.order.as_deref() {
+        None | Some("asc") => false,
+        Some("desc") => true,
+        Some(_) => return Err("order must be asc or desc"),
+    };
+    let mut todos: Vec<_> = ordered(store, params).collect();
+    todos.sort_by(|a, b| {
+        let by_key = if descending { key(b, a) } else { key(a, b) };
+        by_key.then_with(|| a.1.id.cmp(&b.1.id))
+    });
+    Ok(todos.into_iter().map(|(_, todo)| todo).collect())
+}
+
+/// Matching todos in insertion order, paired with their sequence numbers.
+fn ordered<'a>(store: &'a Store, params: &'a ListParams) -> impl Iterator<Item = (u64, &'a Todo)> + 'a {
+    store
+        .insertion_order
+        .iter()
+        .filter_map(|(seq, id)| Some((*seq, store.todos.get(id)?)))
+        .filter(|(_, todo)| params.matches(todo))
+}
+
+/// Cursors are opaque to clients: URL-safe base64 of the last sequence
+/// number served.
+fn cursor_page(store: &Store, params: &ListParams, cursor: &str) -> Response {
+    let after = if cursor.is_empty() {
+        None
+    } else {
+        match decode_cursor(cursor) {
+            Some(seq) => Some(seq),
+            None => return problem(StatusCode::BAD_REQUEST, "invalid cursor"),
+        }
+    };
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let mut remaining = ordered(store, params).filter(|(seq, _)| after.is_none_or(|after| *seq > after));
+    let page: Vec<_> = remaining.by_ref().take(limit).collect();
+    let next_cursor = match (remaining.next(), page.last()) {
+        (Some(_), Some((last, _))) => Some(BASE64_URL_SAFE_NO_PAD.encode(last.to_string())),
+        _ => None,
+    };
+    let total = ordered(store, params).count();
+    let todos = page.into_iter().map(|(_, todo)| todo.clone()).collect();
+    let links: Vec<_> = next_cursor
+        .iter()
+        .map(|cursor| page_link(params, &[("cursor", cursor.clone()), ("limit", limit.to_string())], "next"))
+        .collect();
+    let mut response = Json(CursorPage { todos, next_cursor }).into_response();
+    set_page_headers(&mut response, total, &links);
+    response
+}
+
+fn offset_page(matching: Vec<&Todo>, params: &ListParams) -> Response {
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let offset = params.offset.unwrap_or(0);
+    let total = matching.len();
+    let todos: Vec<Todo> = matching.into_iter().skip(offset).take(limit).cloned().collect();
+
+    let page = |offset: usize| [("limit", limit.to_string()), ("offset", offset.to_string())];
+    let mut links = Vec::new();
+    if offset + limit < total {
+        links.push(page_link(params, &page(offset + limit), "next"));
+    }
+    if offset > 0 {
+        links.push(page_link(params, &page(offset.saturating_sub(limit)), "prev"));
+    }
+    let mut response = Json(todos).into_response();
+    set_page_headers(&mut response, total, &links);
+    response
+}
+
+/// An RFC 8288 link to another page of `GET /todos`: `position` plus the
+/// request's filters and ordering, so following it keeps listing the same
+/// sequence.
+fn page_link(params: &ListParams, position: &[(&str, String)], rel: &str) -> String {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    query.extend_pairs(position.iter().map(|(k, v)| (*k, v.as_str())));
+    if let Some(completed) = params.completed {
+        query.append_pair("completed", if completed { "true" } else { "false" });
+    }
+    if let Some(title) = &params.title_contains {
+        query.append_pair("title_contains", title);
+    }
+    if let Some(sort) = &params.sort {
+        query.append_pair("sort", sort);
+    }
+    if let Some(order) = &params.order {
+        query.append_pair("order", order);
+    }
+    format!(r#"</todos?{}>; rel="{rel}""#, query.finish())
+}
+
+/// `X-Total-Count` is the number of todos matching the filters across all
+/// pages.
+fn set_page_headers(response: &mut Response, total: usize, links: &[String]) {
+    let headers = response.headers_mut();
+    headers.insert("x-total-count", HeaderValue::from(total));
+    if !links.is_empty() {
+        if let Ok(link) = HeaderValue::from_str(&links.join(", ")) {
+            headers.insert("link", link);
+        }
+    }
+}
+
+fn decode_cursor(cursor: &str) -> Option<u64> {
+    let bytes = BASE64_URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    std::str::from_utf8(&bytes).ok()?.parse().ok()
+}
+
+/// ASCII-case-insensitive title search. Each todo scores the fraction of query
+/// words its title contains; non-matches are dropped. Ties keep insertion
+/// order.
+#[utoipa::path(
+    get,
+    path = "/todos/search",
+    tag = "todos",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Hits, best first", body = Vec<SearchHit>),
+        (status = 400, description = "A parameter does not parse", content_type = "application/problem+json", body = Problem),
+    )
+)]
+async fn search_todos(Tenant(db): Tenant, params: Result<Query<SearchParams>, QueryRejection>) -> Response {
+    let params = match params {
+        Ok(Query(params)) => params,
+        Err(rejection) => return problem(StatusCode::BAD_REQUEST, rejection.body_text()),
+    };
+    let words: Vec<String> = params.q.split_whitespace().map(str::to_ascii_lowercase).collect();
+    let store = db.read().await;
+    let mut hits: Vec<SearchHit> = store
+        .insertion_order
+        .values()
+        .filter_map(|id| store.todos.get(id))
+        .filter_map(|todo| {
+            let title = todo.title.to_ascii_lowercase();
+            let matched: Vec<&String> = words.iter().filter(|w| title.contains(w.as_str())).collect();
+            if matched.is_empty() {
+                return None;
+            }
+            Some(SearchHit {
+                todo: todo.clone(),
+                score: matched.len() as f64 / words.len() as f64,
+                highlight: params.highlight.then(|| highlight(&todo.title, &matched)),
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(params.limit.unwrap_or(DEFAULT_PAGE_SIZE));
+    Json(hits).into_response()
+}
+
+/// Wrap each occurrence of the lowercase `words` in `<em>`. ASCII
+/// lowercasing keeps byte offsets aligned with `title`.
+fn highlight(title: &str, words: &[&String]) -> String {
+    let lower = title.to_ascii_lowercase();
+    let mut marked = vec![false; title.len()];
+    for word in words {
+        for (start, _) in lower.match_indices(word.as_str()) {
+            marked[start..start + word.len()].iter_mut().for_each(|m| *m = true);
+        }
+    }
+    let mut out = String::new();
+    let mut open = false;
+    for (i, ch) in title.char_indices() {
+        if marked[i] != open {
+            out.push_str(if open { "</em>" } else { "<em>" });
+            open = marked[i];
+        }
+        out.push(ch);
+    }
+    if open {
+        out.push_str("</em>");
+    }
+    out
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/events",
+    tag = "todos",
+    responses((
+        status = 200,
+        description = "Server-Sent Events: one `created`, `updated` or `deleted` event per change, its data a \
+            `TodoEvent`. `lagged` (data `{\"missed\": n}`) means events were dropped and the list should be \
+            fetched again.",
+        content_type = "text/event-stream",
+        body = TodoEvent
+    ))
+)]
+/// Stream every change made after the request as Server-Sent Events, with
+/// keep-alive comments while idle. Subscribing happens before the response
+/// starts, so a change made once the headers arrive is never missed.
+async fn todo_events(Tenant(db): Tenant) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let changes = db.read().await.changes.subscribe();
+    let events = futures_util::stream::unfold(changes, |mut changes| async move {
+        let event = match changes.next().await? {
+            Ok(change) => Event::default()
+                .event(change.name())
+                .json_data(&change)
+                .expect("events always serialize"),
+            Err(missed) => Event::default().event("lagged").data(format!(r#"{{"missed":{missed}}}"#)),
+        };
+        Some((Ok(event), changes))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/ws",
+    tag = "todos",
+    responses((
+        status = 101,
+        description = "WebSocket: one JSON `TodoEvent` text message per change. \
+            `{\"type\": \"lagged\", \"missed\": n}` means messages were dropped and the list should be fetched \
+            again. Messages from the client are ignored.",
+        body = TodoEvent
+    ))
+)]
+/// Push every change made after the upgrade as a JSON text message, the
+/// WebSocket twin of [`todo_events`].
+async fn todo_socket(Tenant(db): Tenant, upgrade: WebSocketUpgrade) -> Response {
+    let changes = db.read().await.changes.subscribe();
+    upgrade.on_upgrade(|socket| push_changes(socket, changes))
+}
+
+/// Forward `changes` to `socket` until either side goes away. A closed
+/// feed means the server is stopping, which the client hears as a 1001
+/// close frame.
+async fn push_changes(mut socket: WebSocket, mut changes: Subscription) {
+    loop {
+        tokio::select! {
+            change = changes.next() => {
+                let text = match change {
+                    Some(Ok(change)) => serde_json::to_string(&change).expect("events always serialize"),
+                    Some(Err(missed)) => format!(r#"{{"type":"lagged","missed":{missed}}}"#),
+                    None => {
+                        let frame = CloseFrame { code: close_code::AWAY, reason: "server shutting down".into() };
+                        let _ = socket.send(Message::Close(Some(frame))).await;
+                        break;
+                    }
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/count",
+    tag = "todos",
+    params(TodoFilter),
+    responses(
+        (status = 200, body = TodoCount),
+        (status = 400, description = "A parameter does not parse", content_type = "application/problem+json", body = Problem),
+    )
+)]
+async fn count_todos(Tenant(db): Tenant, filter: Result<Query<TodoFilter>, QueryRejection>) -> Response {
+    let filter = match filter {
+        Ok(Query(filter)) => filter,
+        Err(rejection) => return problem(StatusCode::BAD_REQUEST, rejection.body_text()),
+    };
+    let store = db.read().await;
+    let count = store
+        .todos
+        .values()
+        .filter(|t| filter.completed.is_none_or(|c| t.completed == c))
+        .count();
+    Json(TodoCount { count: count as u64 }).into_response()
+}
+
+/// Responds with the created todo, or with `CreatedAndList` when the client
+/// sends `Prefer: embed=list`. A repeated `Idempotency-Key` replays the todo
+/// created the first time instead of creating another, even when titles
+/// must be unique.
+#[utoipa::path(
+    post,
+    path = "/todos",
+    tag = "todos",
+    request_body = CreateTodo,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replay the todo created with this key"),
+        ("Prefer" = Option<String>, Header, description = "`embed=list` answers with a `CreatedAndList`"),
+    ),
+    responses(
+        (status = 201, description = "The new todo", body = Todo),
+        (status = 400, description = "The body is not JSON", content_type = "application/problem+json", body = Problem),
+        (status = 409, description = "Titles must be unique and this one is taken", content_type = "application/problem+json", body = Problem),
+        (status = 422, description = "A field is missing or invalid", content_type = "application/problem+json", body = Problem),
+    )
+)]
 async fn create_todo(
-    State(db): State<Db>,
-    Json(input): Json<CreateTodo>,
-) -> (StatusCode, Json<Todo>) {
-    let todo = Todo {
-        id: Uuid::new_v4(),
-        title: input.title,
-        completed: input.completed,
+    Tenant(db): Tenant,
+    headers: HeaderMap,
+    input: Result<Json<CreateTodo>, JsonRejection>,
+) -> Result<(StatusCode, Response), Response> {
+    let Json(input) = input.map_err(rejected)?;
+    let mut store = db.write().await;
+    let key = headers
+        .get("idempotency-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let replayed = key
+        .as_ref()
+        .and_then(|key| store.idempotency_keys.get(key))
+        .and_then(|id| store.todos.get(id))
+        .cloned();
+    let todo = match replayed {
+        Some(todo) => todo,
+        None => {
+            if let Err(reason) = store.check_title(&input.title) {
+                return Err(problem(StatusCode::UNPROCESSABLE_ENTITY, reason));
+            }
+            if let Some(existing) = store.title_taken(&input.title, None) {
+                return Err(duplicate_title(existing));
+            }
+            let todo = Todo {
+                id: store.new_id(),
+                title: normalize_title(&input.title),
+                completed: input.completed,
+                schema_version: None,
+            };
+            store.record_revision(&todo);
+            store.insert(todo.clone());
+            if let Some(key) = key {
+                store.idempotency_keys.insert(key, todo.id);
+            }
+            todo
+        }
     };
-    db.write().await.insert(todo.id, todo.clone());
-    (StatusCode::CREATED, Json(todo))
+    if prefers(&headers, "embed=list") {
+        let list = ordered(&store, &ListParams::default()).map(|(_, todo)| todo.clone()).collect();
+        let body = CreatedAndList { created: todo, list };
+        return Ok((StatusCode::CREATED, Json(body).into_response()));
+    }
+    Ok((StatusCode::CREATED, Json(todo).into_response()))
+}
+
+/// Create every todo in the array under one lock and return them in request
+/// order. One invalid or taken title refuses the whole batch.
+#[utoipa::path(
+    post,
+    path = "/todos/batch",
+    tag = "todos",
+    request_body = Vec<CreateTodo>,
+    responses(
+        (status = 201, description = "The new todos, in request order", body = Vec<Todo>),
+        (status = 400, description = "The body is not JSON", content_type = "application/problem+json", body = Problem),
+        (status = 409, description = "Titles must be unique and one is taken or repeated", content_type = "application/problem+json", body = Problem),
+        (status = 422, description = "A field is missing or invalid", content_type = "application/problem+json", body = Problem),
+    )
+)]
+async fn create_todos_batch(
+    Tenant(db): Tenant,
+    inputs: Result<Json<Vec<CreateTodo>>, JsonRejection>,
+) -> Result<(StatusCode, Json<Vec<Todo>>), Response> {
+    let Json(inputs) = inputs.map_err(rejected)?;
+    let mut store = db.write().await;
+    let mut titles = HashMap::new();
+    for (index, input) in inputs.iter().enumerate() {
+        if let Err(reason) = store.check_title(&input.title) {
+            return Err(problem(StatusCode::UNPROCESSABLE_ENTITY, format!("[{index}]: {reason}")));
+        }
+        if let Some(existing) = store.title_taken(&input.title, None) {
+            return Err(duplicate_title(existing));
+        }
+        if let Some(first) = titles.insert(normalize_title(&input.title), index).filter(|_| store.unique_titles) {
+            let detail = format!("[{index}]: same title as [{first}]");
+            return Err(problem(StatusCode::CONFLICT, detail));
+        }
+    }
+    let created = inputs
+        .into_iter()
+        .map(|input| {
+            let todo = Todo {
+                id: store.new_id(),
+                title: normalize_title(&input.title),
+                completed: input.completed,
+                schema_version: None,
+            };
+            store.record_revision(&todo);
+            store.insert(todo.clone());
+            todo
+        })
+        .collect();
+    Ok((StatusCode::CREATED, Json(created)))
 }
 
-async fn get_todo(
-    State(db): State<Db>,
+/// Responds with the todo and its current `ETag`.
+#[utoipa::path(
+    get,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(("id" = Uuid, Path, description = "Todo id")),
+    responses(
+        (status = 200, body = Todo, headers(("etag" = String))),
+        (status = 404, description = "No such todo"),
+    )
+)]
+async fn get_todo(Tenant(db): Tenant, Path(id): Path<Uuid>) -> Result<Response, StatusCode> {
+    let store = db.read().await;
+    let todo = store.todos.get(&id).cloned().ok_or(StatusCode::NOT_FOUND)?;
+    let etag = store.etag(&id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(([(header::ETAG, etag)], Json(todo)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/history",
+    tag = "todos",
+    params(("id" = Uuid, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "Every revision, oldest first", body = Vec<TodoRevision>),
+        (status = 404, description = "No such todo"),
+    )
+)]
+async fn get_history(
+    Tenant(db): Tenant,
     Path(id): Path<Uuid>,
-) -> Result<Json<Todo>, StatusCode> {
-    let todos = db.read().await;
-    todos.get(&id).cloned().map(Json).ok_or(StatusCode::NOT_FOUND)
+) -> Result<Json<Vec<TodoRevision>>, StatusCode> {
+    let store = db.read().await;
+    store.history.get(&id).cloned().map(Json).ok_or(StatusCode::NOT_FOUND)
 }
 
+/// Honors `If-Match`; responds with the updated todo and its new `ETag`.
+#[utoipa::path(
+    put,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(("id" = Uuid, Path, description = "Todo id"), ("If-Match" = Option<String>, Header, description = "Only act if the todo's current ETag matches (or `*`)")),
+    request_body = UpdateTodo,
+    responses(
+        (status = 200, body = Todo, headers(("etag" = String))),
+        (status = 400, description = "The body is not JSON", content_type = "application/problem+json", body = Problem),
+        (status = 404, description = "No such todo"),
+        (status = 409, description = "Titles must be unique and this one is taken", content_type = "application/problem+json", body = Problem),
+        (status = 412, description = "`If-Match` does not match"),
+        (status = 422, description = "A field is invalid", content_type = "application/problem+json", body = Problem),
+    )
+)]
 async fn update_todo(
-    State(db): State<Db>,
+    Tenant(db): Tenant,
     Path(id): Path<Uuid>,
-    Json(input): Json<UpdateTodo>,
-) -> Result<Json<Todo>, StatusCode> {
-    let mut todos = db.write().await;
-    let todo = todos.get_mut(&id).ok_or(StatusCode::NOT_FOUND)?;
+    headers: HeaderMap,
+    input: Result<Json<UpdateTodo>, JsonRejection>,
+) -> Result<Response, Response> {
+    let Json(input) = input.map_err(rejected)?;
+    let mut store = db.write().await;
+    store.check_if_match(&id, &headers).map_err(IntoResponse::into_response)?;
+    if let Some(title) = &input.title {
+        if let Err(reason) = store.check_title(title) {
+            return Err(problem(StatusCode::UNPROCESSABLE_ENTITY, reason));
+        }
+        if let Some(existing) = store.title_taken(title, Some(id)) {
+            return Err(duplicate_title(existing));
+        }
+    }
+    let todo = store.todos.get_mut(&id).ok_or(StatusCode::NOT_FOUND.into_response())?;
     if let Some(title) = input.title {
         todo.title = title;
     }
     if let Some(completed) = input.completed {
         todo.completed = completed;
     }
-    Ok(Json(todo.clone()))
+    let todo = todo.clone();
+    store.record_revision(&todo);
+    let etag = store.etag(&id).ok_or(StatusCode::NOT_FOUND.into_response())?;
+    Ok(([(header::ETAG, etag)], Json(todo)).into_response())
+}
+
+/// JSON Merge Patch (RFC 7396): the body must be sent as
+/// `application/merge-patch+json` (415 otherwise) and is applied with
+/// [`Todo::apply_merge_patch`], so `null` on a required field or a value
+/// of the wrong type is a 422 problem, as is changing `id` or a title
+/// [`Store::check_title`] refuses; a taken title is a 409. Unlike PUT, a
+/// patch that changes nothing records no revision. Honors `If-Match`.
+#[utoipa::path(
+    patch,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(("id" = Uuid, Path, description = "Todo id"), ("If-Match" = Option<String>, Header, description = "Only act if the todo's current ETag matches (or `*`)")),
+    request_body(content = UpdateTodo, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, body = Todo, headers(("etag" = String))),
+        (status = 400, description = "The body is not JSON", content_type = "application/problem+json", body = Problem),
+        (status = 404, description = "No such todo"),
+        (status = 409, description = "Titles must be unique and this one is taken", content_type = "application/problem+json", body = Problem),
+        (status = 412, description = "`If-Match` does not match"),
+        (status = 415, description = "Not sent as `application/merge-patch+json`", content_type = "application/problem+json", body = Problem),
+        (status = 422, description = "The patch cannot be applied", content_type = "application/problem+json", body = Problem),
+    )
+)]
+async fn patch_todo(Tenant(db): Tenant, Path(id): Path<Uuid>, headers: HeaderMap, body: Bytes) -> Response {
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(str::trim);
+    if media_type != Some("application/merge-patch+json") {
+        return problem(StatusCode::UNSUPPORTED_MEDIA_TYPE, "PATCH takes application/merge-patch+json");
+    }
+    let patch: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(patch) => patch,
+        Err(e) => return problem(StatusCode::BAD_REQUEST, format!("invalid JSON: {e}")),
+    };
+
+    let mut store = db.write().await;
+    if let Err(status) = store.check_if_match(&id, &headers) {
+        return status.into_response();
+    }
+    let Some(todo) = store.todos.get(&id) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let mut patched = todo.clone();
+    if let Err(e) = patched.apply_merge_patch(&patch) {
+        return problem(StatusCode::UNPROCESSABLE_ENTITY, e.to_string());
+    }
+    if patched.id != id {
+        return problem(StatusCode::UNPROCESSABLE_ENTITY, "`id` cannot be changed");
+    }
+    if patched != *todo {
+        if patched.title != todo.title {
+            if let Err(reason) = store.check_title(&patched.title) {
+                return problem(StatusCode::UNPROCESSABLE_ENTITY, reason);
+            }
+            if let Some(existing) = store.title_taken(&patched.title, Some(id)) {
+                return duplicate_title(existing);
+            }
+        }
+        store.todos.insert(id, patched.clone());
+        store.record_revision(&patched);
+    }
+    match store.etag(&id) {
+        Some(etag) => ([(header::ETAG, etag)], Json(patched)).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/todos", tag = "todos", responses((status = 200, body = DeletedCount)))]
+async fn delete_all(Tenant(db): Tenant) -> Json<DeletedCount> {
+    let mut store = db.write().await;
+    let deleted = store.todos.len() as u64;
+    for id in store.insertion_order.values() {
+        store.changes.send(TodoEvent::Deleted { id: *id });
+    }
+    store.todos.clear();
+    store.history.clear();
+    store.insertion_order.clear();
+    Json(DeletedCount { deleted })
 }
 
+/// Honors `If-Match`. Responds 204 by default, or 200 with the removed todo
+/// when the client sends `Prefer: return=representation`.
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = Uuid, Path, description = "Todo id"),
+        ("If-Match" = Option<String>, Header, description = "Only act if the todo's current ETag matches (or `*`)"),
+        ("Prefer" = Option<String>, Header, description = "`return=representation` answers with the removed todo"),
+    ),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 200, description = "Deleted; the removed todo", body = Todo),
+        (status = 404, description = "No such todo"),
+        (status = 412, description = "`If-Match` does not match"),
+    )
+)]
 async fn delete_todo(
-    State(db): State<Db>,
+    Tenant(db): Tenant,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
-    let mut todos = db.write().await;
-    todos.remove(&id).map(|_| StatusCode::NO_CONTENT).ok_or(StatusCode::NOT_FOUND)
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let mut store = db.write().await;
+    store.check_if_match(&id, &headers)?;
+    store.history.remove(&id);
+    let todo = store.todos.remove(&id).ok_or(StatusCode::NOT_FOUND)?;
+    store.insertion_order.retain(|_, v| *v != id);
+    store.changes.send(TodoEvent::Deleted { id });
+    if prefers(&headers, "return=representation") {
+        Ok(Json(todo).into_response())
+    } else {
+        Ok(StatusCode::NO_CONTENT.into_response())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn deterministic_ids_skip_every_taken_one() {
+        let mut store = Store {
+            entropy: Entropy::Deterministic,
+            ..Store::default()
+        };
+        for n in [1, 2, 3] {
+            let id = Uuid::from_u128(n);
+            let todo = Todo {
+                id,
+                title: format!("Seeded {n}"),
+                completed: false,
+                schema_version: None,
+            };
+            store.todos.insert(id, todo);
+        }
+        assert_eq!(store.new_id(), Uuid::from_u128(4));
+        store.next_seq = 1;
+        assert_eq!(store.new_id(), Uuid::from_u128(4));
+    }
+
     #[test]
     fn todo_serializes_to_json() {
         let todo = Todo {
             id: Uuid::nil(),
             title: "Test".to_string(),
             completed: false,
+            schema_version: None,
         };
         let json = serde_json::to_value(&todo).unwrap();
         assert_eq!(json["id"], "00000000-0000-0000-0000-000000000000");
@@ -139,6 +1602,7 @@ mod tests {
             id: Uuid::new_v4(),
             title: "Roundtrip".to_string(),
             completed: true,
+            schema_version: None,
         };
         let json = serde_json::to_string(&todo).unwrap();
         let back: Todo = serde_json::from_str(&json).unwrap();
@@ -180,4 +1644,17 @@ mod tests {
         assert_eq!(input.title.as_deref(), Some("New title"));
         assert!(input.completed.is_none());
     }
+
+    #[test]
+    fn seed_is_a_json_array_or_json_lines() {
+        let todo = r#"{"id":"00000000-0000-0000-0000-000000000007","title":"Seeded","completed":true}"#;
+        let from_array = parse_seed(&format!("[{todo}, {todo}]")).unwrap();
+        let from_lines = parse_seed(&format!("{todo}\n\n{todo}\n")).unwrap();
+        assert_eq!(from_array, from_lines);
+        assert_eq!(from_lines.len(), 2);
+        assert_eq!(from_lines[0].id, Uuid::from_u128(7));
+
+        let err = parse_seed(&format!("{todo}\n{{\"title\":\"no id\"}}")).unwrap_err();
+        assert!(err.starts_with("line 2:"), "{err}");
+    }
 }
@@ -1,10 +1,73 @@
+use mock_server::{Config, Storage};
 use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+/// Log to stdout, filtered by `RUST_LOG` (default `info`), as JSON lines
+/// when `LOG_FORMAT=json` and as text otherwise.
+fn init_logging() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let logs = tracing_subscriber::fmt().with_env_filter(filter);
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => logs.json().with_current_span(true).with_span_list(false).init(),
+        _ => logs.init(),
+    }
+}
+
+/// Resolves on Ctrl-C (SIGINT) or, on Unix, SIGTERM, the signal container
+/// runtimes stop with.
+async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c().await.expect("listening for Ctrl-C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        signal(SignalKind::terminate()).expect("listening for SIGTERM").recv().await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        () = interrupt => {}
+        () = terminate => {}
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
+    init_logging();
     let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
     let addr = format!("127.0.0.1:{port}");
+    let config = Config::from_env()?;
     let listener = TcpListener::bind(&addr).await?;
-    println!("listening on {addr}");
-    mock_server::run(listener).await
+    match &config.storage {
+        Storage::Memory => tracing::info!("listening on {addr}"),
+        Storage::JsonFile(path) => tracing::info!("listening on {addr}, storing todos in {}", path.display()),
+    }
+    if let Some(path) = &config.seed_file {
+        tracing::info!("seeding an empty store from {}", path.display());
+    }
+    if let Some(chaos) = &config.chaos {
+        tracing::info!("chaos mode: failing {:.0}% of requests", chaos.rate * 100.0);
+    }
+    if let Some(limit) = &config.rate_limit {
+        tracing::info!("rate limit: {} requests per second per client, bursts of {}", limit.per_second, limit.burst);
+    }
+    if let Some(tokens) = &config.auth_tokens {
+        tracing::info!("auth: requiring one of {} bearer token(s)", tokens.len());
+    }
+    if let Some(max) = config.max_title_len {
+        tracing::info!("refusing titles over {max} characters");
+    }
+    if config.unique_titles {
+        tracing::info!("refusing duplicate titles");
+    }
+    if let Some(cors) = &config.cors {
+        match &cors.origins {
+            Some(origins) => tracing::info!("cors: allowing {} origin(s)", origins.len()),
+            None => tracing::info!("cors: allowing any origin"),
+        }
+    }
+    mock_server::run_with_config(listener, config, shutdown_signal()).await?;
+    tracing::info!("stopped");
+    Ok(())
 }
@@ -0,0 +1,55 @@
+//! The OpenAPI document served at `GET /openapi.json`.
+//!
+//! # Design
+//! Generated by utoipa from the `#[utoipa::path]` annotation on each
+//! handler and the `ToSchema` derives on the bodies (the shared DTOs get
+//! theirs from `todo-schema`'s `openapi` feature), so the spec is rebuilt
+//! from the code on every compile rather than maintained beside it. The
+//! middleware-driven modes (auth, rate limiting, chaos, faults) are off by
+//! default and not described per operation, and neither is the `X-Tenant`
+//! header every route accepts.
+
+use axum::Json;
+use utoipa::OpenApi;
+
+use crate::{CreatedAndList, CursorPage, Problem, TodoEvent};
+
+/// Every route of the server, with its parameters, bodies and responses.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Todo API",
+        description = "Reference todo server for the rust-to-c client. Every route serves the tenant named by the \
+            `X-Tenant` header, else the `X-Api-Key` header, else the default tenant."
+    ),
+    paths(
+        crate::capabilities,
+        crate::list_todos,
+        crate::create_todo,
+        crate::delete_all,
+        crate::create_todos_batch,
+        crate::count_todos,
+        crate::todo_events,
+        crate::todo_socket,
+        crate::search_todos,
+        crate::get_todo,
+        crate::update_todo,
+        crate::patch_todo,
+        crate::delete_todo,
+        crate::get_history,
+        crate::admin_reset,
+        crate::admin_seed,
+        crate::tenants::list_tenants,
+        crate::inject_faults,
+    ),
+    components(schemas(CursorPage, CreatedAndList, Problem, TodoEvent)),
+    tags(
+        (name = "todos", description = "The todo API"),
+        (name = "testing", description = "Controls for test suites; not part of a real deployment"),
+    )
+)]
+pub struct ApiDoc;
+
+pub(crate) async fn document() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -0,0 +1,175 @@
+//! Per-client token buckets answering 429 once a client runs dry.
+//!
+//! # Design
+//! A client is whoever holds the credential in `Authorization` or
+//! `X-Api-Key`, falling back to the peer IP, so tests that share one
+//! address can still get separate budgets by sending different keys.
+//! Requests without either share one bucket (in-process tests have no peer
+//! address). Buckets refill continuously rather than per fixed window, and
+//! are never evicted: the mock serves a handful of clients at a time.
+//!
+//! Every limited response carries `X-RateLimit-Limit` (the bucket size),
+//! `X-RateLimit-Remaining` and `X-RateLimit-Reset` (seconds until the
+//! bucket is full again); a 429 adds `Retry-After`, the seconds until one
+//! more request would pass.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{problem, RateLimit};
+
+pub(crate) struct Limiter {
+    limit: RateLimit,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// What one request drew from its bucket.
+#[derive(Debug, PartialEq)]
+struct Draw {
+    allowed: bool,
+    remaining: u32,
+    /// Seconds until the bucket is full.
+    reset: u64,
+    /// Seconds until a token is available; set when denied.
+    retry_after: Option<u64>,
+}
+
+impl Limiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Limiter {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn draw(&self, client: String, now: Instant) -> Draw {
+        let burst = f64::from(self.limit.burst);
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.limit.per_second).min(burst);
+        bucket.updated = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        let secs = |tokens: f64| (tokens / self.limit.per_second).ceil() as u64;
+        Draw {
+            allowed,
+            remaining: bucket.tokens.floor() as u32,
+            reset: secs(burst - bucket.tokens),
+            retry_after: (!allowed).then(|| secs(1.0 - bucket.tokens).max(1)),
+        }
+    }
+}
+
+/// The credential the request carries, else its peer IP.
+fn client(request: &Request) -> String {
+    let headers = request.headers();
+    let credential = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(key) = credential(header::AUTHORIZATION.as_str()).or_else(|| credential("x-api-key")) {
+        return format!("key {key}");
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip {}", addr.ip()),
+        None => "anonymous".to_string(),
+    }
+}
+
+/// Control routes (`/_…`) are not limited, so tests can always reset state.
+pub(crate) async fn rate_limit(State(limiter): State<Arc<Limiter>>, request: Request, next: Next) -> Response {
+    if request.uri().path().starts_with("/_") {
+        return next.run(request).await;
+    }
+    let draw = limiter.draw(client(&request), Instant::now());
+    let mut response = match draw.retry_after {
+        None => next.run(request).await,
+        Some(secs) => {
+            let mut response = problem(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded");
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+            response
+        }
+    };
+    set_headers(response.headers_mut(), limiter.limit.burst, &draw);
+    response
+}
+
+fn set_headers(headers: &mut HeaderMap, limit: u32, draw: &Draw) {
+    headers.insert("x-ratelimit-limit", HeaderValue::from(limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(draw.remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(draw.reset));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn limiter() -> Limiter {
+        Limiter::new(RateLimit {
+            burst: 2,
+            per_second: 0.5,
+        })
+    }
+
+    #[test]
+    fn bucket_empties_then_refills() {
+        let limiter = limiter();
+        let start = Instant::now();
+        let draw = |secs| limiter.draw("a".to_string(), start + Duration::from_secs(secs));
+        assert_eq!(
+            draw(0),
+            Draw {
+                allowed: true,
+                remaining: 1,
+                reset: 2,
+                retry_after: None
+            }
+        );
+        assert!(draw(0).allowed);
+        assert_eq!(
+            draw(0),
+            Draw {
+                allowed: false,
+                remaining: 0,
+                reset: 4,
+                retry_after: Some(2)
+            }
+        );
+        // Two seconds at half a token per second buy one more request.
+        assert!(draw(2).allowed);
+        assert!(!draw(2).allowed);
+    }
+
+    #[test]
+    fn clients_have_separate_buckets() {
+        let limiter = limiter();
+        let now = Instant::now();
+        for _ in 0..2 {
+            assert!(limiter.draw("a".to_string(), now).allowed);
+        }
+        assert!(!limiter.draw("a".to_string(), now).allowed);
+        assert!(limiter.draw("b".to_string(), now).allowed);
+    }
+}
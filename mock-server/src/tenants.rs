@@ -0,0 +1,258 @@
+//! Separate stores per tenant, so suites sharing one server never see each
+//! other's todos.
+//!
+//! # Design
+//! A request's tenant is its `X-Tenant` header, else a fingerprint of its
+//! `X-Api-Key`; requests with neither use the default tenant, which is all
+//! a single-tenant client ever sees. Keys are fingerprinted (`key-` plus 16
+//! hex digits of their MD5) so neither `GET /_admin/tenants` nor the
+//! snapshot file ever holds a credential. Handlers take a [`Tenant`]
+//! instead of the router state, and a tenant's store is created empty,
+//! with the server's settings, the first time a handler asks for it.
+//! Everything per-store is per-tenant: ids, history, idempotency keys,
+//! injected faults, the change feed and `/_admin/reset`.
+//!
+//! Tenants are never evicted, so at most [`MAX_TENANTS`] named ones are
+//! created; requests for further new tenants get 503 until the server
+//! restarts.
+//!
+//! The [`Storage::JsonFile`] snapshot keeps the default tenant's store at
+//! the top level, as before tenants existed, and adds the others under
+//! `tenants`, so older snapshots load unchanged.
+
+use std::{borrow::Cow, collections::BTreeMap, io, sync::Arc};
+
+use axum::{
+    extract::{FromRequestParts, State},
+    http::{request::Parts, HeaderMap, StatusCode},
+    response::Response,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use utoipa::ToSchema;
+
+use crate::{problem, ChangeFeed, Db, Storage, Store};
+
+/// How many named tenants the server creates before refusing new ones.
+pub const MAX_TENANTS: usize = 1024;
+
+/// Every tenant's store, plus the settings new ones start with.
+pub(crate) struct Tenants {
+    default: Db,
+    named: RwLock<BTreeMap<String, Db>>,
+    /// An empty store whose settings and change feed new tenants copy.
+    template: Store,
+    storage: Storage,
+    /// Held while saving, so snapshots are written one at a time.
+    saving: Mutex<()>,
+}
+
+/// What [`Storage::JsonFile`] holds.
+#[derive(Default)]
+struct Snapshot {
+    default: Store,
+    tenants: BTreeMap<String, Store>,
+}
+
+impl Snapshot {
+    /// Split off `tenants` by hand rather than with `#[serde(flatten)]`,
+    /// which buffers the map and then cannot read `insertion_order`'s
+    /// keys back as numbers.
+    fn parse(bytes: &[u8]) -> serde_json::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_slice(bytes)?;
+        let tenants = match value.as_object_mut().and_then(|fields| fields.remove("tenants")) {
+            Some(tenants) => serde_json::from_value(tenants)?,
+            None => BTreeMap::new(),
+        };
+        Ok(Snapshot {
+            default: serde_json::from_value(value)?,
+            tenants,
+        })
+    }
+}
+
+/// [`Snapshot`], borrowing the stores it writes.
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    #[serde(flatten)]
+    default: &'a Store,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    tenants: BTreeMap<&'a str, &'a Store>,
+}
+
+impl Tenants {
+    /// Every tenant last saved to `storage` (none in memory), each given
+    /// `template`'s settings. `seed` fills the default tenant if it starts
+    /// out empty.
+    pub(crate) fn open(
+        template: Store,
+        storage: Storage,
+        seed: impl FnOnce(&mut Store) -> io::Result<()>,
+    ) -> io::Result<Self> {
+        let saved = match &storage {
+            Storage::Memory => Snapshot::default(),
+            Storage::JsonFile(path) => match std::fs::read(path) {
+                Ok(bytes) => Snapshot::parse(&bytes)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display())))?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Snapshot::default(),
+                Err(e) => return Err(e),
+            },
+        };
+        let mut default = template.with_data(saved.default);
+        if default.todos.is_empty() {
+            seed(&mut default)?;
+        }
+        let named = saved
+            .tenants
+            .into_iter()
+            .map(|(name, data)| (name, Arc::new(RwLock::new(template.with_data(data)))))
+            .collect();
+        Ok(Tenants {
+            default: Arc::new(RwLock::new(default)),
+            named: RwLock::new(named),
+            template,
+            storage,
+            saving: Mutex::new(()),
+        })
+    }
+
+    /// The store for `name`, or the default tenant's for `None`, creating
+    /// it empty on first use. `None` once [`MAX_TENANTS`] exist and `name`
+    /// is not one of them.
+    pub(crate) async fn get(&self, name: Option<&str>) -> Option<Db> {
+        let Some(name) = name else {
+            return Some(self.default.clone());
+        };
+        if let Some(db) = self.named.read().await.get(name) {
+            return Some(db.clone());
+        }
+        let mut named = self.named.write().await;
+        if named.len() >= MAX_TENANTS && !named.contains_key(name) {
+            return None;
+        }
+        let db = named
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(RwLock::new(self.template.with_data(Store::default()))));
+        Some(db.clone())
+    }
+
+    /// The store for `name` if it exists; never creates one.
+    pub(crate) async fn find(&self, name: Option<&str>) -> Option<Db> {
+        match name {
+            None => Some(self.default.clone()),
+            Some(name) => self.named.read().await.get(name).cloned(),
+        }
+    }
+
+    /// The feed whose closing ends every tenant's event streams.
+    pub(crate) fn changes(&self) -> &ChangeFeed {
+        &self.template.changes
+    }
+
+    /// Write the snapshot for [`Storage::JsonFile`]; a no-op in memory.
+    /// Goes through a temporary file and a rename, so a crash mid-write
+    /// leaves the previous snapshot intact. Each save starts after the
+    /// previous one finished and sees every write made before it, so the
+    /// file always ends up with the latest state. The stores are only
+    /// locked while serializing; the file is written without them.
+    pub(crate) async fn save(&self) -> io::Result<()> {
+        let Storage::JsonFile(path) = &self.storage else {
+            return Ok(());
+        };
+        let _saving = self.saving.lock().await;
+        let bytes = {
+            let named = self.named.read().await;
+            let default = self.default.read().await;
+            let mut stores = Vec::with_capacity(named.len());
+            for (name, db) in named.iter() {
+                stores.push((name.as_str(), db.read().await));
+            }
+            let snapshot = SnapshotRef {
+                default: &default,
+                tenants: stores.iter().map(|(name, store)| (*name, &**store)).collect(),
+            };
+            serde_json::to_vec(&snapshot)?
+        };
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, path).await
+    }
+}
+
+/// The tenant a request belongs to: its `X-Tenant` header, else the
+/// fingerprint of its `X-Api-Key`; `None` for the default tenant.
+pub(crate) fn tenant_name(headers: &HeaderMap) -> Option<Cow<'_, str>> {
+    let header = |name| Some(headers.get(name)?.to_str().ok()?.trim()).filter(|value| !value.is_empty());
+    if let Some(tenant) = header("x-tenant") {
+        return Some(Cow::Borrowed(tenant));
+    }
+    let digest = md5::compute(header("x-api-key")?).0;
+    let hex: String = digest[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+    Some(Cow::Owned(format!("key-{hex}")))
+}
+
+/// The requesting tenant's store, created on first use.
+pub(crate) struct Tenant(pub(crate) Db);
+
+impl FromRequestParts<Arc<Tenants>> for Tenant {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, tenants: &Arc<Tenants>) -> Result<Self, Response> {
+        match tenants.get(tenant_name(&parts.headers).as_deref()).await {
+            Some(db) => Ok(Tenant(db)),
+            None => Err(problem(StatusCode::SERVICE_UNAVAILABLE, format!("no room for more than {MAX_TENANTS} tenants"))),
+        }
+    }
+}
+
+/// One row of `GET /_admin/tenants`.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TenantSummary {
+    /// `None` for the default tenant.
+    pub name: Option<String>,
+    pub todos: u64,
+}
+
+/// `GET /_admin/tenants`: the default tenant, then every named tenant in
+/// name order, with how many todos each holds.
+#[utoipa::path(
+    get,
+    path = "/_admin/tenants",
+    tag = "testing",
+    responses((status = 200, description = "Every tenant", body = Vec<TenantSummary>))
+)]
+pub(crate) async fn list_tenants(State(tenants): State<Arc<Tenants>>) -> Json<Vec<TenantSummary>> {
+    let mut summaries = vec![TenantSummary {
+        name: None,
+        todos: tenants.default.read().await.todos.len() as u64,
+    }];
+    for (name, db) in tenants.named.read().await.iter() {
+        summaries.push(TenantSummary {
+            name: Some(name.clone()),
+            todos: db.read().await.todos.len() as u64,
+        });
+    }
+    Json(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs.iter().map(|(name, value)| (name.parse().unwrap(), value.parse().unwrap())).collect()
+    }
+
+    #[test]
+    fn tenant_header_wins_over_api_key() {
+        let name = |pairs| tenant_name(&headers(pairs)).map(Cow::into_owned);
+        let keyed = name(&[("x-api-key", "k1")]).unwrap();
+        assert!(keyed.starts_with("key-") && keyed.len() == 20 && !keyed.contains("k1"), "{keyed}");
+        assert_eq!(name(&[]), None);
+        assert_eq!(name(&[("x-api-key", "k1"), ("x-tenant", "suite-a")]).as_deref(), Some("suite-a"));
+        assert_eq!(name(&[("x-api-key", "k1"), ("x-tenant", " ")]), Some(keyed));
+        assert_ne!(name(&[("x-api-key", "k2")]), name(&[("x-api-key", "k1")]));
+    }
+}
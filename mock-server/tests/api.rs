@@ -1,6 +1,9 @@
 use axum::http::{self, Request, StatusCode};
 use http_body_util::BodyExt;
-use mock_server::{app, Todo};
+use mock_server::{
+    app, app_with, app_with_config, Capabilities, Chaos, Config, Cors, CursorPage, Entropy, Problem, RateLimit,
+    Storage, TenantSummary, Todo, TodoCount, TodoRevision, DEFAULT_CORS_HEADERS, DEFAULT_CORS_METHODS,
+};
 use tower::ServiceExt;
 
 async fn body_json<T: serde::de::DeserializeOwned>(response: axum::response::Response) -> T {
@@ -21,6 +24,491 @@ fn json_request(method: &str, uri: &str, body: &str) -> Request<String> {
         .unwrap()
 }
 
+// --- capabilities ---
+
+#[tokio::test]
+async fn capabilities_lists_supported_fields() {
+    let app = app();
+    let resp = app
+        .oneshot(Request::builder().uri("/capabilities").body(String::new()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let caps: Capabilities = body_json(resp).await;
+    assert_eq!(caps.schema_version, 1);
+    assert_eq!(caps.supported_fields, vec!["id", "title", "completed"]);
+}
+
+// --- openapi ---
+
+#[tokio::test]
+async fn openapi_document_covers_every_route() {
+    let app = app();
+    let resp = app.clone().oneshot(get("/openapi.json")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let spec: serde_json::Value = body_json(resp).await;
+
+    let mut operations = Vec::new();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for method in item.as_object().unwrap().keys() {
+            operations.push(format!("{} {path}", method.to_uppercase()));
+        }
+    }
+    operations.sort();
+    assert_eq!(
+        operations,
+        [
+            "DELETE /todos",
+            "DELETE /todos/{id}",
+            "GET /_admin/tenants",
+            "GET /capabilities",
+            "GET /todos",
+            "GET /todos/count",
+            "GET /todos/events",
+            "GET /todos/search",
+            "GET /todos/ws",
+            "GET /todos/{id}",
+            "GET /todos/{id}/history",
+            "PATCH /todos/{id}",
+            "POST /_admin/reset",
+            "POST /_admin/seed",
+            "POST /_faults",
+            "POST /todos",
+            "POST /todos/batch",
+            "PUT /todos/{id}",
+        ]
+    );
+
+    // Every documented operation is routed: an unrouted path or method
+    // would be a bare 404 or 405.
+    for operation in &operations {
+        let (method, path) = operation.split_once(' ').unwrap();
+        let uri = path.replace("{id}", &uuid::Uuid::nil().to_string());
+        let resp = app.clone().oneshot(json_request(method, &uri, "{}")).await.unwrap();
+        let routed = resp.status() != StatusCode::METHOD_NOT_ALLOWED
+            && (resp.status() != StatusCode::NOT_FOUND || path.contains("{id}"));
+        assert!(routed, "{operation}: {}", resp.status());
+    }
+
+    let todo = &spec["components"]["schemas"]["Todo"];
+    assert_eq!(todo["required"], serde_json::json!(["id", "title", "completed"]));
+    assert!(todo["properties"]["schema_version"].is_object());
+}
+
+// --- fault injection ---
+
+#[tokio::test]
+async fn injected_faults_fail_then_recover() {
+    let app = app();
+    let resp = app
+        .clone()
+        .oneshot(json_request("POST", "/_faults", r#"{"count":2,"status":503}"#))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    for expected in [StatusCode::SERVICE_UNAVAILABLE, StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK] {
+        let resp = app
+            .clone()
+            .oneshot(Request::builder().uri("/todos").body(String::new()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), expected);
+    }
+}
+
+#[tokio::test]
+async fn chaos_fails_requests_with_5xx() {
+    let chaos = Chaos {
+        rate: 1.0,
+        retry_after: Some(7),
+        seed: Some(42),
+    };
+    let app = app_with_config(Config {
+        chaos: Some(chaos),
+        ..Config::default()
+    })
+    .unwrap();
+    let mut statuses = Vec::new();
+    for _ in 0..16 {
+        let resp = app.clone().oneshot(get("/todos")).await.unwrap();
+        let retry_after = header(&resp, "retry-after").map(str::to_string);
+        match resp.status() {
+            StatusCode::SERVICE_UNAVAILABLE => assert_eq!(retry_after.as_deref(), Some("7")),
+            StatusCode::INTERNAL_SERVER_ERROR => assert_eq!(retry_after, None),
+            other => panic!("unexpected {other}"),
+        }
+        statuses.push(resp.status());
+    }
+    assert!(statuses.contains(&StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(statuses.contains(&StatusCode::SERVICE_UNAVAILABLE));
+
+    // Control routes are never failed.
+    let resp = app.oneshot(json_request("POST", "/_faults", r#"{"count":0,"status":500}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn rate_limit_answers_429_per_client() {
+    let app = app_with_config(Config {
+        rate_limit: Some(RateLimit {
+            burst: 2,
+            per_second: 0.01,
+        }),
+        ..Config::default()
+    })
+    .unwrap();
+    let as_client = |key: &str| Request::builder().uri("/todos").header("x-api-key", key).body(String::new()).unwrap();
+
+    let resp = app.clone().oneshot(as_client("a")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(header(&resp, "x-ratelimit-limit"), Some("2"));
+    assert_eq!(header(&resp, "x-ratelimit-remaining"), Some("1"));
+    app.clone().oneshot(as_client("a")).await.unwrap();
+
+    let resp = app.clone().oneshot(as_client("a")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&resp, "x-ratelimit-remaining"), Some("0"));
+    let retry_after: u64 = header(&resp, "retry-after").unwrap().parse().unwrap();
+    assert!((1..=100).contains(&retry_after), "{retry_after}");
+    assert_eq!(body_json::<Problem>(resp).await.status, 429);
+
+    // Another key has its own budget.
+    let resp = app.oneshot(as_client("b")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn auth_requires_a_known_bearer_token() {
+    let app = app_with_config(Config {
+        auth_tokens: Some(vec!["s3cret".to_string()]),
+        ..Config::default()
+    })
+    .unwrap();
+    let with_auth = |value: &str| {
+        Request::builder().uri("/todos").header(http::header::AUTHORIZATION, value).body(String::new()).unwrap()
+    };
+
+    let resp = app.clone().oneshot(get("/todos")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(header(&resp, "www-authenticate"), Some(r#"Bearer realm="todos""#));
+
+    for wrong in ["Bearer nope", "Basic s3cret", "s3cret"] {
+        let resp = app.clone().oneshot(with_auth(wrong)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "{wrong}");
+    }
+    let resp = app.clone().oneshot(with_auth("Bearer nope")).await.unwrap();
+    assert!(header(&resp, "www-authenticate").unwrap().contains(r#"error="invalid_token""#));
+
+    let resp = app.oneshot(with_auth("Bearer s3cret")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn cors_answers_preflights_before_auth() {
+    let app = app_with_config(Config {
+        auth_tokens: Some(vec!["s3cret".to_string()]),
+        cors: Some(Cors {
+            origins: Some(vec![http::HeaderValue::from_static("http://localhost:5173")]),
+            methods: DEFAULT_CORS_METHODS.to_vec(),
+            headers: DEFAULT_CORS_HEADERS.map(http::HeaderName::from_static).to_vec(),
+        }),
+        ..Config::default()
+    })
+    .unwrap();
+    let from = |origin: &str, request: http::request::Builder| {
+        request.header(http::header::ORIGIN, origin).body(String::new()).unwrap()
+    };
+    let preflight = Request::builder()
+        .method("OPTIONS")
+        .uri("/todos/1")
+        .header("access-control-request-method", "PATCH")
+        .header("access-control-request-headers", "authorization, if-match");
+
+    let resp = app.clone().oneshot(from("http://localhost:5173", preflight)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(header(&resp, "access-control-allow-origin"), Some("http://localhost:5173"));
+    assert!(header(&resp, "access-control-allow-methods").unwrap().contains("PATCH"));
+    assert!(header(&resp, "access-control-allow-headers").unwrap().contains("if-match"));
+
+    // A refusal still carries the headers a browser needs to read it.
+    let resp = app.clone().oneshot(from("http://localhost:5173", Request::builder().uri("/todos"))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(header(&resp, "access-control-allow-origin"), Some("http://localhost:5173"));
+    assert!(header(&resp, "access-control-expose-headers").unwrap().contains("www-authenticate"));
+
+    let resp = app.oneshot(from("http://evil.example", Request::builder().uri("/todos"))).await.unwrap();
+    assert_eq!(header(&resp, "access-control-allow-origin"), None);
+}
+
+/// Log lines written through a `tracing` JSON subscriber.
+#[derive(Clone, Default)]
+struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn requests_are_logged_with_status_and_latency() {
+    let logs = LogBuffer::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let request = Request::builder().uri("/todos/not-a-uuid").header("x-correlation-id", "abc-123");
+    let resp = app().oneshot(request.body(String::new()).unwrap()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    let line: serde_json::Value = serde_json::from_str(output.lines().last().unwrap()).unwrap();
+    assert_eq!(line["fields"]["status"], 400, "{line}");
+    assert!(line["fields"]["latency"].as_str().unwrap().ends_with("μs"), "{line}");
+    assert_eq!(line["span"]["method"], "GET", "{line}");
+    assert_eq!(line["span"]["path"], "/todos/not-a-uuid", "{line}");
+    assert_eq!(line["span"]["correlation_id"], "abc-123", "{line}");
+}
+
+// --- admin ---
+
+#[tokio::test]
+async fn admin_seed_stores_todos_with_their_ids() {
+    let app = app_with(Entropy::Deterministic);
+    let seed = r#"[
+        {"id":"00000000-0000-0000-0000-000000000003","title":"Seeded","completed":true},
+        {"id":"6f1c0d1e-3b5a-4c1e-9a57-0c9b2f7e8d41","title":"Fixed","completed":false}
+    ]"#;
+    let resp = app.clone().oneshot(json_request("POST", "/_admin/seed", seed)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let seeded: Vec<Todo> = body_json(resp).await;
+
+    let listed: Vec<Todo> = body_json(app.clone().oneshot(get("/todos")).await.unwrap()).await;
+    assert_eq!(listed, seeded);
+    let resp = app.clone().oneshot(get("/todos/6f1c0d1e-3b5a-4c1e-9a57-0c9b2f7e8d41")).await.unwrap();
+    assert_eq!(body_json::<Todo>(resp).await.title, "Fixed");
+
+    // A deterministic id already taken by a seeded todo is skipped.
+    let resp = app.oneshot(json_request("POST", "/todos", r#"{"title":"New"}"#)).await.unwrap();
+    assert_eq!(body_json::<Todo>(resp).await.id, uuid::Uuid::from_u128(4));
+}
+
+#[tokio::test]
+async fn seed_file_fills_an_empty_store_only() {
+    let dir = std::env::temp_dir();
+    let seed = dir.join(format!("mock-server-seed-{}.jsonl", std::process::id()));
+    let store = dir.join(format!("mock-server-seeded-store-{}.json", std::process::id()));
+    std::fs::write(&seed, r#"{"id":"00000000-0000-0000-0000-00000000000a","title":"From file","completed":false}"#).unwrap();
+    let config = Config {
+        storage: Storage::JsonFile(store.clone()),
+        seed_file: Some(seed.clone()),
+        ..Config::default()
+    };
+    let titles = |app: axum::Router| async move {
+        let todos: Vec<Todo> = body_json(app.oneshot(get("/todos")).await.unwrap()).await;
+        todos.into_iter().map(|t| t.title).collect::<Vec<_>>()
+    };
+
+    let first = app_with_config(config.clone()).unwrap();
+    assert_eq!(titles(first.clone()).await, ["From file"]);
+    let resp = first.oneshot(json_request("POST", "/todos", r#"{"title":"Added"}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // The saved store wins over the seed on restart.
+    let second = app_with_config(config).unwrap();
+    assert_eq!(titles(second).await, ["From file", "Added"]);
+    std::fs::remove_file(&seed).ok();
+    std::fs::remove_file(&store).ok();
+}
+
+#[tokio::test]
+async fn admin_reset_empties_the_store_and_restarts_ids() {
+    let app = app_with(Entropy::Deterministic);
+    for _ in 0..2 {
+        app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"t"}"#)).await.unwrap();
+    }
+    let resp = app.clone().oneshot(json_request("POST", "/_admin/reset", "")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let listed: Vec<Todo> = body_json(app.clone().oneshot(get("/todos")).await.unwrap()).await;
+    assert!(listed.is_empty());
+
+    let resp = app.oneshot(json_request("POST", "/todos", r#"{"title":"t"}"#)).await.unwrap();
+    assert_eq!(body_json::<Todo>(resp).await.id, uuid::Uuid::from_u128(1));
+}
+
+// --- tenants ---
+
+/// `request` on behalf of tenant `name`, via `X-Tenant`.
+fn as_tenant(name: &str, mut request: Request<String>) -> Request<String> {
+    request.headers_mut().insert("x-tenant", name.parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn tenants_are_isolated_listed_and_persisted() {
+    let path = std::env::temp_dir().join(format!("mock-server-tenants-{}.json", std::process::id()));
+    let config = Config {
+        entropy: Entropy::Deterministic,
+        storage: Storage::JsonFile(path.clone()),
+        ..Config::default()
+    };
+    let app = app_with_config(config.clone()).unwrap();
+    let create = |title: &str| json_request("POST", "/todos", &format!(r#"{{"title":"{title}"}}"#));
+    let titles = |app: axum::Router, request: Request<String>| async move {
+        let todos: Vec<Todo> = body_json(app.oneshot(request).await.unwrap()).await;
+        todos.into_iter().map(|t| t.title).collect::<Vec<_>>()
+    };
+
+    app.clone().oneshot(create("Default")).await.unwrap();
+    let resp = app.clone().oneshot(as_tenant("suite-a", create("A"))).await.unwrap();
+    // Each tenant numbers its own todos.
+    assert_eq!(body_json::<Todo>(resp).await.id, uuid::Uuid::from_u128(1));
+    let mut keyed = create("Keyed");
+    keyed.headers_mut().insert("x-api-key", "k1".parse().unwrap());
+    app.clone().oneshot(keyed).await.unwrap();
+
+    assert_eq!(titles(app.clone(), get("/todos")).await, ["Default"]);
+    assert_eq!(titles(app.clone(), as_tenant("suite-a", get("/todos"))).await, ["A"]);
+    assert_eq!(titles(app.clone(), as_tenant("suite-b", get("/todos"))).await, Vec::<String>::new());
+
+    let resp = app.clone().oneshot(as_tenant("suite-a", json_request("POST", "/_admin/reset", ""))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(titles(app.clone(), get("/todos")).await, ["Default"]);
+    app.clone().oneshot(as_tenant("suite-a", create("A again"))).await.unwrap();
+
+    let resp = app.oneshot(get("/_admin/tenants")).await.unwrap();
+    let tenants: Vec<TenantSummary> = body_json(resp).await;
+    let tenants: Vec<_> = tenants.iter().map(|t| (t.name.as_deref(), t.todos)).collect();
+    // The API key is listed (and saved) only as a fingerprint.
+    assert_eq!(tenants[2..], [(Some("suite-a"), 1), (Some("suite-b"), 0)]);
+    assert_eq!(tenants[0], (None, 1));
+    assert!(tenants[1].0.unwrap().starts_with("key-") && tenants[1].1 == 1);
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("k1"));
+
+    let restarted = app_with_config(config).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(titles(restarted.clone(), get("/todos")).await, ["Default"]);
+    assert_eq!(titles(restarted, as_tenant("suite-a", get("/todos"))).await, ["A again"]);
+}
+
+// --- change feed ---
+
+/// The next SSE event on `body` as its name and JSON data.
+async fn next_event(body: &mut axum::body::Body, buffer: &mut String) -> (String, serde_json::Value) {
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                block.lines().find_map(|line| line.strip_prefix(name)).map(str::to_string).unwrap_or_default()
+            };
+            return (field("event: "), serde_json::from_str(&field("data: ")).unwrap());
+        }
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(5), body.frame())
+            .await
+            .expect("an event within five seconds")
+            .expect("the stream stays open")
+            .unwrap();
+        buffer.push_str(std::str::from_utf8(&frame.into_data().unwrap()).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn change_feed_streams_creates_updates_and_deletes() {
+    let app = app();
+    let resp = app.clone().oneshot(get("/todos/events")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[http::header::CONTENT_TYPE], "text/event-stream");
+    let mut events = resp.into_body();
+    let mut buffer = String::new();
+
+    let resp = app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Walk dog"}"#)).await.unwrap();
+    let created: Todo = body_json(resp).await;
+    let uri = format!("/todos/{}", created.id);
+    app.clone().oneshot(json_request("PUT", &uri, r#"{"completed":true}"#)).await.unwrap();
+    app.clone().oneshot(json_request("DELETE", &uri, "")).await.unwrap();
+
+    let (name, data) = next_event(&mut events, &mut buffer).await;
+    assert_eq!(name, "created");
+    assert_eq!(data, serde_json::json!({"type": "created", "todo": created}));
+    let (name, data) = next_event(&mut events, &mut buffer).await;
+    assert_eq!(name, "updated");
+    assert_eq!(data["todo"]["completed"], true);
+    let (name, data) = next_event(&mut events, &mut buffer).await;
+    assert_eq!(name, "deleted");
+    assert_eq!(data, serde_json::json!({"type": "deleted", "id": created.id}));
+}
+
+#[tokio::test]
+async fn websocket_pushes_changes_as_json_messages() {
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let app = app();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = app.clone();
+    let server = tokio::spawn(async move { axum::serve(listener, router).await });
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/todos/ws")).await.unwrap();
+
+    let resp = app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Walk dog"}"#)).await.unwrap();
+    let created: Todo = body_json(resp).await;
+    app.clone().oneshot(json_request("DELETE", &format!("/todos/{}", created.id), "")).await.unwrap();
+
+    let expected = [
+        serde_json::json!({"type": "created", "todo": created}),
+        serde_json::json!({"type": "deleted", "id": created.id}),
+    ];
+    for expected in expected {
+        let message = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+            .await
+            .expect("a message within five seconds")
+            .expect("the socket stays open")
+            .unwrap();
+        let Message::Text(text) = message else {
+            panic!("expected a text message, got {message:?}");
+        };
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&text).unwrap(), expected);
+    }
+    server.abort();
+}
+
+#[tokio::test]
+async fn shutdown_ends_event_streams_and_stops_the_server() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(mock_server::run(listener, async {
+        let _ = stopped.await;
+    }));
+
+    let mut events = tokio::net::TcpStream::connect(addr).await.unwrap();
+    events.write_all(b"GET /todos/events HTTP/1.1\r\nhost: test\r\n\r\n").await.unwrap();
+    let mut status_line = [0; 15];
+    events.read_exact(&mut status_line).await.unwrap();
+    assert_eq!(&status_line, b"HTTP/1.1 200 OK");
+
+    stop.send(()).unwrap();
+    let five_seconds = std::time::Duration::from_secs(5);
+    let stopped = tokio::time::timeout(five_seconds, server).await.expect("the server stops within five seconds");
+    stopped.unwrap().unwrap();
+    let mut rest = Vec::new();
+    tokio::time::timeout(five_seconds, events.read_to_end(&mut rest)).await.unwrap().unwrap();
+    assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+}
+
 // --- list ---
 
 #[tokio::test]
@@ -36,6 +524,162 @@ async fn list_todos_empty() {
     assert!(todos.is_empty());
 }
 
+/// Five todos titled `t0`..`t4`; the odd ones completed.
+async fn five_todos() -> axum::Router {
+    let app = app_with(Entropy::Deterministic);
+    for i in 0..5 {
+        let body = format!(r#"{{"title":"t{i}","completed":{}}}"#, i % 2 == 1);
+        app.clone().oneshot(json_request("POST", "/todos", &body)).await.unwrap();
+    }
+    app
+}
+
+fn get(uri: &str) -> Request<String> {
+    Request::builder().uri(uri).body(String::new()).unwrap()
+}
+
+fn header<'a>(resp: &'a axum::response::Response, name: &str) -> Option<&'a str> {
+    resp.headers().get(name).map(|v| v.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn offset_pages_link_their_neighbours() {
+    let app = five_todos().await;
+    let resp = app.clone().oneshot(get("/todos?limit=2&offset=2")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(header(&resp, "x-total-count"), Some("5"));
+    assert_eq!(
+        header(&resp, "link"),
+        Some(r#"</todos?limit=2&offset=4>; rel="next", </todos?limit=2&offset=0>; rel="prev""#)
+    );
+    let titles: Vec<String> = body_json::<Vec<Todo>>(resp).await.into_iter().map(|t| t.title).collect();
+    assert_eq!(titles, ["t2", "t3"]);
+
+    // Filters narrow the total and are carried into the links.
+    let resp = app.oneshot(get("/todos?limit=2&completed=false")).await.unwrap();
+    assert_eq!(header(&resp, "x-total-count"), Some("3"));
+    assert_eq!(header(&resp, "link"), Some(r#"</todos?limit=2&offset=2&completed=false>; rel="next""#));
+}
+
+#[tokio::test]
+async fn cursor_pages_follow_the_link_to_the_end() {
+    let app = five_todos().await;
+    let mut uri = "/todos?cursor=&limit=2".to_string();
+    let mut titles = Vec::new();
+    loop {
+        let resp = app.clone().oneshot(get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(header(&resp, "x-total-count"), Some("5"));
+        let next = header(&resp, "link").map(|link| link[1..link.find('>').unwrap()].to_string());
+        let page: CursorPage = body_json(resp).await;
+        titles.extend(page.todos.into_iter().map(|t| t.title));
+        assert_eq!(next.is_some(), page.next_cursor.is_some());
+        match next {
+            Some(next) => uri = next,
+            None => break,
+        }
+    }
+    assert_eq!(titles, ["t0", "t1", "t2", "t3", "t4"]);
+}
+
+#[tokio::test]
+async fn list_filters_narrow_the_result() {
+    let app = five_todos().await;
+    let resp = app.oneshot(get("/todos?completed=true&title_contains=t3")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let titles: Vec<String> = body_json::<Vec<Todo>>(resp).await.into_iter().map(|t| t.title).collect();
+    assert_eq!(titles, ["t3"]);
+}
+
+#[tokio::test]
+async fn sorting_breaks_ties_by_id() {
+    let app = app();
+    for title in ["b", "a", "b", "a"] {
+        let body = format!(r#"{{"title":"{title}"}}"#);
+        app.clone().oneshot(json_request("POST", "/todos", &body)).await.unwrap();
+    }
+    let list = |uri: &'static str| {
+        let app = app.clone();
+        async move { body_json::<Vec<Todo>>(app.oneshot(get(uri)).await.unwrap()).await }
+    };
+    let created = list("/todos").await;
+    let asc = list("/todos?sort=title").await;
+    let desc = list("/todos?sort=title&order=desc").await;
+
+    let key = |t: &Todo| (t.title.clone(), t.id);
+    let mut expected: Vec<_> = created.iter().map(key).collect();
+    expected.sort();
+    assert_eq!(asc.iter().map(key).collect::<Vec<_>>(), expected);
+    // Descending flips the titles but not the id tie-break.
+    expected.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    assert_eq!(desc.iter().map(key).collect::<Vec<_>>(), expected);
+
+    let newest_first = list("/todos?sort=created_at&order=desc").await;
+    assert!(newest_first.iter().map(|t| t.id).eq(created.iter().rev().map(|t| t.id)));
+}
+
+#[tokio::test]
+async fn offset_pages_keep_the_sort() {
+    let app = five_todos().await;
+    let resp = app.oneshot(get("/todos?sort=title&order=desc&limit=2")).await.unwrap();
+    assert_eq!(
+        header(&resp, "link"),
+        Some(r#"</todos?limit=2&offset=2&sort=title&order=desc>; rel="next""#)
+    );
+    let titles: Vec<String> = body_json::<Vec<Todo>>(resp).await.into_iter().map(|t| t.title).collect();
+    assert_eq!(titles, ["t4", "t3"]);
+}
+
+#[tokio::test]
+async fn invalid_query_parameters_are_problems() {
+    let app = five_todos().await;
+    let uris = [
+        "/todos?completed=maybe",
+        "/todos?tag=home",
+        "/todos?cursor=!!",
+        "/todos?sort=priority",
+        "/todos?sort=title&order=up",
+        "/todos?cursor=&sort=title",
+        "/todos/search?q=milk&limit=many",
+        "/todos/count?completed=maybe",
+    ];
+    for uri in uris {
+        let resp = app.clone().oneshot(get(uri)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(header(&resp, "content-type"), Some("application/problem+json"), "{uri}");
+        let problem: Problem = body_json(resp).await;
+        assert_eq!((problem.status, problem.title.as_str()), (400, "Bad Request"), "{uri}");
+        assert!(!problem.detail.is_empty(), "{uri}");
+    }
+}
+
+// --- count ---
+
+#[tokio::test]
+async fn count_pending_excludes_completed() {
+    let app = app();
+    for (title, completed) in [("a", false), ("b", true), ("c", false)] {
+        let body = format!(r#"{{"title":"{title}","completed":{completed}}}"#);
+        let resp = app.clone().oneshot(json_request("POST", "/todos", &body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    let count = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let resp = app
+                .oneshot(Request::builder().uri(uri).body(String::new()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            body_json::<TodoCount>(resp).await.count
+        }
+    };
+    assert_eq!(count("/todos/count?completed=false").await, 2);
+    assert_eq!(count("/todos/count?completed=true").await, 1);
+    assert_eq!(count("/todos/count").await, 3);
+}
+
 // --- create ---
 
 #[tokio::test]
@@ -52,6 +696,84 @@ async fn create_todo_returns_201() {
     assert!(!todo.completed);
 }
 
+#[tokio::test]
+async fn deterministic_ids_and_clock() {
+    let app = app_with(Entropy::Deterministic);
+    for title in ["b", "a"] {
+        let body = format!(r#"{{"title":"{title}"}}"#);
+        let resp = app.clone().oneshot(json_request("POST", "/todos", &body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri("/todos").body(String::new()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        body_bytes(resp).await,
+        r#"[{"id":"00000000-0000-0000-0000-000000000001","title":"b","completed":false},{"id":"00000000-0000-0000-0000-000000000002","title":"a","completed":false}]"#
+    );
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/todos/00000000-0000-0000-0000-000000000002/history")
+                .body(String::new())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let history: Vec<TodoRevision> = body_json(resp).await;
+    assert_eq!(history[0].changed_at, 0);
+}
+
+#[tokio::test]
+async fn json_file_storage_survives_a_restart() {
+    let path = std::env::temp_dir().join(format!("mock-server-store-{}.json", std::process::id()));
+    let config = Config {
+        entropy: Entropy::Deterministic,
+        storage: Storage::JsonFile(path.clone()),
+        ..Config::default()
+    };
+    let list = || Request::builder().uri("/todos").body(String::new()).unwrap();
+
+    let first = app_with_config(config.clone()).unwrap();
+    let resp = first.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Keep me"}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created: Todo = body_json(resp).await;
+    let before = body_bytes(first.oneshot(list()).await.unwrap()).await;
+
+    let second = app_with_config(config.clone()).unwrap();
+    assert_eq!(body_bytes(second.clone().oneshot(list()).await.unwrap()).await, before);
+    let resp = second
+        .clone()
+        .oneshot(Request::builder().uri(format!("/todos/{}/history", created.id)).body(String::new()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(body_json::<Vec<TodoRevision>>(resp).await.len(), 1);
+    // Sequence numbers carry over too, so the next id does not collide.
+    let resp = second.oneshot(json_request("POST", "/todos", r#"{"title":"Another"}"#)).await.unwrap();
+    assert_ne!(body_json::<Todo>(resp).await.id, created.id);
+
+    std::fs::write(&path, "not json").unwrap();
+    let err = app_with_config(config).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+async fn create_todo_normalizes_title() {
+    let app = app();
+    let resp = app
+        .oneshot(json_request("POST", "/todos", r#"{"title":"  Buy   milk "}"#))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let todo: Todo = body_json(resp).await;
+    assert_eq!(todo.title, "Buy milk");
+}
+
 #[tokio::test]
 async fn create_todo_with_completed_true() {
     let app = app();
@@ -80,6 +802,102 @@ async fn create_todo_malformed_json_returns_422() {
     assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
 }
 
+#[tokio::test]
+async fn create_todo_with_matching_content_md5() {
+    let app = app();
+    let mut req = json_request("POST", "/todos", r#"{"title":"Buy milk"}"#);
+    req.headers_mut()
+        .insert("content-md5", "S+T6VpQxKjVAFAr0xI/YLw==".parse().unwrap());
+    let resp = app.oneshot(req).await.unwrap();
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn create_todo_with_corrupted_body_returns_400() {
+    let app = app();
+    // Digest of `{"title":"Buy milk"}`, sent with a different body.
+    let mut req = json_request("POST", "/todos", r#"{"title":"Buy silk"}"#);
+    req.headers_mut()
+        .insert("content-md5", "S+T6VpQxKjVAFAr0xI/YLw==".parse().unwrap());
+    let resp = app.oneshot(req).await.unwrap();
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(header(&resp, "content-type"), Some("application/problem+json"));
+}
+
+#[tokio::test]
+async fn invalid_input_gets_a_problem_body() {
+    let app = app_with_config(Config {
+        max_title_len: Some(10),
+        ..Config::default()
+    })
+    .unwrap();
+    let resp = app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Short"}"#)).await.unwrap();
+    let id = body_json::<Todo>(resp).await.id;
+    let uri = format!("/todos/{id}");
+
+    for (request, status, detail) in [
+        (json_request("POST", "/todos", r#"{"title":" \t "}"#), 422, "title must not be empty"),
+        (json_request("POST", "/todos", r#"{"title":"Much too long"}"#), 422, "13 characters, more than the 10 allowed"),
+        (json_request("POST", "/todos", r#"{"title":5}"#), 422, "invalid type: integer `5`, expected a string"),
+        (json_request("POST", "/todos", r#"{"title":"#), 400, "EOF while parsing"),
+        (json_request("POST", "/todos/batch", r#"[{"title":"ok"},{"title":""}]"#), 422, "[1]: title must not be empty"),
+        (json_request("PUT", &uri, r#"{"title":""}"#), 422, "title must not be empty"),
+        (json_request("PUT", &uri, r#"{"completed":"yes"}"#), 422, "invalid type: string \"yes\", expected a boolean"),
+        (merge_patch(id, r#"{"title":"Much too long"}"#), 422, "more than the 10 allowed"),
+    ] {
+        let label = format!("{} {}", request.method(), request.body());
+        let resp = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(resp.status().as_u16(), status, "{label}");
+        assert_eq!(header(&resp, "content-type"), Some("application/problem+json"), "{label}");
+        let problem: Problem = body_json(resp).await;
+        assert_eq!(problem.status, status, "{label}");
+        assert!(problem.detail.contains(detail), "{label}: {}", problem.detail);
+    }
+
+    // A batch with one bad title creates none of them.
+    let listed: Vec<Todo> = body_json(app.oneshot(get("/todos")).await.unwrap()).await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].title, "Short");
+}
+
+#[tokio::test]
+async fn unique_titles_turn_duplicates_into_conflicts() {
+    let app = app_with_config(Config {
+        unique_titles: true,
+        ..Config::default()
+    })
+    .unwrap();
+    let mut ids = Vec::new();
+    for title in ["Walk dog", "Buy milk"] {
+        let body = format!(r#"{{"title":"{title}"}}"#);
+        let resp = app.clone().oneshot(json_request("POST", "/todos", &body)).await.unwrap();
+        ids.push(body_json::<Todo>(resp).await.id);
+    }
+    let (walk, milk) = (ids[0], ids[1]);
+
+    for (request, conflicts_with) in [
+        (json_request("POST", "/todos", r#"{"title":"Walk  dog "}"#), Some(walk)),
+        (json_request("POST", "/todos/batch", r#"[{"title":"Feed cat"},{"title":"Buy milk"}]"#), Some(milk)),
+        (json_request("POST", "/todos/batch", r#"[{"title":"Feed cat"},{"title":"Feed cat"}]"#), None),
+        (json_request("PUT", &format!("/todos/{milk}"), r#"{"title":"Walk dog"}"#), Some(walk)),
+        (merge_patch(walk, r#"{"title":"Buy milk"}"#), Some(milk)),
+    ] {
+        let label = format!("{} {}", request.method(), request.body());
+        let resp = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT, "{label}");
+        let problem: Problem = body_json(resp).await;
+        assert_eq!(problem.conflicts_with, conflicts_with, "{label}");
+    }
+
+    // Keeping a todo's own title is not a conflict.
+    let resp = app.clone().oneshot(json_request("PUT", &format!("/todos/{walk}"), r#"{"title":"Walk dog"}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let listed: Vec<Todo> = body_json(app.oneshot(get("/todos")).await.unwrap()).await;
+    assert_eq!(listed.len(), 2);
+}
+
 // --- get ---
 
 #[tokio::test]
@@ -131,6 +949,62 @@ async fn update_todo_not_found() {
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
+// --- patch ---
+
+fn merge_patch(id: uuid::Uuid, body: &str) -> Request<String> {
+    Request::builder()
+        .method("PATCH")
+        .uri(format!("/todos/{id}"))
+        .header(http::header::CONTENT_TYPE, "application/merge-patch+json")
+        .body(body.to_string())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn patch_merges_present_fields() {
+    let app = app();
+    let resp = app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Keep"}"#)).await.unwrap();
+    let created: Todo = body_json(resp).await;
+
+    let resp = app.clone().oneshot(merge_patch(created.id, r#"{"completed":true}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().contains_key(http::header::ETAG));
+    let patched: Todo = body_json(resp).await;
+    assert_eq!((patched.title.as_str(), patched.completed), ("Keep", true));
+
+    // A patch that changes nothing adds no revision.
+    let resp = app.clone().oneshot(merge_patch(created.id, r#"{"completed":true}"#)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app.oneshot(get(&format!("/todos/{}/history", created.id))).await.unwrap();
+    assert_eq!(body_json::<Vec<TodoRevision>>(resp).await.len(), 2);
+}
+
+#[tokio::test]
+async fn patch_rejects_what_merge_patch_cannot_apply() {
+    let app = app();
+    let resp = app.clone().oneshot(json_request("POST", "/todos", r#"{"title":"Keep"}"#)).await.unwrap();
+    let id = body_json::<Todo>(resp).await.id;
+
+    let cases = [
+        (merge_patch(id, r#"{"title":null}"#), StatusCode::UNPROCESSABLE_ENTITY),
+        (merge_patch(id, r#"{"completed":"yes"}"#), StatusCode::UNPROCESSABLE_ENTITY),
+        (merge_patch(id, &format!(r#"{{"id":"{}"}}"#, uuid::Uuid::nil())), StatusCode::UNPROCESSABLE_ENTITY),
+        (merge_patch(id, "{"), StatusCode::BAD_REQUEST),
+        (json_request("PATCH", &format!("/todos/{id}"), r#"{"completed":true}"#), StatusCode::UNSUPPORTED_MEDIA_TYPE),
+    ];
+    for (request, status) in cases {
+        let resp = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(resp.status(), status);
+        assert_eq!(header(&resp, "content-type"), Some("application/problem+json"));
+    }
+    let resp = app.clone().oneshot(merge_patch(uuid::Uuid::nil(), "{}")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = app.oneshot(get(&format!("/todos/{id}"))).await.unwrap();
+    let todo: Todo = body_json(resp).await;
+    assert_eq!((todo.title.as_str(), todo.completed), ("Keep", false));
+}
+
 // --- delete ---
 
 #[tokio::test]
@@ -194,7 +1068,7 @@ async fn crud_lifecycle() {
         .unwrap()
         .call(
             Request::builder()
-                .uri(&format!("/todos/{id}"))
+                .uri(format!("/todos/{id}"))
                 .body(String::new())
                 .unwrap(),
         )
@@ -244,7 +1118,7 @@ async fn crud_lifecycle() {
         .call(
             Request::builder()
                 .method("DELETE")
-                .uri(&format!("/todos/{id}"))
+                .uri(format!("/todos/{id}"))
                 .body(String::new())
                 .unwrap(),
         )
@@ -260,7 +1134,7 @@ async fn crud_lifecycle() {
         .unwrap()
         .call(
             Request::builder()
-                .uri(&format!("/todos/{id}"))
+                .uri(format!("/todos/{id}"))
                 .body(String::new())
                 .unwrap(),
         )
@@ -0,0 +1,417 @@
+//! Wire types of the todo API, shared by the client and the mock server.
+//!
+//! # Design
+//! `todo-core` and `mock-server` both re-export these types, so the JSON
+//! one side writes is by construction the JSON the other side reads: a
+//! renamed or retyped field fails the build of both instead of an
+//! integration test. Only the shapes and the helpers that depend on nothing
+//! but the shapes live here; anything tied to a client or a server (request
+//! building, storage) stays in its crate.
+//!
+//! Failures are [`SchemaError`]s; `todo-core` converts them into its
+//! `ApiError`, so `?` works unchanged there.
+//!
+//! Like `todo-core`, the crate is `#![no_std]` + `alloc` with default
+//! features off; `std` adds only [`CreateTodo::write_batch_json`]. The
+//! `openapi` feature derives `utoipa::ToSchema` for the request and
+//! response types, so the mock server's published spec is generated from
+//! the same definitions.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
+
+/// Why a schema helper rejected its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The input breaks a rule of the schema, such as clearing a required
+    /// field.
+    Validation(String),
+
+    /// The value could not be written out as JSON.
+    Serialization(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Validation(msg) => write!(f, "validation failed: {msg}"),
+            SchemaError::Serialization(msg) => write!(f, "serialization failed: {msg}"),
+        }
+    }
+}
+
+impl core::error::Error for SchemaError {}
+
+/// A single todo item.
+///
+/// `completed` deserializes leniently because some non-Rust servers send it
+/// as `0`/`1` or `"true"`/`"false"`; it always serializes as a JSON boolean.
+///
+/// `schema_version` is only present when the server stamps objects with the
+/// schema that produced them; it is omitted from the JSON when `None`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Todo {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(deserialize_with = "lenient_bool")]
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+}
+
+impl Todo {
+    /// Apply an RFC 7386 JSON merge patch in place.
+    ///
+    /// Present keys overwrite, absent keys are left alone, and unknown keys
+    /// are ignored. `null` clears the nullable `schema_version` and is
+    /// rejected on the required fields, as is any value of the wrong type;
+    /// completed is checked strictly here, unlike on deserialize. On error
+    /// the todo is left unchanged.
+    pub fn apply_merge_patch(&mut self, patch: &serde_json::Value) -> Result<(), SchemaError> {
+        let Some(fields) = patch.as_object() else {
+            return Err(SchemaError::Validation("merge patch must be a JSON object".to_string()));
+        };
+        let mut patched = self.clone();
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                (field @ ("id" | "title" | "completed"), serde_json::Value::Null) => {
+                    return Err(SchemaError::Validation(format!("`{field}` cannot be cleared")));
+                }
+                ("id", serde_json::Value::String(id)) => {
+                    patched.id = Uuid::parse_str(id).map_err(|e| SchemaError::Validation(format!("`id`: {e}")))?;
+                }
+                ("title", serde_json::Value::String(title)) => patched.title = title.clone(),
+                ("completed", serde_json::Value::Bool(completed)) => patched.completed = *completed,
+                ("schema_version", serde_json::Value::Null) => patched.schema_version = None,
+                ("schema_version", version) => {
+                    let version = version.as_u64().and_then(|v| u32::try_from(v).ok()).ok_or_else(|| {
+                        SchemaError::Validation("`schema_version` must be an unsigned 32-bit integer".to_string())
+                    })?;
+                    patched.schema_version = Some(version);
+                }
+                (field @ ("id" | "title"), _) => {
+                    return Err(SchemaError::Validation(format!("`{field}` must be a string")));
+                }
+                ("completed", _) => {
+                    return Err(SchemaError::Validation("`completed` must be a boolean".to_string()));
+                }
+                _ => {}
+            }
+        }
+        *self = patched;
+        Ok(())
+    }
+}
+
+/// One entry in a todo's revision history, as served by
+/// `GET /todos/{id}/history`.
+///
+/// `revision` starts at 1 for the create and increments on every update.
+/// `changed_at` is Unix epoch milliseconds as recorded by the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TodoRevision {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(deserialize_with = "lenient_bool")]
+    pub completed: bool,
+    pub revision: u64,
+    pub changed_at: u64,
+}
+
+/// Accept `true`/`false`, `0`/`1`, and `"true"`/`"false"` as a boolean.
+pub fn lenient_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    struct LenientBool;
+
+    impl Visitor<'_> for LenientBool {
+        type Value = bool;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a boolean, 0/1, or \"true\"/\"false\"")
+        }
+
+        fn visit_bool<E: de::Error>(self, v: bool) -> Result<bool, E> {
+            Ok(v)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<bool, E> {
+            match v {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<bool, E> {
+            match v {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+            match v {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            }
+        }
+    }
+
+    deserializer.deserialize_any(LenientBool)
+}
+
+/// Request payload for creating a new todo. `completed` defaults to `false`
+/// when omitted, so clients only need to send a title.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateTodo {
+    pub title: String,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// Canonical form of a todo title: NFC-normalized, trimmed, with internal
+/// whitespace runs collapsed to a single space.
+///
+/// The server stores titles in this form on create and the client builds
+/// them with it, so duplicate detection agrees on both sides. Titles set by
+/// PUT or PATCH are stored as sent; duplicate checks still compare them
+/// normalized.
+pub fn normalize_title(s: &str) -> String {
+    let nfc: String = s.nfc().collect();
+    nfc.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl CreateTodo {
+    /// Build a create payload with a normalized title.
+    pub fn new(title: &str, completed: bool) -> Self {
+        Self {
+            title: normalize_title(title),
+            completed,
+        }
+    }
+
+    /// Stream `inputs` to `writer` as a JSON array, one element at a time.
+    ///
+    /// Produces the same bytes as serializing the whole slice, without
+    /// buffering the full body in a `String` first. Pass a `BufWriter` when
+    /// the underlying sink issues a syscall per write.
+    #[cfg(feature = "std")]
+    pub fn write_batch_json<W: std::io::Write>(inputs: &[CreateTodo], mut writer: W) -> Result<(), SchemaError> {
+        let io_err = |e: std::io::Error| SchemaError::Serialization(e.to_string());
+        writer.write_all(b"[").map_err(io_err)?;
+        for (i, input) in inputs.iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b",").map_err(io_err)?;
+            }
+            serde_json::to_writer(&mut writer, input).map_err(|e| SchemaError::Serialization(e.to_string()))?;
+        }
+        writer.write_all(b"]").map_err(io_err)?;
+        writer.flush().map_err(io_err)
+    }
+}
+
+/// Request payload for updating an existing todo. Only the fields present in
+/// the JSON are applied; omitted fields remain unchanged on the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateTodo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+}
+
+impl UpdateTodo {
+    /// Reject updates with no fields set, which would be a no-op PUT.
+    pub fn require_non_empty(self) -> Result<UpdateTodo, SchemaError> {
+        if self.title.is_none() && self.completed.is_none() {
+            return Err(SchemaError::Validation("update has no fields set".to_string()));
+        }
+        Ok(self)
+    }
+
+    /// Serialize with object keys sorted and no insignificant whitespace.
+    ///
+    /// Used as the signed body when a request signer is configured, so the
+    /// bytes do not depend on field declaration order or on whether
+    /// `serde_json`'s `preserve_order` feature is enabled somewhere in the
+    /// build. The fields are all scalars, so sorting the top level is enough.
+    pub fn to_canonical_json(&self) -> Result<String, SchemaError> {
+        let serialization = |e: serde_json::Error| SchemaError::Serialization(e.to_string());
+        let serde_json::Value::Object(fields) = serde_json::to_value(self).map_err(serialization)? else {
+            return Err(SchemaError::Serialization("update is not a JSON object".to_string()));
+        };
+        let sorted: BTreeMap<String, serde_json::Value> = fields.into_iter().collect();
+        serde_json::to_string(&sorted).map_err(serialization)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed_from(raw: &str) -> Result<bool, serde_json::Error> {
+        let body = format!(r#"{{"id":"00000000-0000-0000-0000-000000000001","title":"T","completed":{raw}}}"#);
+        serde_json::from_str::<Todo>(&body).map(|t| t.completed)
+    }
+
+    #[test]
+    fn completed_accepts_booleans() {
+        assert!(completed_from("true").unwrap());
+        assert!(!completed_from("false").unwrap());
+    }
+
+    #[test]
+    fn completed_accepts_integers() {
+        assert!(completed_from("1").unwrap());
+        assert!(!completed_from("0").unwrap());
+        assert!(completed_from("2").is_err());
+    }
+
+    #[test]
+    fn completed_accepts_strings() {
+        assert!(completed_from(r#""true""#).unwrap());
+        assert!(!completed_from(r#""false""#).unwrap());
+        assert!(completed_from(r#""yes""#).is_err());
+    }
+
+    #[test]
+    fn completed_serializes_as_boolean() {
+        let todo: Todo = serde_json::from_str(
+            r#"{"id":"00000000-0000-0000-0000-000000000001","title":"T","completed":1}"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&todo).unwrap();
+        assert_eq!(json["completed"], serde_json::Value::Bool(true));
+    }
+
+    #[test]
+    fn normalize_title_trims_and_collapses() {
+        assert_eq!(normalize_title("  Buy   milk "), "Buy milk");
+        assert_eq!(normalize_title("Buy\t\nmilk"), "Buy milk");
+        assert_eq!(CreateTodo::new("  Buy   milk ", false).title, "Buy milk");
+    }
+
+    #[test]
+    fn normalize_title_composes_nfc() {
+        // "e" + combining acute accent composes to a single U+00E9.
+        assert_eq!(normalize_title("caf\u{0065}\u{0301}"), "caf\u{00e9}");
+    }
+
+    #[test]
+    fn normalize_title_is_idempotent() {
+        for raw in ["  Buy   milk ", "caf\u{0065}\u{0301}  au  lait", "", "   "] {
+            let once = normalize_title(raw);
+            assert_eq!(normalize_title(&once), once);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_batch_json_roundtrips() {
+        let inputs = vec![
+            CreateTodo {
+                title: "First".to_string(),
+                completed: false,
+            },
+            CreateTodo {
+                title: "Second \"quoted\"".to_string(),
+                completed: true,
+            },
+        ];
+        let mut buf = Vec::new();
+        CreateTodo::write_batch_json(&inputs, &mut buf).unwrap();
+        let back: Vec<CreateTodo> = serde_json::from_slice(&buf).unwrap();
+        let fields = |todos: &[CreateTodo]| todos.iter().map(|t| (t.title.clone(), t.completed)).collect::<Vec<_>>();
+        assert_eq!(fields(&back), fields(&inputs));
+        assert_eq!(buf, serde_json::to_vec(&inputs).unwrap());
+    }
+
+    fn sample_todo() -> Todo {
+        Todo {
+            id: Uuid::nil(),
+            title: "Before".to_string(),
+            completed: false,
+            schema_version: None,
+        }
+    }
+
+    #[test]
+    fn merge_patch_sets_present_fields_only() {
+        let mut todo = sample_todo();
+        todo.apply_merge_patch(&serde_json::json!({ "title": "After", "unknown": 1 })).unwrap();
+        assert_eq!(todo.title, "After");
+        assert!(!todo.completed);
+    }
+
+    #[test]
+    fn merge_patch_rejects_null_on_required_field() {
+        let mut todo = sample_todo();
+        let err = todo.apply_merge_patch(&serde_json::json!({ "title": null })).unwrap_err();
+        assert!(matches!(err, SchemaError::Validation(_)));
+        assert_eq!(todo, sample_todo());
+    }
+
+    #[test]
+    fn merge_patch_rejects_type_mismatch_atomically() {
+        let mut todo = sample_todo();
+        let patch = serde_json::json!({ "title": "After", "completed": "yes" });
+        let err = todo.apply_merge_patch(&patch).unwrap_err();
+        assert!(matches!(err, SchemaError::Validation(msg) if msg.contains("completed")));
+        assert_eq!(todo, sample_todo());
+    }
+
+    #[test]
+    fn merge_patch_sets_and_clears_schema_version() {
+        let mut todo = sample_todo();
+        todo.apply_merge_patch(&serde_json::json!({ "schema_version": 2 })).unwrap();
+        assert_eq!(todo.schema_version, Some(2));
+        todo.apply_merge_patch(&serde_json::json!({ "schema_version": null })).unwrap();
+        assert_eq!(todo.schema_version, None);
+    }
+
+    #[test]
+    fn merge_patch_rejects_a_non_integer_schema_version() {
+        let mut todo = sample_todo();
+        for version in [serde_json::json!("2"), serde_json::json!(-1), serde_json::json!(1.5)] {
+            let err = todo.apply_merge_patch(&serde_json::json!({ "schema_version": version })).unwrap_err();
+            assert!(matches!(err, SchemaError::Validation(msg) if msg.contains("schema_version")));
+        }
+        assert_eq!(todo, sample_todo());
+    }
+
+    #[test]
+    fn canonical_json_is_sorted_and_byte_identical() {
+        let a = UpdateTodo {
+            title: Some("Ship it".to_string()),
+            completed: Some(false),
+        };
+        let b: UpdateTodo = serde_json::from_str(r#"{ "completed": false, "title": "Ship it" }"#).unwrap();
+        assert_eq!(a.to_canonical_json().unwrap(), b.to_canonical_json().unwrap());
+        assert_eq!(a.to_canonical_json().unwrap(), r#"{"completed":false,"title":"Ship it"}"#);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_batch_json_empty() {
+        let mut buf = Vec::new();
+        CreateTodo::write_batch_json(&[], &mut buf).unwrap();
+        assert_eq!(buf, b"[]");
+    }
+}
@@ -0,0 +1,244 @@
+//! Recorded traffic in HAR 1.2 form, for contract tests against a real server.
+//!
+//! # Design
+//! HAR is what browsers, mitmproxy and most API proxies export, so captures
+//! need no conversion. Only the fields a replay reads are modeled and
+//! unknown keys are ignored, unlike the vectors: a capture is whatever the
+//! tool wrote.
+//!
+//! [`parse`] rejects what should never be checked in or cannot be replayed:
+//! credentials (`authorization`, cookies), aborted requests (status 0) and
+//! base64 bodies. Entries [`Entry::route`] does not recognize, such as
+//! health checks, are kept and left for the runner to skip.
+//!
+//! An entry may carry `"_expect_error"` (HAR allows custom `_` fields): the
+//! [`ErrorKind`] the client is known to report for that response, for quirks
+//! it rejects on purpose.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::ErrorKind;
+
+/// A HAR file; only `log.entries` is read.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Log {
+    pub entries: Vec<Entry>,
+}
+
+/// One request/response exchange.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Entry {
+    pub request: Request,
+    pub response: Response,
+    #[serde(default, rename = "_expect_error")]
+    pub expect_error: Option<ErrorKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Request {
+    /// Upper-case method name, e.g. `GET`.
+    pub method: String,
+    /// Absolute URL, query string included.
+    pub url: String,
+    pub headers: Vec<Header>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<Header>,
+    pub content: Content,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Content {
+    /// Absent when the response had no body.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// `base64` for binary bodies, which [`parse`] rejects.
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+/// The API operation an entry exercised, named after the client's
+/// `parse_*` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    ListTodos,
+    CreateTodo,
+    DeleteAll,
+    CreateTodosBatch,
+    CountTodos,
+    SearchTodos,
+    GetTodo,
+    UpdateTodo,
+    PatchTodo,
+    DeleteTodo,
+    History,
+    Capabilities,
+}
+
+/// Headers that carry credentials.
+const CREDENTIALS: [&str; 4] = ["authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+impl Entry {
+    /// The URL's path, without scheme, host or query.
+    pub fn path(&self) -> &str {
+        let url = &self.request.url;
+        let after_scheme = url.find("://").map_or(url.as_str(), |i| &url[i + 3..]);
+        let path = after_scheme.find('/').map_or("/", |i| &after_scheme[i..]);
+        path.split(['?', '#']).next().unwrap_or(path)
+    }
+
+    /// Which operation this was, matched on the path after its last
+    /// `todos` segment so a deployment prefix such as `/api/v1` is ignored.
+    /// `None` for anything else.
+    pub fn route(&self) -> Option<Route> {
+        let segments: Vec<&str> = self.path().split('/').filter(|s| !s.is_empty()).collect();
+        if segments.last() == Some(&"capabilities") {
+            return (self.request.method == "GET").then_some(Route::Capabilities);
+        }
+        let start = segments.iter().rposition(|s| *s == "todos")?;
+        Some(match (self.request.method.as_str(), &segments[start + 1..]) {
+            ("GET", []) => Route::ListTodos,
+            ("POST", []) => Route::CreateTodo,
+            ("DELETE", []) => Route::DeleteAll,
+            ("POST", ["batch"]) => Route::CreateTodosBatch,
+            ("GET", ["count"]) => Route::CountTodos,
+            ("GET", ["search"]) => Route::SearchTodos,
+            ("GET", [_]) => Route::GetTodo,
+            ("PUT", [_]) => Route::UpdateTodo,
+            ("PATCH", [_]) => Route::PatchTodo,
+            ("DELETE", [_]) => Route::DeleteTodo,
+            ("GET", [_, "history"]) => Route::History,
+            _ => return None,
+        })
+    }
+
+    /// The response headers as `(name, value)` pairs, in capture order.
+    pub fn response_headers(&self) -> Vec<(String, String)> {
+        self.response.headers.iter().map(|h| (h.name.clone(), h.value.clone())).collect()
+    }
+
+    /// The response body; empty when there was none.
+    pub fn response_body(&self) -> &str {
+        self.response.content.text.as_deref().unwrap_or_default()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let headers = self.request.headers.iter().chain(&self.response.headers);
+        if let Some(header) = headers.into_iter().find(|h| CREDENTIALS.contains(&h.name.to_ascii_lowercase().as_str())) {
+            return Err(format!("carries a `{}` header; strip credentials before checking in", header.name));
+        }
+        if self.response.status == 0 {
+            return Err("has no response (status 0)".to_string());
+        }
+        if self.response.content.encoding.is_some() {
+            return Err("has an encoded body; export bodies as text".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize and validate one HAR file. Problems name the entry by its
+/// index, method and URL.
+pub fn parse(source: &str) -> Result<Har, String> {
+    let har: Har = serde_json::from_str(source).map_err(|e| e.to_string())?;
+    if har.log.entries.is_empty() {
+        return Err("no entries".to_string());
+    }
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        entry
+            .validate()
+            .map_err(|e| format!("entry {i} ({} {}) {e}", entry.request.method, entry.request.url))?;
+    }
+    Ok(har)
+}
+
+/// Every `*.har` file in `dir`, in file name order, paired with its stem.
+pub fn load_dir(dir: &Path) -> Result<Vec<(String, Har)>, String> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map_err(|e| format!("reading {}: {e}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "har"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let source = std::fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))?;
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            parse(&source).map(|har| (stem, har)).map_err(|e| format!("{}: {e}", path.display()))
+        })
+        .collect()
+}
+
+/// The repo's `test-vectors/captures/` directory.
+pub fn captures_dir() -> PathBuf {
+    crate::vectors_dir().join("captures")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str) -> Entry {
+        Entry {
+            request: Request {
+                method: method.to_string(),
+                url: url.to_string(),
+                headers: Vec::new(),
+            },
+            response: Response {
+                status: 200,
+                headers: Vec::new(),
+                content: Content { text: None, encoding: None },
+            },
+            expect_error: None,
+        }
+    }
+
+    #[test]
+    fn routes_by_method_and_path() {
+        let id = "00000000-0000-0000-0000-000000000001";
+        assert_eq!(entry("GET", "https://api.example.com/todos?completed=true").route(), Some(Route::ListTodos));
+        assert_eq!(entry("GET", &format!("https://api.example.com/api/v1/todos/{id}")).route(), Some(Route::GetTodo));
+        assert_eq!(entry("PATCH", &format!("http://localhost:3000/todos/{id}")).route(), Some(Route::PatchTodo));
+        assert_eq!(entry("GET", "http://localhost:3000/todos/count").route(), Some(Route::CountTodos));
+        assert_eq!(entry("GET", &format!("http://localhost:3000/todos/{id}/history")).route(), Some(Route::History));
+        assert_eq!(entry("GET", "http://localhost:3000/capabilities").route(), Some(Route::Capabilities));
+        assert_eq!(entry("GET", "http://localhost:3000/healthz").route(), None);
+        assert_eq!(entry("POST", &format!("http://localhost:3000/todos/{id}")).route(), None);
+    }
+
+    #[test]
+    fn rejects_credentials_and_aborted_requests() {
+        let har = |entry: &str| format!(r#"{{"log": {{"version": "1.2", "entries": [{entry}]}}}}"#);
+        let ok = r#"{"request": {"method": "GET", "url": "http://h/todos", "headers": []},
+                     "response": {"status": 200, "headers": [], "content": {"size": 2, "text": "[]"}}}"#;
+        assert_eq!(parse(&har(ok)).unwrap().log.entries[0].response_body(), "[]");
+        let err = parse(&har(&ok.replace(r#""headers": []},"#, r#""headers": [{"name": "Cookie", "value": "s=1"}]},"#)))
+            .unwrap_err();
+        assert_eq!(err, "entry 0 (GET http://h/todos) carries a `Cookie` header; strip credentials before checking in");
+        let err = parse(&har(&ok.replace("200", "0"))).unwrap_err();
+        assert_eq!(err, "entry 0 (GET http://h/todos) has no response (status 0)");
+    }
+
+    #[test]
+    fn loads_every_checked_in_capture() {
+        assert!(!load_dir(&captures_dir()).unwrap().is_empty());
+    }
+}
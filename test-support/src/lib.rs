@@ -0,0 +1,294 @@
+//! Typed model and loader for the JSON test vectors in `test-vectors/`.
+//!
+//! # Design
+//! Every consumer (the core's tests, the conformance runner, the C
+//! conformance generator, bindings) reads vectors through [`parse`], so the
+//! format is defined once:
+//! - serde does the shape checks: unknown keys are rejected at every level
+//!   and required fields must be present, so a typo in a vector fails
+//!   loudly instead of silently skipping a check;
+//! - [`TestVector::validate`] then checks what serde cannot, such as a case
+//!   expecting both a result and an error.
+//!
+//! This crate depends on nothing from the workspace but the mock server
+//! (only with the `server` feature), so `todo-core` can use it from its own
+//! tests. Values stay in their JSON spelling: ids are strings, methods
+//! upper-case names, errors [`ErrorKind`] variants named like the C ABI's
+//! `FfiErrorCode`.
+//!
+//! Recorded traffic from real servers is loaded by [`har`], and live-HTTP
+//! tests start their server through `server`.
+
+pub mod har;
+#[cfg(feature = "server")]
+pub mod server;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One vector file: the cases for a single operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestVector {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestCase {
+    pub name: String,
+    /// The todo id the operation targets, for get/update/delete.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_id: Option<String>,
+    /// The create or update payload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<TodoInput>,
+    pub expected_request: ExpectedRequest,
+    pub simulated_response: SimulatedResponse,
+    /// Absent for operations that return nothing, and for errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_result: Option<ExpectedResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_error: Option<ErrorKind>,
+    /// The HTTP status an `expected_error` carries, for errors that come
+    /// from a status (`Http`, `NotFound`, `RateLimited`, ...). The C ABI
+    /// reports it as `http_status`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_http_status: Option<u16>,
+}
+
+/// Fields of a `CreateTodo` or `UpdateTodo`; a create requires `title`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TodoInput {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedRequest {
+    /// Upper-case method name, e.g. `PUT`.
+    pub method: String,
+    /// Path relative to the client's base URL, starting with `/`.
+    pub path: String,
+    /// In the order the client sends them.
+    pub headers: Vec<(String, String)>,
+    /// The JSON body, compared as JSON rather than text. `None` for no body.
+    pub body: Option<Value>,
+}
+
+/// The response a test feeds to the parse step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimulatedResponse {
+    pub status: u16,
+    /// Only the headers the parse step looks at, such as `retry-after`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExpectedResult {
+    Todo(ExpectedTodo),
+    List(Vec<ExpectedTodo>),
+}
+
+/// The todo fields every surface exposes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedTodo {
+    pub id: String,
+    pub title: String,
+    pub completed: bool,
+}
+
+/// How a parse is expected to fail, named like the `FfiErrorCode` variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+    Unauthorized,
+    NotFound,
+    Conflict,
+    PreconditionFailed,
+    Http,
+    Deserialization,
+    Serialization,
+    Validation,
+    RateLimited,
+    SchemaMismatch,
+    Transport,
+    Tls,
+}
+
+impl ErrorKind {
+    /// The vector spelling, e.g. `NotFound`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Unauthorized => "Unauthorized",
+            ErrorKind::NotFound => "NotFound",
+            ErrorKind::Conflict => "Conflict",
+            ErrorKind::PreconditionFailed => "PreconditionFailed",
+            ErrorKind::Http => "Http",
+            ErrorKind::Deserialization => "Deserialization",
+            ErrorKind::Serialization => "Serialization",
+            ErrorKind::Validation => "Validation",
+            ErrorKind::RateLimited => "RateLimited",
+            ErrorKind::SchemaMismatch => "SchemaMismatch",
+            ErrorKind::Transport => "Transport",
+            ErrorKind::Tls => "Tls",
+        }
+    }
+}
+
+const METHODS: [&str; 8] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE"];
+
+impl TestVector {
+    /// Check the rules serde cannot express. Each problem names its case.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cases.is_empty() {
+            return Err("no cases".to_string());
+        }
+        let mut names = HashSet::new();
+        for case in &self.cases {
+            if !names.insert(case.name.as_str()) {
+                return Err(format!("case {}: duplicate name", case.name));
+            }
+            case.validate().map_err(|e| format!("case {}: {e}", case.name))?;
+        }
+        Ok(())
+    }
+}
+
+impl TestCase {
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("empty name".to_string());
+        }
+        if self.expected_result.is_some() && self.expected_error.is_some() {
+            return Err("both expected_result and expected_error".to_string());
+        }
+        if self.expected_http_status.is_some() && self.expected_error.is_none() {
+            return Err("expected_http_status without expected_error".to_string());
+        }
+        let request = &self.expected_request;
+        if !METHODS.contains(&request.method.as_str()) {
+            return Err(format!("unknown method {:?}", request.method));
+        }
+        if !request.path.starts_with('/') {
+            return Err(format!("path {:?} does not start with /", request.path));
+        }
+        if request.body.as_ref().is_some_and(|body| !body.is_object()) {
+            return Err("request body is not a JSON object".to_string());
+        }
+        if !(100..=599).contains(&self.simulated_response.status) {
+            return Err(format!("status {} is not an HTTP status", self.simulated_response.status));
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize and validate one vector file.
+pub fn parse(source: &str) -> Result<TestVector, String> {
+    let vector: TestVector = serde_json::from_str(source).map_err(|e| e.to_string())?;
+    vector.validate()?;
+    Ok(vector)
+}
+
+/// [`parse`] the file at `path`; errors name the file.
+pub fn load(path: &Path) -> Result<TestVector, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))?;
+    parse(&source).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Every `*.json` file in `dir`, in file name order, paired with its stem.
+pub fn load_dir(dir: &Path) -> Result<Vec<(String, TestVector)>, String> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map_err(|e| format!("reading {}: {e}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            load(path).map(|vector| (stem, vector))
+        })
+        .collect()
+}
+
+/// The repo's `test-vectors/` directory.
+pub fn vectors_dir() -> PathBuf {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-vectors")).to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASE: &str = r#"{
+        "name": "found",
+        "input_id": "00000000-0000-0000-0000-000000000001",
+        "expected_request": {"method": "GET", "path": "/todos/00000000-0000-0000-0000-000000000001", "headers": [], "body": null},
+        "simulated_response": {"status": 404, "body": ""},
+        "expected_error": "NotFound"
+    }"#;
+
+    fn vector(case: &str) -> String {
+        format!(r#"{{"name": "get-todo", "cases": [{case}]}}"#)
+    }
+
+    #[test]
+    fn parses_a_case() {
+        let vector = parse(&vector(CASE)).unwrap();
+        assert_eq!(vector.cases[0].expected_error, Some(ErrorKind::NotFound));
+        assert_eq!(vector.cases[0].expected_request.body, None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let err = parse(&vector(&CASE.replace("\"input_id\"", "\"inputid\""))).unwrap_err();
+        assert!(err.contains("unknown field `inputid`"), "{err}");
+        let err = parse(&vector(&CASE.replace("\"status\": 404", "\"status\": 404, \"header\": []"))).unwrap_err();
+        assert!(err.contains("unknown field `header`"), "{err}");
+    }
+
+    #[test]
+    fn rejects_missing_required_fields() {
+        let err = parse(&vector(&CASE.replace("\"status\": 404, ", ""))).unwrap_err();
+        assert!(err.contains("missing field `status`"), "{err}");
+        let err = parse(&vector(&CASE.replace("\"NotFound\"", "\"Missing\""))).unwrap_err();
+        assert!(err.contains("unknown variant `Missing`"), "{err}");
+    }
+
+    #[test]
+    fn validation_names_the_case() {
+        let both = CASE.replace(
+            "\"expected_error\"",
+            r#""expected_result": {"id": "1", "title": "t", "completed": false}, "expected_error""#,
+        );
+        assert_eq!(parse(&vector(&both)).unwrap_err(), "case found: both expected_result and expected_error");
+        let err = parse(&vector(&CASE.replace("\"GET\"", "\"get\""))).unwrap_err();
+        assert_eq!(err, r#"case found: unknown method "get""#);
+        let err = parse(&vector(&format!("{CASE}, {CASE}"))).unwrap_err();
+        assert_eq!(err, "case found: duplicate name");
+        let status_only = CASE.replace(r#""expected_error": "NotFound""#, r#""expected_http_status": 404"#);
+        let err = parse(&vector(&status_only)).unwrap_err();
+        assert_eq!(err, "case found: expected_http_status without expected_error");
+    }
+
+    #[test]
+    fn loads_every_checked_in_vector() {
+        let vectors = load_dir(&vectors_dir()).unwrap();
+        let stems: Vec<&str> = vectors.iter().map(|(stem, _)| stem.as_str()).collect();
+        assert_eq!(stems, ["create", "delete", "get", "list", "update"]);
+    }
+}
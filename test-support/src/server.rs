@@ -0,0 +1,79 @@
+//! A mock server for tests that need real HTTP, behind the `server` feature.
+//!
+//! # Design
+//! Each [`TestServer`] binds a random local port and runs on its own thread
+//! with a current-thread runtime, so blocking clients (ureq, the FFI, child
+//! processes) can call it from the test thread. Dropping it resolves the
+//! server's shutdown future and joins the thread, so no test leaves a
+//! server or runtime behind.
+
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+use tokio::sync::oneshot;
+
+/// A running mock server; shut down on drop.
+pub struct TestServer {
+    pub base_url: String,
+    stop: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Start the mock server on a random port.
+pub fn spawn_server() -> TestServer {
+    spawn_server_with(mock_server::Config::default())
+}
+
+/// [`spawn_server`], with the server set up by `config`.
+pub fn spawn_server_with(config: mock_server::Config) -> TestServer {
+    let std_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = std_listener.local_addr().unwrap();
+    std_listener.set_nonblocking(true).unwrap();
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let thread = std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let listener = tokio::net::TcpListener::from_std(std_listener).unwrap();
+            mock_server::run_with_config(listener, config, async {
+                let _ = stopped.await;
+            })
+            .await
+        })
+        .unwrap();
+    });
+
+    TestServer {
+        base_url: format!("http://{addr}"),
+        stop: Some(stop),
+        thread: Some(thread),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_the_server_stops_it() {
+        let server = spawn_server();
+        let addr = server.base_url.strip_prefix("http://").unwrap().to_string();
+        assert!(std::net::TcpStream::connect(&addr).is_ok());
+        drop(server);
+        assert!(std::net::TcpStream::connect(&addr).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Regenerates `test-vectors/*.json` from the real mock server.
+//!
+//! # Design
+//! Each [`ScriptCase`] gets a fresh mock server on a random port, with ids
+//! and clock made deterministic (`Entropy::Deterministic`), so todos are
+//! `...0001`, `...0002`, ... in creation order and reruns are byte-for-byte
+//! identical. The case seeds the server with a few creates, then performs
+//! one operation through `todo-core` over real HTTP and records:
+//! - the request the core built, as `expected_request` (path relative to
+//!   the server);
+//! - the server's status and body, as `simulated_response`;
+//! - what the core parsed from them, as `expected_result`, or the error
+//!   kind (spelled like `FfiErrorCode`) as `expected_error`, plus the HTTP
+//!   status it carries as `expected_http_status`.
+//!
+//! Error cases get their response from the server's fault injection where
+//! it can produce one (a bare status), and from a canned [`Reply`] where it
+//! cannot: error bodies, `Retry-After`, malformed or empty success bodies.
+//!
+//! The vectors are output, not source: to change one, edit [`SCRIPTS`] and
+//! rerun `cargo run -p todo-vectorgen`.
+
+use mock_server::Entropy;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use todo_core::{ApiError, CreateTodo, HttpMethod, HttpRequest, HttpResponse, Todo, TodoClient, UpdateTodo};
+use uuid::Uuid;
+
+/// The operation a case records, with its inputs. Ids are the `u128` form
+/// of the deterministic UUIDs.
+pub enum Step {
+    List,
+    Get(u128),
+    Create { title: &'static str, completed: bool },
+    Update { id: u128, title: Option<&'static str>, completed: Option<bool> },
+    Delete(u128),
+}
+
+/// Where the response to a case's step comes from.
+pub enum Reply {
+    /// Whatever the server answers.
+    Server,
+    /// The server's fault injection (`POST /_faults`): `status`, empty body.
+    Fault(u16),
+    /// A fixed response, for what the mock server never sends. The request
+    /// is still built by the core, but not sent.
+    Canned {
+        status: u16,
+        headers: &'static [(&'static str, &'static str)],
+        body: &'static str,
+    },
+}
+
+pub struct ScriptCase {
+    pub name: &'static str,
+    /// Todos created, in order, before `step` runs.
+    pub seed: &'static [(&'static str, bool)],
+    pub step: Step,
+    pub reply: Reply,
+}
+
+const fn canned(status: u16, body: &'static str) -> Reply {
+    Reply::Canned { status, headers: &[], body }
+}
+
+/// One vector file: `test-vectors/<file>.json`.
+pub struct Script {
+    pub file: &'static str,
+    pub name: &'static str,
+    pub cases: &'static [ScriptCase],
+}
+
+/// Id no seeded todo gets, for the not-found cases.
+const MISSING: u128 = 0x99;
+
+pub const SCRIPTS: &[Script] = &[
+    Script {
+        file: "list",
+        name: "list-todos",
+        cases: &[
+            ScriptCase { name: "empty_list", seed: &[], step: Step::List, reply: Reply::Server },
+            ScriptCase {
+                name: "two_items",
+                seed: &[("First", false), ("Second", true)],
+                step: Step::List,
+                reply: Reply::Server,
+            },
+            ScriptCase { name: "service_unavailable", seed: &[], step: Step::List, reply: Reply::Fault(503) },
+            ScriptCase {
+                name: "malformed_json",
+                seed: &[],
+                step: Step::List,
+                reply: canned(200, r#"[{"id":"not-a-uuid","title":"x","completed":false}]"#),
+            },
+        ],
+    },
+    Script {
+        file: "get",
+        name: "get-todo",
+        cases: &[
+            ScriptCase { name: "found", seed: &[("Test", false)], step: Step::Get(1), reply: Reply::Server },
+            ScriptCase { name: "not_found", seed: &[], step: Step::Get(MISSING), reply: Reply::Server },
+            ScriptCase { name: "unauthorized", seed: &[], step: Step::Get(1), reply: Reply::Fault(401) },
+            ScriptCase { name: "forbidden", seed: &[], step: Step::Get(1), reply: Reply::Fault(403) },
+            ScriptCase {
+                name: "server_error_with_body",
+                seed: &[],
+                step: Step::Get(1),
+                reply: canned(500, r#"{"error":"database unavailable"}"#),
+            },
+            ScriptCase {
+                name: "rate_limited",
+                seed: &[],
+                step: Step::Get(1),
+                reply: Reply::Canned { status: 429, headers: &[("retry-after", "30")], body: "" },
+            },
+            ScriptCase { name: "malformed_json", seed: &[], step: Step::Get(1), reply: canned(200, r#"{"id":"#) },
+            ScriptCase { name: "empty_body", seed: &[], step: Step::Get(1), reply: canned(200, "") },
+        ],
+    },
+    Script {
+        file: "create",
+        name: "create-todo",
+        cases: &[
+            ScriptCase {
+                name: "basic_create",
+                seed: &[],
+                step: Step::Create { title: "Buy milk", completed: false },
+                reply: Reply::Server,
+            },
+            ScriptCase {
+                name: "create_with_completed_true",
+                seed: &[("Buy milk", false)],
+                step: Step::Create { title: "Already done", completed: true },
+                reply: Reply::Server,
+            },
+            ScriptCase {
+                name: "conflict",
+                seed: &[],
+                step: Step::Create { title: "Buy milk", completed: false },
+                reply: canned(409, r#"{"error":"duplicate title"}"#),
+            },
+            ScriptCase {
+                name: "empty_body",
+                seed: &[],
+                step: Step::Create { title: "Buy milk", completed: false },
+                reply: canned(201, ""),
+            },
+        ],
+    },
+    Script {
+        file: "update",
+        name: "update-todo",
+        cases: &[
+            ScriptCase {
+                name: "update_title_only",
+                seed: &[("Test", false)],
+                step: Step::Update { id: 1, title: Some("Updated title"), completed: None },
+                reply: Reply::Server,
+            },
+            ScriptCase {
+                name: "update_completed_only",
+                seed: &[("Test", false)],
+                step: Step::Update { id: 1, title: None, completed: Some(true) },
+                reply: Reply::Server,
+            },
+            ScriptCase {
+                name: "not_found",
+                seed: &[],
+                step: Step::Update { id: MISSING, title: Some("Updated title"), completed: None },
+                reply: Reply::Server,
+            },
+        ],
+    },
+    Script {
+        file: "delete",
+        name: "delete-todo",
+        cases: &[
+            ScriptCase { name: "success", seed: &[("Test", false)], step: Step::Delete(1), reply: Reply::Server },
+            ScriptCase { name: "not_found", seed: &[], step: Step::Delete(MISSING), reply: Reply::Server },
+            ScriptCase { name: "rate_limited", seed: &[], step: Step::Delete(1), reply: Reply::Fault(429) },
+        ],
+    },
+];
+
+#[derive(Serialize)]
+struct VectorFile {
+    name: &'static str,
+    cases: Vec<VectorCase>,
+}
+
+#[derive(Serialize)]
+struct VectorCase {
+    name: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input_id: Option<String>,
+    /// Raw so the JSON keeps the field order the core serialized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<Box<RawValue>>,
+    expected_request: ExpectedRequest,
+    simulated_response: SimulatedResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_result: Option<Expected>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_error: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_http_status: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct ExpectedRequest {
+    method: &'static str,
+    path: String,
+    /// Raw like the bodies, to keep each header pair on one line.
+    headers: Box<RawValue>,
+    body: Option<Box<RawValue>>,
+}
+
+#[derive(Serialize)]
+struct SimulatedResponse {
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<Box<RawValue>>,
+    body: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Expected {
+    Todo(TodoFields),
+    List(Vec<TodoFields>),
+}
+
+/// The fields of a todo every surface exposes.
+#[derive(Serialize)]
+struct TodoFields {
+    id: String,
+    title: String,
+    completed: bool,
+}
+
+impl From<Todo> for TodoFields {
+    fn from(todo: Todo) -> Self {
+        TodoFields { id: todo.id.to_string(), title: todo.title, completed: todo.completed }
+    }
+}
+
+/// Run every script and render each vector file as `(file name, contents)`.
+pub fn generate() -> Result<Vec<(String, String)>, String> {
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("starting the runtime: {e}"))?;
+    let agent = ureq::Agent::config_builder().http_status_as_error(false).build().new_agent();
+    SCRIPTS
+        .iter()
+        .map(|script| {
+            let cases = script
+                .cases
+                .iter()
+                .map(|case| record(&runtime, &agent, case).map_err(|e| format!("{}/{}: {e}", script.file, case.name)))
+                .collect::<Result<_, _>>()?;
+            let file = VectorFile { name: script.name, cases };
+            let mut json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+            json.push('\n');
+            Ok((format!("{}.json", script.file), json))
+        })
+        .collect()
+}
+
+/// A mock server on a random port, stopped on drop.
+struct Server {
+    base_url: String,
+    task: tokio::task::JoinHandle<std::io::Result<()>>,
+}
+
+impl Server {
+    fn start(runtime: &tokio::runtime::Runtime) -> Result<Server, String> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").map_err(|e| format!("binding the server: {e}"))?;
+        let base_url = format!("http://{}", listener.local_addr().map_err(|e| e.to_string())?);
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let _entered = runtime.enter();
+        let listener = tokio::net::TcpListener::from_std(listener).map_err(|e| e.to_string())?;
+        let task = runtime.spawn(mock_server::run_with(listener, Entropy::Deterministic, std::future::pending()));
+        Ok(Server { base_url, task })
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn record(runtime: &tokio::runtime::Runtime, agent: &ureq::Agent, case: &ScriptCase) -> Result<VectorCase, String> {
+    let server = Server::start(runtime)?;
+    let client = TodoClient::try_new(&server.base_url).map_err(|e| e.to_string())?;
+    for (title, completed) in case.seed {
+        let input = CreateTodo { title: title.to_string(), completed: *completed };
+        let req = client.build_create_todo(&input).map_err(|e| e.to_string())?;
+        client
+            .parse_create_todo(execute(agent, &req)?)
+            .map_err(|e| format!("seeding {title:?}: {e}"))?;
+    }
+
+    let (input_id, input, req) = match case.step {
+        Step::List => (None, None, client.build_list_todos()),
+        Step::Get(id) => (Some(id), None, client.build_get_todo(Uuid::from_u128(id))),
+        Step::Delete(id) => (Some(id), None, client.build_delete_todo(Uuid::from_u128(id))),
+        Step::Create { title, completed } => {
+            let input = CreateTodo { title: title.to_string(), completed };
+            (None, Some(raw(&input)?), client.build_create_todo(&input).map_err(|e| e.to_string())?)
+        }
+        Step::Update { id, title, completed } => {
+            let input = UpdateTodo { title: title.map(str::to_string), completed };
+            let req = client.build_update_todo(Uuid::from_u128(id), &input).map_err(|e| e.to_string())?;
+            (Some(id), Some(raw(&input)?), req)
+        }
+    };
+    let response = match case.reply {
+        Reply::Server => execute(agent, &req)?,
+        Reply::Fault(status) => {
+            inject_fault(agent, &server.base_url, status)?;
+            execute(agent, &req)?
+        }
+        Reply::Canned { status, headers, body } => HttpResponse {
+            status,
+            headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            body: body.to_string(),
+        },
+    };
+    let simulated_response = SimulatedResponse {
+        status: response.status,
+        headers: (!response.headers.is_empty()).then(|| raw(&response.headers)).transpose()?,
+        body: response.body.clone(),
+    };
+    let parsed: Result<Option<Expected>, ApiError> = match case.step {
+        Step::List => client
+            .parse_list_todos(response)
+            .map(|todos| Some(Expected::List(todos.into_iter().map(TodoFields::from).collect()))),
+        Step::Get(_) => client.parse_get_todo(response).map(|todo| Some(Expected::Todo(todo.into()))),
+        Step::Create { .. } => client.parse_create_todo(response).map(|todo| Some(Expected::Todo(todo.into()))),
+        Step::Update { .. } => client.parse_update_todo(response).map(|todo| Some(Expected::Todo(todo.into()))),
+        Step::Delete(_) => client.parse_delete_todo(response).map(|()| None),
+    };
+    let (expected_result, expected_error, expected_http_status) = match parsed {
+        Ok(result) => (result, None, None),
+        Err(err) => (None, Some(todo_conformance::surface::error_kind(&err)), http_status(&err)),
+    };
+
+    let url = req.url();
+    let path = url.strip_prefix(&server.base_url).ok_or_else(|| format!("{url} is not under {}", server.base_url))?;
+    Ok(VectorCase {
+        name: case.name,
+        input_id: input_id.map(|id| Uuid::from_u128(id).to_string()),
+        input,
+        expected_request: ExpectedRequest {
+            method: req.method.as_str(),
+            path: path.to_string(),
+            headers: raw(&req.headers)?,
+            body: req.body.clone().map(RawValue::from_string).transpose().map_err(|e| format!("request body: {e}"))?,
+        },
+        simulated_response,
+        expected_result,
+        expected_error,
+        expected_http_status,
+    })
+}
+
+/// The status an error came from, as the C ABI reports it in `http_status`.
+fn http_status(err: &ApiError) -> Option<u16> {
+    Some(match err {
+        ApiError::Unauthorized => 401,
+        ApiError::NotFound | ApiError::NotFoundId(_) => 404,
+        ApiError::Conflict(_) => 409,
+        ApiError::PreconditionFailed => 412,
+        ApiError::RateLimited { .. } => 429,
+        ApiError::HttpError { status, .. } => *status,
+        _ => return None,
+    })
+}
+
+/// Make the server answer its next request with a bare `status`.
+fn inject_fault(agent: &ureq::Agent, base_url: &str, status: u16) -> Result<(), String> {
+    let body = format!(r#"{{"count":1,"status":{status}}}"#);
+    let response = agent
+        .post(format!("{base_url}/_faults"))
+        .header("content-type", "application/json")
+        .send(body.as_str())
+        .map_err(|e| format!("injecting a fault: {e}"))?;
+    match response.status().as_u16() {
+        204 => Ok(()),
+        other => Err(format!("injecting a fault: status {other}")),
+    }
+}
+
+fn raw<T: Serialize>(value: &T) -> Result<Box<RawValue>, String> {
+    serde_json::value::to_raw_value(value).map_err(|e| e.to_string())
+}
+
+/// Send `req` to the server; non-2xx statuses are data, not errors.
+fn execute(agent: &ureq::Agent, req: &HttpRequest) -> Result<HttpResponse, String> {
+    let mut builder = ureq::http::Request::builder().method(req.method.as_str()).uri(req.url());
+    for (name, value) in &req.headers {
+        builder = builder.header(name, value);
+    }
+    let result = match (&req.method, &req.body) {
+        (HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch, Some(body)) => {
+            agent.run(builder.body(body.clone()).map_err(|e| e.to_string())?)
+        }
+        _ => agent.run(builder.body(()).map_err(|e| e.to_string())?),
+    };
+    let mut response = result.map_err(|e| format!("{} {}: {e}", req.method.as_str(), req.url()))?;
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    Ok(HttpResponse { status: response.status().as_u16(), headers: Vec::new(), body })
+}
@@ -0,0 +1,54 @@
+//! `todo-vectorgen [--out <dir>] [--check]`: regenerate the test vectors
+//! from the mock server into `<dir>` (default: the repo's `test-vectors/`).
+//! With `--check`, write nothing and fail if any file would change.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: todo-vectorgen [--out <dir>] [--check]";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let mut out = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-vectors"));
+    let mut check = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--out" => match args.next() {
+                Some(dir) => out = PathBuf::from(dir),
+                None => return fail(USAGE),
+            },
+            _ => return fail(USAGE),
+        }
+    }
+
+    let files = match todo_vectorgen::generate() {
+        Ok(files) => files,
+        Err(e) => return fail(&e),
+    };
+    let mut stale = false;
+    for (name, contents) in files {
+        let path = out.join(&name);
+        if std::fs::read_to_string(&path).is_ok_and(|current| current == contents) {
+            continue;
+        }
+        if check {
+            println!("{} is stale", path.display());
+            stale = true;
+        } else if let Err(e) = std::fs::write(&path, contents) {
+            return fail(&format!("writing {}: {e}", path.display()));
+        } else {
+            println!("wrote {}", path.display());
+        }
+    }
+    if stale {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fail(message: &str) -> ExitCode {
+    eprintln!("todo-vectorgen: {message}");
+    ExitCode::FAILURE
+}
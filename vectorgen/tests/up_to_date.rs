@@ -0,0 +1,21 @@
+//! The checked-in vectors are exactly what the mock server produces today.
+
+use std::path::Path;
+
+#[test]
+fn checked_in_vectors_are_up_to_date() {
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-vectors"));
+    for (name, generated) in todo_vectorgen::generate().unwrap() {
+        let checked_in = std::fs::read_to_string(dir.join(&name)).unwrap();
+        assert!(checked_in == generated, "test-vectors/{name} is stale; run `cargo run -p todo-vectorgen`");
+    }
+}
+
+#[test]
+fn generated_vectors_load() {
+    for (name, generated) in todo_vectorgen::generate().unwrap() {
+        let stem = name.trim_end_matches(".json");
+        let cases = todo_conformance::parse_vector(stem, &generated).unwrap();
+        assert_eq!(cases.len(), todo_vectorgen::SCRIPTS.iter().find(|s| s.file == stem).unwrap().cases.len());
+    }
+}